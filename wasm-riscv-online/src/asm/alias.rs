@@ -0,0 +1,334 @@
+//! objdump-style disassembly: prefers the binutils pseudo-instruction aliases
+//! (`nop`, `ret`, `li`, `rdcycle`, ...) over the canonical forms and follows
+//! objdump's operand syntax (no blank after commas, shift amounts and upper
+//! immediates in hex, branch targets as addresses relative to 0).
+//! Compressed instructions are shown by their base mnemonic, as objdump does.
+use super::*;
+use crate::riscv::imm::Xlen;
+
+pub(super) fn format(inst: &Instruction, xlen: Xlen) -> String {
+    match inst {
+        Instruction::RV32I(i) => rv32i(i, xlen),
+        Instruction::RV64I(i) => rv64i(i, xlen),
+        Instruction::RV128I(i) => rv128i(i),
+        Instruction::RVC(c) => rvc(c, xlen),
+        Instruction::RVZicsr(z) => zicsr(z, xlen),
+        Instruction::RV32A(a) => a.format_with(","),
+        Instruction::RV64A(a) => a.format_with(","),
+        Instruction::RV128A(a) => a.format_with(","),
+        Instruction::RVZabha(a) => a.format_with(","),
+        Instruction::RVPriv(p) => privileged(p),
+        _ => inst.disassembly(),
+    }
+}
+
+fn x(reg: u8) -> String {
+    to_register(reg)
+}
+
+fn f(reg: u8) -> &'static str {
+    const NAMES: [&str; 32] = [
+        "ft0", "ft1", "ft2", "ft3", "ft4", "ft5", "ft6", "ft7", "fs0", "fs1", "fa0", "fa1", "fa2",
+        "fa3", "fa4", "fa5", "fa6", "fa7", "fs2", "fs3", "fs4", "fs5", "fs6", "fs7", "fs8", "fs9",
+        "fs10", "fs11", "ft8", "ft9", "ft10", "ft11",
+    ];
+    NAMES[(reg & 0x1f) as usize]
+}
+
+/// Branch/jump target of an instruction located at address 0.
+fn target(off: i64, xlen: Xlen) -> String {
+    match xlen {
+        Xlen::X32 => format!("{:x}", off as u32),
+        _ => format!("{:x}", off as u64),
+    }
+}
+
+fn shamt(imm: &crate::riscv::imm::Imm, xlen: Xlen) -> u32 {
+    match xlen {
+        Xlen::X32 => imm.low_u32() & 0x1f,
+        Xlen::X64 => imm.low_u32() & 0x3f,
+        Xlen::X128 => imm.low_u32() & 0x7f,
+    }
+}
+
+fn op3(mnem: &str, a: &str, b: &str, c: impl core::fmt::Display) -> String {
+    format!("{} {},{},{}", mnem, a, b, c)
+}
+
+fn mem(mnem: &str, reg: &str, off: i64, base: u8) -> String {
+    format!("{} {},{}({})", mnem, reg, off, x(base))
+}
+
+fn branch(mnem: &str, b: &BType, xlen: Xlen) -> String {
+    let t = target(b.imm.as_i64(), xlen);
+    match (mnem, b.rs1, b.rs2) {
+        ("beq", rs, 0) => format!("beqz {},{}", x(rs), t),
+        ("bne", rs, 0) => format!("bnez {},{}", x(rs), t),
+        ("bge", 0, rs) => format!("blez {},{}", x(rs), t),
+        ("bge", rs, 0) => format!("bgez {},{}", x(rs), t),
+        ("blt", rs, 0) => format!("bltz {},{}", x(rs), t),
+        ("blt", 0, rs) => format!("bgtz {},{}", x(rs), t),
+        _ => op3(mnem, &x(b.rs1), &x(b.rs2), t),
+    }
+}
+
+fn jal(rd: u8, off: i64, xlen: Xlen) -> String {
+    match rd {
+        0 => format!("j {}", target(off, xlen)),
+        1 => format!("jal {}", target(off, xlen)),
+        _ => format!("jal {},{}", x(rd), target(off, xlen)),
+    }
+}
+
+fn jalr(rd: u8, rs1: u8, off: i64) -> String {
+    match (rd, rs1, off) {
+        (0, 1, 0) => "ret".to_string(),
+        (0, _, 0) => format!("jr {}", x(rs1)),
+        (0, _, _) => format!("jr {}({})", off, x(rs1)),
+        (1, _, 0) => format!("jalr {}", x(rs1)),
+        (1, _, _) => format!("jalr {}({})", off, x(rs1)),
+        (_, _, 0) => format!("jalr {},{}", x(rd), x(rs1)),
+        _ => format!("jalr {},{}({})", x(rd), off, x(rs1)),
+    }
+}
+
+fn addi(rd: u8, rs1: u8, imm: i64) -> String {
+    match (rd, rs1, imm) {
+        (0, 0, 0) => "nop".to_string(),
+        (_, 0, _) => format!("li {},{}", x(rd), imm),
+        (_, _, 0) => format!("mv {},{}", x(rd), x(rs1)),
+        _ => op3("addi", &x(rd), &x(rs1), imm),
+    }
+}
+
+fn rv32i(inst: &RV32I, xlen: Xlen) -> String {
+    use RV32I::*;
+    let rr = |m: &str, r: &RType| op3(m, &x(r.rd), &x(r.rs1), x(r.rs2));
+    let ri = |m: &str, i: &IType| op3(m, &x(i.rd), &x(i.rs1), i.imm.as_i64());
+    match inst {
+        Lui(u) => format!("lui {},{:#x}", x(u.rd), u.imm.low_u32() >> 12),
+        Auipc(u) => format!("auipc {},{:#x}", x(u.rd), u.imm.low_u32() >> 12),
+        Jal(j) => jal(j.rd, j.imm.as_i64(), xlen),
+        Jalr(i) => jalr(i.rd, i.rs1, i.imm.as_i64()),
+
+        Beq(b) => branch("beq", b, xlen),
+        Bne(b) => branch("bne", b, xlen),
+        Blt(b) => branch("blt", b, xlen),
+        Bge(b) => branch("bge", b, xlen),
+        Bltu(b) => branch("bltu", b, xlen),
+        Bgeu(b) => branch("bgeu", b, xlen),
+
+        Lb(i) => mem("lb", &x(i.rd), i.imm.as_i64(), i.rs1),
+        Lh(i) => mem("lh", &x(i.rd), i.imm.as_i64(), i.rs1),
+        Lw(i) => mem("lw", &x(i.rd), i.imm.as_i64(), i.rs1),
+        Lbu(i) => mem("lbu", &x(i.rd), i.imm.as_i64(), i.rs1),
+        Lhu(i) => mem("lhu", &x(i.rd), i.imm.as_i64(), i.rs1),
+        Sb(s) => mem("sb", &x(s.rs2), s.imm.as_i64(), s.rs1),
+        Sh(s) => mem("sh", &x(s.rs2), s.imm.as_i64(), s.rs1),
+        Sw(s) => mem("sw", &x(s.rs2), s.imm.as_i64(), s.rs1),
+
+        Addi(i) => addi(i.rd, i.rs1, i.imm.as_i64()),
+        Slti(i) => ri("slti", i),
+        Sltiu(i) if i.imm.as_i64() == 1 => format!("seqz {},{}", x(i.rd), x(i.rs1)),
+        Sltiu(i) => ri("sltiu", i),
+        Xori(i) if i.imm.as_i64() == -1 => format!("not {},{}", x(i.rd), x(i.rs1)),
+        Xori(i) => ri("xori", i),
+        Ori(i) => ri("ori", i),
+        Andi(i) if i.imm.as_i64() == 255 => format!("zext.b {},{}", x(i.rd), x(i.rs1)),
+        Andi(i) => ri("andi", i),
+        Slli(i) => op3("slli", &x(i.rd), &x(i.rs1), format!("{:#x}", shamt(&i.imm, xlen))),
+        Srli(i) => op3("srli", &x(i.rd), &x(i.rs1), format!("{:#x}", shamt(&i.imm, xlen))),
+        Srai(i) => op3("srai", &x(i.rd), &x(i.rs1), format!("{:#x}", shamt(&i.imm, xlen))),
+
+        Add(r) => rr("add", r),
+        Sub(r) if r.rs1 == 0 => format!("neg {},{}", x(r.rd), x(r.rs2)),
+        Sub(r) => rr("sub", r),
+        Sll(r) => rr("sll", r),
+        Slt(r) if r.rs2 == 0 => format!("sltz {},{}", x(r.rd), x(r.rs1)),
+        Slt(r) if r.rs1 == 0 => format!("sgtz {},{}", x(r.rd), x(r.rs2)),
+        Slt(r) => rr("slt", r),
+        Sltu(r) if r.rs1 == 0 => format!("snez {},{}", x(r.rd), x(r.rs2)),
+        Sltu(r) => rr("sltu", r),
+        Xor(r) => rr("xor", r),
+        Srl(r) => rr("srl", r),
+        Sra(r) => rr("sra", r),
+        Or(r) => rr("or", r),
+        And(r) => rr("and", r),
+
+        Mul(r) => rr("mul", r),
+        Mulh(r) => rr("mulh", r),
+        Mulhsu(r) => rr("mulhsu", r),
+        Mulhu(r) => rr("mulhu", r),
+        Div(r) => rr("div", r),
+        Divu(r) => rr("divu", r),
+        Rem(r) => rr("rem", r),
+        Remu(r) => rr("remu", r),
+
+        // objdump leaves out the default iorw, iorw
+        Fence(i) if i.imm.low_u32() == 0x0ff => "fence".to_string(),
+        Fence(i) => super::rv32i::fence(i.imm.low_u32(), ","),
+        FenceI(_) => "fence.i".to_string(),
+        Ecall(_) => "ecall".to_string(),
+        Ebreak(_) => "ebreak".to_string(),
+    }
+}
+
+fn rv64i(inst: &RV64I, xlen: Xlen) -> String {
+    use RV64I::*;
+    let rr = |m: &str, r: &RType| op3(m, &x(r.rd), &x(r.rs1), x(r.rs2));
+    let sh = |m: &str, i: &IType| op3(m, &x(i.rd), &x(i.rs1), format!("{:#x}", shamt(&i.imm, xlen)));
+    match inst {
+        Lwu(i) => mem("lwu", &x(i.rd), i.imm.as_i64(), i.rs1),
+        Ld(i) => mem("ld", &x(i.rd), i.imm.as_i64(), i.rs1),
+        Sd(s) => mem("sd", &x(s.rs2), s.imm.as_i64(), s.rs1),
+
+        Sll(r) => rr("sll", r),
+        Srl(r) => rr("srl", r),
+        Sra(r) => rr("sra", r),
+
+        Slli(i) => sh("slli", i),
+        Srli(i) => sh("srli", i),
+        Srai(i) => sh("srai", i),
+
+        Addiw(i) if i.imm.as_i64() == 0 => format!("sext.w {},{}", x(i.rd), x(i.rs1)),
+        Addiw(i) => op3("addiw", &x(i.rd), &x(i.rs1), i.imm.as_i64()),
+        Slliw(i) => op3("slliw", &x(i.rd), &x(i.rs1), format!("{:#x}", i.imm.low_u32() & 0x1f)),
+        Srliw(i) => op3("srliw", &x(i.rd), &x(i.rs1), format!("{:#x}", i.imm.low_u32() & 0x1f)),
+        Sraiw(i) => op3("sraiw", &x(i.rd), &x(i.rs1), format!("{:#x}", i.imm.low_u32() & 0x1f)),
+
+        Addw(r) => rr("addw", r),
+        Subw(r) if r.rs1 == 0 => format!("negw {},{}", x(r.rd), x(r.rs2)),
+        Subw(r) => rr("subw", r),
+        Sllw(r) => rr("sllw", r),
+        Srlw(r) => rr("srlw", r),
+        Sraw(r) => rr("sraw", r),
+
+        Mulw(r) => rr("mulw", r),
+        Divw(r) => rr("divw", r),
+        Divuw(r) => rr("divuw", r),
+        Remw(r) => rr("remw", r),
+        Remuw(r) => rr("remuw", r),
+    }
+}
+
+fn rv128i(inst: &RV128I) -> String {
+    use RV128I::*;
+    let m = inst.mnemonic();
+    match inst {
+        Ldu(i) | Lq(i) => mem(m, &x(i.rd), i.imm.as_i64(), i.rs1),
+        Sq(s) => mem(m, &x(s.rs2), s.imm.as_i64(), s.rs1),
+        Addid(i) => op3(m, &x(i.rd), &x(i.rs1), i.imm.as_i64()),
+        Sllid(i) | Srlid(i) | Sraid(i) => op3(m, &x(i.rd), &x(i.rs1), format!("{:#x}", i.imm.low_u32())),
+        Addd(r) | Subd(r) | Slld(r) | Srld(r) | Srad(r) | Muld(r) | Divd(r) | Divud(r)
+        | Remd(r) | Remud(r) => op3(m, &x(r.rd), &x(r.rs1), x(r.rs2)),
+    }
+}
+
+fn rvc(inst: &RVC, xlen: Xlen) -> String {
+    use RVC::*;
+    let ca = |m: &str, c: &CAType| op3(m, &x(c.rdrs1), &x(c.rdrs1), x(c.rs2));
+    let ci_sh = |m: &str, c: &CIType, sh: u32| op3(m, &x(c.rdrs1), &x(c.rdrs1), format!("{:#x}", sh));
+    match inst {
+        Caddi4spn(c) => op3("addi", &x(c.rd), "sp", c.uimm.low32()),
+        Cfld(c) => mem("fld", f(c.rd), c.imm.low_u32() as i64, c.rs1),
+        Clq(c) => mem("lq", &x(c.rd), c.imm.low_u32() as i64, c.rs1),
+        Clw(c) => mem("lw", &x(c.rd), c.imm.low_u32() as i64, c.rs1),
+        Cflw(c) => mem("flw", f(c.rd), c.imm.low_u32() as i64, c.rs1),
+        Cld(c) => mem("ld", &x(c.rd), c.imm.low_u32() as i64, c.rs1),
+        Cfsd(c) => mem("fsd", f(c.rs2), c.imm.low_u32() as i64, c.rs1),
+        Csq(c) => mem("sq", &x(c.rs2), c.imm.low_u32() as i64, c.rs1),
+        Csw(c) => mem("sw", &x(c.rs2), c.imm.low_u32() as i64, c.rs1),
+        Cfsw(c) => mem("fsw", f(c.rs2), c.imm.low_u32() as i64, c.rs1),
+        Csd(c) => mem("sd", &x(c.rs2), c.imm.low_u32() as i64, c.rs1),
+
+        Cnop(_) => "nop".to_string(),
+        Caddi(c) => addi(c.rdrs1, c.rdrs1, c.imm.as_i64()),
+        Cjal(c) => jal(1, c.target.as_i64(), xlen),
+        Caddiw(c) if c.imm.as_i64() == 0 => format!("sext.w {},{}", x(c.rdrs1), x(c.rdrs1)),
+        Caddiw(c) => op3("addiw", &x(c.rdrs1), &x(c.rdrs1), c.imm.as_i64()),
+        Cli(c) => format!("li {},{}", x(c.rdrs1), c.imm.as_i64()),
+        Caddi16sp(c) => op3("addi", "sp", "sp", c.imm.as_i64()),
+        Clui(c) => format!("lui {},{:#x}", x(c.rdrs1), (c.imm.as_i64() >> 12) as u32 & 0xfffff),
+        Csrli(c) => ci_sh("srli", c, c.imm.low_u32()),
+        Csrli64(c) => ci_sh("srli", c, 64),
+        Csrai(c) => ci_sh("srai", c, c.imm.low_u32()),
+        Csrai64(c) => ci_sh("srai", c, 64),
+        Candi(c) => op3("andi", &x(c.rdrs1), &x(c.rdrs1), c.imm.as_i64()),
+        Csub(c) => ca("sub", c),
+        Cxor(c) => ca("xor", c),
+        Cor(c) => ca("or", c),
+        Cand(c) => ca("and", c),
+        Csubw(c) => ca("subw", c),
+        Caddw(c) => ca("addw", c),
+        Cj(c) => jal(0, c.target.as_i64(), xlen),
+        Cbeqz(c) => format!("beqz {},{}", x(c.rs1), target(c.off.as_i64(), xlen)),
+        Cbnez(c) => format!("bnez {},{}", x(c.rs1), target(c.off.as_i64(), xlen)),
+
+        Cslli(c) => ci_sh("slli", c, c.imm.low_u32()),
+        Cslli64(c) => ci_sh("slli", c, 64),
+        Cfldsp(c) => mem("fld", f(c.rdrs1), c.imm.low_u32() as i64, 2),
+        Clqsp(c) => mem("lq", &x(c.rdrs1), c.imm.low_u32() as i64, 2),
+        Clwsp(c) => mem("lw", &x(c.rdrs1), c.imm.low_u32() as i64, 2),
+        Cflwsp(c) => mem("flw", f(c.rdrs1), c.imm.low_u32() as i64, 2),
+        Cldsp(c) => mem("ld", &x(c.rdrs1), c.imm.low_u32() as i64, 2),
+        Cjr(c) => jalr(0, c.rdrs1, 0),
+        Cmv(c) => format!("mv {},{}", x(c.rdrs1), x(c.rs2)),
+        Cebreak(_) => "ebreak".to_string(),
+        Cmop(c) => format!("c.mop.{}", c.rdrs1),
+        Cntl(c) => RVZihint::ntl(c.rs2).map_or_else(|| "unknown".to_string(), |h| h.to_string()),
+        Cjalr(c) => jalr(1, c.rdrs1, 0),
+        Cadd(c) => op3("add", &x(c.rdrs1), &x(c.rdrs1), x(c.rs2)),
+        Cfsdsp(c) => mem("fsd", f(c.rs2), c.imm.low_u32() as i64, 2),
+        Csqsp(c) => mem("sq", &x(c.rs2), c.imm.low_u32() as i64, 2),
+        Cswsp(c) => mem("sw", &x(c.rs2), c.imm.low_u32() as i64, 2),
+        Cfswsp(c) => mem("fsw", f(c.rs2), c.imm.low_u32() as i64, 2),
+        Csdsp(c) => mem("sd", &x(c.rs2), c.imm.low_u32() as i64, 2),
+
+        Clbu(c) => mem("lbu", &x(c.rd), c.imm.low_u32() as i64, c.rs1),
+        Clhu(c) => mem("lhu", &x(c.rd), c.imm.low_u32() as i64, c.rs1),
+        Clh(c) => mem("lh", &x(c.rd), c.imm.low_u32() as i64, c.rs1),
+        Csb(c) => mem("sb", &x(c.rs2), c.imm.low_u32() as i64, c.rs1),
+        Csh(c) => mem("sh", &x(c.rs2), c.imm.low_u32() as i64, c.rs1),
+        CzextB(c) => format!("zext.b {},{}", x(c.rdrs1), x(c.rdrs1)),
+        CsextB(c) => format!("sext.b {},{}", x(c.rdrs1), x(c.rdrs1)),
+        CzextH(c) => format!("zext.h {},{}", x(c.rdrs1), x(c.rdrs1)),
+        CsextH(c) => format!("sext.h {},{}", x(c.rdrs1), x(c.rdrs1)),
+        CzextW(c) => format!("zext.w {},{}", x(c.rdrs1), x(c.rdrs1)),
+        Cnot(c) => format!("not {},{}", x(c.rdrs1), x(c.rdrs1)),
+        Cmul(c) => ca("mul", c),
+        // push/pop and the table jumps have no single base instruction
+        CmPush(c) => format!("cm.push {},-{}", c.rlist_string(","), c.stack_adj),
+        CmPop(c) => format!("cm.pop {},{}", c.rlist_string(","), c.stack_adj),
+        CmPopretz(c) => format!("cm.popretz {},{}", c.rlist_string(","), c.stack_adj),
+        CmPopret(c) => format!("cm.popret {},{}", c.rlist_string(","), c.stack_adj),
+        CmMvsa01(c) => format!("cm.mvsa01 {},{}", x(c.rdrs1), x(c.rs2)),
+        CmMva01s(c) => format!("cm.mva01s {},{}", x(c.rdrs1), x(c.rs2)),
+        CmJt(_) | CmJalt(_) => inst.to_string(),
+    }
+}
+
+fn zicsr(inst: &RVZicsr, xlen: Xlen) -> String {
+    let (mnem, ops) = inst.short_form(Some(xlen));
+    if ops.is_empty() {
+        mnem.to_string()
+    } else {
+        format!("{} {}", mnem, ops.join(","))
+    }
+}
+
+/// `sfence.vma` and friends drop trailing zero operands, as objdump does.
+fn privileged(p: &RVPriv) -> String {
+    use RVPriv::*;
+    match p {
+        SfenceVma(r) | HfenceVvma(r) | HfenceGvma(r) | SinvalVma(r) | HinvalVvma(r) | HinvalGvma(r)
+            if r.rs2 == 0 =>
+        {
+            if r.rs1 == 0 {
+                p.mnemonic().to_string()
+            } else {
+                format!("{} {}", p.mnemonic(), x(r.rs1))
+            }
+        }
+        _ => p.format_with(","),
+    }
+}
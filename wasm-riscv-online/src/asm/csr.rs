@@ -0,0 +1,202 @@
+//! Control and status register names, following the privileged spec and the
+//! binutils CSR table. Numbers without a name are printed as hex.
+use crate::riscv::imm::Xlen;
+
+/// Fixed CSRs: (number, name, RV32 only)
+static CSRS: &[(u16, &str, bool)] = &[
+    // unprivileged floating-point, vector, entropy and table-jump CSRs
+    (0x001, "fflags", false),
+    (0x002, "frm", false),
+    (0x003, "fcsr", false),
+    (0x008, "vstart", false),
+    (0x009, "vxsat", false),
+    (0x00a, "vxrm", false),
+    (0x00f, "vcsr", false),
+    (0x015, "seed", false),
+    (0x017, "jvt", false),
+    // unprivileged counters/timers
+    (0xc00, "cycle", false),
+    (0xc01, "time", false),
+    (0xc02, "instret", false),
+    (0xc20, "vl", false),
+    (0xc21, "vtype", false),
+    (0xc22, "vlenb", false),
+    (0xc80, "cycleh", true),
+    (0xc81, "timeh", true),
+    (0xc82, "instreth", true),
+    // supervisor
+    (0x100, "sstatus", false),
+    (0x104, "sie", false),
+    (0x105, "stvec", false),
+    (0x106, "scounteren", false),
+    (0x10a, "senvcfg", false),
+    (0x140, "sscratch", false),
+    (0x141, "sepc", false),
+    (0x142, "scause", false),
+    (0x143, "stval", false),
+    (0x144, "sip", false),
+    (0x14d, "stimecmp", false),
+    (0x15d, "stimecmph", true),
+    (0x150, "siselect", false),
+    (0x151, "sireg", false),
+    (0x15c, "stopei", false),
+    (0x180, "satp", false),
+    (0x5a8, "scontext", false),
+    (0xdb0, "stopi", false),
+    // hypervisor and virtual supervisor
+    (0x600, "hstatus", false),
+    (0x602, "hedeleg", false),
+    (0x603, "hideleg", false),
+    (0x604, "hie", false),
+    (0x605, "htimedelta", false),
+    (0x606, "hcounteren", false),
+    (0x607, "hgeie", false),
+    (0x608, "hvien", false),
+    (0x609, "hvictl", false),
+    (0x60a, "henvcfg", false),
+    (0x612, "hedelegh", true),
+    (0x613, "hidelegh", true),
+    (0x615, "htimedeltah", true),
+    (0x618, "hvienh", true),
+    (0x61a, "henvcfgh", true),
+    (0x643, "htval", false),
+    (0x644, "hip", false),
+    (0x645, "hvip", false),
+    (0x646, "hviprio1", false),
+    (0x647, "hviprio2", false),
+    (0x655, "hviph", true),
+    (0x656, "hviprio1h", true),
+    (0x657, "hviprio2h", true),
+    (0x64a, "htinst", false),
+    (0x680, "hgatp", false),
+    (0x6a8, "hcontext", false),
+    (0xe12, "hgeip", false),
+    (0x200, "vsstatus", false),
+    (0x204, "vsie", false),
+    (0x205, "vstvec", false),
+    (0x214, "vsieh", true),
+    (0x240, "vsscratch", false),
+    (0x241, "vsepc", false),
+    (0x242, "vscause", false),
+    (0x243, "vstval", false),
+    (0x244, "vsip", false),
+    (0x24d, "vstimecmp", false),
+    (0x250, "vsiselect", false),
+    (0x251, "vsireg", false),
+    (0x254, "vsiph", true),
+    (0x25c, "vstopei", false),
+    (0x25d, "vstimecmph", true),
+    (0x280, "vsatp", false),
+    (0xeb0, "vstopi", false),
+    // machine information, trap setup and handling
+    (0xf11, "mvendorid", false),
+    (0xf12, "marchid", false),
+    (0xf13, "mimpid", false),
+    (0xf14, "mhartid", false),
+    (0xf15, "mconfigptr", false),
+    (0x300, "mstatus", false),
+    (0x301, "misa", false),
+    (0x302, "medeleg", false),
+    (0x303, "mideleg", false),
+    (0x304, "mie", false),
+    (0x305, "mtvec", false),
+    (0x306, "mcounteren", false),
+    (0x30a, "menvcfg", false),
+    (0x310, "mstatush", true),
+    (0x312, "medelegh", true),
+    (0x31a, "menvcfgh", true),
+    (0x320, "mcountinhibit", false),
+    (0x340, "mscratch", false),
+    (0x341, "mepc", false),
+    (0x342, "mcause", false),
+    (0x343, "mtval", false),
+    (0x344, "mip", false),
+    (0x34a, "mtinst", false),
+    (0x34b, "mtval2", false),
+    (0x740, "mnscratch", false),
+    (0x741, "mnepc", false),
+    (0x742, "mncause", false),
+    (0x744, "mnstatus", false),
+    (0x747, "mseccfg", false),
+    (0x757, "mseccfgh", true),
+    (0xb00, "mcycle", false),
+    (0xb02, "minstret", false),
+    (0xb80, "mcycleh", true),
+    (0xb82, "minstreth", true),
+    // debug/trace
+    (0x7a0, "tselect", false),
+    (0x7a1, "tdata1", false),
+    (0x7a2, "tdata2", false),
+    (0x7a3, "tdata3", false),
+    (0x7a4, "tinfo", false),
+    (0x7a5, "tcontrol", false),
+    (0x7a8, "mcontext", false),
+    (0x7aa, "mscontext", false),
+    (0x7b0, "dcsr", false),
+    (0x7b1, "dpc", false),
+    (0x7b2, "dscratch0", false),
+    (0x7b3, "dscratch1", false),
+];
+
+/// Numbered CSR families: (number of the first index, prefix, first index,
+/// last index, suffix, RV32 only)
+static CSR_RANGES: &[(u16, &str, u16, u16, &str, bool)] = &[
+    (0xc03, "hpmcounter", 3, 31, "", false),
+    (0xc83, "hpmcounter", 3, 31, "h", true),
+    (0xb03, "mhpmcounter", 3, 31, "", false),
+    (0xb83, "mhpmcounter", 3, 31, "h", true),
+    (0x323, "mhpmevent", 3, 31, "", false),
+    (0x723, "mhpmevent", 3, 31, "h", true),
+    (0x3a0, "pmpcfg", 0, 15, "", false),
+    (0x3b0, "pmpaddr", 0, 63, "", false),
+    (0x10c, "sstateen", 0, 3, "", false),
+    (0x60c, "hstateen", 0, 3, "", false),
+    (0x61c, "hstateen", 0, 3, "h", true),
+    (0x30c, "mstateen", 0, 3, "", false),
+    (0x31c, "mstateen", 0, 3, "h", true),
+];
+
+/// Hypervisor and virtual-supervisor CSRs, which only exist with the H
+/// extension: the ones whose privilege field (bits 9:8) is 2.
+pub fn is_hypervisor_csr(csr: u16) -> bool {
+    (csr >> 8) & 0b11 == 0b10
+}
+
+/// Name of a CSR number, if it has one, and whether it is RV32 only.
+pub fn csr_name(csr: u16) -> Option<(String, bool)> {
+    if let Some((_, name, rv32)) = CSRS.iter().find(|(n, _, _)| *n == csr) {
+        return Some((name.to_string(), *rv32));
+    }
+    CSR_RANGES.iter().find_map(|&(base, prefix, first, last, suffix, rv32)| {
+        let idx = csr.checked_sub(base)? + first;
+        if idx <= last { Some((format!("{}{}{}", prefix, idx, suffix), rv32)) } else { None }
+    })
+}
+
+/// CSR operand text: the name when known, otherwise hex. With a known xlen
+/// other than RV32, the RV32-only CSRs are hex too, as they have no name
+/// the assembler takes there.
+pub fn csr_to_string(csr: u16, xlen: Option<Xlen>) -> String {
+    match csr_name(csr) {
+        Some((_, true)) if xlen.is_some_and(|x| x != Xlen::X32) => format!("{:#x}", csr),
+        Some((name, _)) => name,
+        None => format!("{:#x}", csr),
+    }
+}
+
+/// Look up a CSR by name: (number, RV32 only).
+pub fn csr_from_name(name: &str) -> Option<(u16, bool)> {
+    let name = name.trim().to_lowercase();
+    if let Some((n, _, rv32)) = CSRS.iter().find(|(_, s, _)| *s == name) {
+        return Some((*n, *rv32));
+    }
+    CSR_RANGES.iter().find_map(|&(base, prefix, first, last, suffix, rv32)| {
+        let idx = name.strip_prefix(prefix)?.strip_suffix(suffix)?;
+        // reject leading zeros and signs so that every number has one spelling
+        if idx.is_empty() || (idx.len() > 1 && idx.starts_with('0')) || !idx.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let idx: u16 = idx.parse().ok()?;
+        if (first..=last).contains(&idx) { Some((base + idx - first, rv32)) } else { None }
+    })
+}
@@ -1,31 +1,63 @@
 #![allow(dead_code)]
 pub use rv32i::RV32I;
 pub use rv64i::RV64I;
+pub use rv128i::RV128I;
 pub use rvc::RVC;
-pub use rvf::RVF;
+pub use rvf::{FpFmt, FpInt, RVF};
 pub use rvzicsr::RVZicsr;
 pub use rva::RV32A;
 pub use rva::RV64A;
 pub use rva::RV128A;
-use crate::riscv::imm::{Imm, Uimm};
+pub use rva::{AmoWidth, RVZabha};
+pub use rvb::RVB;
+pub use rvk::RVK;
+pub use rvpriv::RVPriv;
+pub use rvv::{VAddr, VArith, VMem, VsetType, RVV};
+pub use rvzicbo::RVZicbo;
+pub use rvzicond::RVZicond;
+pub use rvzihint::RVZihint;
+pub use rvzimop::RVZimop;
+use crate::riscv::imm::{Imm, Uimm, Xlen};
 
 pub mod rv32i;
 pub mod rv64i;
+pub mod rv128i;
 pub mod rvc;
 pub mod rvf;
 pub mod rvzicsr;
 pub mod rva;
+pub mod rvb;
+pub mod rvk;
+pub mod rvpriv;
+pub mod rvv;
+pub mod rvzicbo;
+pub mod rvzicond;
+pub mod rvzihint;
+pub mod rvzimop;
+pub mod csr;
+mod alias;
+mod regs;
 
 #[derive(Debug, Clone, Copy)]
 pub enum Instruction {
     RV32I(RV32I),
     RV64I(RV64I),
+    RV128I(RV128I),
     RVC(RVC),
     RVZicsr(RVZicsr),
     RVF(RVF),
     RV32A(RV32A),
     RV64A(RV64A),
     RV128A(RV128A),
+    RVB(RVB),
+    RVK(RVK),
+    RVV(RVV),
+    RVPriv(RVPriv),
+    RVZicond(RVZicond),
+    RVZimop(RVZimop),
+    RVZabha(RVZabha),
+    RVZicbo(RVZicbo),
+    RVZihint(RVZihint),
 }
 
 impl Instruction {
@@ -33,14 +65,53 @@ impl Instruction {
         match self {
             Self::RV32I(rv32i) => rv32i.to_string(),
             Self::RV64I(rv64i) => rv64i.to_string(),
+            Self::RV128I(rv128i) => rv128i.to_string(),
             Self::RVC(rvc) => rvc.to_string(),
             Self::RVZicsr(rvzicsr) => rvzicsr.to_string(),
             Self::RVF(rvf) => rvf.to_string(),
             Self::RV32A(rv32a) => rv32a.to_string(),
             Self::RV64A(rv64a) => rv64a.to_string(),
             Self::RV128A(rv128a) => rv128a.to_string(),
+            Self::RVB(rvb) => rvb.to_string(),
+            Self::RVK(rvk) => rvk.to_string(),
+            Self::RVV(rvv) => rvv.to_string(),
+            Self::RVPriv(p) => p.to_string(),
+            Self::RVZicond(z) => z.to_string(),
+            Self::RVZimop(z) => z.to_string(),
+            Self::RVZabha(a) => a.to_string(),
+            Self::RVZicbo(z) => z.to_string(),
+            Self::RVZihint(h) => h.to_string(),
         }
     }
+
+    /// Like `disassembly`, but floating-point operands are named as the
+    /// integer registers they live in under Zfinx.
+    pub fn disassembly_zfinx(&self) -> String {
+        match self {
+            Self::RVF(rvf) => rvf.to_string_zfinx(),
+            _ => self.disassembly(),
+        }
+    }
+
+    /// Canonical text for a known xlen; only the CSR instructions print
+    /// differently (see `RVZicsr::to_string_xlen`).
+    pub fn disassembly_xlen(&self, xlen: Xlen) -> String {
+        match self {
+            Self::RVZicsr(z) => z.to_string_xlen(xlen),
+            _ => self.disassembly(),
+        }
+    }
+
+    /// objdump-style text using the binutils pseudo-instruction aliases
+    /// (`nop`, `ret`, `li`, `rdcycle`, ...).
+    pub fn disassembly_objdump(&self, xlen: Xlen) -> String {
+        alias::format(self, xlen)
+    }
+
+    /// Integer registers the instruction reads or writes (see `regs`).
+    pub fn int_registers(&self) -> Vec<u8> {
+        regs::int_registers(self)
+    }
 }
 
 impl From<RV32I> for Instruction {
@@ -55,6 +126,12 @@ impl From<RV64I> for Instruction {
     }
 }
 
+impl From<RV128I> for Instruction {
+    fn from(src: RV128I) -> Instruction {
+        Instruction::RV128I(src)
+    }
+}
+
 impl From<RVC> for Instruction {
     fn from(src: RVC) -> Instruction {
         Instruction::RVC(src)
@@ -91,6 +168,60 @@ impl From<RV128A> for Instruction {
     }
 }
 
+impl From<RVB> for Instruction {
+    fn from(src: RVB) -> Instruction {
+        Instruction::RVB(src)
+    }
+}
+
+impl From<RVK> for Instruction {
+    fn from(src: RVK) -> Instruction {
+        Instruction::RVK(src)
+    }
+}
+
+impl From<RVV> for Instruction {
+    fn from(src: RVV) -> Instruction {
+        Instruction::RVV(src)
+    }
+}
+
+impl From<RVPriv> for Instruction {
+    fn from(src: RVPriv) -> Instruction {
+        Instruction::RVPriv(src)
+    }
+}
+
+impl From<RVZicond> for Instruction {
+    fn from(src: RVZicond) -> Instruction {
+        Instruction::RVZicond(src)
+    }
+}
+
+impl From<RVZimop> for Instruction {
+    fn from(src: RVZimop) -> Instruction {
+        Instruction::RVZimop(src)
+    }
+}
+
+impl From<RVZicbo> for Instruction {
+    fn from(src: RVZicbo) -> Instruction {
+        Instruction::RVZicbo(src)
+    }
+}
+
+impl From<RVZihint> for Instruction {
+    fn from(src: RVZihint) -> Instruction {
+        Instruction::RVZihint(src)
+    }
+}
+
+impl From<RVZabha> for Instruction {
+    fn from(src: RVZabha) -> Instruction {
+        Instruction::RVZabha(src)
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct UType {
     pub rd: u8,
@@ -201,6 +332,15 @@ pub struct CJType {
     pub target: Imm,
 }
 
+/// Zcmp push/pop operands: the register-list code (4 = `{ra}`, 5 =
+/// `{ra, s0}`, ... 15 = `{ra, s0-s11}`) and the whole stack adjustment in
+/// bytes. The encoding keeps only the part above what the list itself needs.
+#[derive(Debug, Clone, Copy)]
+pub struct CMPPType {
+    pub rlist: u8,
+    pub stack_adj: u32,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct CsrRType {
     pub rd: u8,
@@ -216,6 +356,26 @@ pub struct CsrIType {
     pub csr: u16,
 }
 
+/// A-extension operands; `aq`/`rl` are the acquire/release ordering bits.
+#[derive(Debug, Clone, Copy)]
+pub struct AType {
+    pub rd: u8,
+    pub rs1: u8,
+    pub rs2: u8,
+    pub aq: bool,
+    pub rl: bool,
+}
+
+/// Scalar-crypto byte-select operands (`aes32esi`, `sm4ed`): `bs` picks
+/// the byte of rs2 to operate on.
+#[derive(Debug, Clone, Copy)]
+pub struct KType {
+    pub rd: u8,
+    pub rs1: u8,
+    pub rs2: u8,
+    pub bs: u8,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct R4Type {
     pub rd: u8,
@@ -309,3 +469,19 @@ pub fn from_register(name: &str) -> Option<u8> {
         _ => None,
     }
 }
+
+pub fn from_fp_register(name: &str) -> Option<u8> {
+    const ABI: [&str; 32] = [
+        "ft0", "ft1", "ft2", "ft3", "ft4", "ft5", "ft6", "ft7", "fs0", "fs1", "fa0", "fa1", "fa2",
+        "fa3", "fa4", "fa5", "fa6", "fa7", "fs2", "fs3", "fs4", "fs5", "fs6", "fs7", "fs8", "fs9",
+        "fs10", "fs11", "ft8", "ft9", "ft10", "ft11",
+    ];
+    let s = name.trim().to_lowercase();
+    // fN numeric
+    if let Some(num) = s.strip_prefix('f') {
+        if let Ok(n) = num.parse::<u8>() {
+            if n <= 31 { return Some(n); }
+        }
+    }
+    ABI.iter().position(|&r| r == s).map(|n| n as u8)
+}
@@ -0,0 +1,204 @@
+//! The integer registers an instruction names, for checks that depend on
+//! the register file rather than the opcode (RV32E/RV64E only have
+//! x0–x15). Fields that hold a function code or an immediate (the rs2 of
+//! unary bit-manipulation ops, the AVL of `vsetivli`, ...) are left out, as
+//! are floating-point and vector registers.
+use super::*;
+
+impl RType {
+    fn regs(&self) -> Vec<u8> {
+        vec![self.rd, self.rs1, self.rs2]
+    }
+
+    /// rd and rs1 of a unary operation; rs2 selects the operation.
+    fn unary_regs(&self) -> Vec<u8> {
+        vec![self.rd, self.rs1]
+    }
+}
+
+impl IType {
+    fn regs(&self) -> Vec<u8> {
+        vec![self.rd, self.rs1]
+    }
+}
+
+impl SType {
+    fn regs(&self) -> Vec<u8> {
+        vec![self.rs1, self.rs2]
+    }
+}
+
+impl AType {
+    fn regs(&self) -> Vec<u8> {
+        vec![self.rd, self.rs1, self.rs2]
+    }
+}
+
+pub(super) fn int_registers(inst: &Instruction) -> Vec<u8> {
+    match inst {
+        Instruction::RV32I(i) => rv32i(i),
+        Instruction::RV64I(i) => rv64i(i),
+        Instruction::RV128I(i) => rv128i(i),
+        Instruction::RVC(c) => rvc(c),
+        Instruction::RVZicsr(z) => match z {
+            RVZicsr::Csrrw(c) | RVZicsr::Csrrs(c) | RVZicsr::Csrrc(c) => vec![c.rd, c.rs1],
+            RVZicsr::Csrrwi(c) | RVZicsr::Csrrsi(c) | RVZicsr::Csrrci(c) => vec![c.rd],
+        },
+        Instruction::RVF(f) => rvf(f),
+        Instruction::RV32A(a) => a.parts().1.regs(),
+        Instruction::RV64A(a) => a.parts().1.regs(),
+        Instruction::RV128A(a) => a.parts().1.regs(),
+        Instruction::RVB(b) => rvb(b),
+        Instruction::RVK(k) => rvk(k),
+        Instruction::RVV(v) => rvv(v),
+        Instruction::RVPriv(p) => rvpriv(p),
+        Instruction::RVZicond(z) => z.operands().regs(),
+        Instruction::RVZimop(RVZimop::MopR(_, r)) => r.unary_regs(),
+        Instruction::RVZimop(RVZimop::MopRR(_, r)) => r.regs(),
+        Instruction::RVZabha(a) => a.parts().2.regs(),
+        Instruction::RVZicbo(z) => match z {
+            RVZicbo::CboInval(r) | RVZicbo::CboClean(r) | RVZicbo::CboFlush(r) | RVZicbo::CboZero(r) => vec![r.rs1],
+            RVZicbo::PrefetchI(i) | RVZicbo::PrefetchR(i) | RVZicbo::PrefetchW(i) => vec![i.rs1],
+        },
+        // the x2..x5 of ntl.* select the hint
+        Instruction::RVZihint(_) => vec![],
+    }
+}
+
+fn rv32i(inst: &RV32I) -> Vec<u8> {
+    use RV32I::*;
+    match inst {
+        Lui(u) | Auipc(u) => vec![u.rd],
+        Jal(j) => vec![j.rd],
+        Beq(b) | Bne(b) | Blt(b) | Bge(b) | Bltu(b) | Bgeu(b) => vec![b.rs1, b.rs2],
+        Jalr(i) | Lb(i) | Lh(i) | Lw(i) | Lbu(i) | Lhu(i) | Addi(i) | Slti(i) | Sltiu(i)
+        | Xori(i) | Ori(i) | Andi(i) | Slli(i) | Srli(i) | Srai(i) => i.regs(),
+        Sb(s) | Sh(s) | Sw(s) => s.regs(),
+        Add(r) | Sub(r) | Sll(r) | Slt(r) | Sltu(r) | Xor(r) | Srl(r) | Sra(r) | Or(r) | And(r)
+        | Mul(r) | Mulh(r) | Mulhsu(r) | Mulhu(r) | Div(r) | Divu(r) | Rem(r) | Remu(r) => r.regs(),
+        Fence(_) | FenceI(_) | Ecall(_) | Ebreak(_) => vec![],
+    }
+}
+
+fn rv64i(inst: &RV64I) -> Vec<u8> {
+    use RV64I::*;
+    match inst {
+        Lwu(i) | Ld(i) | Slli(i) | Srli(i) | Srai(i) | Addiw(i) | Slliw(i) | Srliw(i) | Sraiw(i) => {
+            i.regs()
+        }
+        Sd(s) => s.regs(),
+        Sll(r) | Srl(r) | Sra(r) | Addw(r) | Subw(r) | Sllw(r) | Srlw(r) | Sraw(r) | Mulw(r)
+        | Divw(r) | Divuw(r) | Remw(r) | Remuw(r) => r.regs(),
+    }
+}
+
+fn rv128i(inst: &RV128I) -> Vec<u8> {
+    use RV128I::*;
+    match inst {
+        Ldu(i) | Lq(i) | Addid(i) | Sllid(i) | Srlid(i) | Sraid(i) => i.regs(),
+        Sq(s) => s.regs(),
+        Addd(r) | Subd(r) | Slld(r) | Srld(r) | Srad(r) | Muld(r) | Divd(r) | Divud(r)
+        | Remd(r) | Remud(r) => r.regs(),
+    }
+}
+
+fn rvc(inst: &RVC) -> Vec<u8> {
+    use RVC::*;
+    match inst {
+        Caddi4spn(c) => vec![c.rd, 2],
+        Clq(c) | Clw(c) | Cld(c) => vec![c.rd, c.rs1],
+        Cfld(c) | Cflw(c) => vec![c.rs1],
+        Csq(c) | Csw(c) | Csd(c) => vec![c.rs1, c.rs2],
+        Cfsd(c) | Cfsw(c) => vec![c.rs1],
+        Cnop(_) | Cjal(_) | Cj(_) | Cebreak(_) | Cmop(_) | Cntl(_) => vec![],
+        Caddi(c) | Caddiw(c) | Cli(c) | Caddi16sp(c) | Clui(c) | Csrli(c) | Csrli64(c)
+        | Csrai(c) | Csrai64(c) | Candi(c) | Cslli(c) | Cslli64(c) => vec![c.rdrs1],
+        Csub(c) | Cxor(c) | Cor(c) | Cand(c) | Csubw(c) | Caddw(c) => vec![c.rdrs1, c.rs2],
+        Cbeqz(c) | Cbnez(c) => vec![c.rs1],
+        Clqsp(c) | Clwsp(c) | Cldsp(c) => vec![c.rdrs1, 2],
+        Cfldsp(_) | Cflwsp(_) => vec![2],
+        Cjr(c) | Cjalr(c) => vec![c.rdrs1],
+        Cmv(c) | Cadd(c) => vec![c.rdrs1, c.rs2],
+        Csqsp(c) | Cswsp(c) | Csdsp(c) => vec![c.rs2, 2],
+        Cfsdsp(_) | Cfswsp(_) => vec![2],
+        Clbu(c) | Clhu(c) | Clh(c) => vec![c.rd, c.rs1],
+        Csb(c) | Csh(c) => vec![c.rs1, c.rs2],
+        CzextB(c) | CsextB(c) | CzextH(c) | CsextH(c) | CzextW(c) | Cnot(c) => vec![c.rdrs1],
+        Cmul(c) => vec![c.rdrs1, c.rs2],
+        CmPush(c) | CmPop(c) | CmPopretz(c) | CmPopret(c) => {
+            let mut regs = c.registers();
+            regs.push(2);
+            regs
+        }
+        CmMvsa01(c) | CmMva01s(c) => vec![c.rdrs1, c.rs2, 10, 11],
+        CmJt(_) | CmJalt(_) => vec![],
+    }
+}
+
+fn rvf(inst: &RVF) -> Vec<u8> {
+    use RVF::*;
+    match inst {
+        Load(_, i) => vec![i.rs1],
+        Store(_, s) => vec![s.rs1],
+        Feq(_, r) | Flt(_, r) | Fle(_, r) | Fclass(_, r) | FcvtToInt(_, _, r) | FmvToInt(_, r)
+        | Fleq(_, r) | Fltq(_, r) | FcvtmodWD(r) | Fmvh(_, r) => vec![r.rd],
+        FcvtFromInt(_, _, r) | FmvFromInt(_, r) => vec![r.rs1],
+        Fmvp(_, r) => vec![r.rs1, r.rs2],
+        _ => vec![],
+    }
+}
+
+fn rvb(inst: &RVB) -> Vec<u8> {
+    use RVB::*;
+    match inst {
+        Slliuw(i) | Rori(i) | Roriw(i) | Bclri(i) | Bexti(i) | Binvi(i) | Bseti(i) => i.regs(),
+        Clz(r) | Ctz(r) | Cpop(r) | Sextb(r) | Sexth(r) | Zexth(r) | Orcb(r) | Rev8(r)
+        | Clzw(r) | Ctzw(r) | Cpopw(r) => r.unary_regs(),
+        Sh1add(r) | Sh2add(r) | Sh3add(r) | Adduw(r) | Sh1adduw(r) | Sh2adduw(r) | Sh3adduw(r)
+        | Andn(r) | Orn(r) | Xnor(r) | Max(r) | Maxu(r) | Min(r) | Minu(r) | Rol(r) | Ror(r)
+        | Rolw(r) | Rorw(r) | Clmul(r) | Clmulr(r) | Clmulh(r) | Bclr(r) | Bext(r) | Binv(r)
+        | Bset(r) => r.regs(),
+    }
+}
+
+fn rvk(inst: &RVK) -> Vec<u8> {
+    use RVK::*;
+    match inst {
+        Aes32dsi(k) | Aes32dsmi(k) | Aes32esi(k) | Aes32esmi(k) | Sm4ed(k) | Sm4ks(k) => {
+            vec![k.rd, k.rs1, k.rs2]
+        }
+        Aes64ks1i(i) => i.regs(),
+        Brev8(r) | Zip(r) | Unzip(r) | Aes64im(r) | Sha256sig0(r) | Sha256sig1(r)
+        | Sha256sum0(r) | Sha256sum1(r) | Sha512sig0(r) | Sha512sig1(r) | Sha512sum0(r)
+        | Sha512sum1(r) | Sm3p0(r) | Sm3p1(r) => r.unary_regs(),
+        Pack(r) | Packh(r) | Packw(r) | Xperm4(r) | Xperm8(r) | Aes64ds(r) | Aes64dsm(r)
+        | Aes64es(r) | Aes64esm(r) | Aes64ks2(r) | Sha512sig0h(r) | Sha512sig0l(r)
+        | Sha512sig1h(r) | Sha512sig1l(r) | Sha512sum0r(r) | Sha512sum1r(r) => r.regs(),
+    }
+}
+
+fn rvv(inst: &RVV) -> Vec<u8> {
+    match inst {
+        RVV::Vsetvli(v) => vec![v.rd, v.rs1],
+        RVV::Vsetivli(v) => vec![v.rd],
+        RVV::Vsetvl(r) => r.regs(),
+        RVV::Load(m) | RVV::Store(m) => match m.mode {
+            VAddr::Strided(rs2) => vec![m.rs1, rs2],
+            _ => vec![m.rs1],
+        },
+        RVV::Arith(a) => a.int_registers(),
+    }
+}
+
+fn rvpriv(inst: &RVPriv) -> Vec<u8> {
+    use RVPriv::*;
+    match inst {
+        Sret(_) | Mret(_) | Mnret(_) | Wfi(_) | WrsNto(_) | WrsSto(_) | SfenceWInval(_)
+        | SfenceInvalIr(_) => vec![],
+        SfenceVma(r) | HfenceVvma(r) | HfenceGvma(r) | SinvalVma(r) | HinvalVvma(r)
+        | HinvalGvma(r) => vec![r.rs1, r.rs2],
+        HlvB(r) | HlvBu(r) | HlvH(r) | HlvHu(r) | HlvxHu(r) | HlvW(r) | HlvxWu(r) | HlvWu(r)
+        | HlvD(r) => vec![r.rd, r.rs1],
+        HsvB(r) | HsvH(r) | HsvW(r) | HsvD(r) => vec![r.rs1, r.rs2],
+    }
+}
@@ -0,0 +1,76 @@
+use super::{to_register, IType, RType, SType};
+
+/// RV128I additions: quad-word loads and stores, and the double-word ALU
+/// operations that play the role the `*w` forms play on RV64.
+#[derive(Debug, Clone, Copy)]
+pub enum RV128I {
+    Ldu(IType),
+    Lq(IType),
+    Sq(SType),
+
+    Addid(IType),
+    Sllid(IType),
+    Srlid(IType),
+    Sraid(IType),
+
+    Addd(RType),
+    Subd(RType),
+    Slld(RType),
+    Srld(RType),
+    Srad(RType),
+
+    // RV128M double-word multiplication and division
+    Muld(RType),
+    Divd(RType),
+    Divud(RType),
+    Remd(RType),
+    Remud(RType),
+}
+
+impl RV128I {
+    pub fn mnemonic(&self) -> &'static str {
+        use RV128I::*;
+        match self {
+            Ldu(_) => "ldu",
+            Lq(_) => "lq",
+            Sq(_) => "sq",
+            Addid(_) => "addid",
+            Sllid(_) => "sllid",
+            Srlid(_) => "srlid",
+            Sraid(_) => "sraid",
+            Addd(_) => "addd",
+            Subd(_) => "subd",
+            Slld(_) => "slld",
+            Srld(_) => "srld",
+            Srad(_) => "srad",
+            Muld(_) => "muld",
+            Divd(_) => "divd",
+            Divud(_) => "divud",
+            Remd(_) => "remd",
+            Remud(_) => "remud",
+        }
+    }
+}
+
+impl core::fmt::Display for RV128I {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        use RV128I::*;
+        let m = self.mnemonic();
+        match self {
+            Ldu(i) | Lq(i) => write!(f, "{} {}, {:?}({})", m, to_register(i.rd), i.imm, to_register(i.rs1)),
+            Sq(s) => write!(f, "{} {}, {:?}({})", m, to_register(s.rs2), s.imm, to_register(s.rs1)),
+            Addid(i) | Sllid(i) | Srlid(i) | Sraid(i) => {
+                write!(f, "{} {}, {}, {:?}", m, to_register(i.rd), to_register(i.rs1), i.imm)
+            }
+            Addd(r) | Subd(r) | Slld(r) | Srld(r) | Srad(r) | Muld(r) | Divd(r) | Divud(r)
+            | Remd(r) | Remud(r) => write!(
+                f,
+                "{} {}, {}, {}",
+                m,
+                to_register(r.rd),
+                to_register(r.rs1),
+                to_register(r.rs2)
+            ),
+        }
+    }
+}
@@ -55,8 +55,9 @@ pub enum RV32I {
     Rem(RType),
     Remu(RType),
 
-    // System instructions with unit type
-    Fence(()),
+    // fence keeps fm/pred/succ in imm; the other system instructions have
+    // no operands
+    Fence(IType),
     FenceI(()),
     Ecall(()),
     Ebreak(()),
@@ -327,10 +328,24 @@ impl RV32I {
                 to_register(r.rs2)
             ),
 
-            Self::Fence(_) => format!("fence"),
+            Self::Fence(i) => fence(i.imm.low_u32(), ", "),
             Self::FenceI(_) => format!("fence.i"),
             Self::Ecall(_) => format!("ecall"),
             Self::Ebreak(_) => format!("ebreak"),
         }
     }
 }
+
+/// `fence pred, succ` with the sets spelled from `iorw`. A zero immediate is
+/// the bare `fence` this crate has always printed for `0x0000000f`.
+pub(super) fn fence(imm12: u32, sep: &str) -> String {
+    fn set(bits: u32) -> String {
+        let s: String = "iorw".chars().enumerate().filter(|&(n, _)| bits & (0b1000 >> n) != 0).map(|(_, c)| c).collect();
+        if s.is_empty() { "0".to_string() } else { s }
+    }
+    match imm12 & 0xfff {
+        0 => "fence".to_string(),
+        crate::isa::IMM12_FENCE_TSO => "fence.tso".to_string(),
+        imm => format!("fence {}{}{}", set(imm >> 4), sep, set(imm)),
+    }
+}
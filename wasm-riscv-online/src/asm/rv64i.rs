@@ -25,6 +25,13 @@ pub enum RV64I {
     Sllw(RType),
     Srlw(RType),
     Sraw(RType),
+
+    // RV64M word-sized multiplication and division
+    Mulw(RType),
+    Divw(RType),
+    Divuw(RType),
+    Remw(RType),
+    Remuw(RType),
 }
 
 impl RV64I {
@@ -141,6 +148,37 @@ impl RV64I {
                 to_register(r.rs1),
                 to_register(r.rs2)
             ),
+
+            Self::Mulw(r) => format!(
+                "mulw {}, {}, {}",
+                to_register(r.rd),
+                to_register(r.rs1),
+                to_register(r.rs2)
+            ),
+            Self::Divw(r) => format!(
+                "divw {}, {}, {}",
+                to_register(r.rd),
+                to_register(r.rs1),
+                to_register(r.rs2)
+            ),
+            Self::Divuw(r) => format!(
+                "divuw {}, {}, {}",
+                to_register(r.rd),
+                to_register(r.rs1),
+                to_register(r.rs2)
+            ),
+            Self::Remw(r) => format!(
+                "remw {}, {}, {}",
+                to_register(r.rd),
+                to_register(r.rs1),
+                to_register(r.rs2)
+            ),
+            Self::Remuw(r) => format!(
+                "remuw {}, {}, {}",
+                to_register(r.rd),
+                to_register(r.rs1),
+                to_register(r.rs2)
+            ),
         }
     }
 }
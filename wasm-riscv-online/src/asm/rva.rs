@@ -1,111 +1,243 @@
 #![allow(dead_code)]
-use super::{to_register, RType};
+use super::{to_register, AType};
 
 #[derive(Debug, Clone, Copy)]
 pub enum RV32A {
     // Load-Reserved/Store-Conditional - Word (32-bit)
-    Lrw(RType),
-    Scw(RType),
+    Lrw(AType),
+    Scw(AType),
     
     // Atomic Memory Operations - Word (32-bit)
-    Amoswapw(RType),
-    Amoaddw(RType),
-    Amoxorw(RType),
-    Amoandw(RType),
-    Amoorw(RType),
-    Amominw(RType),
-    Amomaxw(RType),
-    Amominuw(RType),
-    Amomaxuw(RType),
+    Amoswapw(AType),
+    Amoaddw(AType),
+    Amoxorw(AType),
+    Amoandw(AType),
+    Amoorw(AType),
+    Amominw(AType),
+    Amomaxw(AType),
+    Amominuw(AType),
+    Amomaxuw(AType),
+
+    // Zacas compare-and-swap
+    Amocasw(AType),
 }
 
 #[derive(Debug, Clone, Copy)]
 pub enum RV64A {
     // Load-Reserved/Store-Conditional - Double Word (64-bit)
-    Lrd(RType),
-    Scd(RType),
+    Lrd(AType),
+    Scd(AType),
     
     // Atomic Memory Operations - Double Word (64-bit)
-    Amoswapd(RType),
-    Amoaddd(RType),
-    Amoxord(RType),
-    Amoandd(RType),
-    Amoord(RType),
-    Amomind(RType),
-    Amomaxd(RType),
-    Amominud(RType),
-    Amomaxud(RType),
+    Amoswapd(AType),
+    Amoaddd(AType),
+    Amoxord(AType),
+    Amoandd(AType),
+    Amoord(AType),
+    Amomind(AType),
+    Amomaxd(AType),
+    Amominud(AType),
+    Amomaxud(AType),
+
+    // Zacas compare-and-swap
+    Amocasd(AType),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum RV128A {
+    // Load-Reserved/Store-Conditional - Quad Word (128-bit)
+    Lrq(AType),
+    Scq(AType),
+    
+    // Atomic Memory Operations - Quad Word (128-bit)
+    Amoswapq(AType),
+    Amoaddq(AType),
+    Amoxorq(AType),
+    Amoandq(AType),
+    Amoorq(AType),
+    Amominq(AType),
+    Amomaxq(AType),
+    Amominuq(AType),
+    Amomaxuq(AType),
+
+    // Zacas compare-and-swap
+    Amocasq(AType),
+}
+
+/// Width of a Zabha AMO.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmoWidth {
+    B,
+    H,
+}
+
+impl AmoWidth {
+    pub fn suffix(self) -> char {
+        match self {
+            AmoWidth::B => 'b',
+            AmoWidth::H => 'h',
+        }
+    }
+}
+
+/// Byte and halfword AMOs (Zabha); there is no `lr`/`sc` at these widths.
+/// `amocas` needs Zacas as well.
+#[derive(Debug, Clone, Copy)]
+pub enum RVZabha {
+    Amoswap(AmoWidth, AType),
+    Amoadd(AmoWidth, AType),
+    Amoxor(AmoWidth, AType),
+    Amoand(AmoWidth, AType),
+    Amoor(AmoWidth, AType),
+    Amomin(AmoWidth, AType),
+    Amomax(AmoWidth, AType),
+    Amominu(AmoWidth, AType),
+    Amomaxu(AmoWidth, AType),
+    Amocas(AmoWidth, AType),
+}
+
+/// `.aq`, `.rl` or `.aqrl` ordering suffix.
+pub fn ordering_suffix(a: &AType) -> &'static str {
+    match (a.aq, a.rl) {
+        (true, true) => ".aqrl",
+        (true, false) => ".aq",
+        (false, true) => ".rl",
+        (false, false) => "",
+    }
+}
+
+/// Full mnemonic and operands: `lr` takes `rd, (rs1)`, everything else
+/// `rd, rs2, (rs1)`.
+fn format_atomic(op: &str, width: char, a: &AType, sep: &str) -> String {
+    let mnem = format!("{}.{}{}", op, width, ordering_suffix(a));
+    if op == "lr" {
+        format!("{} {}{}({})", mnem, to_register(a.rd), sep, to_register(a.rs1))
+    } else {
+        format!(
+            "{} {}{}{}{}({})",
+            mnem,
+            to_register(a.rd),
+            sep,
+            to_register(a.rs2),
+            sep,
+            to_register(a.rs1)
+        )
+    }
 }
 
 impl RV32A {
-    pub fn to_string(&self) -> String {
+    /// Base operation name (without width and ordering suffix) and operands.
+    pub fn parts(&self) -> (&'static str, &AType) {
         match self {
-            Self::Lrw(r) => format!("lr.w {}, {}", to_register(r.rd), to_register(r.rs1)),
-            Self::Scw(r) => format!("sc.w {}, {}", to_register(r.rs2), to_register(r.rs1)),
-            Self::Amoswapw(r) => format!("amoswap.w {}, {}", to_register(r.rs2), to_register(r.rs1)),
-            Self::Amoaddw(r) => format!("amoadd.w {}, {}", to_register(r.rs2), to_register(r.rs1)),
-            Self::Amoxorw(r) => format!("amoxor.w {}, {}", to_register(r.rs2), to_register(r.rs1)),
-            Self::Amoandw(r) => format!("amoand.w {}, {}", to_register(r.rs2), to_register(r.rs1)),
-            Self::Amoorw(r) => format!("amoor.w {}, {}", to_register(r.rs2), to_register(r.rs1)),
-            Self::Amominw(r) => format!("amomin.w {}, {}", to_register(r.rs2), to_register(r.rs1)),
-            Self::Amomaxw(r) => format!("amomax.w {}, {}", to_register(r.rs2), to_register(r.rs1)),
-            Self::Amominuw(r) => format!("amominu.w {}, {}", to_register(r.rs2), to_register(r.rs1)),
-            Self::Amomaxuw(r) => format!("amomaxu.w {}, {}", to_register(r.rs2), to_register(r.rs1)),
+            Self::Lrw(a) => ("lr", a),
+            Self::Scw(a) => ("sc", a),
+            Self::Amoswapw(a) => ("amoswap", a),
+            Self::Amoaddw(a) => ("amoadd", a),
+            Self::Amoxorw(a) => ("amoxor", a),
+            Self::Amoandw(a) => ("amoand", a),
+            Self::Amoorw(a) => ("amoor", a),
+            Self::Amominw(a) => ("amomin", a),
+            Self::Amomaxw(a) => ("amomax", a),
+            Self::Amominuw(a) => ("amominu", a),
+            Self::Amomaxuw(a) => ("amomaxu", a),
+            Self::Amocasw(a) => ("amocas", a),
         }
     }
+
+    pub fn to_string(&self) -> String {
+        self.format_with(", ")
+    }
+
+    pub(crate) fn format_with(&self, sep: &str) -> String {
+        let (op, a) = self.parts();
+        format_atomic(op, 'w', a, sep)
+    }
 }
 
 impl RV64A {
-    pub fn to_string(&self) -> String {
+    /// Base operation name (without width and ordering suffix) and operands.
+    pub fn parts(&self) -> (&'static str, &AType) {
         match self {
-            Self::Lrd(r) => format!("lr.d {}, {}", to_register(r.rd), to_register(r.rs1)),
-            Self::Scd(r) => format!("sc.d {}, {}", to_register(r.rs2), to_register(r.rs1)),
-            Self::Amoswapd(r) => format!("amoswap.d {} ,{}, {}", to_register(r.rd),to_register(r.rs2), to_register(r.rs1)),
-            Self::Amoaddd(r) => format!("amoadd.d {} ,{}, {}", to_register(r.rd),to_register(r.rs2), to_register(r.rs1)),
-            Self::Amoxord(r) => format!("amoxor.d {} ,{}, {}", to_register(r.rd),to_register(r.rs2), to_register(r.rs1)),
-            Self::Amoandd(r) => format!("amoand.d {} ,{}, {}", to_register(r.rd),to_register(r.rs2), to_register(r.rs1)),
-            Self::Amoord(r) => format!("amoor.d {} ,{}, {}", to_register(r.rd),to_register(r.rs2), to_register(r.rs1)),
-            Self::Amomind(r) => format!("amomin.d {} ,{}, {}", to_register(r.rd),to_register(r.rs2), to_register(r.rs1)),
-            Self::Amomaxd(r) => format!("amomax.d {} ,{}, {}", to_register(r.rd),to_register(r.rs2), to_register(r.rs1)),
-            Self::Amominud(r) => format!("amominu.d {} ,{}, {}", to_register(r.rd),to_register(r.rs2), to_register(r.rs1)),
-            Self::Amomaxud(r) => format!("amomaxu.d {} ,{}, {}", to_register(r.rd),to_register(r.rs2), to_register(r.rs1)),
+            Self::Lrd(a) => ("lr", a),
+            Self::Scd(a) => ("sc", a),
+            Self::Amoswapd(a) => ("amoswap", a),
+            Self::Amoaddd(a) => ("amoadd", a),
+            Self::Amoxord(a) => ("amoxor", a),
+            Self::Amoandd(a) => ("amoand", a),
+            Self::Amoord(a) => ("amoor", a),
+            Self::Amomind(a) => ("amomin", a),
+            Self::Amomaxd(a) => ("amomax", a),
+            Self::Amominud(a) => ("amominu", a),
+            Self::Amomaxud(a) => ("amomaxu", a),
+            Self::Amocasd(a) => ("amocas", a),
         }
     }
-}
- 
-#[derive(Debug, Clone, Copy)]
-pub enum RV128A {
-    // Load-Reserved/Store-Conditional - Quad Word (128-bit)
-    Lrq(RType),
-    Scq(RType),
-    
-    // Atomic Memory Operations - Quad Word (128-bit)
-    Amoswapq(RType),
-    Amoaddq(RType),
-    Amoxorq(RType),
-    Amoandq(RType),
-    Amoorq(RType),
-    Amominq(RType),
-    Amomaxq(RType),
-    Amominuq(RType),
-    Amomaxuq(RType),
+
+    pub fn to_string(&self) -> String {
+        self.format_with(", ")
+    }
+
+    pub(crate) fn format_with(&self, sep: &str) -> String {
+        let (op, a) = self.parts();
+        format_atomic(op, 'd', a, sep)
+    }
 }
 
 impl RV128A {
+    /// Base operation name (without width and ordering suffix) and operands.
+    pub fn parts(&self) -> (&'static str, &AType) {
+        match self {
+            Self::Lrq(a) => ("lr", a),
+            Self::Scq(a) => ("sc", a),
+            Self::Amoswapq(a) => ("amoswap", a),
+            Self::Amoaddq(a) => ("amoadd", a),
+            Self::Amoxorq(a) => ("amoxor", a),
+            Self::Amoandq(a) => ("amoand", a),
+            Self::Amoorq(a) => ("amoor", a),
+            Self::Amominq(a) => ("amomin", a),
+            Self::Amomaxq(a) => ("amomax", a),
+            Self::Amominuq(a) => ("amominu", a),
+            Self::Amomaxuq(a) => ("amomaxu", a),
+            Self::Amocasq(a) => ("amocas", a),
+        }
+    }
+
     pub fn to_string(&self) -> String {
+        self.format_with(", ")
+    }
+
+    pub(crate) fn format_with(&self, sep: &str) -> String {
+        let (op, a) = self.parts();
+        format_atomic(op, 'q', a, sep)
+    }
+}
+
+impl RVZabha {
+    /// Base operation name, width and operands.
+    pub fn parts(&self) -> (&'static str, AmoWidth, &AType) {
+        use RVZabha::*;
         match self {
-            Self::Lrq(r) => format!("lr.q {}, {}", to_register(r.rd), to_register(r.rs1)),
-            Self::Scq(r) => format!("sc.q {}, {}", to_register(r.rs2), to_register(r.rs1)),
-            Self::Amoswapq(r) => format!("amoswap.q {} ,{}, {}", to_register(r.rd), to_register(r.rs2), to_register(r.rs1)),
-            Self::Amoaddq(r) => format!("amoadd.q {} ,{}, {}", to_register(r.rd), to_register(r.rs2), to_register(r.rs1)),
-            Self::Amoxorq(r) => format!("amoxor.q {} ,{}, {}", to_register(r.rd), to_register(r.rs2), to_register(r.rs1)),
-            Self::Amoandq(r) => format!("amoand.q {} ,{}, {}", to_register(r.rd), to_register(r.rs2), to_register(r.rs1)),
-            Self::Amoorq(r) => format!("amoor.q {} ,{}, {}", to_register(r.rd), to_register(r.rs2), to_register(r.rs1)),
-            Self::Amominq(r) => format!("amomin.q {} ,{}, {}", to_register(r.rd), to_register(r.rs2), to_register(r.rs1)),
-            Self::Amomaxq(r) => format!("amomax.q {} ,{}, {}", to_register(r.rd), to_register(r.rs2), to_register(r.rs1)),
-            Self::Amominuq(r) => format!("amominu.q {} ,{}, {}", to_register(r.rd), to_register(r.rs2), to_register(r.rs1)),
-            Self::Amomaxuq(r) => format!("amomaxu.q {} ,{}, {}", to_register(r.rd), to_register(r.rs2), to_register(r.rs1)),
+            Amoswap(w, a) => ("amoswap", *w, a),
+            Amoadd(w, a) => ("amoadd", *w, a),
+            Amoxor(w, a) => ("amoxor", *w, a),
+            Amoand(w, a) => ("amoand", *w, a),
+            Amoor(w, a) => ("amoor", *w, a),
+            Amomin(w, a) => ("amomin", *w, a),
+            Amomax(w, a) => ("amomax", *w, a),
+            Amominu(w, a) => ("amominu", *w, a),
+            Amomaxu(w, a) => ("amomaxu", *w, a),
+            Amocas(w, a) => ("amocas", *w, a),
         }
     }
+
+    pub(crate) fn format_with(&self, sep: &str) -> String {
+        let (op, width, a) = self.parts();
+        format_atomic(op, width.suffix(), a, sep)
+    }
+}
+
+impl core::fmt::Display for RVZabha {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.format_with(", "))
+    }
 }
-        
\ No newline at end of file
@@ -0,0 +1,142 @@
+use super::{to_register, IType, RType};
+
+/// Bit manipulation: Zba, Zbb, Zbc and Zbs. Unary operations (`clz`,
+/// `rev8`, ...) keep only rd and rs1; shifts by immediate keep the shamt in
+/// `imm`.
+#[derive(Debug, Clone, Copy)]
+pub enum RVB {
+    // Zba
+    Sh1add(RType),
+    Sh2add(RType),
+    Sh3add(RType),
+    // Zba, RV64
+    Adduw(RType),
+    Sh1adduw(RType),
+    Sh2adduw(RType),
+    Sh3adduw(RType),
+    Slliuw(IType),
+
+    // Zbb
+    Andn(RType),
+    Orn(RType),
+    Xnor(RType),
+    Clz(RType),
+    Ctz(RType),
+    Cpop(RType),
+    Max(RType),
+    Maxu(RType),
+    Min(RType),
+    Minu(RType),
+    Sextb(RType),
+    Sexth(RType),
+    Zexth(RType),
+    Rol(RType),
+    Ror(RType),
+    Rori(IType),
+    Orcb(RType),
+    Rev8(RType),
+    // Zbb, RV64
+    Clzw(RType),
+    Ctzw(RType),
+    Cpopw(RType),
+    Rolw(RType),
+    Rorw(RType),
+    Roriw(IType),
+
+    // Zbc
+    Clmul(RType),
+    Clmulr(RType),
+    Clmulh(RType),
+
+    // Zbs
+    Bclr(RType),
+    Bclri(IType),
+    Bext(RType),
+    Bexti(IType),
+    Binv(RType),
+    Binvi(IType),
+    Bset(RType),
+    Bseti(IType),
+}
+
+impl RVB {
+    /// Instructions that only exist on RV64 and wider.
+    pub fn is_rv64_only(self) -> bool {
+        use RVB::*;
+        matches!(
+            self,
+            Adduw(_) | Sh1adduw(_) | Sh2adduw(_) | Sh3adduw(_) | Slliuw(_) | Clzw(_) | Ctzw(_)
+                | Cpopw(_) | Rolw(_) | Rorw(_) | Roriw(_)
+        )
+    }
+
+    pub fn mnemonic(&self) -> &'static str {
+        use RVB::*;
+        match self {
+            Sh1add(_) => "sh1add",
+            Sh2add(_) => "sh2add",
+            Sh3add(_) => "sh3add",
+            Adduw(_) => "add.uw",
+            Sh1adduw(_) => "sh1add.uw",
+            Sh2adduw(_) => "sh2add.uw",
+            Sh3adduw(_) => "sh3add.uw",
+            Slliuw(_) => "slli.uw",
+            Andn(_) => "andn",
+            Orn(_) => "orn",
+            Xnor(_) => "xnor",
+            Clz(_) => "clz",
+            Ctz(_) => "ctz",
+            Cpop(_) => "cpop",
+            Max(_) => "max",
+            Maxu(_) => "maxu",
+            Min(_) => "min",
+            Minu(_) => "minu",
+            Sextb(_) => "sext.b",
+            Sexth(_) => "sext.h",
+            Zexth(_) => "zext.h",
+            Rol(_) => "rol",
+            Ror(_) => "ror",
+            Rori(_) => "rori",
+            Orcb(_) => "orc.b",
+            Rev8(_) => "rev8",
+            Clzw(_) => "clzw",
+            Ctzw(_) => "ctzw",
+            Cpopw(_) => "cpopw",
+            Rolw(_) => "rolw",
+            Rorw(_) => "rorw",
+            Roriw(_) => "roriw",
+            Clmul(_) => "clmul",
+            Clmulr(_) => "clmulr",
+            Clmulh(_) => "clmulh",
+            Bclr(_) => "bclr",
+            Bclri(_) => "bclri",
+            Bext(_) => "bext",
+            Bexti(_) => "bexti",
+            Binv(_) => "binv",
+            Binvi(_) => "binvi",
+            Bset(_) => "bset",
+            Bseti(_) => "bseti",
+        }
+    }
+}
+
+impl core::fmt::Display for RVB {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        use RVB::*;
+        let m = self.mnemonic();
+        let x = to_register;
+        match self {
+            Slliuw(i) | Rori(i) | Roriw(i) | Bclri(i) | Bexti(i) | Binvi(i) | Bseti(i) => {
+                write!(f, "{} {}, {}, {}", m, x(i.rd), x(i.rs1), i.imm.low_u32() & 0x3f)
+            }
+            Clz(r) | Ctz(r) | Cpop(r) | Sextb(r) | Sexth(r) | Zexth(r) | Orcb(r) | Rev8(r)
+            | Clzw(r) | Ctzw(r) | Cpopw(r) => write!(f, "{} {}, {}", m, x(r.rd), x(r.rs1)),
+            Sh1add(r) | Sh2add(r) | Sh3add(r) | Adduw(r) | Sh1adduw(r) | Sh2adduw(r)
+            | Sh3adduw(r) | Andn(r) | Orn(r) | Xnor(r) | Max(r) | Maxu(r) | Min(r) | Minu(r)
+            | Rol(r) | Ror(r) | Rolw(r) | Rorw(r) | Clmul(r) | Clmulr(r) | Clmulh(r) | Bclr(r)
+            | Bext(r) | Binv(r) | Bset(r) => {
+                write!(f, "{} {}, {}, {}", m, x(r.rd), x(r.rs1), x(r.rs2))
+            }
+        }
+    }
+}
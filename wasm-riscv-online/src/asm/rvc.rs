@@ -1,6 +1,8 @@
 use super::{
-    to_register, CAType, CBType, CIType, CIWType, CJType, CLType, CRType, CSSType, CSType,
+    rvf::to_fp_register,
+    to_register, CAType, CBType, CIType, CIWType, CJType, CLType, CMPPType, CRType, CSSType, CSType, RVZihint,
 };
+use crate::riscv::imm::Xlen;
 
 #[derive(Debug, Clone, Copy)]
 pub enum RVC {
@@ -55,20 +57,85 @@ pub enum RVC {
     Cswsp(CSSType),
     Cfswsp(CSSType),
     Csdsp(CSSType),
+
+    // Zcmop: the reserved `c.lui xN, 0` points, N odd below 16, in rdrs1
+    Cmop(CIType),
+
+    // Zihintntl: the `c.add x0, x2..x5` hints, rs2 naming the level
+    Cntl(CRType),
+
+    // Zcb: byte/half-word loads and stores, unary ops on rdrs1 and c.mul
+    Clbu(CLType),
+    Clhu(CLType),
+    Clh(CLType),
+    Csb(CSType),
+    Csh(CSType),
+    CzextB(CIType),
+    CsextB(CIType),
+    CzextH(CIType),
+    CsextH(CIType),
+    CzextW(CIType),
+    Cnot(CIType),
+    Cmul(CAType),
+
+    // Zcmp: push/pop of ra and s0-s11, and the a0/a1 <-> sreg moves (the
+    // CAType holds the s registers in rdrs1 and rs2)
+    CmPush(CMPPType),
+    CmPop(CMPPType),
+    CmPopretz(CMPPType),
+    CmPopret(CMPPType),
+    CmMvsa01(CAType),
+    CmMva01s(CAType),
+    // Zcmt: jump-table index
+    CmJt(u8),
+    CmJalt(u8),
+}
+
+impl CMPPType {
+    /// ra, then s0 upwards; list 15 takes s10 and s11 together.
+    pub fn registers(&self) -> Vec<u8> {
+        const SAVED: [u8; 12] = [8, 9, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27];
+        let n = match self.rlist {
+            4..=14 => (self.rlist - 4) as usize,
+            15 => 12,
+            _ => 0,
+        };
+        let mut regs = vec![1];
+        regs.extend_from_slice(&SAVED[..n]);
+        regs
+    }
+
+    /// The stack space the saved registers need, rounded up to 16 bytes.
+    pub fn base_adj(&self, xlen: Xlen) -> u32 {
+        let size = match xlen {
+            Xlen::X32 => 4,
+            _ => 8,
+        };
+        (self.registers().len() as u32 * size + 15) & !15
+    }
+
+    /// `{ra}`, `{ra, s0}` or `{ra, s0-sN}`.
+    pub fn rlist_string(&self, sep: &str) -> String {
+        match self.registers().len() {
+            1 => "{ra}".to_string(),
+            2 => format!("{{ra{}s0}}", sep),
+            n => format!("{{ra{}s0-s{}}}", sep, n - 2),
+        }
+    }
 }
 
 impl RVC {
     pub fn to_string(&self) -> String {
         match self {
             Self::Caddi4spn(ciw) => format!(
-                "c.addi {}, {}, {:?}",
+                "c.addi4spn {}, {}, {:?}",
                 to_register(ciw.rd),
                 to_register(2),
                 ciw.uimm
             ),
             Self::Cfld(cl) => format!(
                 "c.fld {}, {:?}({})",
-                to_register(cl.rd),
+                to_fp_register(cl.rd),
                 cl.imm,
                 to_register(cl.rs1)
             ),
@@ -86,7 +153,7 @@ impl RVC {
             ),
             Self::Cflw(cl) => format!(
                 "c.flw {}, {:?}({})",
-                to_register(cl.rd),
+                to_fp_register(cl.rd),
                 cl.imm,
                 to_register(cl.rs1)
             ),
@@ -98,7 +165,7 @@ impl RVC {
             ),
             Self::Cfsd(cs) => format!(
                 "c.fsd {}, {:?}({})",
-                to_register(cs.rs2),
+                to_fp_register(cs.rs2),
                 cs.imm,
                 to_register(cs.rs1)
             ),
@@ -116,7 +183,7 @@ impl RVC {
             ),
             Self::Cfsw(cs) => format!(
                 "c.fsw {}, {:?}({})",
-                to_register(cs.rs2),
+                to_fp_register(cs.rs2),
                 cs.imm,
                 to_register(cs.rs1)
             ),
@@ -134,7 +201,7 @@ impl RVC {
                 to_register(ci.rdrs1),
                 ci.imm
             ),
-            Self::Cjal(cj) => format!("c.jal {},{:?}", to_register(0), cj.target),
+            Self::Cjal(cj) => format!("c.jal {}, {:?}", to_register(1), cj.target),
             Self::Caddiw(ci) => format!(
                 "c.addiw {}, {}, {:?}",
                 to_register(ci.rdrs1),
@@ -247,7 +314,7 @@ impl RVC {
             ),
             Self::Cfldsp(ci) => format!(
                 "c.fldsp {}, {:?}({})",
-                to_register(ci.rdrs1),
+                to_fp_register(ci.rdrs1),
                 ci.imm,
                 to_register(2)
             ),
@@ -265,7 +332,7 @@ impl RVC {
             ),
             Self::Cflwsp(ci) => format!(
                 "c.flwsp {}, {:?}({})",
-                to_register(ci.rdrs1),
+                to_fp_register(ci.rdrs1),
                 ci.imm,
                 to_register(2)
             ),
@@ -283,6 +350,11 @@ impl RVC {
                 to_register(cr.rs2)
             ),
             Self::Cebreak(_cr) => format!("c.ebreak"),
+            Self::Cmop(ci) => format!("c.mop.{}", ci.rdrs1),
+            Self::Cntl(cr) => match RVZihint::ntl(cr.rs2).and_then(|h| h.compressed_mnemonic()) {
+                Some(m) => m.to_string(),
+                None => "unknown".to_string(),
+            },
             Self::Cjalr(cr) => format!("c.jalr {}, 0({})", to_register(1), to_register(cr.rdrs1)),
             Self::Cadd(cr) => format!(
                 "c.add {}, {}, {}",
@@ -292,7 +364,7 @@ impl RVC {
             ),
             Self::Cfsdsp(css) => format!(
                 "c.fsdsp {}, {:?}({})",
-                to_register(css.rs2),
+                to_fp_register(css.rs2),
                 css.imm,
                 to_register(2)
             ),
@@ -310,7 +382,7 @@ impl RVC {
             ),
             Self::Cfswsp(css) => format!(
                 "c.fswsp {}, {:?}({})",
-                to_register(css.rs2),
+                to_fp_register(css.rs2),
                 css.imm,
                 to_register(2)
             ),
@@ -320,6 +392,33 @@ impl RVC {
                 css.imm,
                 to_register(2)
             ),
+
+            Self::Clbu(cl) => format!("c.lbu {}, {:?}({})", to_register(cl.rd), cl.imm, to_register(cl.rs1)),
+            Self::Clhu(cl) => format!("c.lhu {}, {:?}({})", to_register(cl.rd), cl.imm, to_register(cl.rs1)),
+            Self::Clh(cl) => format!("c.lh {}, {:?}({})", to_register(cl.rd), cl.imm, to_register(cl.rs1)),
+            Self::Csb(cs) => format!("c.sb {}, {:?}({})", to_register(cs.rs2), cs.imm, to_register(cs.rs1)),
+            Self::Csh(cs) => format!("c.sh {}, {:?}({})", to_register(cs.rs2), cs.imm, to_register(cs.rs1)),
+            Self::CzextB(ci) => format!("c.zext.b {}", to_register(ci.rdrs1)),
+            Self::CsextB(ci) => format!("c.sext.b {}", to_register(ci.rdrs1)),
+            Self::CzextH(ci) => format!("c.zext.h {}", to_register(ci.rdrs1)),
+            Self::CsextH(ci) => format!("c.sext.h {}", to_register(ci.rdrs1)),
+            Self::CzextW(ci) => format!("c.zext.w {}", to_register(ci.rdrs1)),
+            Self::Cnot(ci) => format!("c.not {}", to_register(ci.rdrs1)),
+            Self::Cmul(ca) => format!(
+                "c.mul {}, {}, {}",
+                to_register(ca.rdrs1),
+                to_register(ca.rdrs1),
+                to_register(ca.rs2),
+            ),
+
+            Self::CmPush(pp) => format!("cm.push {}, -{}", pp.rlist_string(", "), pp.stack_adj),
+            Self::CmPop(pp) => format!("cm.pop {}, {}", pp.rlist_string(", "), pp.stack_adj),
+            Self::CmPopretz(pp) => format!("cm.popretz {}, {}", pp.rlist_string(", "), pp.stack_adj),
+            Self::CmPopret(pp) => format!("cm.popret {}, {}", pp.rlist_string(", "), pp.stack_adj),
+            Self::CmMvsa01(ca) => format!("cm.mvsa01 {}, {}", to_register(ca.rdrs1), to_register(ca.rs2)),
+            Self::CmMva01s(ca) => format!("cm.mva01s {}, {}", to_register(ca.rdrs1), to_register(ca.rs2)),
+            Self::CmJt(index) => format!("cm.jt {}", index),
+            Self::CmJalt(index) => format!("cm.jalt {}", index),
         }
     }
 }
@@ -1,240 +1,396 @@
-use super::{to_register, IType, R4Type, RType, SType};  
-  
-// 添加浮点寄存器转换函数 
-fn to_fp_register(ins: u8) -> String {  
-    match ins {  
-        0..=31 => format!("f{}", ins),  
-        _ => "unknown".to_string(),  
-    }  
-}  
-  
-#[derive(Debug, Clone, Copy)]  
-pub enum RVF {  
-    // RV32F  
-    Flw(IType),  
-    Fsw(SType),  
-    Fmadds(R4Type),  
-    Fmsubs(R4Type),  
-    Fnmadds(R4Type),  
-    Fnmsubs(R4Type),  
-    Fadds(RType),  
-    Fsubs(RType),  
-    Fmuls(RType),  
-    Fdivs(RType),  
-    Fsqrts(RType),  
-    Fsgnjs(RType),  
-    Fsgnjns(RType),  
-    Fsgnjxs(RType),  
-    Fmins(RType),  
-    Fmaxs(RType),  
-    Fcvtws(RType),  
-    Fcvtwus(RType),  
-    Fmvxw(RType),  
-    Feqs(RType),  
-    Flts(RType),  
-    Fles(RType),  
-    Fclasss(RType),  
-    Fcvtsw(RType),  
-    Fcvtswu(RType),  
-    Fmvwx(RType),  
-    // RV64F  
-    Fcvtls(RType),  
-    Fcvtlus(RType),  
-    Fcvtsl(RType),  
-    Fcvtslu(RType),  
-}  
-  
-impl RVF {  
-    pub fn to_string(&self) -> String {  
-        match self {  
-            Self::Flw(i) => format!(  
-                "flw {}, {:?}({})",  
-                to_fp_register(i.rd),  
-                i.imm,  
-                to_register(i.rs1)  
-            ),  
-            Self::Fsw(s) => format!(  
-                "fsw {}, {:?}({})",  
-                to_fp_register(s.rs2),  
-                s.imm,  
-                to_register(s.rs1)  
-            ),  
-              
-            // Fused multiply-add - 四操作数格式  
-            Self::Fmadds(r4) => format!(  
-                "fmadd.s {}, {}, {}, {}",  
-                to_fp_register(r4.rd),  
-                to_fp_register(r4.rs1),  
-                to_fp_register(r4.rs2),  
-                to_fp_register(r4.rs3)  
-            ),  
-            Self::Fmsubs(r4) => format!(  
-                "fmsub.s {}, {}, {}, {}",  
-                to_fp_register(r4.rd),  
-                to_fp_register(r4.rs1),  
-                to_fp_register(r4.rs2),  
-                to_fp_register(r4.rs3)  
-            ),  
-            Self::Fnmadds(r4) => format!(  
-                "fnmadd.s {}, {}, {}, {}",  
-                to_fp_register(r4.rd),  
-                to_fp_register(r4.rs1),  
-                to_fp_register(r4.rs2),  
-                to_fp_register(r4.rs3)  
-            ),  
-            Self::Fnmsubs(r4) => format!(  
-                "fnmsub.s {}, {}, {}, {}",  
-                to_fp_register(r4.rd),  
-                to_fp_register(r4.rs1),  
-                to_fp_register(r4.rs2),  
-                to_fp_register(r4.rs3)  
-            ),  
-              
-            // 算术运算 - 三操作数格式  
-            Self::Fadds(r) => format!(  
-                "fadd.s {}, {}, {}",  
-                to_fp_register(r.rd),  
-                to_fp_register(r.rs1),  
-                to_fp_register(r.rs2)  
-            ),  
-            Self::Fsubs(r) => format!(  
-                "fsub.s {}, {}, {}",  
-                to_fp_register(r.rd),  
-                to_fp_register(r.rs1),  
-                to_fp_register(r.rs2)  
-            ),  
-            Self::Fmuls(r) => format!(  
-                "fmul.s {}, {}, {}",  
-                to_fp_register(r.rd),  
-                to_fp_register(r.rs1),  
-                to_fp_register(r.rs2)  
-            ),  
-            Self::Fdivs(r) => format!(  
-                "fdiv.s {}, {}, {}",  
-                to_fp_register(r.rd),  
-                to_fp_register(r.rs1),  
-                to_fp_register(r.rs2)  
-            ),  
-            Self::Fsqrts(r) => format!(  
-                "fsqrt.s {}, {}",  
-                to_fp_register(r.rd),  
-                to_fp_register(r.rs1)  
-            ),  
-            Self::Fmins(r) => format!(  
-                "fmin.s {}, {}, {}",  
-                to_fp_register(r.rd),  
-                to_fp_register(r.rs1),  
-                to_fp_register(r.rs2)  
-            ),  
-            Self::Fmaxs(r) => format!(  
-                "fmax.s {}, {}, {}",  
-                to_fp_register(r.rd),  
-                to_fp_register(r.rs1),  
-                to_fp_register(r.rs2)  
-            ),  
-              
-            // 符号注入  
-            Self::Fsgnjs(r) => format!(  
-                "fsgnj.s {}, {}, {}",  
-                to_fp_register(r.rd),  
-                to_fp_register(r.rs1),  
-                to_fp_register(r.rs2)  
-            ),  
-            Self::Fsgnjns(r) => format!(  
-                "fsgnjn.s {}, {}, {}",  
-                to_fp_register(r.rd),  
-                to_fp_register(r.rs1),  
-                to_fp_register(r.rs2)  
-            ),  
-            Self::Fsgnjxs(r) => format!(  
-                "fsgnjx.s {}, {}, {}",  
-                to_fp_register(r.rd),  
-                to_fp_register(r.rs1),  
-                to_fp_register(r.rs2)  
-            ),  
-              
-            // 比较 - 结果写入整数寄存器  
-            Self::Feqs(r) => format!(  
-                "feq.s {}, {}, {}",  
-                to_register(r.rd),  
-                to_fp_register(r.rs1),  
-                to_fp_register(r.rs2)  
-            ),  
-            Self::Flts(r) => format!(  
-                "flt.s {}, {}, {}",  
-                to_register(r.rd),  
-                to_fp_register(r.rs1),  
-                to_fp_register(r.rs2)  
-            ),  
-            Self::Fles(r) => format!(  
-                "fle.s {}, {}, {}",  
-                to_register(r.rd),  
-                to_fp_register(r.rs1),  
-                to_fp_register(r.rs2)  
-            ),  
-              
-            // 转换指令  
-            Self::Fcvtws(r) => format!(  
-                "fcvt.w.s {}, {}",  
-                to_register(r.rd),  
-                to_fp_register(r.rs1)  
-            ),  
-            Self::Fcvtwus(r) => format!(  
-                "fcvt.wu.s {}, {}",  
-                to_register(r.rd),  
-                to_fp_register(r.rs1)  
-            ),  
-            Self::Fcvtsw(r) => format!(  
-                "fcvt.s.w {}, {}",  
-                to_fp_register(r.rd),  
-                to_register(r.rs1)  
-            ),  
-            Self::Fcvtswu(r) => format!(  
-                "fcvt.s.wu {}, {}",  
-                to_fp_register(r.rd),  
-                to_register(r.rs1)  
-            ),  
-              
-            // 搬移指令  
-            Self::Fmvxw(r) => format!(  
-                "fmv.x.w {}, {}",  
-                to_register(r.rd),  
-                to_fp_register(r.rs1)  
-            ),  
-            Self::Fmvwx(r) => format!(  
-                "fmv.w.x {}, {}",  
-                to_fp_register(r.rd),  
-                to_register(r.rs1)  
-            ),  
-              
-            // 分类指令  
-            Self::Fclasss(r) => format!(  
-                "fclass.s {}, {}",  
-                to_register(r.rd),  
-                to_fp_register(r.rs1)  
-            ),  
-              
-            // RV64F 扩展  
-            Self::Fcvtls(r) => format!(  
-                "fcvt.l.s {}, {}",  
-                to_register(r.rd),  
-                to_fp_register(r.rs1)  
-            ),  
-            Self::Fcvtlus(r) => format!(  
-                "fcvt.lu.s {}, {}",  
-                to_register(r.rd),  
-                to_fp_register(r.rs1)  
-            ),  
-            Self::Fcvtsl(r) => format!(  
-                "fcvt.s.l {}, {}",  
-                to_fp_register(r.rd),  
-                to_register(r.rs1)  
-            ),  
-            Self::Fcvtslu(r) => format!(  
-                "fcvt.s.lu {}, {}",  
-                to_fp_register(r.rd),  
-                to_register(r.rs1)  
-            ),  
-        }  
-    }  
-}
\ No newline at end of file
+use super::{to_register, IType, R4Type, RType, SType};
+use crate::isa::*;
+
+// 添加浮点寄存器转换函数
+pub(super) fn to_fp_register(ins: u8) -> String {
+    match ins {
+        0..=31 => format!("f{}", ins),
+        _ => "unknown".to_string(),
+    }
+}
+
+/// Names of the floating-point operands: `fN`, or under Zfinx/Zdinx/Zhinx
+/// the integer registers that hold them.
+fn fp_register_names(zfinx: bool) -> fn(u8) -> String {
+    if zfinx { to_register } else { to_fp_register }
+}
+
+/// Floating-point format, the `fmt` field (funct2 of R4-type, the low two
+/// bits of funct7 otherwise): single (F), double (D), half (Zfh) or quad (Q).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FpFmt {
+    S,
+    D,
+    H,
+    Q,
+}
+
+impl FpFmt {
+    pub fn from_bits(bits: u8) -> FpFmt {
+        match bits & 0b11 {
+            FUNCT2_FMT_S => FpFmt::S,
+            FUNCT2_FMT_D => FpFmt::D,
+            FUNCT2_FMT_H => FpFmt::H,
+            _ => FpFmt::Q,
+        }
+    }
+
+    pub fn bits(self) -> u8 {
+        match self {
+            FpFmt::S => FUNCT2_FMT_S,
+            FpFmt::D => FUNCT2_FMT_D,
+            FpFmt::H => FUNCT2_FMT_H,
+            FpFmt::Q => FUNCT2_FMT_Q,
+        }
+    }
+
+    /// Load/store width (funct3 of LOAD-FP/STORE-FP).
+    pub fn width(self) -> u8 {
+        match self {
+            FpFmt::H => FUNCT3_WIDTH_H,
+            FpFmt::S => FUNCT3_WIDTH_W,
+            FpFmt::D => FUNCT3_WIDTH_D,
+            FpFmt::Q => FUNCT3_WIDTH_Q,
+        }
+    }
+
+    pub fn from_width(funct3: u8) -> Option<FpFmt> {
+        [FpFmt::H, FpFmt::S, FpFmt::D, FpFmt::Q].iter().copied().find(|f| f.width() == funct3)
+    }
+
+    pub fn suffix(self) -> &'static str {
+        match self {
+            FpFmt::S => "s",
+            FpFmt::D => "d",
+            FpFmt::H => "h",
+            FpFmt::Q => "q",
+        }
+    }
+
+    pub fn from_suffix(s: &str) -> Option<FpFmt> {
+        [FpFmt::S, FpFmt::D, FpFmt::H, FpFmt::Q].iter().copied().find(|f| f.suffix() == s)
+    }
+
+    /// Significand precision in bits, including the implicit one.
+    fn precision(self) -> u32 {
+        match self {
+            FpFmt::H => 11,
+            FpFmt::S => 24,
+            FpFmt::D => 53,
+            FpFmt::Q => 113,
+        }
+    }
+
+    /// Letter used by `flw`/`fmv.x.w`: single precision is a "word".
+    fn mem_suffix(self) -> &'static str {
+        match self {
+            FpFmt::S => "w",
+            f => f.suffix(),
+        }
+    }
+}
+
+/// Integer operand of a conversion; the value goes in the rs2 field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FpInt {
+    W,
+    Wu,
+    L,
+    Lu,
+}
+
+impl FpInt {
+    pub fn from_rs2(rs2: u8) -> Option<FpInt> {
+        match rs2 {
+            FUNCT_RS2_CVT_W => Some(FpInt::W),
+            FUNCT_RS2_CVT_WU => Some(FpInt::Wu),
+            FUNCT_RS2_CVT_L => Some(FpInt::L),
+            FUNCT_RS2_CVT_LU => Some(FpInt::Lu),
+            _ => None,
+        }
+    }
+
+    pub fn rs2(self) -> u8 {
+        match self {
+            FpInt::W => FUNCT_RS2_CVT_W,
+            FpInt::Wu => FUNCT_RS2_CVT_WU,
+            FpInt::L => FUNCT_RS2_CVT_L,
+            FpInt::Lu => FUNCT_RS2_CVT_LU,
+        }
+    }
+
+    pub fn suffix(self) -> &'static str {
+        match self {
+            FpInt::W => "w",
+            FpInt::Wu => "wu",
+            FpInt::L => "l",
+            FpInt::Lu => "lu",
+        }
+    }
+
+    pub fn from_suffix(s: &str) -> Option<FpInt> {
+        [FpInt::W, FpInt::Wu, FpInt::L, FpInt::Lu].iter().copied().find(|i| i.suffix() == s)
+    }
+
+    /// `l`/`lu` only exist on RV64 and wider.
+    pub fn is_64(self) -> bool {
+        matches!(self, FpInt::L | FpInt::Lu)
+    }
+
+    fn bits(self) -> u32 {
+        if self.is_64() { 64 } else { 32 }
+    }
+}
+
+/// The constants `fli` loads, by the index in its rs1 field; `min` is the
+/// smallest positive normal number of the format (see [`fli_constant`]).
+pub const FLI_CONSTANTS: [&str; 32] = [
+    "-1.0", "min", "1.52587890625e-05", "3.0517578125e-05", "0.00390625", "0.0078125", "0.0625",
+    "0.125", "0.25", "0.3125", "0.375", "0.4375", "0.5", "0.625", "0.75", "0.875", "1.0", "1.25",
+    "1.5", "1.75", "2.0", "2.5", "3.0", "4.0", "8.0", "16.0", "128.0", "256.0", "32768.0",
+    "65536.0", "inf", "nan",
+];
+
+/// The value `fli.<fmt>` loads for `index`, with `min` spelled out.
+pub fn fli_constant(fmt: FpFmt, index: u8) -> &'static str {
+    match (index & 0x1f, fmt) {
+        (1, FpFmt::H) => "6.103515625e-05",
+        (1, FpFmt::S) => "1.17549435e-38",
+        (1, FpFmt::D) => "2.2250738585072014e-308",
+        (1, FpFmt::Q) => "3.362103143112093506262677817321753e-4932",
+        (i, _) => FLI_CONSTANTS[i as usize],
+    }
+}
+
+/// F, D, Q, Zfh and Zfa instructions; every variant carries its format.
+#[derive(Debug, Clone, Copy)]
+pub enum RVF {
+    /// `flh`/`flw`/`fld`/`flq`
+    Load(FpFmt, IType),
+    /// `fsh`/`fsw`/`fsd`/`fsq`
+    Store(FpFmt, SType),
+    Fmadd(FpFmt, R4Type),
+    Fmsub(FpFmt, R4Type),
+    Fnmsub(FpFmt, R4Type),
+    Fnmadd(FpFmt, R4Type),
+    Fadd(FpFmt, RType),
+    Fsub(FpFmt, RType),
+    Fmul(FpFmt, RType),
+    Fdiv(FpFmt, RType),
+    Fsqrt(FpFmt, RType),
+    Fsgnj(FpFmt, RType),
+    Fsgnjn(FpFmt, RType),
+    Fsgnjx(FpFmt, RType),
+    Fmin(FpFmt, RType),
+    Fmax(FpFmt, RType),
+    Feq(FpFmt, RType),
+    Flt(FpFmt, RType),
+    Fle(FpFmt, RType),
+    Fclass(FpFmt, RType),
+    /// `fcvt.<to>.<from>` between two formats
+    Fcvt(FpFmt, FpFmt, RType),
+    /// `fcvt.<int>.<fmt>`
+    FcvtToInt(FpInt, FpFmt, RType),
+    /// `fcvt.<fmt>.<int>`
+    FcvtFromInt(FpFmt, FpInt, RType),
+    /// `fmv.x.<fmt>`
+    FmvToInt(FpFmt, RType),
+    /// `fmv.<fmt>.x`
+    FmvFromInt(FpFmt, RType),
+
+    // Zfa
+    /// `fli.<fmt>`; rs1 holds the index into [`FLI_CONSTANTS`]
+    Fli(FpFmt, RType),
+    Fminm(FpFmt, RType),
+    Fmaxm(FpFmt, RType),
+    Fround(FpFmt, RType),
+    Froundnx(FpFmt, RType),
+    /// `fcvtmod.w.d`, which always rounds towards zero
+    FcvtmodWD(RType),
+    /// `fmvh.x.d` (RV32) / `fmvh.x.q` (RV64): the upper half of the bits
+    Fmvh(FpFmt, RType),
+    /// `fmvp.d.x` (RV32) / `fmvp.q.x` (RV64): the bits from rs1 (low) and rs2 (high)
+    Fmvp(FpFmt, RType),
+    Fleq(FpFmt, RType),
+    Fltq(FpFmt, RType),
+}
+
+/// Assembler name of a rounding mode (the funct3 field of rm-carrying
+/// instructions); 0b101 and 0b110 are reserved.
+pub fn rounding_mode_name(rm: u8) -> Option<&'static str> {
+    match rm {
+        0b000 => Some("rne"),
+        0b001 => Some("rtz"),
+        0b010 => Some("rdn"),
+        0b011 => Some("rup"),
+        0b100 => Some("rmm"),
+        0b111 => Some("dyn"),
+        _ => None,
+    }
+}
+
+pub fn rounding_mode_from_name(name: &str) -> Option<u8> {
+    (0..8).find(|&rm| rounding_mode_name(rm) == Some(name.trim()))
+}
+
+impl RVF {
+    /// The format the instruction operates on; for conversions between
+    /// formats, the destination.
+    pub fn fmt(&self) -> FpFmt {
+        use RVF::*;
+        match *self {
+            Load(f, _) | Store(f, _) => f,
+            Fmadd(f, _) | Fmsub(f, _) | Fnmsub(f, _) | Fnmadd(f, _) => f,
+            Fadd(f, _) | Fsub(f, _) | Fmul(f, _) | Fdiv(f, _) | Fsqrt(f, _) | Fsgnj(f, _)
+            | Fsgnjn(f, _) | Fsgnjx(f, _) | Fmin(f, _) | Fmax(f, _) | Feq(f, _) | Flt(f, _)
+            | Fle(f, _) | Fclass(f, _) | Fcvt(f, _, _) | FcvtToInt(_, f, _)
+            | FcvtFromInt(f, _, _) | FmvToInt(f, _) | FmvFromInt(f, _) => f,
+            Fli(f, _) | Fminm(f, _) | Fmaxm(f, _) | Fround(f, _) | Froundnx(f, _) | Fmvh(f, _)
+            | Fmvp(f, _) | Fleq(f, _) | Fltq(f, _) => f,
+            FcvtmodWD(_) => FpFmt::D,
+        }
+    }
+
+    /// Every format the instruction reads or writes.
+    pub fn formats(&self) -> (FpFmt, Option<FpFmt>) {
+        match *self {
+            RVF::Fcvt(to, from, _) => (to, Some(from)),
+            _ => (self.fmt(), None),
+        }
+    }
+
+    /// The rounding mode of instructions that carry one.
+    pub fn rounding_mode(&self) -> Option<u8> {
+        use RVF::*;
+        match self {
+            Fmadd(_, r4) | Fmsub(_, r4) | Fnmsub(_, r4) | Fnmadd(_, r4) => Some(r4.funct3),
+            Fadd(_, r) | Fsub(_, r) | Fmul(_, r) | Fdiv(_, r) | Fsqrt(_, r) | Fcvt(_, _, r)
+            | FcvtToInt(_, _, r) | FcvtFromInt(_, _, r) | Fround(_, r) | Froundnx(_, r)
+            | FcvtmodWD(r) => Some(r.funct3),
+            _ => None,
+        }
+    }
+
+    /// Rounding mode the assembler fills in when none is written: `rne` for
+    /// conversions that are always exact (as LLVM does), `dyn` otherwise.
+    /// `fcvtmod.w.d` has no default; its `rtz` is always written.
+    pub fn default_rounding_mode(&self) -> u8 {
+        match *self {
+            RVF::Fcvt(to, from, _) if to.precision() > from.precision() => FRM_RNE,
+            RVF::FcvtFromInt(to, int, _) if to.precision() > int.bits() => FRM_RNE,
+            _ => FRM_DYN,
+        }
+    }
+
+    pub fn mnemonic(&self) -> String {
+        use RVF::*;
+        let op = match self {
+            Load(f, _) => return format!("fl{}", f.mem_suffix()),
+            Store(f, _) => return format!("fs{}", f.mem_suffix()),
+            Fcvt(to, from, _) => return format!("fcvt.{}.{}", to.suffix(), from.suffix()),
+            FcvtToInt(int, f, _) => return format!("fcvt.{}.{}", int.suffix(), f.suffix()),
+            FcvtFromInt(f, int, _) => return format!("fcvt.{}.{}", f.suffix(), int.suffix()),
+            FmvToInt(f, _) => return format!("fmv.x.{}", f.mem_suffix()),
+            FmvFromInt(f, _) => return format!("fmv.{}.x", f.mem_suffix()),
+            FcvtmodWD(_) => return "fcvtmod.w.d".to_string(),
+            Fmvh(f, _) => return format!("fmvh.x.{}", f.suffix()),
+            Fmvp(f, _) => return format!("fmvp.{}.x", f.suffix()),
+            Fmadd(..) => "fmadd",
+            Fmsub(..) => "fmsub",
+            Fnmsub(..) => "fnmsub",
+            Fnmadd(..) => "fnmadd",
+            Fadd(..) => "fadd",
+            Fsub(..) => "fsub",
+            Fmul(..) => "fmul",
+            Fdiv(..) => "fdiv",
+            Fsqrt(..) => "fsqrt",
+            Fsgnj(..) => "fsgnj",
+            Fsgnjn(..) => "fsgnjn",
+            Fsgnjx(..) => "fsgnjx",
+            Fmin(..) => "fmin",
+            Fmax(..) => "fmax",
+            Feq(..) => "feq",
+            Flt(..) => "flt",
+            Fle(..) => "fle",
+            Fclass(..) => "fclass",
+            Fli(..) => "fli",
+            Fminm(..) => "fminm",
+            Fmaxm(..) => "fmaxm",
+            Fround(..) => "fround",
+            Froundnx(..) => "froundnx",
+            Fleq(..) => "fleq",
+            Fltq(..) => "fltq",
+        };
+        format!("{}.{}", op, self.fmt().suffix())
+    }
+
+    /// Floating-point register operands, each with the format it holds.
+    pub fn fp_operands(&self) -> Vec<(u8, FpFmt)> {
+        use RVF::*;
+        match *self {
+            Load(t, i) => vec![(i.rd, t)],
+            Store(t, s) => vec![(s.rs2, t)],
+            Fmadd(t, r) | Fmsub(t, r) | Fnmsub(t, r) | Fnmadd(t, r) => {
+                vec![(r.rd, t), (r.rs1, t), (r.rs2, t), (r.rs3, t)]
+            }
+            Fadd(t, r) | Fsub(t, r) | Fmul(t, r) | Fdiv(t, r) | Fsgnj(t, r) | Fsgnjn(t, r)
+            | Fsgnjx(t, r) | Fmin(t, r) | Fmax(t, r) | Fminm(t, r) | Fmaxm(t, r) => {
+                vec![(r.rd, t), (r.rs1, t), (r.rs2, t)]
+            }
+            Fsqrt(t, r) | Fround(t, r) | Froundnx(t, r) => vec![(r.rd, t), (r.rs1, t)],
+            Fcvt(to, from, r) => vec![(r.rd, to), (r.rs1, from)],
+            Feq(t, r) | Flt(t, r) | Fle(t, r) | Fleq(t, r) | Fltq(t, r) => vec![(r.rs1, t), (r.rs2, t)],
+            Fclass(t, r) | FcvtToInt(_, t, r) | FmvToInt(t, r) | Fmvh(t, r) => vec![(r.rs1, t)],
+            FcvtmodWD(r) => vec![(r.rs1, FpFmt::D)],
+            FcvtFromInt(t, _, r) | FmvFromInt(t, r) | Fli(t, r) | Fmvp(t, r) => vec![(r.rd, t)],
+        }
+    }
+
+    /// Canonical text; a rounding mode other than the default is appended
+    /// as the last operand.
+    pub fn to_string(&self) -> String {
+        self.format(false)
+    }
+
+    /// Text under Zfinx/Zdinx/Zhinx, where the operands are integer registers.
+    pub fn to_string_zfinx(self) -> String {
+        self.format(true)
+    }
+
+    fn format(&self, zfinx: bool) -> String {
+        let text = format!("{} {}", self.mnemonic(), self.operands(zfinx).join(", "));
+        let rm = self.rounding_mode().filter(|&rm| rm != self.default_rounding_mode());
+        match rm.and_then(rounding_mode_name) {
+            Some(name) => format!("{}, {}", text, name),
+            None => text,
+        }
+    }
+
+    fn operands(&self, zfinx: bool) -> Vec<String> {
+        use RVF::*;
+        let f = fp_register_names(zfinx);
+        let x = to_register;
+        match self {
+            Load(_, i) => vec![f(i.rd), format!("{:?}({})", i.imm, x(i.rs1))],
+            Store(_, s) => vec![f(s.rs2), format!("{:?}({})", s.imm, x(s.rs1))],
+            Fmadd(_, r) | Fmsub(_, r) | Fnmsub(_, r) | Fnmadd(_, r) => {
+                vec![f(r.rd), f(r.rs1), f(r.rs2), f(r.rs3)]
+            }
+            Fadd(_, r) | Fsub(_, r) | Fmul(_, r) | Fdiv(_, r) | Fsgnj(_, r) | Fsgnjn(_, r)
+            | Fsgnjx(_, r) | Fmin(_, r) | Fmax(_, r) | Fminm(_, r) | Fmaxm(_, r) => {
+                vec![f(r.rd), f(r.rs1), f(r.rs2)]
+            }
+            Fsqrt(_, r) | Fcvt(_, _, r) | Fround(_, r) | Froundnx(_, r) => vec![f(r.rd), f(r.rs1)],
+            // comparisons, classify and float -> integer write an integer register
+            Feq(_, r) | Flt(_, r) | Fle(_, r) | Fleq(_, r) | Fltq(_, r) => {
+                vec![x(r.rd), f(r.rs1), f(r.rs2)]
+            }
+            Fclass(_, r) | FcvtToInt(_, _, r) | FmvToInt(_, r) | FcvtmodWD(r) | Fmvh(_, r) => {
+                vec![x(r.rd), f(r.rs1)]
+            }
+            FcvtFromInt(_, _, r) | FmvFromInt(_, r) => vec![f(r.rd), x(r.rs1)],
+            Fli(t, r) => vec![f(r.rd), fli_constant(*t, r.rs1).to_string()],
+            Fmvp(_, r) => vec![f(r.rd), x(r.rs1), x(r.rs2)],
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use super::{to_register, IType, KType, RType};
+
+/// Scalar cryptography: Zbkb and Zbkx (the parts not already in Zbb/Zbc),
+/// Zknd, Zkne, Zknh, Zksed and Zksh. Unary operations keep only rd and rs1;
+/// `aes64ks1i` keeps its round number in `imm`.
+#[derive(Debug, Clone, Copy)]
+pub enum RVK {
+    // Zbkb
+    Pack(RType),
+    Packh(RType),
+    Brev8(RType),
+    // Zbkb, RV32
+    Zip(RType),
+    Unzip(RType),
+    // Zbkb, RV64
+    Packw(RType),
+
+    // Zbkx
+    Xperm4(RType),
+    Xperm8(RType),
+
+    // Zknd/Zkne, RV32
+    Aes32dsi(KType),
+    Aes32dsmi(KType),
+    Aes32esi(KType),
+    Aes32esmi(KType),
+    // Zknd/Zkne, RV64
+    Aes64ds(RType),
+    Aes64dsm(RType),
+    Aes64es(RType),
+    Aes64esm(RType),
+    Aes64im(RType),
+    Aes64ks1i(IType),
+    Aes64ks2(RType),
+
+    // Zknh
+    Sha256sig0(RType),
+    Sha256sig1(RType),
+    Sha256sum0(RType),
+    Sha256sum1(RType),
+    // Zknh, RV32
+    Sha512sig0h(RType),
+    Sha512sig0l(RType),
+    Sha512sig1h(RType),
+    Sha512sig1l(RType),
+    Sha512sum0r(RType),
+    Sha512sum1r(RType),
+    // Zknh, RV64
+    Sha512sig0(RType),
+    Sha512sig1(RType),
+    Sha512sum0(RType),
+    Sha512sum1(RType),
+
+    // Zksed
+    Sm4ed(KType),
+    Sm4ks(KType),
+    // Zksh
+    Sm3p0(RType),
+    Sm3p1(RType),
+}
+
+impl RVK {
+    /// Instructions that only exist on RV32.
+    pub fn is_rv32_only(self) -> bool {
+        use RVK::*;
+        matches!(
+            self,
+            Zip(_) | Unzip(_) | Aes32dsi(_) | Aes32dsmi(_) | Aes32esi(_) | Aes32esmi(_)
+                | Sha512sig0h(_) | Sha512sig0l(_) | Sha512sig1h(_) | Sha512sig1l(_)
+                | Sha512sum0r(_) | Sha512sum1r(_)
+        )
+    }
+
+    /// Instructions that only exist on RV64 and wider.
+    pub fn is_rv64_only(self) -> bool {
+        use RVK::*;
+        matches!(
+            self,
+            Packw(_) | Aes64ds(_) | Aes64dsm(_) | Aes64es(_) | Aes64esm(_) | Aes64im(_)
+                | Aes64ks1i(_) | Aes64ks2(_) | Sha512sig0(_) | Sha512sig1(_) | Sha512sum0(_)
+                | Sha512sum1(_)
+        )
+    }
+
+    pub fn mnemonic(&self) -> &'static str {
+        use RVK::*;
+        match self {
+            Pack(_) => "pack",
+            Packh(_) => "packh",
+            Brev8(_) => "brev8",
+            Zip(_) => "zip",
+            Unzip(_) => "unzip",
+            Packw(_) => "packw",
+            Xperm4(_) => "xperm4",
+            Xperm8(_) => "xperm8",
+            Aes32dsi(_) => "aes32dsi",
+            Aes32dsmi(_) => "aes32dsmi",
+            Aes32esi(_) => "aes32esi",
+            Aes32esmi(_) => "aes32esmi",
+            Aes64ds(_) => "aes64ds",
+            Aes64dsm(_) => "aes64dsm",
+            Aes64es(_) => "aes64es",
+            Aes64esm(_) => "aes64esm",
+            Aes64im(_) => "aes64im",
+            Aes64ks1i(_) => "aes64ks1i",
+            Aes64ks2(_) => "aes64ks2",
+            Sha256sig0(_) => "sha256sig0",
+            Sha256sig1(_) => "sha256sig1",
+            Sha256sum0(_) => "sha256sum0",
+            Sha256sum1(_) => "sha256sum1",
+            Sha512sig0h(_) => "sha512sig0h",
+            Sha512sig0l(_) => "sha512sig0l",
+            Sha512sig1h(_) => "sha512sig1h",
+            Sha512sig1l(_) => "sha512sig1l",
+            Sha512sum0r(_) => "sha512sum0r",
+            Sha512sum1r(_) => "sha512sum1r",
+            Sha512sig0(_) => "sha512sig0",
+            Sha512sig1(_) => "sha512sig1",
+            Sha512sum0(_) => "sha512sum0",
+            Sha512sum1(_) => "sha512sum1",
+            Sm4ed(_) => "sm4ed",
+            Sm4ks(_) => "sm4ks",
+            Sm3p0(_) => "sm3p0",
+            Sm3p1(_) => "sm3p1",
+        }
+    }
+}
+
+impl core::fmt::Display for RVK {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        use RVK::*;
+        let m = self.mnemonic();
+        let x = to_register;
+        match self {
+            Aes32dsi(k) | Aes32dsmi(k) | Aes32esi(k) | Aes32esmi(k) | Sm4ed(k) | Sm4ks(k) => {
+                write!(f, "{} {}, {}, {}, {}", m, x(k.rd), x(k.rs1), x(k.rs2), k.bs)
+            }
+            Aes64ks1i(i) => write!(f, "{} {}, {}, {}", m, x(i.rd), x(i.rs1), i.imm.low_u32() & 0xf),
+            Brev8(r) | Zip(r) | Unzip(r) | Aes64im(r) | Sha256sig0(r) | Sha256sig1(r)
+            | Sha256sum0(r) | Sha256sum1(r) | Sha512sig0(r) | Sha512sig1(r) | Sha512sum0(r)
+            | Sha512sum1(r) | Sm3p0(r) | Sm3p1(r) => write!(f, "{} {}, {}", m, x(r.rd), x(r.rs1)),
+            Pack(r) | Packh(r) | Packw(r) | Xperm4(r) | Xperm8(r) | Aes64ds(r) | Aes64dsm(r)
+            | Aes64es(r) | Aes64esm(r) | Aes64ks2(r) | Sha512sig0h(r) | Sha512sig0l(r)
+            | Sha512sig1h(r) | Sha512sig1l(r) | Sha512sum0r(r) | Sha512sum1r(r) => {
+                write!(f, "{} {}, {}, {}", m, x(r.rd), x(r.rs1), x(r.rs2))
+            }
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use super::{to_register, RType};
+
+/// Privileged instructions: trap returns, `wfi`, the address-translation
+/// fences (`sfence.vma`, `hfence.*`, Svinval) and the hypervisor
+/// virtual-machine loads and stores. Fences keep rs1/rs2, `hlv` keeps rd and
+/// rs1, `hsv` keeps rs1 and rs2. The Zawrs waits are unprivileged but sit
+/// next to `wfi` in the encoding space.
+#[derive(Debug, Clone, Copy)]
+pub enum RVPriv {
+    Sret(()),
+    Mret(()),
+    Mnret(()),
+    Wfi(()),
+    // Zawrs
+    WrsNto(()),
+    WrsSto(()),
+
+    SfenceVma(RType),
+    HfenceVvma(RType),
+    HfenceGvma(RType),
+
+    // Svinval
+    SinvalVma(RType),
+    HinvalVvma(RType),
+    HinvalGvma(RType),
+    SfenceWInval(()),
+    SfenceInvalIr(()),
+
+    // H
+    HlvB(RType),
+    HlvBu(RType),
+    HlvH(RType),
+    HlvHu(RType),
+    HlvxHu(RType),
+    HlvW(RType),
+    HlvxWu(RType),
+    HsvB(RType),
+    HsvH(RType),
+    HsvW(RType),
+    // H, RV64
+    HlvWu(RType),
+    HlvD(RType),
+    HsvD(RType),
+}
+
+impl RVPriv {
+    /// Instructions that only exist on RV64 and wider.
+    pub fn is_rv64_only(self) -> bool {
+        matches!(self, RVPriv::HlvWu(_) | RVPriv::HlvD(_) | RVPriv::HsvD(_))
+    }
+
+    /// The hypervisor fences and virtual-machine loads and stores (H).
+    pub fn is_hypervisor(self) -> bool {
+        use RVPriv::*;
+        matches!(
+            self,
+            HfenceVvma(_) | HfenceGvma(_) | HinvalVvma(_) | HinvalGvma(_) | HlvB(_) | HlvBu(_)
+                | HlvH(_) | HlvHu(_) | HlvxHu(_) | HlvW(_) | HlvxWu(_) | HsvB(_) | HsvH(_)
+                | HsvW(_) | HlvWu(_) | HlvD(_) | HsvD(_)
+        )
+    }
+
+    pub fn mnemonic(&self) -> &'static str {
+        use RVPriv::*;
+        match self {
+            Sret(_) => "sret",
+            Mret(_) => "mret",
+            Mnret(_) => "mnret",
+            Wfi(_) => "wfi",
+            WrsNto(_) => "wrs.nto",
+            WrsSto(_) => "wrs.sto",
+            SfenceVma(_) => "sfence.vma",
+            HfenceVvma(_) => "hfence.vvma",
+            HfenceGvma(_) => "hfence.gvma",
+            SinvalVma(_) => "sinval.vma",
+            HinvalVvma(_) => "hinval.vvma",
+            HinvalGvma(_) => "hinval.gvma",
+            SfenceWInval(_) => "sfence.w.inval",
+            SfenceInvalIr(_) => "sfence.inval.ir",
+            HlvB(_) => "hlv.b",
+            HlvBu(_) => "hlv.bu",
+            HlvH(_) => "hlv.h",
+            HlvHu(_) => "hlv.hu",
+            HlvxHu(_) => "hlvx.hu",
+            HlvW(_) => "hlv.w",
+            HlvxWu(_) => "hlvx.wu",
+            HsvB(_) => "hsv.b",
+            HsvH(_) => "hsv.h",
+            HsvW(_) => "hsv.w",
+            HlvWu(_) => "hlv.wu",
+            HlvD(_) => "hlv.d",
+            HsvD(_) => "hsv.d",
+        }
+    }
+
+    pub(crate) fn format_with(&self, sep: &str) -> String {
+        use RVPriv::*;
+        let m = self.mnemonic();
+        let x = to_register;
+        match self {
+            Sret(_) | Mret(_) | Mnret(_) | Wfi(_) | WrsNto(_) | WrsSto(_) | SfenceWInval(_)
+            | SfenceInvalIr(_) => m.to_string(),
+            SfenceVma(r) | HfenceVvma(r) | HfenceGvma(r) | SinvalVma(r) | HinvalVvma(r) | HinvalGvma(r) => {
+                format!("{} {}{}{}", m, x(r.rs1), sep, x(r.rs2))
+            }
+            HlvB(r) | HlvBu(r) | HlvH(r) | HlvHu(r) | HlvxHu(r) | HlvW(r) | HlvxWu(r) | HlvWu(r) | HlvD(r) => {
+                format!("{} {}{}({})", m, x(r.rd), sep, x(r.rs1))
+            }
+            HsvB(r) | HsvH(r) | HsvW(r) | HsvD(r) => format!("{} {}{}({})", m, x(r.rs2), sep, x(r.rs1)),
+        }
+    }
+}
+
+impl core::fmt::Display for RVPriv {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.format_with(", "))
+    }
+}
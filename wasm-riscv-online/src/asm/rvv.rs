@@ -0,0 +1,649 @@
+use super::rvf::to_fp_register;
+use super::{to_register, RType};
+use crate::isa::*;
+
+/// Vector extension (RVV 1.0): configuration, loads/stores and the OP-V
+/// arithmetic forms. Arithmetic keeps the raw fields and is named through
+/// the tables below, so every instruction the tables know decodes, prints
+/// and encodes the same way.
+#[derive(Debug, Clone, Copy)]
+pub enum RVV {
+    Vsetvli(VsetType),
+    /// `rs1` holds the 5-bit immediate AVL.
+    Vsetivli(VsetType),
+    Vsetvl(RType),
+    Load(VMem),
+    /// `vd` is the store data register (vs3).
+    Store(VMem),
+    Arith(VArith),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct VsetType {
+    pub rd: u8,
+    pub rs1: u8,
+    pub vtypei: u16,
+}
+
+/// Addressing mode of a vector load/store; strided and indexed forms carry
+/// rs2 or vs2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VAddr {
+    Unit,
+    WholeReg,
+    Mask,
+    FaultFirst,
+    Strided(u8),
+    Unordered(u8),
+    Ordered(u8),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct VMem {
+    pub vd: u8,
+    pub rs1: u8,
+    pub mode: VAddr,
+    /// Element width in bits: 8, 16, 32 or 64.
+    pub eew: u16,
+    /// Number of fields minus one (segment accesses, whole-register count).
+    pub nf: u8,
+    /// The vm bit: `false` means masked by v0.
+    pub vm: bool,
+}
+
+/// Which of OPI*, OPM* and OPF* an arithmetic instruction belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VGroup {
+    I,
+    M,
+    F,
+}
+
+/// Kind of the second source: vs1, a scalar (x, or f in the FP group) or
+/// the 5-bit immediate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VSrc {
+    V,
+    X,
+    I,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct VArith {
+    pub funct6: u8,
+    pub group: VGroup,
+    pub kind: VSrc,
+    pub vd: u8,
+    pub vs2: u8,
+    /// vs1, rs1 or the raw 5-bit immediate, depending on `kind`.
+    pub src: u8,
+    /// The vm bit: `false` means masked by v0 (or v0 is the carry input).
+    pub vm: bool,
+}
+
+impl VArith {
+    /// OP-V funct3 for `group` and `kind`; `None` for OPM/OPF immediates.
+    pub fn funct3(group: VGroup, kind: VSrc) -> Option<u8> {
+        Some(match (group, kind) {
+            (VGroup::I, VSrc::V) => FUNCT3_V_OPIVV,
+            (VGroup::F, VSrc::V) => FUNCT3_V_OPFVV,
+            (VGroup::M, VSrc::V) => FUNCT3_V_OPMVV,
+            (VGroup::I, VSrc::I) => FUNCT3_V_OPIVI,
+            (VGroup::I, VSrc::X) => FUNCT3_V_OPIVX,
+            (VGroup::F, VSrc::X) => FUNCT3_V_OPFVF,
+            (VGroup::M, VSrc::X) => FUNCT3_V_OPMVX,
+            _ => return None,
+        })
+    }
+
+    pub fn from_funct3(funct3: u8) -> Option<(VGroup, VSrc)> {
+        Some(match funct3 {
+            FUNCT3_V_OPIVV => (VGroup::I, VSrc::V),
+            FUNCT3_V_OPFVV => (VGroup::F, VSrc::V),
+            FUNCT3_V_OPMVV => (VGroup::M, VSrc::V),
+            FUNCT3_V_OPIVI => (VGroup::I, VSrc::I),
+            FUNCT3_V_OPIVX => (VGroup::I, VSrc::X),
+            FUNCT3_V_OPFVF => (VGroup::F, VSrc::X),
+            FUNCT3_V_OPMVX => (VGroup::M, VSrc::X),
+            _ => return None,
+        })
+    }
+
+    fn unary(&self) -> Option<&'static VUnary> {
+        UNARY.iter().find(|u| {
+            u.funct6 == self.funct6
+                && u.group == self.group
+                && u.kind == self.kind
+                && match u.sub {
+                    Sub::Src(c) => self.src == c,
+                    Sub::Vs2(c) => self.vs2 == c,
+                }
+                && (u.maskable || self.vm)
+                && match u.ops {
+                    UOps::Vd => self.vs2 == 0,
+                    UOps::Group => self.vd.is_multiple_of(self.src + 1) && self.vs2.is_multiple_of(self.src + 1),
+                    _ => true,
+                }
+        })
+    }
+
+    fn binary(&self) -> Option<&'static VOp> {
+        BINARY.iter().find(|o| {
+            o.funct6 == self.funct6
+                && o.group == self.group
+                && o.srcs & src_bit(self.kind) != 0
+                && match o.shape {
+                    Shape::Carry => !self.vm,
+                    Shape::Mask | Shape::Compress => self.vm,
+                    _ => true,
+                }
+        })
+    }
+
+    /// Whether the fields name a defined instruction.
+    pub fn is_valid(&self) -> bool {
+        self.unary().is_some() || self.binary().is_some()
+    }
+
+    /// Whether a masked instruction writes a vector result over v0, which
+    /// the spec reserves. Mask results (compares, `vmadc`, `vmsbf.m`, ...),
+    /// reductions and scalar results may sit in v0; `vadc`/`vmerge` may not.
+    pub fn masked_vd_overlaps_v0(&self) -> bool {
+        if self.vm || self.vd != 0 {
+            return false;
+        }
+        if let Some(u) = self.unary() {
+            return matches!(u.ops, UOps::VdVs2 | UOps::Vd) && !matches!(u.name, "vmsbf.m" | "vmsof.m" | "vmsif.m");
+        }
+        match self.binary() {
+            // the compares are funct6 011xxx in OPI and OPF
+            Some(o) => o.funct6 >> 3 != 0b011 && !matches!(o.shape, Shape::Reduce | Shape::CarryOut),
+            None => false,
+        }
+    }
+
+    /// Integer registers named: a scalar source outside the FP group, and
+    /// the destination of `vmv.x.s`, `vcpop.m` and `vfirst.m`.
+    pub fn int_registers(&self) -> Vec<u8> {
+        let mut regs = Vec::new();
+        if self.kind == VSrc::X && self.group != VGroup::F {
+            regs.push(self.src);
+        }
+        if self.unary().map(|u| u.ops) == Some(UOps::XdVs2) {
+            regs.push(self.vd);
+        }
+        regs
+    }
+
+    pub fn mnemonic(&self) -> String {
+        if let Some(u) = self.unary() {
+            return u.name.to_string();
+        }
+        match self.binary() {
+            Some(o) => format!("{}.{}", o.name, o.shape.suffix(self.group, self.kind, self.vm)),
+            None => "unknown".to_string(),
+        }
+    }
+
+    fn src_string(&self, signed: bool) -> String {
+        match self.kind {
+            VSrc::V => format!("v{}", self.src),
+            VSrc::X if self.group == VGroup::F => to_fp_register(self.src),
+            VSrc::X => to_register(self.src),
+            VSrc::I if signed => (((self.src as i8) << 3) >> 3).to_string(),
+            VSrc::I => self.src.to_string(),
+        }
+    }
+}
+
+impl core::fmt::Display for VArith {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let m = self.mnemonic();
+        let mask = if self.vm { "" } else { ", v0.t" };
+        if let Some(u) = self.unary() {
+            return match u.ops {
+                UOps::VdVs2 | UOps::Group => write!(f, "{} v{}, v{}{}", m, self.vd, self.vs2, mask),
+                UOps::XdVs2 => write!(f, "{} {}, v{}{}", m, to_register(self.vd), self.vs2, mask),
+                UOps::FdVs2 => write!(f, "{} {}, v{}", m, to_fp_register(self.vd), self.vs2),
+                UOps::VdSrc => write!(f, "{} v{}, {}", m, self.vd, self.src_string(true)),
+                UOps::Vd => write!(f, "{} v{}{}", m, self.vd, mask),
+            };
+        }
+        let shape = match self.binary() {
+            Some(o) => o.shape,
+            None => return f.write_str(&m),
+        };
+        let src = self.src_string(!matches!(shape, Shape::Uimm | Shape::Narrow));
+        match shape {
+            Shape::MulAdd => write!(f, "{} v{}, {}, v{}{}", m, self.vd, src, self.vs2, mask),
+            Shape::Carry | Shape::CarryOut if !self.vm => {
+                write!(f, "{} v{}, v{}, {}, v0", m, self.vd, self.vs2, src)
+            }
+            _ => write!(f, "{} v{}, v{}, {}{}", m, self.vd, self.vs2, src, mask),
+        }
+    }
+}
+
+impl VMem {
+    /// LOAD-FP/STORE-FP width code for `eew`.
+    pub fn width_code(eew: u16) -> Option<u8> {
+        Some(match eew {
+            8 => FUNCT3_V_WIDTH_8,
+            16 => FUNCT3_V_WIDTH_16,
+            32 => FUNCT3_V_WIDTH_32,
+            64 => FUNCT3_V_WIDTH_64,
+            _ => return None,
+        })
+    }
+
+    pub fn eew_of(width: u8) -> Option<u16> {
+        Some(match width {
+            FUNCT3_V_WIDTH_8 => 8,
+            FUNCT3_V_WIDTH_16 => 16,
+            FUNCT3_V_WIDTH_32 => 32,
+            FUNCT3_V_WIDTH_64 => 64,
+            _ => return None,
+        })
+    }
+
+    /// Whether the fields name a defined load (or store, with `store`).
+    pub fn is_valid(&self, store: bool) -> bool {
+        if self.nf > 7 || VMem::width_code(self.eew).is_none() {
+            return false;
+        }
+        match self.mode {
+            // groups of 1, 2, 4 or 8 registers, aligned to their size
+            VAddr::WholeReg => {
+                self.vm
+                    && matches!(self.nf, 0 | 1 | 3 | 7)
+                    && self.vd.is_multiple_of(self.nf + 1)
+                    && (!store || self.eew == 8)
+            }
+            VAddr::Mask => self.vm && self.nf == 0 && self.eew == 8,
+            VAddr::FaultFirst => !store,
+            _ => true,
+        }
+    }
+
+    pub fn mnemonic(&self, store: bool) -> String {
+        let ls = if store { "s" } else { "l" };
+        let seg = if self.nf > 0 { format!("seg{}", self.nf + 1) } else { String::new() };
+        match self.mode {
+            VAddr::Unit => format!("v{}{}e{}.v", ls, seg, self.eew),
+            VAddr::FaultFirst => format!("v{}{}e{}ff.v", ls, seg, self.eew),
+            VAddr::WholeReg if store => format!("vs{}r.v", self.nf + 1),
+            VAddr::WholeReg => format!("vl{}re{}.v", self.nf + 1, self.eew),
+            VAddr::Mask => format!("v{}m.v", ls),
+            VAddr::Strided(_) => format!("v{}s{}e{}.v", ls, seg, self.eew),
+            VAddr::Unordered(_) => format!("v{}ux{}ei{}.v", ls, seg, self.eew),
+            VAddr::Ordered(_) => format!("v{}ox{}ei{}.v", ls, seg, self.eew),
+        }
+    }
+
+    pub fn to_string(self, store: bool) -> String {
+        let mask = if self.vm { "" } else { ", v0.t" };
+        let base = format!("{} v{}, ({})", self.mnemonic(store), self.vd, to_register(self.rs1));
+        match self.mode {
+            VAddr::Strided(rs2) => format!("{}, {}{}", base, to_register(rs2), mask),
+            VAddr::Unordered(vs2) | VAddr::Ordered(vs2) => format!("{}, v{}{}", base, vs2, mask),
+            _ => format!("{}{}", base, mask),
+        }
+    }
+}
+
+/// `e32, m4, ta, ma` for a vtype immediate; reserved encodings are printed
+/// as the raw number, as LLVM does.
+pub fn vtype_to_string(vtypei: u16) -> String {
+    let vsew = (vtypei >> 3) & 0b111;
+    let vlmul = vtypei & 0b111;
+    if vtypei >> 8 != 0 || vsew > 3 || vlmul == 0b100 {
+        return vtypei.to_string();
+    }
+    let lmul = match vlmul {
+        0b000 => "m1",
+        0b001 => "m2",
+        0b010 => "m4",
+        0b011 => "m8",
+        0b101 => "mf8",
+        0b110 => "mf4",
+        _ => "mf2",
+    };
+    let ta = if vtypei & 0x40 != 0 { "ta" } else { "tu" };
+    let ma = if vtypei & 0x80 != 0 { "ma" } else { "mu" };
+    format!("e{}, {}, {}, {}", 8 << vsew, lmul, ta, ma)
+}
+
+impl RVV {
+    pub fn mnemonic(&self) -> String {
+        match self {
+            RVV::Vsetvli(_) => "vsetvli".to_string(),
+            RVV::Vsetivli(_) => "vsetivli".to_string(),
+            RVV::Vsetvl(_) => "vsetvl".to_string(),
+            RVV::Load(m) => m.mnemonic(false),
+            RVV::Store(m) => m.mnemonic(true),
+            RVV::Arith(a) => a.mnemonic(),
+        }
+    }
+}
+
+impl core::fmt::Display for RVV {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let x = to_register;
+        match self {
+            RVV::Vsetvli(v) => write!(f, "vsetvli {}, {}, {}", x(v.rd), x(v.rs1), vtype_to_string(v.vtypei)),
+            RVV::Vsetivli(v) => write!(f, "vsetivli {}, {}, {}", x(v.rd), v.rs1, vtype_to_string(v.vtypei)),
+            RVV::Vsetvl(r) => write!(f, "vsetvl {}, {}, {}", x(r.rd), x(r.rs1), x(r.rs2)),
+            RVV::Load(m) => f.write_str(&m.to_string(false)),
+            RVV::Store(m) => f.write_str(&m.to_string(true)),
+            RVV::Arith(a) => write!(f, "{}", a),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Operation tables
+// ---------------------------------------------------------------------------
+
+/// Source kinds an operation accepts (`VX` is `.vf` in the FP group).
+pub(crate) const VV: u8 = 1;
+pub(crate) const VX: u8 = 2;
+pub(crate) const VI: u8 = 4;
+
+pub(crate) fn src_bit(kind: VSrc) -> u8 {
+    match kind {
+        VSrc::V => VV,
+        VSrc::X => VX,
+        VSrc::I => VI,
+    }
+}
+
+/// Operand layout and mnemonic suffix of a two-source operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Shape {
+    /// `vd, vs2, vs1/rs1/simm5` with `.vv`/`.vx`/`.vi` (`.vf` for FP).
+    Plain,
+    /// As `Plain` with an unsigned immediate (shifts, slides, gathers).
+    Uimm,
+    /// Narrowing, vs2 is double width: `.wv`/`.wx`/`.wi`, unsigned immediate.
+    Narrow,
+    /// Widening with a double-width vs2: `.wv`/`.wx`/`.wf`.
+    Wide,
+    /// Reductions: `.vs`.
+    Reduce,
+    /// Mask-register logical: `.mm`, never masked.
+    Mask,
+    /// Multiply-add: `vd, vs1/rs1, vs2`.
+    MulAdd,
+    /// v0 is the carry/borrow/merge input (`vm` = 0): `.vvm` with a trailing `v0`.
+    Carry,
+    /// Carry-out: the `Carry` form when `vm` = 0, `Plain` when `vm` = 1.
+    CarryOut,
+    /// `vcompress.vm`, never masked.
+    Compress,
+}
+
+impl Shape {
+    pub(crate) fn suffix(self, group: VGroup, kind: VSrc, vm: bool) -> String {
+        let s = match kind {
+            VSrc::V => 'v',
+            VSrc::X if group == VGroup::F => 'f',
+            VSrc::X => 'x',
+            VSrc::I => 'i',
+        };
+        match self {
+            Shape::Plain | Shape::Uimm | Shape::MulAdd => format!("v{}", s),
+            Shape::Narrow | Shape::Wide => format!("w{}", s),
+            Shape::Reduce => "vs".to_string(),
+            Shape::Mask => "mm".to_string(),
+            Shape::Compress => "vm".to_string(),
+            Shape::CarryOut if vm => format!("v{}", s),
+            Shape::Carry | Shape::CarryOut => format!("v{}m", s),
+        }
+    }
+}
+
+pub(crate) struct VOp {
+    pub name: &'static str,
+    pub funct6: u8,
+    pub group: VGroup,
+    pub srcs: u8,
+    pub shape: Shape,
+}
+
+const fn op(name: &'static str, funct6: u8, group: VGroup, srcs: u8, shape: Shape) -> VOp {
+    VOp { name, funct6, group, srcs, shape }
+}
+
+/// Field of a unary operation that holds its sub-opcode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Sub {
+    /// vs1/rs1/imm field, the operand is vs2.
+    Src(u8),
+    /// vs2 field, the operand is vs1/rs1/imm.
+    Vs2(u8),
+}
+
+/// Operands printed by a unary operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum UOps {
+    VdVs2,
+    XdVs2,
+    FdVs2,
+    VdSrc,
+    /// `vid.v`: vs2 must be zero as well.
+    Vd,
+    /// `vmv<nr>r.v`: vd, vs2, both aligned to the group size.
+    Group,
+}
+
+pub(crate) struct VUnary {
+    pub name: &'static str,
+    pub funct6: u8,
+    pub group: VGroup,
+    pub kind: VSrc,
+    pub sub: Sub,
+    pub maskable: bool,
+    pub ops: UOps,
+}
+
+const fn un(name: &'static str, funct6: u8, group: VGroup, kind: VSrc, sub: Sub, maskable: bool, ops: UOps) -> VUnary {
+    VUnary { name, funct6, group, kind, sub, maskable, ops }
+}
+
+use self::Shape::*;
+use self::VGroup::{F, I, M};
+
+pub(crate) const BINARY: &[VOp] = &[
+    // OPIVV / OPIVX / OPIVI
+    op("vadd", 0b000000, I, VV | VX | VI, Plain),
+    op("vsub", 0b000010, I, VV | VX, Plain),
+    op("vrsub", 0b000011, I, VX | VI, Plain),
+    op("vminu", 0b000100, I, VV | VX, Plain),
+    op("vmin", 0b000101, I, VV | VX, Plain),
+    op("vmaxu", 0b000110, I, VV | VX, Plain),
+    op("vmax", 0b000111, I, VV | VX, Plain),
+    op("vand", 0b001001, I, VV | VX | VI, Plain),
+    op("vor", 0b001010, I, VV | VX | VI, Plain),
+    op("vxor", 0b001011, I, VV | VX | VI, Plain),
+    op("vrgather", 0b001100, I, VV | VX | VI, Uimm),
+    op("vrgatherei16", 0b001110, I, VV, Plain),
+    op("vslideup", 0b001110, I, VX | VI, Uimm),
+    op("vslidedown", 0b001111, I, VX | VI, Uimm),
+    op("vadc", 0b010000, I, VV | VX | VI, Carry),
+    op("vmadc", 0b010001, I, VV | VX | VI, CarryOut),
+    op("vsbc", 0b010010, I, VV | VX, Carry),
+    op("vmsbc", 0b010011, I, VV | VX, CarryOut),
+    op("vmerge", 0b010111, I, VV | VX | VI, Carry),
+    op("vmseq", 0b011000, I, VV | VX | VI, Plain),
+    op("vmsne", 0b011001, I, VV | VX | VI, Plain),
+    op("vmsltu", 0b011010, I, VV | VX, Plain),
+    op("vmslt", 0b011011, I, VV | VX, Plain),
+    op("vmsleu", 0b011100, I, VV | VX | VI, Plain),
+    op("vmsle", 0b011101, I, VV | VX | VI, Plain),
+    op("vmsgtu", 0b011110, I, VX | VI, Plain),
+    op("vmsgt", 0b011111, I, VX | VI, Plain),
+    op("vsaddu", 0b100000, I, VV | VX | VI, Plain),
+    op("vsadd", 0b100001, I, VV | VX | VI, Plain),
+    op("vssubu", 0b100010, I, VV | VX, Plain),
+    op("vssub", 0b100011, I, VV | VX, Plain),
+    op("vsll", 0b100101, I, VV | VX | VI, Uimm),
+    op("vsmul", 0b100111, I, VV | VX, Plain),
+    op("vsrl", 0b101000, I, VV | VX | VI, Uimm),
+    op("vsra", 0b101001, I, VV | VX | VI, Uimm),
+    op("vssrl", 0b101010, I, VV | VX | VI, Uimm),
+    op("vssra", 0b101011, I, VV | VX | VI, Uimm),
+    op("vnsrl", 0b101100, I, VV | VX | VI, Narrow),
+    op("vnsra", 0b101101, I, VV | VX | VI, Narrow),
+    op("vnclipu", 0b101110, I, VV | VX | VI, Narrow),
+    op("vnclip", 0b101111, I, VV | VX | VI, Narrow),
+    op("vwredsumu", 0b110000, I, VV, Reduce),
+    op("vwredsum", 0b110001, I, VV, Reduce),
+    // OPMVV / OPMVX
+    op("vredsum", 0b000000, M, VV, Reduce),
+    op("vredand", 0b000001, M, VV, Reduce),
+    op("vredor", 0b000010, M, VV, Reduce),
+    op("vredxor", 0b000011, M, VV, Reduce),
+    op("vredminu", 0b000100, M, VV, Reduce),
+    op("vredmin", 0b000101, M, VV, Reduce),
+    op("vredmaxu", 0b000110, M, VV, Reduce),
+    op("vredmax", 0b000111, M, VV, Reduce),
+    op("vaaddu", 0b001000, M, VV | VX, Plain),
+    op("vaadd", 0b001001, M, VV | VX, Plain),
+    op("vasubu", 0b001010, M, VV | VX, Plain),
+    op("vasub", 0b001011, M, VV | VX, Plain),
+    op("vslide1up", 0b001110, M, VX, Plain),
+    op("vslide1down", 0b001111, M, VX, Plain),
+    op("vcompress", 0b010111, M, VV, Compress),
+    op("vmandn", 0b011000, M, VV, Mask),
+    op("vmand", 0b011001, M, VV, Mask),
+    op("vmor", 0b011010, M, VV, Mask),
+    op("vmxor", 0b011011, M, VV, Mask),
+    op("vmorn", 0b011100, M, VV, Mask),
+    op("vmnand", 0b011101, M, VV, Mask),
+    op("vmnor", 0b011110, M, VV, Mask),
+    op("vmxnor", 0b011111, M, VV, Mask),
+    op("vdivu", 0b100000, M, VV | VX, Plain),
+    op("vdiv", 0b100001, M, VV | VX, Plain),
+    op("vremu", 0b100010, M, VV | VX, Plain),
+    op("vrem", 0b100011, M, VV | VX, Plain),
+    op("vmulhu", 0b100100, M, VV | VX, Plain),
+    op("vmul", 0b100101, M, VV | VX, Plain),
+    op("vmulhsu", 0b100110, M, VV | VX, Plain),
+    op("vmulh", 0b100111, M, VV | VX, Plain),
+    op("vmadd", 0b101001, M, VV | VX, MulAdd),
+    op("vnmsub", 0b101011, M, VV | VX, MulAdd),
+    op("vmacc", 0b101101, M, VV | VX, MulAdd),
+    op("vnmsac", 0b101111, M, VV | VX, MulAdd),
+    op("vwaddu", 0b110000, M, VV | VX, Plain),
+    op("vwadd", 0b110001, M, VV | VX, Plain),
+    op("vwsubu", 0b110010, M, VV | VX, Plain),
+    op("vwsub", 0b110011, M, VV | VX, Plain),
+    op("vwaddu", 0b110100, M, VV | VX, Wide),
+    op("vwadd", 0b110101, M, VV | VX, Wide),
+    op("vwsubu", 0b110110, M, VV | VX, Wide),
+    op("vwsub", 0b110111, M, VV | VX, Wide),
+    op("vwmulu", 0b111000, M, VV | VX, Plain),
+    op("vwmulsu", 0b111010, M, VV | VX, Plain),
+    op("vwmul", 0b111011, M, VV | VX, Plain),
+    op("vwmaccu", 0b111100, M, VV | VX, MulAdd),
+    op("vwmacc", 0b111101, M, VV | VX, MulAdd),
+    op("vwmaccus", 0b111110, M, VX, MulAdd),
+    op("vwmaccsu", 0b111111, M, VV | VX, MulAdd),
+    // OPFVV / OPFVF
+    op("vfadd", 0b000000, F, VV | VX, Plain),
+    op("vfredusum", 0b000001, F, VV, Reduce),
+    op("vfsub", 0b000010, F, VV | VX, Plain),
+    op("vfredosum", 0b000011, F, VV, Reduce),
+    op("vfmin", 0b000100, F, VV | VX, Plain),
+    op("vfredmin", 0b000101, F, VV, Reduce),
+    op("vfmax", 0b000110, F, VV | VX, Plain),
+    op("vfredmax", 0b000111, F, VV, Reduce),
+    op("vfsgnj", 0b001000, F, VV | VX, Plain),
+    op("vfsgnjn", 0b001001, F, VV | VX, Plain),
+    op("vfsgnjx", 0b001010, F, VV | VX, Plain),
+    op("vfslide1up", 0b001110, F, VX, Plain),
+    op("vfslide1down", 0b001111, F, VX, Plain),
+    op("vfmerge", 0b010111, F, VX, Carry),
+    op("vmfeq", 0b011000, F, VV | VX, Plain),
+    op("vmfle", 0b011001, F, VV | VX, Plain),
+    op("vmflt", 0b011011, F, VV | VX, Plain),
+    op("vmfne", 0b011100, F, VV | VX, Plain),
+    op("vmfgt", 0b011101, F, VX, Plain),
+    op("vmfge", 0b011111, F, VX, Plain),
+    op("vfdiv", 0b100000, F, VV | VX, Plain),
+    op("vfrdiv", 0b100001, F, VX, Plain),
+    op("vfmul", 0b100100, F, VV | VX, Plain),
+    op("vfrsub", 0b100111, F, VX, Plain),
+    op("vfmadd", 0b101000, F, VV | VX, MulAdd),
+    op("vfnmadd", 0b101001, F, VV | VX, MulAdd),
+    op("vfmsub", 0b101010, F, VV | VX, MulAdd),
+    op("vfnmsub", 0b101011, F, VV | VX, MulAdd),
+    op("vfmacc", 0b101100, F, VV | VX, MulAdd),
+    op("vfnmacc", 0b101101, F, VV | VX, MulAdd),
+    op("vfmsac", 0b101110, F, VV | VX, MulAdd),
+    op("vfnmsac", 0b101111, F, VV | VX, MulAdd),
+    op("vfwadd", 0b110000, F, VV | VX, Plain),
+    op("vfwredusum", 0b110001, F, VV, Reduce),
+    op("vfwsub", 0b110010, F, VV | VX, Plain),
+    op("vfwredosum", 0b110011, F, VV, Reduce),
+    op("vfwadd", 0b110100, F, VV | VX, Wide),
+    op("vfwsub", 0b110110, F, VV | VX, Wide),
+    op("vfwmul", 0b111000, F, VV | VX, Plain),
+    op("vfwmacc", 0b111100, F, VV | VX, MulAdd),
+    op("vfwnmacc", 0b111101, F, VV | VX, MulAdd),
+    op("vfwmsac", 0b111110, F, VV | VX, MulAdd),
+    op("vfwnmsac", 0b111111, F, VV | VX, MulAdd),
+];
+
+pub(crate) const UNARY: &[VUnary] = &[
+    un("vmv.v.v", 0b010111, I, VSrc::V, Sub::Vs2(0), false, UOps::VdSrc),
+    un("vmv.v.x", 0b010111, I, VSrc::X, Sub::Vs2(0), false, UOps::VdSrc),
+    un("vmv.v.i", 0b010111, I, VSrc::I, Sub::Vs2(0), false, UOps::VdSrc),
+    un("vmv1r.v", 0b100111, I, VSrc::I, Sub::Src(0), false, UOps::Group),
+    un("vmv2r.v", 0b100111, I, VSrc::I, Sub::Src(1), false, UOps::Group),
+    un("vmv4r.v", 0b100111, I, VSrc::I, Sub::Src(3), false, UOps::Group),
+    un("vmv8r.v", 0b100111, I, VSrc::I, Sub::Src(7), false, UOps::Group),
+    un("vmv.x.s", 0b010000, M, VSrc::V, Sub::Src(0), false, UOps::XdVs2),
+    un("vcpop.m", 0b010000, M, VSrc::V, Sub::Src(0b10000), true, UOps::XdVs2),
+    un("vfirst.m", 0b010000, M, VSrc::V, Sub::Src(0b10001), true, UOps::XdVs2),
+    un("vmv.s.x", 0b010000, M, VSrc::X, Sub::Vs2(0), false, UOps::VdSrc),
+    un("vzext.vf8", 0b010010, M, VSrc::V, Sub::Src(0b00010), true, UOps::VdVs2),
+    un("vsext.vf8", 0b010010, M, VSrc::V, Sub::Src(0b00011), true, UOps::VdVs2),
+    un("vzext.vf4", 0b010010, M, VSrc::V, Sub::Src(0b00100), true, UOps::VdVs2),
+    un("vsext.vf4", 0b010010, M, VSrc::V, Sub::Src(0b00101), true, UOps::VdVs2),
+    un("vzext.vf2", 0b010010, M, VSrc::V, Sub::Src(0b00110), true, UOps::VdVs2),
+    un("vsext.vf2", 0b010010, M, VSrc::V, Sub::Src(0b00111), true, UOps::VdVs2),
+    un("vmsbf.m", 0b010100, M, VSrc::V, Sub::Src(0b00001), true, UOps::VdVs2),
+    un("vmsof.m", 0b010100, M, VSrc::V, Sub::Src(0b00010), true, UOps::VdVs2),
+    un("vmsif.m", 0b010100, M, VSrc::V, Sub::Src(0b00011), true, UOps::VdVs2),
+    un("viota.m", 0b010100, M, VSrc::V, Sub::Src(0b10000), true, UOps::VdVs2),
+    un("vid.v", 0b010100, M, VSrc::V, Sub::Src(0b10001), true, UOps::Vd),
+    un("vfmv.f.s", 0b010000, F, VSrc::V, Sub::Src(0), false, UOps::FdVs2),
+    un("vfmv.s.f", 0b010000, F, VSrc::X, Sub::Vs2(0), false, UOps::VdSrc),
+    un("vfmv.v.f", 0b010111, F, VSrc::X, Sub::Vs2(0), false, UOps::VdSrc),
+    un("vfcvt.xu.f.v", 0b010010, F, VSrc::V, Sub::Src(0b00000), true, UOps::VdVs2),
+    un("vfcvt.x.f.v", 0b010010, F, VSrc::V, Sub::Src(0b00001), true, UOps::VdVs2),
+    un("vfcvt.f.xu.v", 0b010010, F, VSrc::V, Sub::Src(0b00010), true, UOps::VdVs2),
+    un("vfcvt.f.x.v", 0b010010, F, VSrc::V, Sub::Src(0b00011), true, UOps::VdVs2),
+    un("vfcvt.rtz.xu.f.v", 0b010010, F, VSrc::V, Sub::Src(0b00110), true, UOps::VdVs2),
+    un("vfcvt.rtz.x.f.v", 0b010010, F, VSrc::V, Sub::Src(0b00111), true, UOps::VdVs2),
+    un("vfwcvt.xu.f.v", 0b010010, F, VSrc::V, Sub::Src(0b01000), true, UOps::VdVs2),
+    un("vfwcvt.x.f.v", 0b010010, F, VSrc::V, Sub::Src(0b01001), true, UOps::VdVs2),
+    un("vfwcvt.f.xu.v", 0b010010, F, VSrc::V, Sub::Src(0b01010), true, UOps::VdVs2),
+    un("vfwcvt.f.x.v", 0b010010, F, VSrc::V, Sub::Src(0b01011), true, UOps::VdVs2),
+    un("vfwcvt.f.f.v", 0b010010, F, VSrc::V, Sub::Src(0b01100), true, UOps::VdVs2),
+    un("vfwcvt.rtz.xu.f.v", 0b010010, F, VSrc::V, Sub::Src(0b01110), true, UOps::VdVs2),
+    un("vfwcvt.rtz.x.f.v", 0b010010, F, VSrc::V, Sub::Src(0b01111), true, UOps::VdVs2),
+    un("vfncvt.xu.f.w", 0b010010, F, VSrc::V, Sub::Src(0b10000), true, UOps::VdVs2),
+    un("vfncvt.x.f.w", 0b010010, F, VSrc::V, Sub::Src(0b10001), true, UOps::VdVs2),
+    un("vfncvt.f.xu.w", 0b010010, F, VSrc::V, Sub::Src(0b10010), true, UOps::VdVs2),
+    un("vfncvt.f.x.w", 0b010010, F, VSrc::V, Sub::Src(0b10011), true, UOps::VdVs2),
+    un("vfncvt.f.f.w", 0b010010, F, VSrc::V, Sub::Src(0b10100), true, UOps::VdVs2),
+    un("vfncvt.rod.f.f.w", 0b010010, F, VSrc::V, Sub::Src(0b10101), true, UOps::VdVs2),
+    un("vfncvt.rtz.xu.f.w", 0b010010, F, VSrc::V, Sub::Src(0b10110), true, UOps::VdVs2),
+    un("vfncvt.rtz.x.f.w", 0b010010, F, VSrc::V, Sub::Src(0b10111), true, UOps::VdVs2),
+    un("vfsqrt.v", 0b010011, F, VSrc::V, Sub::Src(0b00000), true, UOps::VdVs2),
+    un("vfrsqrt7.v", 0b010011, F, VSrc::V, Sub::Src(0b00100), true, UOps::VdVs2),
+    un("vfrec7.v", 0b010011, F, VSrc::V, Sub::Src(0b00101), true, UOps::VdVs2),
+    un("vfclass.v", 0b010011, F, VSrc::V, Sub::Src(0b10000), true, UOps::VdVs2),
+];
@@ -0,0 +1,64 @@
+use super::{to_register, IType, Instruction, RType, RV32I};
+use crate::isa::{IMM5_PREFETCH_I, IMM5_PREFETCH_R, IMM5_PREFETCH_W};
+use crate::riscv::imm::Imm;
+
+/// Cache-block operations: management (Zicbom), zeroing (Zicboz) and the
+/// prefetch hints (Zicbop). `cbo.*` keep their address in rs1. A prefetch is
+/// `ori x0, rs1, imm` whose low five bits pick the kind, so its IType holds
+/// only the 32-byte aligned offset.
+#[derive(Debug, Clone, Copy)]
+pub enum RVZicbo {
+    CboInval(RType),
+    CboClean(RType),
+    CboFlush(RType),
+    CboZero(RType),
+    PrefetchI(IType),
+    PrefetchR(IType),
+    PrefetchW(IType),
+}
+
+impl RVZicbo {
+    pub fn mnemonic(&self) -> &'static str {
+        use RVZicbo::*;
+        match self {
+            CboInval(_) => "cbo.inval",
+            CboClean(_) => "cbo.clean",
+            CboFlush(_) => "cbo.flush",
+            CboZero(_) => "cbo.zero",
+            PrefetchI(_) => "prefetch.i",
+            PrefetchR(_) => "prefetch.r",
+            PrefetchW(_) => "prefetch.w",
+        }
+    }
+
+    /// The prefetch kind in imm[4:0], `None` for `cbo.*`.
+    pub fn prefetch(&self) -> Option<(u32, &IType)> {
+        use RVZicbo::*;
+        match self {
+            PrefetchI(i) => Some((IMM5_PREFETCH_I, i)),
+            PrefetchR(i) => Some((IMM5_PREFETCH_R, i)),
+            PrefetchW(i) => Some((IMM5_PREFETCH_W, i)),
+            CboInval(_) | CboClean(_) | CboFlush(_) | CboZero(_) => None,
+        }
+    }
+
+    /// The `ori` a prefetch hint is encoded as; `cbo.*` are not hints.
+    pub fn hint_base(&self) -> Option<Instruction> {
+        let (sel, i) = self.prefetch()?;
+        let imm = Imm::new((i.imm.low_u32() | sel) & 0xfff, 12);
+        Some(RV32I::Ori(IType { rd: 0, imm, ..*i }).into())
+    }
+}
+
+impl core::fmt::Display for RVZicbo {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        use RVZicbo::*;
+        let m = self.mnemonic();
+        match self {
+            CboInval(r) | CboClean(r) | CboFlush(r) | CboZero(r) => write!(f, "{} ({})", m, to_register(r.rs1)),
+            PrefetchI(i) | PrefetchR(i) | PrefetchW(i) => {
+                write!(f, "{} {}({})", m, i.imm.as_i64(), to_register(i.rs1))
+            }
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use super::{to_register, RType};
+
+/// Integer conditional operations (Zicond): `rd = rs2 ==/!= 0 ? 0 : rs1`.
+#[derive(Debug, Clone, Copy)]
+pub enum RVZicond {
+    CzeroEqz(RType),
+    CzeroNez(RType),
+}
+
+impl RVZicond {
+    pub fn mnemonic(&self) -> &'static str {
+        match self {
+            RVZicond::CzeroEqz(_) => "czero.eqz",
+            RVZicond::CzeroNez(_) => "czero.nez",
+        }
+    }
+
+    pub fn operands(&self) -> &RType {
+        match self {
+            RVZicond::CzeroEqz(r) | RVZicond::CzeroNez(r) => r,
+        }
+    }
+}
+
+impl core::fmt::Display for RVZicond {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let r = self.operands();
+        write!(f, "{} {}, {}, {}", self.mnemonic(), to_register(r.rd), to_register(r.rs1), to_register(r.rs2))
+    }
+}
@@ -1,4 +1,5 @@
-use super::{to_register, CsrIType, CsrRType};
+use super::{csr::csr_to_string, to_register, CsrIType, CsrRType};
+use crate::riscv::imm::Xlen;
 
 #[derive(Debug, Clone, Copy)]
 pub enum RVZicsr {
@@ -10,45 +11,107 @@ pub enum RVZicsr {
     Csrrci(CsrIType),
 }
 
+pub const CSR_FFLAGS: u16 = 0x001;
+pub const CSR_FRM: u16 = 0x002;
+pub const CSR_FCSR: u16 = 0x003;
+
+/// `rdcycle`-style counter reads. The high halves only exist on RV32, so
+/// without a known xlen they print as `csrr rd, cycleh`, which assembles on
+/// RV32 and is rejected elsewhere just like the CSR itself.
+fn counter_alias(csr: u16, xlen: Option<Xlen>) -> Option<&'static str> {
+    let rv32 = xlen == Some(Xlen::X32);
+    match csr {
+        0xc00 => Some("rdcycle"),
+        0xc01 => Some("rdtime"),
+        0xc02 => Some("rdinstret"),
+        0xc80 if rv32 => Some("rdcycleh"),
+        0xc81 if rv32 => Some("rdtimeh"),
+        0xc82 if rv32 => Some("rdinstreth"),
+        _ => None,
+    }
+}
+
 impl RVZicsr {
+    /// The CSR number the instruction accesses.
+    pub fn csr(&self) -> u16 {
+        use RVZicsr::*;
+        match self {
+            Csrrw(c) | Csrrs(c) | Csrrc(c) => c.csr,
+            Csrrwi(c) | Csrrsi(c) | Csrrci(c) => c.csr,
+        }
+    }
+
     pub fn to_string(&self) -> String {
+        self.format(None)
+    }
+
+    /// Text for a known xlen: `rdcycleh` on RV32, RV32-only CSRs as hex
+    /// elsewhere.
+    pub fn to_string_xlen(self, xlen: Xlen) -> String {
+        self.format(Some(xlen))
+    }
+
+    fn format(&self, xlen: Option<Xlen>) -> String {
+        let (mnem, ops) = self.short_form(xlen);
+        if ops.is_empty() {
+            mnem.to_string()
+        } else {
+            format!("{} {}", mnem, ops.join(", "))
+        }
+    }
+
+    /// Mnemonic and operands, using the standard pseudo-instructions
+    /// (`csrr`, `csrw`, `rdcycle`, `frcsr`, ...) when rd or rs1 is zero.
+    pub(crate) fn short_form(&self, xlen: Option<Xlen>) -> (&'static str, Vec<String>) {
+        use RVZicsr::*;
+        let x = to_register;
+        let csr = |n| csr_to_string(n, xlen);
         match self {
-            Self::Csrrw(csr) => format!(  
-                "csrrw {}, {:#x}, {}",  
-                to_register(csr.rd),  
-                csr.csr,  
-                to_register(csr.rs1)  
-            ),  
-            Self::Csrrs(csr) => format!(  
-                "csrrs {}, {:#x}, {}",  
-                to_register(csr.rd),  
-                csr.csr,  
-                to_register(csr.rs1)  
-            ),  
-            Self::Csrrc(csr) => format!(  
-                "csrrc {}, {:#x}, {}",  
-                to_register(csr.rd),  
-                csr.csr,  
-                to_register(csr.rs1)  
-            ),  
-            Self::Csrrwi(csr) => format!(  
-                "csrrwi {}, {:#x}, {:?}",  
-                to_register(csr.rd),  
-                csr.csr,  
-                csr.uimm  
-            ),  
-            Self::Csrrsi(csr) => format!(  
-                "csrrsi {}, {:#x}, {:?}",  
-                to_register(csr.rd),  
-                csr.csr,  
-                csr.uimm  
-            ),  
-            Self::Csrrci(csr) => format!(  
-                "csrrci {}, {:#x}, {:?}",  
-                to_register(csr.rd),  
-                csr.csr,  
-                csr.uimm  
-            ),  
+            Csrrs(c) if c.rs1 == 0 => match (counter_alias(c.csr, xlen), c.csr) {
+                (Some(m), _) => (m, vec![x(c.rd)]),
+                (None, CSR_FCSR) => ("frcsr", vec![x(c.rd)]),
+                (None, CSR_FRM) => ("frrm", vec![x(c.rd)]),
+                (None, CSR_FFLAGS) => ("frflags", vec![x(c.rd)]),
+                (None, _) => ("csrr", vec![x(c.rd), csr(c.csr)]),
+            },
+            Csrrs(c) if c.rd == 0 => ("csrs", vec![csr(c.csr), x(c.rs1)]),
+            Csrrs(c) => ("csrrs", vec![x(c.rd), csr(c.csr), x(c.rs1)]),
+
+            Csrrw(c) => {
+                let short = match c.csr {
+                    CSR_FCSR => Some("fscsr"),
+                    CSR_FRM => Some("fsrm"),
+                    CSR_FFLAGS => Some("fsflags"),
+                    _ => None,
+                };
+                match (short, c.rd) {
+                    (Some(m), 0) => (m, vec![x(c.rs1)]),
+                    (Some(m), _) => (m, vec![x(c.rd), x(c.rs1)]),
+                    (None, 0) => ("csrw", vec![csr(c.csr), x(c.rs1)]),
+                    (None, _) => ("csrrw", vec![x(c.rd), csr(c.csr), x(c.rs1)]),
+                }
+            }
+            Csrrc(c) if c.rd == 0 => ("csrc", vec![csr(c.csr), x(c.rs1)]),
+            Csrrc(c) => ("csrrc", vec![x(c.rd), csr(c.csr), x(c.rs1)]),
+
+            Csrrwi(c) => {
+                let short = match c.csr {
+                    CSR_FRM => Some("fsrmi"),
+                    CSR_FFLAGS => Some("fsflagsi"),
+                    _ => None,
+                };
+                let imm = format!("{:?}", c.uimm);
+                match (short, c.rd) {
+                    (Some(m), 0) => (m, vec![imm]),
+                    (Some(m), _) => (m, vec![x(c.rd), imm]),
+                    (None, 0) => ("csrwi", vec![csr(c.csr), imm]),
+                    (None, _) => ("csrrwi", vec![x(c.rd), csr(c.csr), imm]),
+                }
+            }
+            Csrrsi(c) if c.rd == 0 => ("csrsi", vec![csr(c.csr), format!("{:?}", c.uimm)]),
+            Csrrsi(c) => ("csrrsi", vec![x(c.rd), csr(c.csr), format!("{:?}", c.uimm)]),
+            Csrrci(c) if c.rd == 0 => ("csrci", vec![csr(c.csr), format!("{:?}", c.uimm)]),
+            Csrrci(c) => ("csrrci", vec![x(c.rd), csr(c.csr), format!("{:?}", c.uimm)]),
         }
     }
 }
@@ -0,0 +1,75 @@
+use super::{IType, Instruction, RType, RV32I};
+use crate::isa::*;
+use crate::riscv::imm::Imm;
+
+/// Pause (Zihintpause) and non-temporal locality (Zihintntl) hints. Both are
+/// base-ISA encodings with no architectural effect, `fence w, 0` and
+/// `add x0, x0, x2..x5`, and take no operands. The `ntl.*` hints also have
+/// compressed forms, `c.add x0, x2..x5` (`RVC::Cntl`).
+#[derive(Debug, Clone, Copy)]
+pub enum RVZihint {
+    Pause(()),
+    NtlP1(()),
+    NtlPall(()),
+    NtlS1(()),
+    NtlAll(()),
+}
+
+impl RVZihint {
+    pub fn mnemonic(&self) -> &'static str {
+        use RVZihint::*;
+        match self {
+            Pause(_) => "pause",
+            NtlP1(_) => "ntl.p1",
+            NtlPall(_) => "ntl.pall",
+            NtlS1(_) => "ntl.s1",
+            NtlAll(_) => "ntl.all",
+        }
+    }
+
+    /// The `ntl.*` hint encoded with `rs2`.
+    pub fn ntl(rs2: u8) -> Option<Self> {
+        use RVZihint::*;
+        Some(match rs2 {
+            RS2_NTL_P1 => NtlP1(()),
+            RS2_NTL_PALL => NtlPall(()),
+            RS2_NTL_S1 => NtlS1(()),
+            RS2_NTL_ALL => NtlAll(()),
+            _ => return None,
+        })
+    }
+
+    /// `c.ntl.*`; `pause` has no compressed form.
+    pub fn compressed_mnemonic(&self) -> Option<&'static str> {
+        use RVZihint::*;
+        match self {
+            Pause(_) => None,
+            NtlP1(_) => Some("c.ntl.p1"),
+            NtlPall(_) => Some("c.ntl.pall"),
+            NtlS1(_) => Some("c.ntl.s1"),
+            NtlAll(_) => Some("c.ntl.all"),
+        }
+    }
+
+    /// The instruction the hint is encoded as.
+    pub fn hint_base(&self) -> Instruction {
+        use RVZihint::*;
+        let rs2 = match self {
+            Pause(_) => {
+                let imm = Imm::new(IMM12_FENCE_PAUSE, 12);
+                return RV32I::Fence(IType { rd: 0, rs1: 0, funct3: FUNCT3_MISC_MEM_FENCE, imm }).into();
+            }
+            NtlP1(_) => RS2_NTL_P1,
+            NtlPall(_) => RS2_NTL_PALL,
+            NtlS1(_) => RS2_NTL_S1,
+            NtlAll(_) => RS2_NTL_ALL,
+        };
+        RV32I::Add(RType { rd: 0, rs1: 0, rs2, funct3: FUNCT3_OP_ADD_SUB, funct7: FUNCT7_OP_ADD }).into()
+    }
+}
+
+impl core::fmt::Display for RVZihint {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.mnemonic())
+    }
+}
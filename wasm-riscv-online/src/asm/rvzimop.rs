@@ -0,0 +1,29 @@
+use super::{to_register, RType};
+
+/// May-be-operations (Zimop): SYSTEM encodings reserved for later
+/// extensions to redefine, which until then write zero to rd. `mop.r.N`
+/// (N = 0..=31) reads rs1, `mop.rr.N` (N = 0..=7) reads rs1 and rs2.
+#[derive(Debug, Clone, Copy)]
+pub enum RVZimop {
+    MopR(u8, RType),
+    MopRR(u8, RType),
+}
+
+impl RVZimop {
+    pub fn mnemonic(&self) -> String {
+        match self {
+            RVZimop::MopR(n, _) => format!("mop.r.{}", n),
+            RVZimop::MopRR(n, _) => format!("mop.rr.{}", n),
+        }
+    }
+}
+
+impl core::fmt::Display for RVZimop {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let x = to_register;
+        match self {
+            RVZimop::MopR(_, r) => write!(f, "{} {}, {}", self.mnemonic(), x(r.rd), x(r.rs1)),
+            RVZimop::MopRR(_, r) => write!(f, "{} {}, {}, {}", self.mnemonic(), x(r.rd), x(r.rs1), x(r.rs2)),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! Expansion of compressed instructions into the base instruction each one
+//! stands for (`c.addi4spn a0, 16` -> `addi a0, sp, 16`), the inverse of
+//! `encode::compress`.
+use crate::asm::*;
+use crate::isa::*;
+use crate::riscv::imm::{Imm, Xlen};
+
+/// Sign-extend the decoded RVC immediate and re-encode it in `bits` bits.
+fn simm(imm: &Imm, bits: u8) -> Imm {
+    Imm::new((imm.as_i64() as u32) & ((1u32 << bits) - 1), bits)
+}
+
+/// Unsigned RVC offset (load/store displacements, `c.addi4spn`) as a 12-bit
+/// immediate.
+fn uimm(v: u32) -> Imm {
+    Imm::new(v, 12)
+}
+
+fn i_type(rd: u8, rs1: u8, funct3: u8, imm: Imm) -> IType {
+    IType { rd, rs1, funct3, imm }
+}
+
+fn s_type(rs1: u8, rs2: u8, funct3: u8, imm: Imm) -> SType {
+    SType { rs1, rs2, funct3, imm }
+}
+
+fn r_type(rd: u8, rs1: u8, rs2: u8, funct3: u8, funct7: u8) -> RType {
+    RType { rd, rs1, rs2, funct3, funct7 }
+}
+
+/// `slli`/`srli`/`srai` on `rd`: the RV32I form on RV32, the 6-bit shamt
+/// form otherwise. RV128C sign-extends the field (32 stands for 96) and
+/// encodes a shift by 64 as a zero field.
+fn shift(c: &CIType, xlen: Xlen, funct3: u8, rv32: fn(IType) -> RV32I, rv64: fn(IType) -> RV64I) -> Instruction {
+    let field = c.imm.low_u32() & 0x3f;
+    let sh = match xlen {
+        Xlen::X128 if field == 0 => 64,
+        Xlen::X128 if field >= 32 => field | 0x40,
+        _ => field,
+    };
+    let i = i_type(c.rdrs1, c.rdrs1, funct3, Imm::new(sh, 12));
+    match xlen {
+        Xlen::X32 => rv32(i).into(),
+        _ => rv64(i).into(),
+    }
+}
+
+/// The base instruction `rvc` expands to under `xlen`; `None` for `c.mop.N`
+/// and the Zcmp/Zcmt instructions, which have no single 32-bit counterpart.
+pub fn expand(rvc: &RVC, xlen: Xlen) -> Option<Instruction> {
+    use RVC::*;
+    let ins = match rvc {
+        Caddi4spn(c) => RV32I::Addi(i_type(c.rd, 2, FUNCT3_OP_ADD_SUB, uimm(c.uimm.low32()))).into(),
+        Clw(c) => RV32I::Lw(i_type(c.rd, c.rs1, FUNCT3_LOAD_LW, uimm(c.imm.low_u32()))).into(),
+        Cflw(c) => RVF::Load(FpFmt::S, i_type(c.rd, c.rs1, FUNCT3_WIDTH_W, uimm(c.imm.low_u32()))).into(),
+        Cld(c) => RV64I::Ld(i_type(c.rd, c.rs1, FUNCT3_LOAD_LD, uimm(c.imm.low_u32()))).into(),
+        Csw(c) => RV32I::Sw(s_type(c.rs1, c.rs2, FUNCT3_STORE_SW, uimm(c.imm.low_u32()))).into(),
+        Cfsw(c) => RVF::Store(FpFmt::S, s_type(c.rs1, c.rs2, FUNCT3_WIDTH_W, uimm(c.imm.low_u32()))).into(),
+        Csd(c) => RV64I::Sd(s_type(c.rs1, c.rs2, FUNCT3_STORE_SD, uimm(c.imm.low_u32()))).into(),
+
+        Cnop(c) => RV32I::Addi(i_type(0, 0, FUNCT3_OP_ADD_SUB, simm(&c.imm, 12))).into(),
+        Caddi(c) => RV32I::Addi(i_type(c.rdrs1, c.rdrs1, FUNCT3_OP_ADD_SUB, simm(&c.imm, 12))).into(),
+        Cjal(c) => RV32I::Jal(JType { rd: 1, imm: simm(&c.target, 21) }).into(),
+        Caddiw(c) => RV64I::Addiw(i_type(c.rdrs1, c.rdrs1, FUNCT3_OP_ADD_SUB, simm(&c.imm, 12))).into(),
+        Cli(c) => RV32I::Addi(i_type(c.rdrs1, 0, FUNCT3_OP_ADD_SUB, simm(&c.imm, 12))).into(),
+        Caddi16sp(c) => RV32I::Addi(i_type(2, 2, FUNCT3_OP_ADD_SUB, simm(&c.imm, 12))).into(),
+        Clui(c) => RV32I::Lui(UType { rd: c.rdrs1, imm: Imm::new(c.imm.as_i64() as u32, 32) }).into(),
+        Csrli(c) => shift(c, xlen, FUNCT3_OP_SRL_SRA, RV32I::Srli, RV64I::Srli),
+        Csrai(c) => shift(c, xlen, FUNCT3_OP_SRL_SRA, RV32I::Srai, RV64I::Srai),
+        Candi(c) => RV32I::Andi(i_type(c.rdrs1, c.rdrs1, FUNCT3_OP_AND, simm(&c.imm, 12))).into(),
+        Csub(c) => RV32I::Sub(r_type(c.rdrs1, c.rdrs1, c.rs2, FUNCT3_OP_ADD_SUB, FUNCT7_OP_SUB)).into(),
+        Cxor(c) => RV32I::Xor(r_type(c.rdrs1, c.rdrs1, c.rs2, FUNCT3_OP_XOR, 0)).into(),
+        Cor(c) => RV32I::Or(r_type(c.rdrs1, c.rdrs1, c.rs2, FUNCT3_OP_OR, 0)).into(),
+        Cand(c) => RV32I::And(r_type(c.rdrs1, c.rdrs1, c.rs2, FUNCT3_OP_AND, 0)).into(),
+        Csubw(c) => RV64I::Subw(r_type(c.rdrs1, c.rdrs1, c.rs2, FUNCT3_OP_ADD_SUB, FUNCT7_OP_SUB)).into(),
+        Caddw(c) => RV64I::Addw(r_type(c.rdrs1, c.rdrs1, c.rs2, FUNCT3_OP_ADD_SUB, FUNCT7_OP_ADD)).into(),
+        Cj(c) => RV32I::Jal(JType { rd: 0, imm: simm(&c.target, 21) }).into(),
+        Cbeqz(c) => RV32I::Beq(BType { rs1: c.rs1, rs2: 0, funct3: FUNCT3_BRANCH_BEQ, imm: simm(&c.off, 13) }).into(),
+        Cbnez(c) => RV32I::Bne(BType { rs1: c.rs1, rs2: 0, funct3: FUNCT3_BRANCH_BNE, imm: simm(&c.off, 13) }).into(),
+
+        Cslli(c) => shift(c, xlen, FUNCT3_OP_SLL, RV32I::Slli, RV64I::Slli),
+        Clwsp(c) => RV32I::Lw(i_type(c.rdrs1, 2, FUNCT3_LOAD_LW, uimm(c.imm.low_u32()))).into(),
+        Cflwsp(c) => RVF::Load(FpFmt::S, i_type(c.rdrs1, 2, FUNCT3_WIDTH_W, uimm(c.imm.low_u32()))).into(),
+        Cldsp(c) => RV64I::Ld(i_type(c.rdrs1, 2, FUNCT3_LOAD_LD, uimm(c.imm.low_u32()))).into(),
+        Cjr(c) => RV32I::Jalr(i_type(0, c.rdrs1, 0, uimm(0))).into(),
+        Cmv(c) => RV32I::Add(r_type(c.rdrs1, 0, c.rs2, FUNCT3_OP_ADD_SUB, FUNCT7_OP_ADD)).into(),
+        Cebreak(_) => RV32I::Ebreak(()).into(),
+        Cjalr(c) => RV32I::Jalr(i_type(1, c.rdrs1, 0, uimm(0))).into(),
+        Cadd(c) => RV32I::Add(r_type(c.rdrs1, c.rdrs1, c.rs2, FUNCT3_OP_ADD_SUB, FUNCT7_OP_ADD)).into(),
+        Cswsp(c) => RV32I::Sw(s_type(2, c.rs2, FUNCT3_STORE_SW, uimm(c.imm.low_u32()))).into(),
+        Cfswsp(c) => RVF::Store(FpFmt::S, s_type(2, c.rs2, FUNCT3_WIDTH_W, uimm(c.imm.low_u32()))).into(),
+        Csdsp(c) => RV64I::Sd(s_type(2, c.rs2, FUNCT3_STORE_SD, uimm(c.imm.low_u32()))).into(),
+
+        Cfld(c) => RVF::Load(FpFmt::D, i_type(c.rd, c.rs1, FUNCT3_WIDTH_D, uimm(c.imm.low_u32()))).into(),
+        Cfsd(c) => RVF::Store(FpFmt::D, s_type(c.rs1, c.rs2, FUNCT3_WIDTH_D, uimm(c.imm.low_u32()))).into(),
+        Cfldsp(c) => RVF::Load(FpFmt::D, i_type(c.rdrs1, 2, FUNCT3_WIDTH_D, uimm(c.imm.low_u32()))).into(),
+        Cfsdsp(c) => RVF::Store(FpFmt::D, s_type(2, c.rs2, FUNCT3_WIDTH_D, uimm(c.imm.low_u32()))).into(),
+
+        Clq(c) => RV128I::Lq(i_type(c.rd, c.rs1, FUNCT3_MISC_MEM_LQ, uimm(c.imm.low_u32()))).into(),
+        Csq(c) => RV128I::Sq(s_type(c.rs1, c.rs2, FUNCT3_STORE_SQ, uimm(c.imm.low_u32()))).into(),
+        Clqsp(c) => RV128I::Lq(i_type(c.rdrs1, 2, FUNCT3_MISC_MEM_LQ, uimm(c.imm.low_u32()))).into(),
+        Csqsp(c) => RV128I::Sq(s_type(2, c.rs2, FUNCT3_STORE_SQ, uimm(c.imm.low_u32()))).into(),
+        Csrli64(c) => shift(c, xlen, FUNCT3_OP_SRL_SRA, RV32I::Srli, RV64I::Srli),
+        Csrai64(c) => shift(c, xlen, FUNCT3_OP_SRL_SRA, RV32I::Srai, RV64I::Srai),
+        Cslli64(c) => shift(c, xlen, FUNCT3_OP_SLL, RV32I::Slli, RV64I::Slli),
+
+        Clbu(c) => RV32I::Lbu(i_type(c.rd, c.rs1, FUNCT3_LOAD_LBU, uimm(c.imm.low_u32()))).into(),
+        Clhu(c) => RV32I::Lhu(i_type(c.rd, c.rs1, FUNCT3_LOAD_LHU, uimm(c.imm.low_u32()))).into(),
+        Clh(c) => RV32I::Lh(i_type(c.rd, c.rs1, FUNCT3_LOAD_LH, uimm(c.imm.low_u32()))).into(),
+        Csb(c) => RV32I::Sb(s_type(c.rs1, c.rs2, FUNCT3_STORE_SB, uimm(c.imm.low_u32()))).into(),
+        Csh(c) => RV32I::Sh(s_type(c.rs1, c.rs2, FUNCT3_STORE_SH, uimm(c.imm.low_u32()))).into(),
+        CzextB(c) => RV32I::Andi(i_type(c.rdrs1, c.rdrs1, FUNCT3_OP_AND, uimm(0xff))).into(),
+        CsextB(c) => RVB::Sextb(r_type(c.rdrs1, c.rdrs1, 0, FUNCT3_OP_SLL, 0)).into(),
+        CzextH(c) => RVB::Zexth(r_type(c.rdrs1, c.rdrs1, 0, 0, 0)).into(),
+        CsextH(c) => RVB::Sexth(r_type(c.rdrs1, c.rdrs1, 0, FUNCT3_OP_SLL, 0)).into(),
+        CzextW(c) => RVB::Adduw(r_type(c.rdrs1, c.rdrs1, 0, FUNCT3_OP_ADD_SUB, 0)).into(),
+        Cnot(c) => RV32I::Xori(i_type(c.rdrs1, c.rdrs1, FUNCT3_OP_XOR, Imm::new(0xfff, 12))).into(),
+        Cmul(c) => RV32I::Mul(r_type(c.rdrs1, c.rdrs1, c.rs2, FUNCT3_M_MUL, FUNCT7_OP_MULDIV)).into(),
+
+        Cntl(c) => RVZihint::ntl(c.rs2)?.into(),
+        Cmop(_) | CmPush(_) | CmPop(_) | CmPopretz(_) | CmPopret(_) | CmMvsa01(_) | CmMva01s(_)
+        | CmJt(_) | CmJalt(_) => return None,
+    };
+    Some(ins)
+}
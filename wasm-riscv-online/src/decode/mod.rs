@@ -1,6 +1,8 @@
+mod expand;
 mod process16;
 mod process32;
-pub use process16::resolve_u16;
+pub use expand::expand;
+pub use process16::{resolve_u16, resolve_u16_zcm};
 pub use process32::resolve_u32;
 
 fn c_reg(regid: u8) -> u8 {
@@ -18,18 +18,18 @@ pub fn resolve_u16(ins: u16, xlen: Xlen) -> core::result::Result<Instruction, ()
     let uimm54876 =
         (((ins >> 11) & 0b11) << 4) | (((ins >> 10) & 0b1) << 8) | (((ins >> 5) & 0b11) << 6);
     let uimm5326 =
-        (((ins >> 11) & 0b111) << 3) | (((ins >> 5) & 0b1) << 6) | (((ins >> 6) & 0b1) << 2);
+        (((ins >> 10) & 0b111) << 3) | (((ins >> 5) & 0b1) << 6) | (((ins >> 6) & 0b1) << 2);
     let nzuimm540 = ((ins >> 2) & 0b11111) | (((ins >> 12) & 0b1) << 5);
     let nzimm540 = nzuimm540;
     let imm540 = nzuimm540;
-    let imm114981067315 = (((ins >> 3) & 0b11) << 1)
-        | (((ins >> 11) & 0b1) << 3)
-        | (((ins >> 2) & 0b1) << 4)
-        | (((ins >> 7) & 0b1) << 5)
-        | (((ins >> 6) & 0b1) << 6)
+    let imm114981067315 = (((ins >> 3) & 0b111) << 1)
+        | (((ins >> 11) & 0b1) << 4)
+        | (((ins >> 2) & 0b1) << 5)
+        | (((ins >> 7) & 0b1) << 6)
+        | (((ins >> 6) & 0b1) << 7)
         | (((ins >> 9) & 0b11) << 8)
-        | (((ins >> 8) & 0b1) << 9)
-        | (((ins >> 11) & 0b1) << 10);
+        | (((ins >> 8) & 0b1) << 10)
+        | (((ins >> 12) & 0b1) << 11);
     let nzimm946875 = (((ins >> 12) & 0b1) << 9)
         | (((ins >> 6) & 0b1) << 4)
         | (((ins >> 5) & 0b1) << 6)
@@ -43,20 +43,22 @@ pub fn resolve_u16(ins: u16, xlen: Xlen) -> core::result::Result<Instruction, ()
         | (((ins >> 10) & 0b11) << 3)
         | (((ins >> 5) & 0b11) << 6)
         | (((ins >> 3) & 0b11) << 1)
-        | (((ins >> 2) & 0b11) << 5);
+        | (((ins >> 2) & 0b1) << 5);
     let uimm54386 =
         (((ins >> 12) & 0b1) << 5) | (((ins >> 5) & 0b11) << 3) | (((ins >> 2) & 0b111) << 6);
     let uimm5_4_96 =
         (((ins >> 12) & 0b1) << 5) | (((ins >> 6) & 0b1) << 4) | (((ins >> 2) & 0b1111) << 6);
     let uimm54276 =
-        (((ins >> 12) & 0b1) << 5) | (((ins >> 4) & 0b111) << 2) | (((ins >> 2) & 0b111) << 6);
+        (((ins >> 12) & 0b1) << 5) | (((ins >> 4) & 0b111) << 2) | (((ins >> 2) & 0b11) << 6);
     let uimm5386 = (((ins >> 10) & 0b111) << 3) | (((ins >> 7) & 0b111) << 6);
     let uimm54_96 = (((ins >> 11) & 0b11) << 4) | (((ins >> 7) & 0b1111) << 6);
-    let uimm5276 = (((ins >> 9) & 0b1111) << 3) | (((ins >> 7) & 0b11) << 6);
+    let uimm5276 = (((ins >> 9) & 0b1111) << 2) | (((ins >> 7) & 0b11) << 6);
     let r24_c = ((ins >> 2) & 0b111) as u8;
     let r79_c = ((ins >> 7) & 0b111) as u8;
     let rdrs1 = ((ins >> 7) & 0b11111) as u8;
     let rs2 = ((ins >> 2) & 0b11111) as u8;
+    // Unsigned offsets and shift amounts are stored with one spare bit so the
+    // (signed) Imm never reads them as negative.
     let ans = match (opcode, funct3) {
         (OPCODE_C0, 0b000) if nzuimm549623 != 0 => RVC::Caddi4spn(CIWType {
             rd: c_reg(r24_c),
@@ -68,70 +70,85 @@ pub fn resolve_u16(ins: u16, xlen: Xlen) -> core::result::Result<Instruction, ()
             rd: c_reg(r24_c),
             rs1: c_reg(r79_c),
             funct3,
-            imm: Imm::new(uimm5376 as u32, 8),
+            imm: Imm::new(uimm5376 as u32, 9),
         })
         .into(),
         (OPCODE_C0, 0b001) if xlen == Xlen::X128 => RVC::Clq(CLType {
             rd: c_reg(r24_c),
             rs1: c_reg(r79_c),
             funct3,
-            imm: Imm::new(uimm54876 as u32, 9),
+            imm: Imm::new(uimm54876 as u32, 10),
         })
         .into(),
         (OPCODE_C0, 0b010) => RVC::Clw(CLType {
             rd: c_reg(r24_c),
             rs1: c_reg(r79_c),
             funct3,
-            imm: Imm::new(uimm5326 as u32, 7),
+            imm: Imm::new(uimm5326 as u32, 8),
         })
         .into(),
         (OPCODE_C0, 0b011) if xlen == Xlen::X32 => RVC::Cflw(CLType {
             rd: c_reg(r24_c),
             rs1: c_reg(r79_c),
             funct3,
-            imm: Imm::new(uimm5326 as u32, 7),
+            imm: Imm::new(uimm5326 as u32, 8),
         })
         .into(),
         (OPCODE_C0, 0b011) if xlen == Xlen::X64 || xlen == Xlen::X128 => RVC::Cld(CLType {
             rd: c_reg(r24_c),
             rs1: c_reg(r79_c),
             funct3,
-            imm: Imm::new(uimm5376 as u32, 8),
+            imm: Imm::new(uimm5376 as u32, 9),
         })
         .into(),
         (OPCODE_C0, 0b101) if xlen == Xlen::X32 || xlen == Xlen::X64 => RVC::Cfsd(CSType {
             rs1: c_reg(r79_c),
             rs2: c_reg(r24_c),
             funct3,
-            imm: Imm::new(uimm5376 as u32, 8),
+            imm: Imm::new(uimm5376 as u32, 9),
         })
         .into(),
         (OPCODE_C0, 0b101) if xlen == Xlen::X128 => RVC::Csq(CSType {
             rs1: c_reg(r79_c),
             rs2: c_reg(r24_c),
             funct3,
-            imm: Imm::new(uimm54876 as u32, 9),
+            imm: Imm::new(uimm54876 as u32, 10),
         })
         .into(),
+        // Zcb byte and half-word accesses: uimm[0] sits in bit 6, uimm[1] in bit 5
+        (OPCODE_C0, 0b100) => {
+            let (rd, rs1) = (c_reg(r24_c), c_reg(r79_c));
+            let bit6 = (ins >> 6) & 1 != 0;
+            let half = Imm::new((((ins >> 5) & 1) << 1) as u32, 3);
+            let byte = Imm::new(((((ins >> 5) & 1) << 1) | ((ins >> 6) & 1)) as u32, 3);
+            match funct6 & 0b111 {
+                0b000 => RVC::Clbu(CLType { rd, rs1, funct3, imm: byte }).into(),
+                0b001 if !bit6 => RVC::Clhu(CLType { rd, rs1, funct3, imm: half }).into(),
+                0b001 => RVC::Clh(CLType { rd, rs1, funct3, imm: half }).into(),
+                0b010 => RVC::Csb(CSType { rs1, rs2: rd, funct3, imm: byte }).into(),
+                0b011 if !bit6 => RVC::Csh(CSType { rs1, rs2: rd, funct3, imm: half }).into(),
+                _ => Err(())?,
+            }
+        }
         (OPCODE_C0, 0b110) => RVC::Csw(CSType {
             rs1: c_reg(r79_c),
             rs2: c_reg(r24_c),
             funct3,
-            imm: Imm::new(uimm5326 as u32, 7),
+            imm: Imm::new(uimm5326 as u32, 8),
         })
         .into(),
         (OPCODE_C0, 0b111) if xlen == Xlen::X32 =>RVC::Cfsw(CSType {
             rs1: c_reg(r79_c),
             rs2: c_reg(r24_c),
             funct3,
-            imm: Imm::new(uimm5326 as u32, 7),
+            imm: Imm::new(uimm5326 as u32, 8),
         })
         .into(),
         (OPCODE_C0, 0b111) if xlen == Xlen::X64 || xlen == Xlen::X128 => RVC::Csd(CSType {
             rs1: c_reg(r79_c),
             rs2: c_reg(r24_c),
             funct3,
-            imm: Imm::new(uimm5376 as u32, 8),
+            imm: Imm::new(uimm5376 as u32, 9),
         })
         .into(),
         (OPCODE_C1, 0b000) if rdrs1 == 0 => RVC::Cnop(CIType {
@@ -175,29 +192,36 @@ pub fn resolve_u16(ins: u16, xlen: Xlen) -> core::result::Result<Instruction, ()
             imm: Imm::new(nzuimm171612, 18),
         })
         .into(),
+        // Zcmop: c.lui xN, 0 with N odd and below 16
+        (OPCODE_C1, 0b011) if rdrs1 % 2 == 1 && rdrs1 < 16 && nzuimm171612 == 0 => RVC::Cmop(CIType {
+            rdrs1,
+            funct3,
+            imm: Imm::new(0, 6),
+        })
+        .into(),
         (OPCODE_C1, 0b100) => match (funct6 & 0b11, ins12, funct2) {
             (0b00, _, _) if !(xlen == Xlen::X32 && ins12) && nzuimm540 != 0 => RVC::Csrli(CIType {
                 rdrs1: c_reg(r79_c),
                 funct3,
-                imm: Imm::new(nzuimm540 as u32, 6),
+                imm: Imm::new(nzuimm540 as u32, 7),
             })
             .into(),
             (0b00, _, _) if xlen == Xlen::X128 && nzuimm540 == 0 => RVC::Csrli64(CIType {
                 rdrs1: c_reg(r79_c),
                 funct3,
-                imm: Imm::new(nzuimm540 as u32, 6),
+                imm: Imm::new(nzuimm540 as u32, 7),
             })
             .into(),
             (0b01, _, _) if !(xlen == Xlen::X32 && ins12) && nzuimm540 != 0 => RVC::Csrai(CIType {
                 rdrs1: c_reg(r79_c),
                 funct3,
-                imm: Imm::new(nzuimm540 as u32, 6),
+                imm: Imm::new(nzuimm540 as u32, 7),
             })
             .into(),
             (0b01, _, _) if xlen == Xlen::X128 && nzuimm540 == 0 => RVC::Csrai64(CIType {
                 rdrs1: c_reg(r79_c),
                 funct3,
-                imm: Imm::new(nzuimm540 as u32, 6),
+                imm: Imm::new(nzuimm540 as u32, 7),
             })
             .into(),
             (0b10, _, _) => RVC::Candi(CIType {
@@ -248,6 +272,26 @@ pub fn resolve_u16(ins: u16, xlen: Xlen) -> core::result::Result<Instruction, ()
                 funct6,
             })
             .into(),
+            // Zcb
+            (0b11, true, 0b10) => RVC::Cmul(CAType {
+                rdrs1: c_reg(r79_c),
+                rs2: c_reg(r24_c),
+                funct2,
+                funct6,
+            })
+            .into(),
+            (0b11, true, 0b11) => {
+                let c = CIType { rdrs1: c_reg(r79_c), funct3, imm: Imm::new(0, 6) };
+                match r24_c {
+                    0b000 => RVC::CzextB(c).into(),
+                    0b001 => RVC::CsextB(c).into(),
+                    0b010 => RVC::CzextH(c).into(),
+                    0b011 => RVC::CsextH(c).into(),
+                    0b100 if xlen != Xlen::X32 => RVC::CzextW(c).into(),
+                    0b101 => RVC::Cnot(c).into(),
+                    _ => Err(())?,
+                }
+            }
             _ => Err(())?,
         },
         (OPCODE_C1, 0b101) => RVC::Cj(CJType {
@@ -271,7 +315,7 @@ pub fn resolve_u16(ins: u16, xlen: Xlen) -> core::result::Result<Instruction, ()
             RVC::Cslli(CIType {
                 rdrs1,
                 funct3,
-                imm: Imm::new(nzuimm540 as u32, 6),
+                imm: Imm::new(nzuimm540 as u32, 7),
             })
             .into()
         }
@@ -279,39 +323,39 @@ pub fn resolve_u16(ins: u16, xlen: Xlen) -> core::result::Result<Instruction, ()
             RVC::Cslli64(CIType {
                 rdrs1,
                 funct3,
-                imm: Imm::new(nzuimm540 as u32, 6),
+                imm: Imm::new(nzuimm540 as u32, 7),
             })
             .into()
         }
         (OPCODE_C2, 0b001) if xlen == Xlen::X32 || xlen == Xlen::X64 => RVC::Cfldsp(CIType {
             rdrs1,
             funct3,
-            imm: Imm::new(uimm54386 as u32, 9),
+            imm: Imm::new(uimm54386 as u32, 10),
         })
         .into(),
         (OPCODE_C2, 0b001) if xlen == Xlen::X128 && rdrs1 != 0 => RVC::Clqsp(CIType {
             rdrs1,
             funct3,
-            imm: Imm::new(uimm5_4_96 as u32, 10),
+            imm: Imm::new(uimm5_4_96 as u32, 11),
         })
         .into(),
         (OPCODE_C2, 0b010) if rdrs1 != 0 => RVC::Clwsp(CIType {
             rdrs1,
             funct3,
-            imm: Imm::new(uimm54276 as u32, 8),
+            imm: Imm::new(uimm54276 as u32, 9),
         })
         .into(),
         (OPCODE_C2, 0b011) if xlen == Xlen::X32 => RVC::Cflwsp(CIType {
             rdrs1,
             funct3,
-            imm: Imm::new(uimm54276 as u32, 8),
+            imm: Imm::new(uimm54276 as u32, 9),
         })
         .into(),
         (OPCODE_C2, 0b011) if (xlen == Xlen::X64 || xlen == Xlen::X128) && rdrs1 != 0 => {
             RVC::Cldsp(CIType {
                 rdrs1,
                 funct3,
-                imm: Imm::new(uimm54386 as u32, 9),
+                imm: Imm::new(uimm54386 as u32, 10),
             })
             .into()
         }
@@ -320,40 +364,89 @@ pub fn resolve_u16(ins: u16, xlen: Xlen) -> core::result::Result<Instruction, ()
             (false, _, _) if rdrs1 != 0 => RVC::Cmv(CRType { rdrs1, rs2, funct4 }).into(),
             (true, 0, 0) => RVC::Cebreak(CRType { rdrs1, rs2, funct4 }).into(),
             (true, _, 0) => RVC::Cjalr(CRType { rdrs1, rs2, funct4 }).into(),
+            (true, 0, RS2_NTL_P1..=RS2_NTL_ALL) => RVC::Cntl(CRType { rdrs1, rs2, funct4 }).into(),
             (true, _, _) if rdrs1 != 0 => RVC::Cadd(CRType { rdrs1, rs2, funct4 }).into(),
             _ => Err(())?,
         },
         (OPCODE_C2, 0b101) if xlen == Xlen::X32 || xlen == Xlen::X64 => RVC::Cfsdsp(CSSType {
             rs2,
             funct3,
-            imm: Imm::new(uimm5386 as u32, 9),
+            imm: Imm::new(uimm5386 as u32, 10),
         })
         .into(),
         (OPCODE_C2, 0b101) if xlen == Xlen::X128 => RVC::Csqsp(CSSType {
             rs2,
             funct3,
-            imm: Imm::new(uimm54_96 as u32, 10),
+            imm: Imm::new(uimm54_96 as u32, 11),
         })
         .into(),
         (OPCODE_C2, 0b110) => RVC::Cswsp(CSSType {
             rs2,
             funct3,
-            imm: Imm::new(uimm5276 as u32, 8),
+            imm: Imm::new(uimm5276 as u32, 9),
         })
         .into(),
         (OPCODE_C2, 0b111) if xlen == Xlen::X32 => RVC::Cfswsp(CSSType {
             rs2,
             funct3,
-            imm: Imm::new(uimm5276 as u32, 8),
+            imm: Imm::new(uimm5276 as u32, 9),
         })
         .into(),
         (OPCODE_C2, 0b111) if xlen == Xlen::X64 || xlen == Xlen::X128 => RVC::Csdsp(CSSType {
             rs2,
             funct3,
-            imm: Imm::new(uimm5386 as u32, 9),
+            imm: Imm::new(uimm5386 as u32, 10),
         })
         .into(),
         _ => Err(())?,
     };
     Ok(ans)
 }
+
+/// Like [`resolve_u16`], but quadrant 2 funct3 101 (`c.fsdsp` on RV32/RV64)
+/// holds the Zcmp push/pop and move instructions and the Zcmt table jumps.
+pub fn resolve_u16_zcm(ins: u16, xlen: Xlen) -> core::result::Result<Instruction, ()> {
+    if ins & 0b11 != OPCODE_C2 || ins >> 13 != 0b101 || xlen == Xlen::X128 {
+        return resolve_u16(ins, xlen);
+    }
+    // s0-s1 are x8-x9, s2-s7 are x18-x23
+    let sreg = |r: u16| -> u8 {
+        let r = (r & 0b111) as u8;
+        if r < 2 { r + 8 } else { r + 16 }
+    };
+    let ans = match (ins >> 10) & 0b111 {
+        0b110 | 0b111 => {
+            let rlist = ((ins >> 4) & 0b1111) as u8;
+            if rlist < 4 {
+                return Err(());
+            }
+            let mut pp = CMPPType { rlist, stack_adj: 0 };
+            pp.stack_adj = pp.base_adj(xlen) + ((ins >> 2) & 0b11) as u32 * 16;
+            match (ins >> 8) & 0b1_1111 {
+                0b11000 => RVC::CmPush(pp),
+                0b11010 => RVC::CmPop(pp),
+                0b11100 => RVC::CmPopretz(pp),
+                0b11110 => RVC::CmPopret(pp),
+                _ => return Err(()),
+            }
+        }
+        0b011 => {
+            let (r1s, r2s) = ((ins >> 7) & 0b111, (ins >> 2) & 0b111);
+            if r1s == r2s {
+                return Err(());
+            }
+            let ca = CAType { rdrs1: sreg(r1s), rs2: sreg(r2s), funct2: ((ins >> 5) & 0b11) as u8, funct6: 0 };
+            match (ins >> 5) & 0b11 {
+                0b01 => RVC::CmMvsa01(ca),
+                0b11 => RVC::CmMva01s(ca),
+                _ => return Err(()),
+            }
+        }
+        0b000 => match ((ins >> 2) & 0xff) as u8 {
+            index if index < 32 => RVC::CmJt(index),
+            index => RVC::CmJalt(index),
+        },
+        _ => return Err(()),
+    };
+    Ok(ans.into())
+}
@@ -1,8 +1,9 @@
 use crate::{asm::Instruction,asm::*, riscv::imm::{Imm, Uimm, Xlen}};
 use crate::isa::*;
+use crate::asm::rvf::rounding_mode_name;
 
 pub fn resolve_u32(ins: u32, xlen: Xlen) -> core::result::Result<Instruction, ()> {
-    use crate::asm::{RVZicsr::*, RV32I::*, RV64I::*, RVF::*, RV32A::*,RV64A::*, RV128A::*};
+    use crate::asm::{RVZicsr::*, RV32I::*, RV64I::*, RV128I::*, RV32A::*,RV64A::*, RV128A::*};
     let opcode = ins & 0b111_1111;
     let rd = ((ins >> 7) & 0b1_1111) as u8;
     let rs1 = ((ins >> 15) & 0b1_1111) as u8;
@@ -26,7 +27,7 @@ pub fn resolve_u32(ins: u32, xlen: Xlen) -> core::result::Result<Instruction, ()
             | (((ins >> 8) & 0b1111) << 1)
             | (((ins >> 25) & 0b111111) << 5)
             | (((ins >> 31) & 0b1) << 12);
-        Imm::new(val, 12)
+        Imm::new(val, 13)
     };
     let imm_u = Imm::new(ins & 0xFFFFF000, 32);
     let imm_j = {
@@ -34,7 +35,7 @@ pub fn resolve_u32(ins: u32, xlen: Xlen) -> core::result::Result<Instruction, ()
             | (((ins & 0b0111_1111_1110_0000_0000_0000_0000_0000) >> 21) << 1)
             | (((ins & 0b0000_0000_0001_0000_0000_0000_0000_0000) >> 20) << 11)
             | (((ins & 0b0000_0000_0000_1111_1111_0000_0000_0000) >> 12) << 12);
-        Imm::new(val, 12)
+        Imm::new(val, 21)
     };
     let uimm_csr = Uimm::new((ins >> 15) & 0b11111, 5);
     let csr = ((ins >> 20) & 0xFFF) as u16;
@@ -77,6 +78,13 @@ pub fn resolve_u32(ins: u32, xlen: Xlen) -> core::result::Result<Instruction, ()
         funct3,
         csr,
     };
+    let a_type = AType {
+        rd,
+        rs1,
+        rs2,
+        aq: funct7 & 0b10 != 0,
+        rl: funct7 & 0b01 != 0,
+    };
     let r4_type = R4Type {
         rd,
         rs1,
@@ -85,6 +93,29 @@ pub fn resolve_u32(ins: u32, xlen: Xlen) -> core::result::Result<Instruction, ()
         funct3,
         funct2,
     };
+    let fmt = FpFmt::from_bits(funct2);
+    // shift immediates: shamt is 5, 6 or 7 bits wide, with the funct bits
+    // above it; the *w forms keep 5 bits and the *d forms 6
+    let shamt_type = |bits: u8| IType { imm: Imm::new((ins >> 20) & ((1 << bits) - 1), 12), ..i_type };
+    let shamt_bits = match xlen {
+        Xlen::X32 => 5,
+        Xlen::X64 => 6,
+        Xlen::X128 => 7,
+    };
+    let shift_funct = funct7 >> (shamt_bits - 5);
+    // Zba/Zbb/Zbc/Zbs reuse OP, OP-IMM and their 32-bit forms with funct7
+    // (or imm12) patterns the base ISA leaves unused
+    if let Some(b) = resolve_rvb(opcode, funct3, funct7, funct12, r_type, i_type, xlen) {
+        return Ok(b.into());
+    }
+    // vector: OP-V, and LOAD-FP/STORE-FP with a vector width code
+    if let Some(v) = resolve_rvv(ins) {
+        return Ok(v.into());
+    }
+    // scalar crypto, in the same opcode space
+    if let Some(k) = resolve_rvk(opcode, funct3, funct7, funct12, r_type, i_type, xlen) {
+        return Ok(k.into());
+    }
     let ans = match opcode {
         OPCODE_LUI => Lui(u_type).into(),
         OPCODE_AUIPC => Auipc(u_type).into(),
@@ -107,6 +138,7 @@ pub fn resolve_u32(ins: u32, xlen: Xlen) -> core::result::Result<Instruction, ()
             FUNCT3_LOAD_LBU => Lbu(i_type).into(),
             FUNCT3_LOAD_LHU => Lhu(i_type).into(),
             FUNCT3_LOAD_LWU if xlen != Xlen::X32 => Lwu(i_type).into(),
+            FUNCT3_LOAD_LDU if xlen == Xlen::X128 => Ldu(i_type).into(),
             _ => Err(())?,
         },
         OPCODE_STORE => match funct3 {
@@ -114,22 +146,47 @@ pub fn resolve_u32(ins: u32, xlen: Xlen) -> core::result::Result<Instruction, ()
             FUNCT3_STORE_SH => Sh(s_type).into(),
             FUNCT3_STORE_SW => Sw(s_type).into(),
             FUNCT3_STORE_SD if xlen != Xlen::X32 => Sd(s_type).into(),
+            FUNCT3_STORE_SQ if xlen == Xlen::X128 => Sq(s_type).into(),
             _ => Err(())?,
         },
         OPCODE_MISC_MEM => match funct3 {
-            FUNCT3_MISC_MEM_FENCE => Fence(()).into(),
+            FUNCT3_MISC_MEM_FENCE if rd == 0 && rs1 == 0 && funct12 == IMM12_FENCE_PAUSE => {
+                RVZihint::Pause(()).into()
+            }
+            FUNCT3_MISC_MEM_FENCE => Fence(i_type).into(),
             FUNCT3_MISC_MEM_FENCE_I => FenceI(()).into(),
+            FUNCT3_MISC_MEM_LQ if xlen == Xlen::X128 => Lq(i_type).into(),
+            // cbo.* only exist where funct3 010 is not lq
+            FUNCT3_MISC_MEM_CBO if rd == 0 => {
+                let r = RType { rs2: 0, funct7: 0, ..r_type };
+                match funct12 {
+                    IMM12_CBO_INVAL => RVZicbo::CboInval(r).into(),
+                    IMM12_CBO_CLEAN => RVZicbo::CboClean(r).into(),
+                    IMM12_CBO_FLUSH => RVZicbo::CboFlush(r).into(),
+                    IMM12_CBO_ZERO => RVZicbo::CboZero(r).into(),
+                    _ => Err(())?,
+                }
+            }
             _ => Err(())?,
         },
         OPCODE_SYSTEM => match funct3 {
-            FUNCT3_SYSTEM_PRIV => match funct12 {
+            // Zimop shares funct3 100 with the hypervisor loads and stores
+            FUNCT3_SYSTEM_MOP if funct12 & IMM12_MOP_R_MASK == IMM12_MOP_R => {
+                let n = ((funct12 >> 6) & 0b1_0000) | ((funct12 >> 4) & 0b1100) | (funct12 & 0b11);
+                RVZimop::MopR(n as u8, r_type).into()
+            }
+            FUNCT3_SYSTEM_MOP if funct7 & FUNCT7_MOP_RR_MASK == FUNCT7_MOP_RR => {
+                let n = ((funct7 >> 3) & 0b100) | ((funct7 >> 1) & 0b11);
+                RVZimop::MopRR(n, r_type).into()
+            }
+            FUNCT3_SYSTEM_PRIV | FUNCT3_SYSTEM_HLSV => match funct12 {
                 FUNCT12_SYSTEM_ECALL if funct3 == FUNCT3_SYSTEM_PRIV && rs1 == 0 && rd == 0 => {
                     Ecall(()).into()
                 }
                 FUNCT12_SYSTEM_EBREAK if funct3 == FUNCT3_SYSTEM_PRIV && rs1 == 0 && rd == 0 => {
                     Ebreak(()).into()
                 }
-                _ => Err(())?,
+                _ => resolve_priv(funct3, funct7, funct12, r_type, xlen).ok_or(())?.into(),
             },
             FUNCT3_SYSTEM_CSRRW => Csrrw(csr_r_type).into(),
             FUNCT3_SYSTEM_CSRRS => Csrrs(csr_r_type).into(),
@@ -144,64 +201,72 @@ pub fn resolve_u32(ins: u32, xlen: Xlen) -> core::result::Result<Instruction, ()
             FUNCT3_OP_SLT => Slti(i_type).into(),
             FUNCT3_OP_SLTU => Sltiu(i_type).into(),
             FUNCT3_OP_XOR => Xori(i_type).into(),
+            // Zicbop prefetches are ori hints with rd = x0
+            FUNCT3_OP_OR if rd == 0 => {
+                let i = IType { imm: Imm::new(funct12 & !0b1_1111, 12), ..i_type };
+                match funct12 & 0b1_1111 {
+                    IMM5_PREFETCH_I => RVZicbo::PrefetchI(i).into(),
+                    IMM5_PREFETCH_R => RVZicbo::PrefetchR(i).into(),
+                    IMM5_PREFETCH_W => RVZicbo::PrefetchW(i).into(),
+                    _ => Ori(i_type).into(),
+                }
+            }
             FUNCT3_OP_OR => Ori(i_type).into(),
             FUNCT3_OP_AND => Andi(i_type).into(),
-            FUNCT3_OP_SLL if funct7 == 0 && xlen == Xlen::X32 => RV32I::Slli(i_type).into(),
-            FUNCT3_OP_SLL if funct7 & 0b1111110 == 0 && xlen == Xlen::X64 => {
-                RV64I::Slli(i_type).into()
-            }
-            FUNCT3_OP_SRL_SRA => match funct7 {
-                FUNCT7_OP_SRL if xlen == Xlen::X32 => RV32I::Srli(i_type).into(),
-                FUNCT7_OP_SRA if xlen == Xlen::X32 => RV32I::Srai(i_type).into(),
-                x if x & 0b1111110 == FUNCT7_OP_SRL && xlen == Xlen::X64 => {
-                    RV64I::Srli(i_type).into()
-                }
-                x if x & 0b1111110 == FUNCT7_OP_SRA && xlen == Xlen::X64 => {
-                    RV64I::Srai(i_type).into()
-                }
+            FUNCT3_OP_SLL if shift_funct == 0 => match xlen {
+                Xlen::X32 => RV32I::Slli(shamt_type(shamt_bits)).into(),
+                _ => RV64I::Slli(shamt_type(shamt_bits)).into(),
+            },
+            FUNCT3_OP_SRL_SRA => match (shift_funct << (shamt_bits - 5), xlen) {
+                (FUNCT7_OP_SRL, Xlen::X32) => RV32I::Srli(shamt_type(shamt_bits)).into(),
+                (FUNCT7_OP_SRA, Xlen::X32) => RV32I::Srai(shamt_type(shamt_bits)).into(),
+                (FUNCT7_OP_SRL, _) => RV64I::Srli(shamt_type(shamt_bits)).into(),
+                (FUNCT7_OP_SRA, _) => RV64I::Srai(shamt_type(shamt_bits)).into(),
                 _ => Err(())?,
             },
             _ => Err(())?,
         },
+        OPCODE_OP if funct7 == FUNCT7_OP_MULDIV => match funct3 {
+            FUNCT3_M_MUL => Mul(r_type).into(),
+            FUNCT3_M_MULH => Mulh(r_type).into(),
+            FUNCT3_M_MULHSU => Mulhsu(r_type).into(),
+            FUNCT3_M_MULHU => Mulhu(r_type).into(),
+            FUNCT3_M_DIV => Div(r_type).into(),
+            FUNCT3_M_DIVU => Divu(r_type).into(),
+            FUNCT3_M_REM => Rem(r_type).into(),
+            FUNCT3_M_REMU => Remu(r_type).into(),
+            _ => Err(())?,
+        },
+        OPCODE_OP if funct7 == FUNCT7_CZERO => match funct3 {
+            FUNCT3_CZERO_EQZ => RVZicond::CzeroEqz(r_type).into(),
+            FUNCT3_CZERO_NEZ => RVZicond::CzeroNez(r_type).into(),
+            _ => Err(())?,
+        },
         OPCODE_OP => match funct3 {
             FUNCT3_OP_ADD_SUB => match funct7 {
+                // Zihintntl: add x0, x0, x2..x5
+                FUNCT7_OP_ADD if rd == 0 && rs1 == 0 => match RVZihint::ntl(rs2) {
+                    Some(h) => h.into(),
+                    None => Add(r_type).into(),
+                },
                 FUNCT7_OP_ADD => Add(r_type).into(),
                 FUNCT7_OP_SUB => Sub(r_type).into(),
-                0b000_0001 => Mul(r_type).into(),
-                _ => Err(())?,
-            },
-            FUNCT3_OP_SLL => match funct7 {
-                0 => RV32I::Sll(r_type).into(),
-                0b000_0001 => Mulh(r_type).into(),
                 _ => Err(())?,
             },
+            FUNCT3_OP_SLL if funct7 == 0 => RV32I::Sll(r_type).into(),
             FUNCT3_OP_SLT if funct7 == 0 => Slt(r_type).into(),
             FUNCT3_OP_SLTU if funct7 == 0 => Sltu(r_type).into(),
-            FUNCT3_OP_XOR => match funct7 {
-                0 => Xor(r_type).into(),
-                0b000_0001 => Mulhsu(r_type).into(),
-                _ => Err(())?,
-            },
+            FUNCT3_OP_XOR if funct7 == 0 => Xor(r_type).into(),
             FUNCT3_OP_SRL_SRA => match funct7 {
                 0 => RV32I::Srl(r_type).into(),
                 0b010_0000 => RV32I::Sra(r_type).into(),
-                0b000_0001 => Div(r_type).into(),
-                _ => Err(())?,
-            },
-            FUNCT3_OP_OR => match funct7 {
-                0 => Or(r_type).into(),
-                0b000_0001 => Divu(r_type).into(),
-                _ => Err(())?,
-            },
-            FUNCT3_OP_AND => match funct7 {
-                0 => And(r_type).into(),
-                0b000_0001 if xlen == Xlen::X32 => Rem(r_type).into(),
-                0b000_0001 if xlen == Xlen::X64 => Remu(r_type).into(),
                 _ => Err(())?,
             },
+            FUNCT3_OP_OR if funct7 == 0 => Or(r_type).into(),
+            FUNCT3_OP_AND if funct7 == 0 => And(r_type).into(),
             _ => Err(())?,
         },
-        OPCODE_OP_IMM32 if xlen == Xlen::X64 => match funct3 {
+        OPCODE_OP_IMM32 if xlen != Xlen::X32 => match funct3 {
             FUNCT3_OP_ADD_SUB => Addiw(i_type).into(),
             FUNCT3_OP_SLL if funct7 == 0 => Slliw(i_type).into(),
             FUNCT3_OP_SRL_SRA => match funct7 {
@@ -211,7 +276,15 @@ pub fn resolve_u32(ins: u32, xlen: Xlen) -> core::result::Result<Instruction, ()
             },
             _ => Err(())?,
         },
-        OPCODE_OP_32 if xlen == Xlen::X64 => match funct3 {
+        OPCODE_OP_32 if xlen != Xlen::X32 && funct7 == FUNCT7_OP_MULDIV => match funct3 {
+            FUNCT3_M_MUL => Mulw(r_type).into(),
+            FUNCT3_M_DIV => Divw(r_type).into(),
+            FUNCT3_M_DIVU => Divuw(r_type).into(),
+            FUNCT3_M_REM => Remw(r_type).into(),
+            FUNCT3_M_REMU => Remuw(r_type).into(),
+            _ => Err(())?,
+        },
+        OPCODE_OP_32 if xlen != Xlen::X32 => match funct3 {
             FUNCT3_OP_ADD_SUB => match funct7 {
                 FUNCT7_OP_ADD => Addw(r_type).into(),
                 FUNCT7_OP_SUB => Subw(r_type).into(),
@@ -225,197 +298,460 @@ pub fn resolve_u32(ins: u32, xlen: Xlen) -> core::result::Result<Instruction, ()
             },
             _ => Err(())?,
         },
-        OPCODE_LOAD_FP => match funct3 {
-            FUNCT3_WIDTH_W => Flw(i_type).into(),
+        // RV128I double-word forms, 6-bit shamt
+        OPCODE_OP_IMM64 if xlen == Xlen::X128 => match funct3 {
+            FUNCT3_OP_ADD_SUB => Addid(i_type).into(),
+            FUNCT3_OP_SLL if funct7 >> 1 == 0 => Sllid(shamt_type(6)).into(),
+            FUNCT3_OP_SRL_SRA => match funct7 & 0b1111110 {
+                FUNCT7_OP_SRL => Srlid(shamt_type(6)).into(),
+                FUNCT7_OP_SRA => Sraid(shamt_type(6)).into(),
+                _ => Err(())?,
+            },
             _ => Err(())?,
         },
-        OPCODE_STORE_FP => match funct3 {
-            FUNCT3_WIDTH_W => Fsw(s_type).into(),
+        OPCODE_OP_64 if xlen == Xlen::X128 && funct7 == FUNCT7_OP_MULDIV => match funct3 {
+            FUNCT3_M_MUL => Muld(r_type).into(),
+            FUNCT3_M_DIV => Divd(r_type).into(),
+            FUNCT3_M_DIVU => Divud(r_type).into(),
+            FUNCT3_M_REM => Remd(r_type).into(),
+            FUNCT3_M_REMU => Remud(r_type).into(),
             _ => Err(())?,
         },
-        OPCODE_FMADD => match funct2 {
-            FUNCT2_FMT_S => Fmadds(r4_type).into(),
+        OPCODE_OP_64 if xlen == Xlen::X128 => match (funct3, funct7) {
+            (FUNCT3_OP_ADD_SUB, FUNCT7_OP_ADD) => Addd(r_type).into(),
+            (FUNCT3_OP_ADD_SUB, FUNCT7_OP_SUB) => Subd(r_type).into(),
+            (FUNCT3_OP_SLL, 0) => Slld(r_type).into(),
+            (FUNCT3_OP_SRL_SRA, FUNCT7_OP_SRL) => Srld(r_type).into(),
+            (FUNCT3_OP_SRL_SRA, FUNCT7_OP_SRA) => Srad(r_type).into(),
             _ => Err(())?,
         },
-        OPCODE_FMSUB => match funct2 {
-            FUNCT2_FMT_S => Fmsubs(r4_type).into(),
-            _ => Err(())?,
+        OPCODE_LOAD_FP => match FpFmt::from_width(funct3) {
+            Some(f) => RVF::Load(f, i_type).into(),
+            None => Err(())?,
         },
-        OPCODE_FNMSUB => match funct2 {
-            FUNCT2_FMT_S => Fnmsubs(r4_type).into(),
-            _ => Err(())?,
-        },
-        OPCODE_FNMADD => match funct2 {
-            FUNCT2_FMT_S => Fnmadds(r4_type).into(),
-            _ => Err(())?,
+        OPCODE_STORE_FP => match FpFmt::from_width(funct3) {
+            Some(f) => RVF::Store(f, s_type).into(),
+            None => Err(())?,
         },
+        OPCODE_FMADD => RVF::Fmadd(fmt, r4_type).into(),
+        OPCODE_FMSUB => RVF::Fmsub(fmt, r4_type).into(),
+        OPCODE_FNMSUB => RVF::Fnmsub(fmt, r4_type).into(),
+        OPCODE_FNMADD => RVF::Fnmadd(fmt, r4_type).into(),
         OPCODE_FP => match rs3 {
-            FUNCT_RS3_FP_ADD => match funct2 {
-                FUNCT2_FMT_S => Fadds(r_type).into(),
-                _ => Err(())?,
-            },
-            FUNCT_RS3_FP_SUB => match funct2 {
-                FUNCT2_FMT_S => Fsubs(r_type).into(),
-                _ => Err(())?,
-            },
-            FUNCT_RS3_FP_MUL => match funct2 {
-                FUNCT2_FMT_S => Fmuls(r_type).into(),
-                _ => Err(())?,
-            },
-            FUNCT_RS3_FP_DIV => match funct2 {
-                FUNCT2_FMT_S => Fdivs(r_type).into(),
-                _ => Err(())?,
-            },
-            FUNCT_RS3_FP_SQRT if rs2 == 0 => match funct2 {
-                FUNCT2_FMT_S => Fsqrts(r_type).into(),
-                _ => Err(())?,
-            },
+            FUNCT_RS3_FP_ADD => RVF::Fadd(fmt, r_type).into(),
+            FUNCT_RS3_FP_SUB => RVF::Fsub(fmt, r_type).into(),
+            FUNCT_RS3_FP_MUL => RVF::Fmul(fmt, r_type).into(),
+            FUNCT_RS3_FP_DIV => RVF::Fdiv(fmt, r_type).into(),
+            FUNCT_RS3_FP_SQRT if rs2 == 0 => RVF::Fsqrt(fmt, r_type).into(),
             FUNCT_RS3_FP_MIN_MAX => match funct3 {
-                FUNCT3_FP_MIN => match funct2 {
-                    FUNCT2_FMT_S => Fmins(r_type).into(),
-                    _ => Err(())?,
-                },
-                FUNCT3_FP_MAX => match funct2 {
-                    FUNCT2_FMT_S => Fmaxs(r_type).into(),
-                    _ => Err(())?,
-                },
+                FUNCT3_FP_MIN => RVF::Fmin(fmt, r_type).into(),
+                FUNCT3_FP_MAX => RVF::Fmax(fmt, r_type).into(),
+                FUNCT3_FP_MINM => RVF::Fminm(fmt, r_type).into(),
+                FUNCT3_FP_MAXM => RVF::Fmaxm(fmt, r_type).into(),
                 _ => Err(())?,
             },
             FUNCT_RS3_FP_SGNJ => match funct3 {
-                FUNCT3_FP_SGNJ => match funct2 {
-                    FUNCT2_FMT_S => Fsgnjs(r_type).into(),
-                    _ => Err(())?,
-                },
-                FUNCT3_FP_SGNJN => match funct2 {
-                    FUNCT2_FMT_S => Fsgnjns(r_type).into(),
-                    _ => Err(())?,
-                },
-                FUNCT3_FP_SGNJX => match funct2 {
-                    FUNCT2_FMT_S => Fsgnjxs(r_type).into(),
-                    _ => Err(())?,
-                },
+                FUNCT3_FP_SGNJ => RVF::Fsgnj(fmt, r_type).into(),
+                FUNCT3_FP_SGNJN => RVF::Fsgnjn(fmt, r_type).into(),
+                FUNCT3_FP_SGNJX => RVF::Fsgnjx(fmt, r_type).into(),
                 _ => Err(())?,
             },
             FUNCT_RS3_FP_CMP => match funct3 {
-                FUNCT3_FP_EQ => match funct2 {
-                    FUNCT2_FMT_S => Feqs(r_type).into(),
-                    _ => Err(())?,
-                },
-                FUNCT3_FP_LT => match funct2 {
-                    FUNCT2_FMT_S => Flts(r_type).into(),
-                    _ => Err(())?,
-                },
-                FUNCT3_FP_LE => match funct2 {
-                    FUNCT2_FMT_S => Fles(r_type).into(),
-                    _ => Err(())?,
-                },
+                FUNCT3_FP_EQ => RVF::Feq(fmt, r_type).into(),
+                FUNCT3_FP_LT => RVF::Flt(fmt, r_type).into(),
+                FUNCT3_FP_LE => RVF::Fle(fmt, r_type).into(),
+                FUNCT3_FP_LEQ => RVF::Fleq(fmt, r_type).into(),
+                FUNCT3_FP_LTQ => RVF::Fltq(fmt, r_type).into(),
                 _ => Err(())?,
             },
-            // fcvt.{w|l}[u].s, fcvt.int.fmt
-            FUNCT_RS3_FP_FCVTX => match rs2 {
-                FUNCT_RS2_CVT_W => match funct2 {
-                    FUNCT2_FMT_S => Fcvtws(r_type).into(),
-                    _ => Err(())?,
-                },
-                FUNCT_RS2_CVT_WU => match funct2 {
-                    FUNCT2_FMT_S => Fcvtwus(r_type).into(),
-                    _ => Err(())?,
-                },
-                FUNCT_RS2_CVT_L if xlen != Xlen::X32 => match funct2 {
-                    FUNCT2_FMT_S => Fcvtls(r_type).into(),
-                    _ => Err(())?,
-                },
-                FUNCT_RS2_CVT_LU if xlen != Xlen::X32 => match funct2 {
-                    FUNCT2_FMT_S => Fcvtlus(r_type).into(),
-                    _ => Err(())?,
-                },
-                _ => Err(())?,
-            },
-            // fcvt.s.{w|l}[u], fcvt.fmt.int
-            FUNCT_RS3_FP_XCVTF => match rs2 {
-                FUNCT_RS2_CVT_W => match funct2 {
-                    FUNCT2_FMT_S => Fcvtsw(r_type).into(),
-                    _ => Err(())?,
-                },
-                FUNCT_RS2_CVT_WU => match funct2 {
-                    FUNCT2_FMT_S => Fcvtswu(r_type).into(),
-                    _ => Err(())?,
-                },
-                FUNCT_RS2_CVT_L if xlen != Xlen::X32 => match funct2 {
-                    FUNCT2_FMT_S => Fcvtsl(r_type).into(),
-                    _ => Err(())?,
-                },
-                FUNCT_RS2_CVT_LU if xlen != Xlen::X32 => match funct2 {
-                    FUNCT2_FMT_S => Fcvtslu(r_type).into(),
-                    _ => Err(())?,
-                },
-                _ => Err(())?,
-            },
-            // fmv.x.w
-            FUNCT_RS3_FP_FMVX_CLASS if rs2 == 0 && funct3 == 0 => match funct2 {
-                FUNCT2_FMT_S => Fmvxw(r_type).into(),
-                _ => Err(())?,
-            },
-            FUNCT_RS3_FP_FMVX_CLASS if rs2 == 0 && funct3 == 1 => match funct2 {
-                FUNCT2_FMT_S => Fclasss(r_type).into(),
+            // fcvt.fmt.fmt: destination fmt in funct2, source fmt in rs2
+            FUNCT_RS3_FP_FCVT_FMT if rs2 <= 0b11 && rs2 != funct2 => {
+                RVF::Fcvt(fmt, FpFmt::from_bits(rs2), r_type).into()
+            }
+            FUNCT_RS3_FP_FCVT_FMT if rs2 == FUNCT_RS2_FROUND => RVF::Fround(fmt, r_type).into(),
+            FUNCT_RS3_FP_FCVT_FMT if rs2 == FUNCT_RS2_FROUNDNX => RVF::Froundnx(fmt, r_type).into(),
+            FUNCT_RS3_FP_FCVTX if rs2 == FUNCT_RS2_FCVTMOD && fmt == FpFmt::D && funct3 == FRM_RTZ => {
+                RVF::FcvtmodWD(r_type).into()
+            }
+            // fcvt.int.fmt
+            FUNCT_RS3_FP_FCVTX => match FpInt::from_rs2(rs2) {
+                Some(int) if !int.is_64() || xlen != Xlen::X32 => {
+                    RVF::FcvtToInt(int, fmt, r_type).into()
+                }
                 _ => Err(())?,
             },
-            // fmv.w.x
-            FUNCT_RS3_FP_XMVF if rs2 == 0 && funct3 == 0 => match funct2 {
-                FUNCT2_FMT_S => Fmvwx(r_type).into(),
+            // fcvt.fmt.int
+            FUNCT_RS3_FP_XCVTF => match FpInt::from_rs2(rs2) {
+                Some(int) if !int.is_64() || xlen != Xlen::X32 => {
+                    RVF::FcvtFromInt(fmt, int, r_type).into()
+                }
                 _ => Err(())?,
             },
+            // fmv.x.fmt needs an integer register at least as wide as fmt
+            FUNCT_RS3_FP_FMVX_CLASS if rs2 == 0 && funct3 == 0 && fmv_fits(fmt, xlen) => {
+                RVF::FmvToInt(fmt, r_type).into()
+            }
+            FUNCT_RS3_FP_FMVX_CLASS if rs2 == 0 && funct3 == 1 => RVF::Fclass(fmt, r_type).into(),
+            FUNCT_RS3_FP_XMVF if rs2 == 0 && funct3 == 0 && fmv_fits(fmt, xlen) => {
+                RVF::FmvFromInt(fmt, r_type).into()
+            }
+            FUNCT_RS3_FP_XMVF if rs2 == FUNCT_RS2_FLI && funct3 == 0 => RVF::Fli(fmt, r_type).into(),
+            // the upper half / a register pair, when fmt is twice XLEN
+            FUNCT_RS3_FP_FMVX_CLASS if rs2 == FUNCT_RS2_FMVH && funct3 == 0 && fmv_split(fmt, xlen) => {
+                RVF::Fmvh(fmt, r_type).into()
+            }
+            FUNCT_RS3_FP_FMVP if funct3 == 0 && fmv_split(fmt, xlen) => RVF::Fmvp(fmt, r_type).into(),
             _ => Err(())?,
         }, // opcode_fp
 
         // atomic instructions
         OPCODE_A => match funct3 {
             FUNCT3_LOAD_LW => match funct5 {
-                FUNCT5_A_LR => Lrw(r_type).into(),
-                FUNCT5_A_SC => Scw(r_type).into(),
-                FUNCT5_A_AMOSWAP => Amoswapw(r_type).into(),
-                FUNCT5_A_AMOADD => Amoaddw(r_type).into(),
-                FUNCT5_A_AMOXOR => Amoxorw(r_type).into(),
-                FUNCT5_A_AMOAND => Amoandw(r_type).into(),
-                FUNCT5_A_AMOOR => Amoorw(r_type).into(),
-                FUNCT5_A_AMOMIN => Amominw(r_type).into(),
-                FUNCT5_A_AMOMAX => Amomaxw(r_type).into(),
-                FUNCT5_A_AMOMINU => Amominuw(r_type).into(),
-                FUNCT5_A_AMOMAXU => Amomaxuw(r_type).into(),
+                FUNCT5_A_LR if rs2 == 0 => Lrw(a_type).into(),
+                FUNCT5_A_SC => Scw(a_type).into(),
+                FUNCT5_A_AMOSWAP => Amoswapw(a_type).into(),
+                FUNCT5_A_AMOADD => Amoaddw(a_type).into(),
+                FUNCT5_A_AMOXOR => Amoxorw(a_type).into(),
+                FUNCT5_A_AMOAND => Amoandw(a_type).into(),
+                FUNCT5_A_AMOOR => Amoorw(a_type).into(),
+                FUNCT5_A_AMOMIN => Amominw(a_type).into(),
+                FUNCT5_A_AMOMAX => Amomaxw(a_type).into(),
+                FUNCT5_A_AMOMINU => Amominuw(a_type).into(),
+                FUNCT5_A_AMOMAXU => Amomaxuw(a_type).into(),
+                FUNCT5_A_AMOCAS => Amocasw(a_type).into(),
+                _ => Err(())?,
+            },
+            // RV32 has only amocas.d, on even/odd register pairs
+            FUNCT3_LOAD_LD if xlen == Xlen::X32 => match funct5 {
+                FUNCT5_A_AMOCAS if rd & 1 == 0 && rs2 & 1 == 0 => Amocasd(a_type).into(),
                 _ => Err(())?,
             },
             FUNCT3_LOAD_LD => match funct5 {
-                FUNCT5_A_LR => Lrd(r_type).into(),
-                FUNCT5_A_SC => Scd(r_type).into(),
-                FUNCT5_A_AMOSWAP => Amoswapd(r_type).into(),
-                FUNCT5_A_AMOADD => Amoaddd(r_type).into(),
-                FUNCT5_A_AMOXOR => Amoxord(r_type).into(),
-                FUNCT5_A_AMOAND => Amoandd(r_type).into(),
-                FUNCT5_A_AMOOR => Amoord(r_type).into(),
-                FUNCT5_A_AMOMIN => Amomind(r_type).into(),
-                FUNCT5_A_AMOMAX => Amomaxd(r_type).into(),
-                FUNCT5_A_AMOMINU => Amominud(r_type).into(),
-                FUNCT5_A_AMOMAXU => Amomaxud(r_type).into(),
+                FUNCT5_A_LR if rs2 == 0 => Lrd(a_type).into(),
+                FUNCT5_A_SC => Scd(a_type).into(),
+                FUNCT5_A_AMOSWAP => Amoswapd(a_type).into(),
+                FUNCT5_A_AMOADD => Amoaddd(a_type).into(),
+                FUNCT5_A_AMOXOR => Amoxord(a_type).into(),
+                FUNCT5_A_AMOAND => Amoandd(a_type).into(),
+                FUNCT5_A_AMOOR => Amoord(a_type).into(),
+                FUNCT5_A_AMOMIN => Amomind(a_type).into(),
+                FUNCT5_A_AMOMAX => Amomaxd(a_type).into(),
+                FUNCT5_A_AMOMINU => Amominud(a_type).into(),
+                FUNCT5_A_AMOMAXU => Amomaxud(a_type).into(),
+                FUNCT5_A_AMOCAS => Amocasd(a_type).into(),
                 _ => Err(())?,
             },
+            // RV64 amocas.q, on register pairs
+            FUNCT3_A_WIDTH_Q if xlen == Xlen::X64 && funct5 == FUNCT5_A_AMOCAS && rd & 1 == 0 && rs2 & 1 == 0 => {
+                Amocasq(a_type).into()
+            }
             // RV128A (.q) width
             x if x == FUNCT3_A_WIDTH_Q && xlen == Xlen::X128 => match funct5 {
-                FUNCT5_A_LR => Lrq(r_type).into(),
-                FUNCT5_A_SC => Scq(r_type).into(),
-                FUNCT5_A_AMOSWAP => Amoswapq(r_type).into(),
-                FUNCT5_A_AMOADD => Amoaddq(r_type).into(),
-                FUNCT5_A_AMOXOR => Amoxorq(r_type).into(),
-                FUNCT5_A_AMOAND => Amoandq(r_type).into(),
-                FUNCT5_A_AMOOR => Amoorq(r_type).into(),
-                FUNCT5_A_AMOMIN => Amominq(r_type).into(),
-                FUNCT5_A_AMOMAX => Amomaxq(r_type).into(),
-                FUNCT5_A_AMOMINU => Amominuq(r_type).into(),
-                FUNCT5_A_AMOMAXU => Amomaxuq(r_type).into(),
+                FUNCT5_A_LR if rs2 == 0 => Lrq(a_type).into(),
+                FUNCT5_A_SC => Scq(a_type).into(),
+                FUNCT5_A_AMOSWAP => Amoswapq(a_type).into(),
+                FUNCT5_A_AMOADD => Amoaddq(a_type).into(),
+                FUNCT5_A_AMOXOR => Amoxorq(a_type).into(),
+                FUNCT5_A_AMOAND => Amoandq(a_type).into(),
+                FUNCT5_A_AMOOR => Amoorq(a_type).into(),
+                FUNCT5_A_AMOMIN => Amominq(a_type).into(),
+                FUNCT5_A_AMOMAX => Amomaxq(a_type).into(),
+                FUNCT5_A_AMOMINU => Amominuq(a_type).into(),
+                FUNCT5_A_AMOMAXU => Amomaxuq(a_type).into(),
+                FUNCT5_A_AMOCAS => Amocasq(a_type).into(),
                 _ => Err(())?,
             },
+            // Zabha byte and halfword AMOs
+            FUNCT3_A_WIDTH_B | FUNCT3_A_WIDTH_H => {
+                let w = if funct3 == FUNCT3_A_WIDTH_B { AmoWidth::B } else { AmoWidth::H };
+                match funct5 {
+                    FUNCT5_A_AMOSWAP => RVZabha::Amoswap(w, a_type).into(),
+                    FUNCT5_A_AMOADD => RVZabha::Amoadd(w, a_type).into(),
+                    FUNCT5_A_AMOXOR => RVZabha::Amoxor(w, a_type).into(),
+                    FUNCT5_A_AMOAND => RVZabha::Amoand(w, a_type).into(),
+                    FUNCT5_A_AMOOR => RVZabha::Amoor(w, a_type).into(),
+                    FUNCT5_A_AMOMIN => RVZabha::Amomin(w, a_type).into(),
+                    FUNCT5_A_AMOMAX => RVZabha::Amomax(w, a_type).into(),
+                    FUNCT5_A_AMOMINU => RVZabha::Amominu(w, a_type).into(),
+                    FUNCT5_A_AMOMAXU => RVZabha::Amomaxu(w, a_type).into(),
+                    FUNCT5_A_AMOCAS => RVZabha::Amocas(w, a_type).into(),
+                    _ => Err(())?,
+                }
+            }
             _ => Err(())?,
         },
         _ => Err(())?,
     };
+    // rounding modes 0b101 and 0b110 are reserved
+    if let Instruction::RVF(f) = &ans {
+        if f.rounding_mode().is_some_and(|rm| rounding_mode_name(rm).is_none()) {
+            return Err(());
+        }
+    }
     Ok(ans)
 }
+
+
+/// `fmv.x.<fmt>` / `fmv.<fmt>.x` exist only when XLEN covers the format:
+/// `.d` needs RV64, `.q` RV128.
+fn fmv_fits(fmt: FpFmt, xlen: Xlen) -> bool {
+    match fmt {
+        FpFmt::H | FpFmt::S => true,
+        FpFmt::D => xlen != Xlen::X32,
+        FpFmt::Q => xlen == Xlen::X128,
+    }
+}
+
+/// Zfa `fmvh.x.<fmt>` / `fmvp.<fmt>.x` move a format twice as wide as XLEN:
+/// `.d` on RV32, `.q` on RV64.
+fn fmv_split(fmt: FpFmt, xlen: Xlen) -> bool {
+    matches!((fmt, xlen), (FpFmt::D, Xlen::X32) | (FpFmt::Q, Xlen::X64))
+}
+
+/// Bit-manipulation instructions; `None` for anything else, including the
+/// RV64-only forms on RV32.
+fn resolve_rvb(opcode: u32, funct3: u8, funct7: u8, imm12: u32, r: RType, i: IType, xlen: Xlen) -> Option<RVB> {
+    use crate::asm::RVB::*;
+    let rv64 = xlen != Xlen::X32;
+    // shifts by immediate: funct6 on RV64, funct7 (shamt < 32) on RV32
+    let funct6 = (imm12 >> 6) as u8;
+    let shamt_ok = rv64 || imm12 & 0x20 == 0;
+    Some(match opcode {
+        OPCODE_OP => match (funct7, funct3) {
+            (FUNCT7_B_ANDN, FUNCT3_OP_AND) => Andn(r),
+            (FUNCT7_B_ANDN, FUNCT3_OP_OR) => Orn(r),
+            (FUNCT7_B_ANDN, FUNCT3_OP_XOR) => Xnor(r),
+            (FUNCT7_B_SHADD, FUNCT3_B_SH1ADD) => Sh1add(r),
+            (FUNCT7_B_SHADD, FUNCT3_B_SH2ADD) => Sh2add(r),
+            (FUNCT7_B_SHADD, FUNCT3_B_SH3ADD) => Sh3add(r),
+            (FUNCT7_B_MINMAX_CLMUL, FUNCT3_B_CLMUL) => Clmul(r),
+            (FUNCT7_B_MINMAX_CLMUL, FUNCT3_B_CLMULR) => Clmulr(r),
+            (FUNCT7_B_MINMAX_CLMUL, FUNCT3_B_CLMULH) => Clmulh(r),
+            (FUNCT7_B_MINMAX_CLMUL, FUNCT3_B_MIN) => Min(r),
+            (FUNCT7_B_MINMAX_CLMUL, FUNCT3_B_MINU) => Minu(r),
+            (FUNCT7_B_MINMAX_CLMUL, FUNCT3_B_MAX) => Max(r),
+            (FUNCT7_B_MINMAX_CLMUL, FUNCT3_B_MAXU) => Maxu(r),
+            (FUNCT7_B_ROT, FUNCT3_OP_SLL) => Rol(r),
+            (FUNCT7_B_ROT, FUNCT3_OP_SRL_SRA) => Ror(r),
+            (FUNCT7_B_BCLR_BEXT, FUNCT3_OP_SLL) => Bclr(r),
+            (FUNCT7_B_BCLR_BEXT, FUNCT3_OP_SRL_SRA) => Bext(r),
+            (FUNCT7_B_BINV, FUNCT3_OP_SLL) => Binv(r),
+            (FUNCT7_B_BSET, FUNCT3_OP_SLL) => Bset(r),
+            // RV64 moves zext.h to OP-32
+            (FUNCT7_B_ADDUW_ZEXTH, FUNCT3_B_ZEXTH) if r.rs2 == 0 && !rv64 => Zexth(r),
+            _ => return None,
+        },
+        OPCODE_OP_IMM => match (funct3, imm12) {
+            (FUNCT3_OP_SLL, IMM12_B_CLZ) => Clz(r),
+            (FUNCT3_OP_SLL, IMM12_B_CTZ) => Ctz(r),
+            (FUNCT3_OP_SLL, IMM12_B_CPOP) => Cpop(r),
+            (FUNCT3_OP_SLL, IMM12_B_SEXTB) => Sextb(r),
+            (FUNCT3_OP_SLL, IMM12_B_SEXTH) => Sexth(r),
+            (FUNCT3_OP_SRL_SRA, IMM12_B_ORCB) => Orcb(r),
+            (FUNCT3_OP_SRL_SRA, IMM12_B_REV8_RV32) if !rv64 => Rev8(r),
+            (FUNCT3_OP_SRL_SRA, IMM12_B_REV8_RV64) if rv64 => Rev8(r),
+            (FUNCT3_OP_SLL, _) if shamt_ok => match funct6 {
+                FUNCT6_B_BCLRI_BEXTI => Bclri(i),
+                FUNCT6_B_BINVI => Binvi(i),
+                FUNCT6_B_BSETI => Bseti(i),
+                _ => return None,
+            },
+            (FUNCT3_OP_SRL_SRA, _) if shamt_ok => match funct6 {
+                FUNCT6_B_RORI => Rori(i),
+                FUNCT6_B_BCLRI_BEXTI => Bexti(i),
+                _ => return None,
+            },
+            _ => return None,
+        },
+        OPCODE_OP_32 if rv64 => match (funct7, funct3) {
+            (FUNCT7_B_ADDUW_ZEXTH, FUNCT3_OP_ADD_SUB) => Adduw(r),
+            (FUNCT7_B_ADDUW_ZEXTH, FUNCT3_B_ZEXTH) if r.rs2 == 0 => Zexth(r),
+            (FUNCT7_B_SHADD, FUNCT3_B_SH1ADD) => Sh1adduw(r),
+            (FUNCT7_B_SHADD, FUNCT3_B_SH2ADD) => Sh2adduw(r),
+            (FUNCT7_B_SHADD, FUNCT3_B_SH3ADD) => Sh3adduw(r),
+            (FUNCT7_B_ROT, FUNCT3_OP_SLL) => Rolw(r),
+            (FUNCT7_B_ROT, FUNCT3_OP_SRL_SRA) => Rorw(r),
+            _ => return None,
+        },
+        OPCODE_OP_IMM32 if rv64 => match (funct3, imm12) {
+            (FUNCT3_OP_SLL, IMM12_B_CLZ) => Clzw(r),
+            (FUNCT3_OP_SLL, IMM12_B_CTZ) => Ctzw(r),
+            (FUNCT3_OP_SLL, IMM12_B_CPOP) => Cpopw(r),
+            (FUNCT3_OP_SLL, _) if funct6 == FUNCT6_B_SLLIUW => Slliuw(i),
+            (FUNCT3_OP_SRL_SRA, _) if funct7 == FUNCT7_B_ROT => Roriw(i),
+            _ => return None,
+        },
+        _ => return None,
+    })
+}
+
+/// Scalar-crypto instructions; `None` for anything else. The forms shared
+/// with Zbb/Zbc (`ror`, `andn`, `clmul`, `rev8`, ...) decode as [`RVB`].
+fn resolve_rvk(opcode: u32, funct3: u8, funct7: u8, imm12: u32, r: RType, i: IType, xlen: Xlen) -> Option<RVK> {
+    use crate::asm::RVK::*;
+    let rv64 = xlen != Xlen::X32;
+    let k = KType { rd: r.rd, rs1: r.rs1, rs2: r.rs2, bs: funct7 >> 5 };
+    let ins = match opcode {
+        OPCODE_OP => match (funct3, funct7) {
+            // pack rd, rs1, zero on RV32 is zext.h, taken by resolve_rvb
+            (FUNCT3_K_PACK, FUNCT7_K_PACK) => Pack(r),
+            (FUNCT3_K_PACKH, FUNCT7_K_PACK) => Packh(r),
+            (FUNCT3_K_XPERM4, FUNCT7_K_XPERM) => Xperm4(r),
+            (FUNCT3_K_XPERM8, FUNCT7_K_XPERM) => Xperm8(r),
+            (0, f) => match f & 0b1_1111 {
+                FUNCT5_K_SM4ED => Sm4ed(k),
+                FUNCT5_K_SM4KS => Sm4ks(k),
+                FUNCT5_K_AES32ESI if !rv64 => Aes32esi(k),
+                FUNCT5_K_AES32ESMI if !rv64 => Aes32esmi(k),
+                FUNCT5_K_AES32DSI if !rv64 => Aes32dsi(k),
+                FUNCT5_K_AES32DSMI if !rv64 => Aes32dsmi(k),
+                _ => match f {
+                    FUNCT7_K_AES64ES => Aes64es(r),
+                    FUNCT7_K_AES64ESM => Aes64esm(r),
+                    FUNCT7_K_AES64DS => Aes64ds(r),
+                    FUNCT7_K_AES64DSM => Aes64dsm(r),
+                    FUNCT7_K_AES64KS2 => Aes64ks2(r),
+                    FUNCT7_K_SHA512SUM0R => Sha512sum0r(r),
+                    FUNCT7_K_SHA512SUM1R => Sha512sum1r(r),
+                    FUNCT7_K_SHA512SIG0L => Sha512sig0l(r),
+                    FUNCT7_K_SHA512SIG1L => Sha512sig1l(r),
+                    FUNCT7_K_SHA512SIG0H => Sha512sig0h(r),
+                    FUNCT7_K_SHA512SIG1H => Sha512sig1h(r),
+                    _ => return None,
+                },
+            },
+            _ => return None,
+        },
+        OPCODE_OP_IMM => match (funct3, imm12) {
+            (FUNCT3_OP_SLL, IMM12_K_SHA256SUM0) => Sha256sum0(r),
+            (FUNCT3_OP_SLL, IMM12_K_SHA256SUM1) => Sha256sum1(r),
+            (FUNCT3_OP_SLL, IMM12_K_SHA256SIG0) => Sha256sig0(r),
+            (FUNCT3_OP_SLL, IMM12_K_SHA256SIG1) => Sha256sig1(r),
+            (FUNCT3_OP_SLL, IMM12_K_SHA512SUM0) => Sha512sum0(r),
+            (FUNCT3_OP_SLL, IMM12_K_SHA512SUM1) => Sha512sum1(r),
+            (FUNCT3_OP_SLL, IMM12_K_SHA512SIG0) => Sha512sig0(r),
+            (FUNCT3_OP_SLL, IMM12_K_SHA512SIG1) => Sha512sig1(r),
+            (FUNCT3_OP_SLL, IMM12_K_SM3P0) => Sm3p0(r),
+            (FUNCT3_OP_SLL, IMM12_K_SM3P1) => Sm3p1(r),
+            (FUNCT3_OP_SLL, IMM12_K_AES64IM) => Aes64im(r),
+            // rnum 0xB..=0xF are reserved
+            (FUNCT3_OP_SLL, x) if x & !0xf == IMM12_K_AES64KS1I && x & 0xf <= 0xa => Aes64ks1i(i),
+            (FUNCT3_OP_SLL, IMM12_K_ZIP) => Zip(r),
+            (FUNCT3_OP_SRL_SRA, IMM12_K_ZIP) => Unzip(r),
+            (FUNCT3_OP_SRL_SRA, IMM12_K_BREV8) => Brev8(r),
+            _ => return None,
+        },
+        OPCODE_OP_32 => match (funct3, funct7) {
+            (FUNCT3_K_PACK, FUNCT7_K_PACK) => Packw(r),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    if (ins.is_rv32_only() && rv64) || (ins.is_rv64_only() && !rv64) {
+        return None;
+    }
+    Some(ins)
+}
+
+/// RVV 1.0; `None` for anything else, including the scalar FP loads and
+/// stores that share LOAD-FP/STORE-FP.
+fn resolve_rvv(ins: u32) -> Option<RVV> {
+    let opcode = ins & 0b111_1111;
+    let vd = ((ins >> 7) & 0b1_1111) as u8;
+    let funct3 = ((ins >> 12) & 0b111) as u8;
+    let rs1 = ((ins >> 15) & 0b1_1111) as u8;
+    let rs2 = ((ins >> 20) & 0b1_1111) as u8;
+    let vm = (ins >> 25) & 1 == 1;
+    match opcode {
+        OPCODE_OP_V if funct3 == FUNCT3_V_OPCFG => {
+            let set = |vtypei: u32| VsetType { rd: vd, rs1, vtypei: vtypei as u16 };
+            Some(match ins >> 30 {
+                0b00 | 0b01 => RVV::Vsetvli(set((ins >> 20) & 0x7ff)),
+                0b11 => RVV::Vsetivli(set((ins >> 20) & 0x3ff)),
+                _ if (ins >> 25) & 0b11_1111 == 0 => RVV::Vsetvl(RType { rd: vd, rs1, rs2, funct3, funct7: 0b100_0000 }),
+                _ => return None,
+            })
+        }
+        OPCODE_OP_V => {
+            let (group, kind) = VArith::from_funct3(funct3)?;
+            let a = VArith { funct6: (ins >> 26) as u8, group, kind, vd, vs2: rs2, src: rs1, vm };
+            // a masked result written over v0 is reserved, as in the parser
+            if a.is_valid() && !a.masked_vd_overlaps_v0() { Some(RVV::Arith(a)) } else { None }
+        }
+        OPCODE_LOAD_FP | OPCODE_STORE_FP => {
+            let store = opcode == OPCODE_STORE_FP;
+            let eew = VMem::eew_of(funct3)?;
+            let nf = (ins >> 29) as u8;
+            // mew = 1 is reserved for element widths above 64
+            if (ins >> 28) & 1 != 0 {
+                return None;
+            }
+            let mode = match ((ins >> 26) & 0b11) as u8 {
+                MOP_V_UNIT => match rs2 {
+                    LUMOP_V_UNIT => VAddr::Unit,
+                    LUMOP_V_WHOLE => VAddr::WholeReg,
+                    LUMOP_V_MASK => VAddr::Mask,
+                    LUMOP_V_FF => VAddr::FaultFirst,
+                    _ => return None,
+                },
+                MOP_V_STRIDED => VAddr::Strided(rs2),
+                MOP_V_INDEXED_UNORDERED => VAddr::Unordered(rs2),
+                _ => VAddr::Ordered(rs2),
+            };
+            let m = VMem { vd, rs1, mode, eew, nf, vm };
+            if !m.is_valid(store) {
+                return None;
+            }
+            Some(if store { RVV::Store(m) } else { RVV::Load(m) })
+        }
+        _ => None,
+    }
+}
+
+/// Trap returns, `wfi`, the translation fences and the hypervisor loads and
+/// stores; `None` for anything else under SYSTEM funct3 000/100.
+fn resolve_priv(funct3: u8, funct7: u8, funct12: u32, r: RType, xlen: Xlen) -> Option<RVPriv> {
+    use crate::asm::RVPriv::*;
+    let ins = match funct3 {
+        FUNCT3_SYSTEM_PRIV if r.rd == 0 => match funct12 {
+            FUNCT12_SYSTEM_SRET if r.rs1 == 0 => Sret(()),
+            FUNCT12_SYSTEM_MRET if r.rs1 == 0 => Mret(()),
+            FUNCT12_SYSTEM_MNRET if r.rs1 == 0 => Mnret(()),
+            FUNCT12_SYSTEM_WFI if r.rs1 == 0 => Wfi(()),
+            FUNCT12_SYSTEM_WRS_NTO if r.rs1 == 0 => WrsNto(()),
+            FUNCT12_SYSTEM_WRS_STO if r.rs1 == 0 => WrsSto(()),
+            _ => match funct7 {
+                FUNCT7_SYSTEM_SFENCE_VMA => SfenceVma(r),
+                FUNCT7_SYSTEM_HFENCE_VVMA => HfenceVvma(r),
+                FUNCT7_SYSTEM_HFENCE_GVMA => HfenceGvma(r),
+                FUNCT7_SYSTEM_SINVAL_VMA => SinvalVma(r),
+                FUNCT7_SYSTEM_HINVAL_VVMA => HinvalVvma(r),
+                FUNCT7_SYSTEM_HINVAL_GVMA => HinvalGvma(r),
+                FUNCT7_SYSTEM_SFENCE_INVAL if r.rs1 == 0 && r.rs2 == 0 => SfenceWInval(()),
+                FUNCT7_SYSTEM_SFENCE_INVAL if r.rs1 == 0 && r.rs2 == 1 => SfenceInvalIr(()),
+                _ => return None,
+            },
+        },
+        FUNCT3_SYSTEM_HLSV => match (funct7, r.rs2) {
+            (FUNCT7_SYSTEM_HLV_B, RS2_HLV_SIGNED) => HlvB(r),
+            (FUNCT7_SYSTEM_HLV_B, RS2_HLV_UNSIGNED) => HlvBu(r),
+            (FUNCT7_SYSTEM_HLV_H, RS2_HLV_SIGNED) => HlvH(r),
+            (FUNCT7_SYSTEM_HLV_H, RS2_HLV_UNSIGNED) => HlvHu(r),
+            (FUNCT7_SYSTEM_HLV_H, RS2_HLVX) => HlvxHu(r),
+            (FUNCT7_SYSTEM_HLV_W, RS2_HLV_SIGNED) => HlvW(r),
+            (FUNCT7_SYSTEM_HLV_W, RS2_HLV_UNSIGNED) => HlvWu(r),
+            (FUNCT7_SYSTEM_HLV_W, RS2_HLVX) => HlvxWu(r),
+            (FUNCT7_SYSTEM_HLV_D, RS2_HLV_SIGNED) => HlvD(r),
+            (FUNCT7_SYSTEM_HSV_B, _) if r.rd == 0 => HsvB(r),
+            (FUNCT7_SYSTEM_HSV_H, _) if r.rd == 0 => HsvH(r),
+            (FUNCT7_SYSTEM_HSV_W, _) if r.rd == 0 => HsvW(r),
+            (FUNCT7_SYSTEM_HSV_D, _) if r.rd == 0 => HsvD(r),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    if ins.is_rv64_only() && xlen == Xlen::X32 {
+        return None;
+    }
+    Some(ins)
+}
@@ -0,0 +1,192 @@
+//! Automatic compression: rewrites base instructions that have an RVC
+//! equivalent, using the same operand rules as GNU as with `-march=..c`
+//! (e.g. `addi sp, sp, -16` -> `c.addi sp, -16`, `lw a0, 4(sp)` -> `c.lwsp`).
+use crate::asm::*;
+use crate::riscv::imm::{Imm, Uimm, Xlen};
+
+/// x8..x15, the registers reachable from the 3-bit RVC register fields.
+fn is_c_reg(r: u8) -> bool {
+    (8..=15).contains(&r)
+}
+
+fn fits_signed(v: i64, bits: u32) -> bool {
+    v >= -(1i64 << (bits - 1)) && v < (1i64 << (bits - 1))
+}
+
+/// Unsigned, `align`-aligned offset below `limit`.
+fn fits_scaled(v: i64, align: i64, limit: i64) -> bool {
+    (0..limit).contains(&v) && v % align == 0
+}
+
+fn imm(v: i64, bits: u8) -> Imm {
+    Imm::new((v as u32) & ((1u32 << bits) - 1), bits)
+}
+
+fn ci(rdrs1: u8, v: i64, bits: u8) -> CIType {
+    CIType { rdrs1, funct3: 0, imm: imm(v, bits) }
+}
+
+fn cr(rdrs1: u8, rs2: u8, funct4: u8) -> CRType {
+    CRType { rdrs1, rs2, funct4 }
+}
+
+fn ca(rdrs1: u8, rs2: u8) -> CAType {
+    CAType { rdrs1, rs2, funct2: 0, funct6: 0 }
+}
+
+/// Two-operand form of a commutative `op rd, rs1, rs2` with all registers
+/// in x8..x15 and rd equal to one of the sources.
+fn commutative(r: &RType) -> Option<CAType> {
+    if !(is_c_reg(r.rd) && is_c_reg(r.rs1) && is_c_reg(r.rs2)) {
+        return None;
+    }
+    if r.rd == r.rs1 {
+        Some(ca(r.rd, r.rs2))
+    } else if r.rd == r.rs2 {
+        Some(ca(r.rd, r.rs1))
+    } else {
+        None
+    }
+}
+
+fn non_commutative(r: &RType) -> Option<CAType> {
+    if is_c_reg(r.rd) && r.rd == r.rs1 && is_c_reg(r.rs2) {
+        Some(ca(r.rd, r.rs2))
+    } else {
+        None
+    }
+}
+
+/// Register-based (`c.lw`) or sp-based (`c.lwsp`) form of a load.
+fn load(i: &IType, align: i64, limits: (i64, i64), reg: fn(CLType) -> RVC, sp: fn(CIType) -> RVC) -> Option<RVC> {
+    let off = i.imm.as_i64();
+    if is_c_reg(i.rd) && is_c_reg(i.rs1) && fits_scaled(off, align, limits.0) {
+        Some(reg(CLType { rd: i.rd, rs1: i.rs1, funct3: 0, imm: imm(off, 12) }))
+    } else if i.rd != 0 && i.rs1 == 2 && fits_scaled(off, align, limits.1) {
+        Some(sp(ci(i.rd, off, 12)))
+    } else {
+        None
+    }
+}
+
+fn store(s: &SType, align: i64, limits: (i64, i64), reg: fn(CSType) -> RVC, sp: fn(CSSType) -> RVC) -> Option<RVC> {
+    let off = s.imm.as_i64();
+    if is_c_reg(s.rs2) && is_c_reg(s.rs1) && fits_scaled(off, align, limits.0) {
+        Some(reg(CSType { rs1: s.rs1, rs2: s.rs2, funct3: 0, imm: imm(off, 12) }))
+    } else if s.rs1 == 2 && fits_scaled(off, align, limits.1) {
+        Some(sp(CSSType { rs2: s.rs2, funct3: 0, imm: imm(off, 12) }))
+    } else {
+        None
+    }
+}
+
+fn shift(i: &IType, xlen: Xlen, right: bool) -> Option<CIType> {
+    let sh = i.imm.low_u32() & 0x7f;
+    // RV128C sign-extends the 6-bit field, so 32..=95 has no compressed form
+    let fits = match xlen {
+        Xlen::X32 => sh <= 0x1f,
+        Xlen::X64 => sh <= 0x3f,
+        Xlen::X128 => !(32..96).contains(&sh),
+    };
+    let regs_ok = if right { is_c_reg(i.rd) } else { i.rd != 0 };
+    if regs_ok && i.rd == i.rs1 && sh != 0 && fits {
+        Some(ci(i.rd, (sh & 0x3f) as i64, 6))
+    } else {
+        None
+    }
+}
+
+fn rv32i(inst: &RV32I, xlen: Xlen) -> Option<RVC> {
+    use RV32I::*;
+    Some(match inst {
+        Addi(i) => {
+            let v = i.imm.as_i64();
+            match (i.rd, i.rs1) {
+                (0, 0) if v == 0 => RVC::Cnop(ci(0, 0, 6)),
+                (rd, 0) if rd != 0 && fits_signed(v, 6) => RVC::Cli(ci(rd, v, 6)),
+                (rd, rs1) if rd != 0 && rd == rs1 && v != 0 && fits_signed(v, 6) => RVC::Caddi(ci(rd, v, 6)),
+                (2, 2) if v != 0 && v % 16 == 0 && fits_signed(v, 10) => RVC::Caddi16sp(ci(2, v, 10)),
+                (rd, 2) if is_c_reg(rd) && v != 0 && fits_scaled(v, 4, 1024) => {
+                    RVC::Caddi4spn(CIWType { rd, funct3: 0, uimm: Uimm::new(v as u32, 10) })
+                }
+                (rd, rs1) if rd != 0 && rs1 != 0 && v == 0 => RVC::Cmv(cr(rd, rs1, 0b1000)),
+                _ => return None,
+            }
+        }
+        Lui(u) => {
+            let v = (u.imm.low_u32() as i32 >> 12) as i64;
+            if u.rd == 0 || u.rd == 2 || v == 0 || !fits_signed(v, 6) {
+                return None;
+            }
+            RVC::Clui(CIType { rdrs1: u.rd, funct3: 0, imm: Imm::new(((v as u32) & 0x3f) << 12, 18) })
+        }
+        Jal(j) => {
+            let off = j.imm.as_i64();
+            if !fits_signed(off, 12) {
+                return None;
+            }
+            let cj = CJType { funct3: 0, target: imm(off, 12) };
+            match j.rd {
+                0 => RVC::Cj(cj),
+                1 if xlen == Xlen::X32 => RVC::Cjal(cj),
+                _ => return None,
+            }
+        }
+        Jalr(i) if i.imm.as_i64() == 0 && i.rs1 != 0 => match i.rd {
+            0 => RVC::Cjr(cr(i.rs1, 0, 0b1000)),
+            1 => RVC::Cjalr(cr(i.rs1, 0, 0b1001)),
+            _ => return None,
+        },
+        Beq(b) | Bne(b) if b.rs2 == 0 && is_c_reg(b.rs1) && fits_signed(b.imm.as_i64(), 9) => {
+            let cb = CBType { rs1: b.rs1, funct3: 0, off: imm(b.imm.as_i64(), 9) };
+            if let Beq(_) = inst { RVC::Cbeqz(cb) } else { RVC::Cbnez(cb) }
+        }
+        Lw(i) => return load(i, 4, (128, 256), RVC::Clw, RVC::Clwsp),
+        Sw(s) => return store(s, 4, (128, 256), RVC::Csw, RVC::Cswsp),
+        Slli(i) => RVC::Cslli(shift(i, xlen, false)?),
+        Srli(i) => RVC::Csrli(shift(i, xlen, true)?),
+        Srai(i) => RVC::Csrai(shift(i, xlen, true)?),
+        Andi(i) if is_c_reg(i.rd) && i.rd == i.rs1 && fits_signed(i.imm.as_i64(), 6) => {
+            RVC::Candi(ci(i.rd, i.imm.as_i64(), 6))
+        }
+        Add(r) if r.rd != 0 => match (r.rs1, r.rs2) {
+            (0, 0) => return None,
+            (0, rs) | (rs, 0) => RVC::Cmv(cr(r.rd, rs, 0b1000)),
+            (rs1, rs2) if rs1 == r.rd => RVC::Cadd(cr(r.rd, rs2, 0b1001)),
+            (rs1, rs2) if rs2 == r.rd => RVC::Cadd(cr(r.rd, rs1, 0b1001)),
+            _ => return None,
+        },
+        Sub(r) => RVC::Csub(non_commutative(r)?),
+        Xor(r) => RVC::Cxor(commutative(r)?),
+        Or(r) => RVC::Cor(commutative(r)?),
+        And(r) => RVC::Cand(commutative(r)?),
+        Ebreak(_) => RVC::Cebreak(cr(0, 0, 0b1001)),
+        _ => return None,
+    })
+}
+
+fn rv64i(inst: &RV64I, xlen: Xlen) -> Option<RVC> {
+    use RV64I::*;
+    Some(match inst {
+        Ld(i) => return load(i, 8, (256, 512), RVC::Cld, RVC::Cldsp),
+        Sd(s) => return store(s, 8, (256, 512), RVC::Csd, RVC::Csdsp),
+        Slli(i) => RVC::Cslli(shift(i, xlen, false)?),
+        Srli(i) => RVC::Csrli(shift(i, xlen, true)?),
+        Srai(i) => RVC::Csrai(shift(i, xlen, true)?),
+        Addiw(i) if i.rd != 0 && i.rd == i.rs1 && fits_signed(i.imm.as_i64(), 6) => {
+            RVC::Caddiw(ci(i.rd, i.imm.as_i64(), 6))
+        }
+        Addw(r) => RVC::Caddw(commutative(r)?),
+        Subw(r) => RVC::Csubw(non_commutative(r)?),
+        _ => return None,
+    })
+}
+
+/// The RVC equivalent of `inst`, if it has one under `xlen`.
+pub fn compress(inst: &Instruction, xlen: Xlen) -> Option<RVC> {
+    match inst {
+        Instruction::RV32I(i) => rv32i(i, xlen),
+        Instruction::RV64I(i) if xlen != Xlen::X32 => rv64i(i, xlen),
+        _ => None,
+    }
+}
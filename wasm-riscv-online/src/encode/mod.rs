@@ -1,2 +1,49 @@
 pub mod process32;
-pub mod process16; // placeholder for future RVC encoder
+pub mod process16;
+pub mod compress;
+
+use crate::asm::Instruction;
+use crate::riscv::imm::Xlen;
+
+/// Machine code of one instruction: a half-word for RVC, a word otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoded {
+    U16(u16),
+    U32(u32),
+}
+
+impl Encoded {
+    /// Size in bytes.
+    pub fn size(self) -> u64 {
+        match self {
+            Encoded::U16(_) => 2,
+            Encoded::U32(_) => 4,
+        }
+    }
+
+    pub fn to_hex(self) -> String {
+        match self {
+            Encoded::U16(v) => format!("0x{:04x}", v),
+            Encoded::U32(v) => format!("0x{:08x}", v),
+        }
+    }
+}
+
+/// Encode any instruction; RVC instructions produce a 16-bit word.
+pub fn encode(inst: &Instruction, xlen: Xlen) -> Result<Encoded, String> {
+    match inst {
+        Instruction::RVC(_) => process16::encode_u16(inst, xlen).map(Encoded::U16),
+        _ => process32::encode_u32(inst, xlen).map(Encoded::U32),
+    }
+}
+
+/// Like [`encode`], but instructions with an RVC equivalent are emitted in
+/// their compressed form (see [`compress::compress`]).
+pub fn encode_compressed(inst: &Instruction, xlen: Xlen) -> Result<Encoded, String> {
+    if let Some(c) = compress::compress(inst, xlen) {
+        if let Ok(half) = process16::encode_u16(&c.into(), xlen) {
+            return Ok(Encoded::U16(half));
+        }
+    }
+    encode(inst, xlen)
+}
@@ -88,6 +88,23 @@ fn encode_ci_addi16sp(imm10: u32) -> Result<u16, String> {
     Ok(ins)
 }
 
+fn encode_ci_lui(rd: u8, imm18: u32) -> Result<u16, String> {
+    // nzimm[17] -> [12], nzimm[16:12] -> [6:2]
+    if rd == 0 || rd == 2 {
+        return Err("c.lui 目的寄存器不能为 x0 或 sp".into());
+    }
+    if (imm18 >> 12) & 0x3f == 0 {
+        return Err("c.lui 的立即数不能为 0".into());
+    }
+    let mut ins: u16 = 0;
+    ins = set_bits(ins, 13, 3, 0b011);
+    ins = set_bits(ins, 12, 1, ((imm18 >> 17) & 0x1) as u16);
+    ins = set_bits(ins, 7, 5, rd as u16);
+    ins = set_bits(ins, 2, 5, ((imm18 >> 12) & 0x1f) as u16);
+    ins = set_bits(ins, 0, 2, OPCODE_C1);
+    Ok(ins)
+}
+
 fn encode_ci_addiw(rd: u8, imm6: u32) -> Result<u16, String> {
     if rd == 0 {
         return Err("c.addiw 目的寄存器不能为 x0".into());
@@ -236,8 +253,8 @@ fn encode_clw(rd: u8, rs1: u8, imm: u32) -> Result<u16, String> {
     let mut ins: u16 = 0;
     // funct3
     ins = set_bits(ins, 13, 3, 0b010);
-    // imm[5:3] -> bits 13:11 already used; according to decode it used (ins>>11)&0b111; that is bits 13..11
-    ins = set_bits(ins, 11, 3, ((imm >> 3) & 0x7) as u16);
+    // imm[5:3] -> bits 12:10
+    ins = set_bits(ins, 10, 3, ((imm >> 3) & 0x7) as u16);
     // rs1' -> bits 9:7
     ins = set_bits(ins, 7, 3, rs1_c);
     // imm[6] -> bit 5
@@ -261,8 +278,8 @@ fn encode_csw(rs2: u8, rs1: u8, imm: u32) -> Result<u16, String> {
     let rs1_c = c_reg_index(rs1)?;
     let mut ins: u16 = 0;
     ins = set_bits(ins, 13, 3, 0b110);
-    // imm[5:3] -> bits 13:11
-    ins = set_bits(ins, 11, 3, ((imm >> 3) & 0x7) as u16);
+    // imm[5:3] -> bits 12:10
+    ins = set_bits(ins, 10, 3, ((imm >> 3) & 0x7) as u16);
     // rs1' -> bits 9:7
     ins = set_bits(ins, 7, 3, rs1_c);
     // imm[6] -> bit 5
@@ -309,7 +326,7 @@ fn encode_cswsp(rs2: u8, imm: u32) -> Result<u16, String> {
     let mut ins: u16 = 0;
     ins = set_bits(ins, 13, 3, 0b110);
     // imm[5:2] -> bits 12:9 (4 bits)
-    ins = set_bits(ins, 9, 4, ((imm >> 3) & 0xF) as u16);
+    ins = set_bits(ins, 9, 4, ((imm >> 2) & 0xF) as u16);
     // imm[7:6] -> bits 8:7 (2 bits)
     ins = set_bits(ins, 7, 2, ((imm >> 6) & 0x3) as u16);
     // rs2 -> bits 6:2
@@ -364,7 +381,7 @@ fn encode_csd(rs2: u8, rs1: u8, imm: u32) -> Result<u16, String> {
 }
 
 fn encode_cldsp(rd: u8, imm: u32) -> Result<u16, String> {
-    // C.LDSP: opcode=10, funct3=011; rd!=x0; imm uses [5|7:6|4:3]
+    // C.LDSP: opcode=10, funct3=011; rd!=x0; imm uses [5|4:3|8:6]
     if rd == 0 {
         return Err("c.ldsp 目的寄存器不能为 x0".into());
     }
@@ -378,10 +395,10 @@ fn encode_cldsp(rd: u8, imm: u32) -> Result<u16, String> {
     ins = set_bits(ins, 12, 1, ((imm >> 5) & 0x1) as u16);
     // rd -> bits 11:7
     ins = set_bits(ins, 7, 5, rd as u16);
-    // imm[7:6] -> bits 6:5
-    ins = set_bits(ins, 5, 2, ((imm >> 6) & 0x3) as u16);
-    // imm[4:3] -> bits 4:3
-    ins = set_bits(ins, 3, 2, ((imm >> 3) & 0x3) as u16);
+    // imm[4:3] -> bits 6:5
+    ins = set_bits(ins, 5, 2, ((imm >> 3) & 0x3) as u16);
+    // imm[8:6] -> bits 4:2
+    ins = set_bits(ins, 2, 3, ((imm >> 6) & 0x7) as u16);
     ins = set_bits(ins, 0, 2, OPCODE_C2 as u16);
     Ok(ins)
 }
@@ -404,6 +421,127 @@ fn encode_csdsp(rs2: u8, imm: u32) -> Result<u16, String> {
     Ok(ins)
 }
 
+fn encode_cntl(rs2: u8) -> Result<u16, String> {
+    // C.NTL.*: c.add x0, rs2 with rs2 in x2..=x5
+    if !(RS2_NTL_P1..=RS2_NTL_ALL).contains(&rs2) {
+        return Err(format!("c.add x0, x{} 不是 c.ntl.* 提示 (rs2 须为 x2 到 x5)", rs2));
+    }
+    let mut ins: u16 = 0;
+    ins = set_bits(ins, 13, 3, 0b100);
+    ins = set_bits(ins, 12, 1, 1);
+    ins = set_bits(ins, 2, 5, rs2 as u16);
+    ins = set_bits(ins, 0, 2, OPCODE_C2);
+    Ok(ins)
+}
+
+fn encode_cmop(n: u8) -> Result<u16, String> {
+    // C.MOP.n: c.lui xn, 0 with n odd in 1..=15
+    if n & 1 == 0 || n > 15 {
+        return Err(format!("c.mop.{} 不存在 (n 为 1 到 15 的奇数)", n));
+    }
+    let mut ins: u16 = 0;
+    ins = set_bits(ins, 13, 3, 0b011);
+    ins = set_bits(ins, 7, 5, n as u16);
+    ins = set_bits(ins, 0, 2, OPCODE_C1);
+    Ok(ins)
+}
+
+/// Zcb loads and stores (quadrant 0, funct3 100): uimm[1] goes to bit 5;
+/// bit 6 holds uimm[0] for bytes and tells `c.lh` from `c.lhu` for halves.
+fn encode_zcb_mem(mnem: &str, funct: u16, reg: u8, rs1: u8, imm: u32, half: Option<bool>) -> Result<u16, String> {
+    if imm >= 4 {
+        return Err(format!("{} 偏移过大 (需 < 4)", mnem));
+    }
+    let bit6 = match half {
+        Some(signed) => {
+            ensure_align(imm, 2)?;
+            signed as u16
+        }
+        None => (imm & 0x1) as u16,
+    };
+    let mut ins: u16 = 0;
+    ins = set_bits(ins, 13, 3, 0b100);
+    ins = set_bits(ins, 10, 3, funct);
+    ins = set_bits(ins, 7, 3, c_reg_index(rs1)?);
+    ins = set_bits(ins, 6, 1, bit6);
+    ins = set_bits(ins, 5, 1, ((imm >> 1) & 0x1) as u16);
+    ins = set_bits(ins, 2, 3, c_reg_index(reg)?);
+    ins = set_bits(ins, 0, 2, OPCODE_C0);
+    Ok(ins)
+}
+
+/// Zcb `c.mul` (funct2 10, rs2' in bits 4:2) and the unary ops (funct2 11,
+/// operation in bits 4:2): `100 111 rd' funct2 xxx 01`.
+fn encode_zcb_alu(rd: u8, funct2: u16, low: u16) -> Result<u16, String> {
+    let mut ins: u16 = 0;
+    ins = set_bits(ins, 13, 3, 0b100);
+    ins = set_bits(ins, 10, 3, 0b111);
+    ins = set_bits(ins, 7, 3, c_reg_index(rd)?);
+    ins = set_bits(ins, 5, 2, funct2);
+    ins = set_bits(ins, 2, 3, low);
+    ins = set_bits(ins, 0, 2, OPCODE_C1);
+    Ok(ins)
+}
+
+/// Zcmp push/pop: `101 op rlist spimm 10`. The stack adjustment is what the
+/// register list needs plus 0, 16, 32 or 48 bytes.
+fn encode_cm_pushpop(mnem: &str, op: u16, pp: &CMPPType, xlen: Xlen) -> Result<u16, String> {
+    if xlen == Xlen::X128 {
+        return Err(format!("{} 不能用于 RV128", mnem));
+    }
+    if !(4..=15).contains(&pp.rlist) {
+        return Err(format!("{} 的寄存器列表无效", mnem));
+    }
+    let base = pp.base_adj(xlen);
+    if pp.stack_adj < base || pp.stack_adj > base + 48 || pp.stack_adj & 0xf != 0 {
+        return Err(format!("{} {} 的栈调整量应为 {} 到 {} 之间 16 的倍数", mnem, pp.rlist_string(", "), base, base + 48));
+    }
+    let mut ins: u16 = 0;
+    ins = set_bits(ins, 13, 3, 0b101);
+    ins = set_bits(ins, 8, 5, op);
+    ins = set_bits(ins, 4, 4, pp.rlist as u16);
+    ins = set_bits(ins, 2, 2, ((pp.stack_adj - base) / 16) as u16);
+    ins = set_bits(ins, 0, 2, OPCODE_C2);
+    Ok(ins)
+}
+
+/// The 3-bit Zcmp register field: s0-s1 (x8-x9) and s2-s7 (x18-x23).
+fn sreg_index(reg: u8) -> Result<u16, String> {
+    match reg {
+        8 | 9 => Ok((reg - 8) as u16),
+        18..=23 => Ok((reg - 16) as u16),
+        _ => Err(format!("寄存器 {} 不是 s0-s7", reg)),
+    }
+}
+
+/// `cm.mvsa01` (funct2 01) / `cm.mva01s` (funct2 11): `101 011 r1s' funct2 r2s' 10`.
+fn encode_cm_mv(r1s: u8, r2s: u8, funct2: u16) -> Result<u16, String> {
+    if r1s == r2s {
+        return Err("cm.mvsa01/cm.mva01s 的两个寄存器不能相同".into());
+    }
+    let mut ins: u16 = 0;
+    ins = set_bits(ins, 13, 3, 0b101);
+    ins = set_bits(ins, 10, 3, 0b011);
+    ins = set_bits(ins, 7, 3, sreg_index(r1s)?);
+    ins = set_bits(ins, 5, 2, funct2);
+    ins = set_bits(ins, 2, 3, sreg_index(r2s)?);
+    ins = set_bits(ins, 0, 2, OPCODE_C2);
+    Ok(ins)
+}
+
+/// Zcmt: `101 000 index 10`; entries below 32 are `cm.jt`, the rest `cm.jalt`.
+fn encode_cm_jt(index: u8, link: bool) -> Result<u16, String> {
+    if link != (index >= 32) {
+        let range = if link { "32 到 255" } else { "0 到 31" };
+        return Err(format!("{} 的索引应为 {}", if link { "cm.jalt" } else { "cm.jt" }, range));
+    }
+    let mut ins: u16 = 0;
+    ins = set_bits(ins, 13, 3, 0b101);
+    ins = set_bits(ins, 2, 8, index as u16);
+    ins = set_bits(ins, 0, 2, OPCODE_C2);
+    Ok(ins)
+}
+
 fn encode_rvc(rvc: &RVC, xlen: Xlen) -> Result<u16, String> {
     use RVC::*;
     match rvc {
@@ -414,6 +552,7 @@ fn encode_rvc(rvc: &RVC, xlen: Xlen) -> Result<u16, String> {
         Cli(ci) => encode_ci_addi_like(ci.rdrs1, ci.imm.low_u32(), 0b010),
         Caddi16sp(ci) => encode_ci_addi16sp(ci.imm.low_u32()),
         Caddiw(ci) => encode_ci_addiw(ci.rdrs1, ci.imm.low_u32()),
+        Clui(ci) => encode_ci_lui(ci.rdrs1, ci.imm.low_u32()),
 
         // CL/CS (with c-registers)
         Clw(cl) => encode_clw(cl.rd, cl.rs1, cl.imm.low_u32()),
@@ -455,7 +594,12 @@ fn encode_rvc(rvc: &RVC, xlen: Xlen) -> Result<u16, String> {
         Cjr(cr) => encode_cr(cr.rdrs1, 0, false),
         Cmv(cr) => encode_cr(cr.rdrs1, cr.rs2, false),
         Cjalr(cr) => encode_cr(cr.rdrs1, 0, true),
+        // c.add x0, rs2 is only defined as a c.ntl.* hint
+        Cntl(cr) => encode_cntl(cr.rs2),
+        Cadd(cr) if cr.rdrs1 == 0 => encode_cntl(cr.rs2),
         Cadd(cr) => encode_cr(cr.rdrs1, cr.rs2, true),
+        Cebreak(_) => Ok(0x9002), // funct4=1001, rd=rs2=0
+        Cmop(ci) => encode_cmop(ci.rdrs1),
 
         // CJ/CB
         Cj(cj) => encode_cj(cj.target.low_u32(), 0b101),
@@ -463,6 +607,30 @@ fn encode_rvc(rvc: &RVC, xlen: Xlen) -> Result<u16, String> {
         Cbeqz(cb) => encode_cb(cb.rs1, cb.off.low_u32(), 0b110),
         Cbnez(cb) => encode_cb(cb.rs1, cb.off.low_u32(), 0b111),
 
+        // Zcb
+        Clbu(cl) => encode_zcb_mem("c.lbu", 0b000, cl.rd, cl.rs1, cl.imm.low_u32(), None),
+        Clhu(cl) => encode_zcb_mem("c.lhu", 0b001, cl.rd, cl.rs1, cl.imm.low_u32(), Some(false)),
+        Clh(cl) => encode_zcb_mem("c.lh", 0b001, cl.rd, cl.rs1, cl.imm.low_u32(), Some(true)),
+        Csb(cs) => encode_zcb_mem("c.sb", 0b010, cs.rs2, cs.rs1, cs.imm.low_u32(), None),
+        Csh(cs) => encode_zcb_mem("c.sh", 0b011, cs.rs2, cs.rs1, cs.imm.low_u32(), Some(false)),
+        Cmul(ca) => encode_zcb_alu(ca.rdrs1, 0b10, c_reg_index(ca.rs2)?),
+        CzextB(ci) => encode_zcb_alu(ci.rdrs1, 0b11, 0b000),
+        CsextB(ci) => encode_zcb_alu(ci.rdrs1, 0b11, 0b001),
+        CzextH(ci) => encode_zcb_alu(ci.rdrs1, 0b11, 0b010),
+        CsextH(ci) => encode_zcb_alu(ci.rdrs1, 0b11, 0b011),
+        CzextW(ci) if xlen != Xlen::X32 => encode_zcb_alu(ci.rdrs1, 0b11, 0b100),
+        Cnot(ci) => encode_zcb_alu(ci.rdrs1, 0b11, 0b101),
+
+        // Zcmp/Zcmt
+        CmPush(pp) => encode_cm_pushpop("cm.push", 0b11000, pp, xlen),
+        CmPop(pp) => encode_cm_pushpop("cm.pop", 0b11010, pp, xlen),
+        CmPopretz(pp) => encode_cm_pushpop("cm.popretz", 0b11100, pp, xlen),
+        CmPopret(pp) => encode_cm_pushpop("cm.popret", 0b11110, pp, xlen),
+        CmMvsa01(ca) => encode_cm_mv(ca.rdrs1, ca.rs2, 0b01),
+        CmMva01s(ca) => encode_cm_mv(ca.rdrs1, ca.rs2, 0b11),
+        CmJt(index) => encode_cm_jt(*index, false),
+        CmJalt(index) => encode_cm_jt(*index, true),
+
         // Unimplemented compressed ops (future)
         _ => Err("该 RVC 指令的编码暂未实现".into()),
     }
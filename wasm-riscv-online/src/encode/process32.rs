@@ -22,6 +22,17 @@ fn i_type(opcode: u32, rd: u8, funct3: u8, rs1: u8, imm12: u32) -> u32 {
         | opcode
 }
 
+#[inline]
+fn r4_type(opcode: u32, rd: u8, rm: u8, rs1: u8, rs2: u8, rs3: u8, fmt: u8) -> u32 {
+    ((rs3 as u32) << 27)
+        | ((fmt as u32 & 0b11) << 25)
+        | ((rs2 as u32) << 20)
+        | ((rs1 as u32) << 15)
+        | ((rm as u32) << 12)
+        | ((rd as u32) << 7)
+        | opcode
+}
+
 #[inline]
 fn s_type(opcode: u32, funct3: u8, rs1: u8, rs2: u8, imm12: u32) -> u32 {
     let imm_low = (imm12 & 0x1F) as u32; // [4:0]
@@ -60,7 +71,6 @@ fn u_type(opcode: u32, rd: u8, imm_hi20: u32) -> u32 {
 #[inline]
 fn j_type(opcode: u32, rd: u8, imm: u32) -> u32 {
     // imm layout in decode val: [20][10:1][11][19:12]
-    // Note: current decode stores only 12 valid bits for J-type imm; we only encode from provided bits.
     let bit_20 = ((imm >> 20) & 0x1) as u32;
     let bits_10_1 = ((imm >> 1) & 0x3FF) as u32; // 10 bits
     let bit_11 = ((imm >> 11) & 0x1) as u32;
@@ -79,15 +89,33 @@ fn validate_shamt(shamt: u32, max_bits: u32) -> Result<(), String> {
     if shamt < (1 << max_bits) { Ok(()) } else { Err(format!("shamt {} out of range for {}-bit", shamt, max_bits)) }
 }
 
-pub fn encode_u32(inst: &Instruction, _xlen: Xlen) -> Result<u32, String> {
+pub fn encode_u32(inst: &Instruction, xlen: Xlen) -> Result<u32, String> {
     match inst {
         Instruction::RV32I(i) => encode_rv32i(i),
-        Instruction::RV64I(i) => encode_rv64i(i),
+        Instruction::RV64I(i) => encode_rv64i(i, xlen),
+        Instruction::RV128I(i) => encode_rv128i(i),
         Instruction::RVZicsr(csr) => encode_zicsr(csr),
-        // RVF/RVC/A extensions will be added later
-        Instruction::RVC(_) => Err("RVC (compressed) encoding is not yet supported".into()),
-        Instruction::RVF(_) => Err("RVF encoding is not yet supported".into()),
-        Instruction::RV32A(_) | Instruction::RV64A(_) | Instruction::RV128A(_) => Err("A-extension encoding is not yet supported".into()),
+        Instruction::RVF(f) => encode_rvf(f),
+        Instruction::RV32A(a) => { let (op, at) = a.parts(); encode_atomic(op, FUNCT3_LOAD_LW, at) }
+        Instruction::RV64A(a) => { let (op, at) = a.parts(); encode_atomic(op, FUNCT3_LOAD_LD, at) }
+        Instruction::RV128A(a) => { let (op, at) = a.parts(); encode_atomic(op, FUNCT3_A_WIDTH_Q, at) }
+        Instruction::RVZabha(a) => {
+            let (op, width, at) = a.parts();
+            let width = match width {
+                AmoWidth::B => FUNCT3_A_WIDTH_B,
+                AmoWidth::H => FUNCT3_A_WIDTH_H,
+            };
+            encode_atomic(op, width, at)
+        }
+        Instruction::RVB(b) => encode_rvb(b, xlen),
+        Instruction::RVK(k) => encode_rvk(k, xlen),
+        Instruction::RVV(v) => encode_rvv(v),
+        Instruction::RVPriv(p) => encode_priv(p, xlen),
+        Instruction::RVZicond(z) => Ok(encode_zicond(z)),
+        Instruction::RVZimop(m) => encode_zimop(m),
+        Instruction::RVZicbo(z) => encode_zicbo(z, xlen),
+        Instruction::RVZihint(h) => encode_u32(&h.hint_base(), xlen),
+        Instruction::RVC(_) => Err("RVC instructions are 16 bits wide; use encode::encode".into()),
     }
 }
 
@@ -97,7 +125,7 @@ fn encode_rv32i(i: &RV32I) -> Result<u32, String> {
         Lui(u)   => u_type(OPCODE_LUI, u.rd, u.imm.low_u32()),
         Auipc(u) => u_type(OPCODE_AUIPC, u.rd, u.imm.low_u32()),
 
-        // J-type (note: current Imm width limitation in decode; partial support)
+        // J-type
         Jal(j)   => j_type(OPCODE_JAL, j.rd, j.imm.low_u32()),
 
         // I-type jumps/loads
@@ -163,26 +191,34 @@ fn encode_rv32i(i: &RV32I) -> Result<u32, String> {
         Or(r)  => r_type(OPCODE_OP, r.rd, FUNCT3_OP_OR, r.rs1, r.rs2, 0),
         And(r) => r_type(OPCODE_OP, r.rd, FUNCT3_OP_AND, r.rs1, r.rs2, 0),
 
-        // M-extension (R-type with funct7 = 0b000_0001)
-        Mul(r)   => r_type(OPCODE_OP, r.rd, FUNCT3_OP_ADD_SUB, r.rs1, r.rs2, 0b000_0001),
-        Mulh(r)  => r_type(OPCODE_OP, r.rd, FUNCT3_OP_SLL,     r.rs1, r.rs2, 0b000_0001),
-        Mulhsu(r)=> r_type(OPCODE_OP, r.rd, FUNCT3_OP_XOR,     r.rs1, r.rs2, 0b000_0001),
-        Mulhu(r) => r_type(OPCODE_OP, r.rd, FUNCT3_OP_SLTU,    r.rs1, r.rs2, 0b000_0001),
-        Div(r)   => r_type(OPCODE_OP, r.rd, FUNCT3_OP_SRL_SRA, r.rs1, r.rs2, 0b000_0001),
-        Divu(r)  => r_type(OPCODE_OP, r.rd, FUNCT3_OP_OR,      r.rs1, r.rs2, 0b000_0001),
-        Rem(r)   => r_type(OPCODE_OP, r.rd, FUNCT3_OP_AND,     r.rs1, r.rs2, 0b000_0001),
-        Remu(r)  => r_type(OPCODE_OP, r.rd, FUNCT3_OP_AND,     r.rs1, r.rs2, 0b000_0001),
+        // M-extension (R-type with funct7 = MULDIV)
+        Mul(r)   => r_type(OPCODE_OP, r.rd, FUNCT3_M_MUL,    r.rs1, r.rs2, FUNCT7_OP_MULDIV),
+        Mulh(r)  => r_type(OPCODE_OP, r.rd, FUNCT3_M_MULH,   r.rs1, r.rs2, FUNCT7_OP_MULDIV),
+        Mulhsu(r)=> r_type(OPCODE_OP, r.rd, FUNCT3_M_MULHSU, r.rs1, r.rs2, FUNCT7_OP_MULDIV),
+        Mulhu(r) => r_type(OPCODE_OP, r.rd, FUNCT3_M_MULHU,  r.rs1, r.rs2, FUNCT7_OP_MULDIV),
+        Div(r)   => r_type(OPCODE_OP, r.rd, FUNCT3_M_DIV,    r.rs1, r.rs2, FUNCT7_OP_MULDIV),
+        Divu(r)  => r_type(OPCODE_OP, r.rd, FUNCT3_M_DIVU,   r.rs1, r.rs2, FUNCT7_OP_MULDIV),
+        Rem(r)   => r_type(OPCODE_OP, r.rd, FUNCT3_M_REM,    r.rs1, r.rs2, FUNCT7_OP_MULDIV),
+        Remu(r)  => r_type(OPCODE_OP, r.rd, FUNCT3_M_REMU,   r.rs1, r.rs2, FUNCT7_OP_MULDIV),
 
         // System
-        Fence(())  => i_type(OPCODE_MISC_MEM, 0, FUNCT3_MISC_MEM_FENCE, 0, 0),
+        Fence(i)   => i_type(OPCODE_MISC_MEM, i.rd, FUNCT3_MISC_MEM_FENCE, i.rs1, i.imm.low_u32()),
         FenceI(()) => i_type(OPCODE_MISC_MEM, 0, FUNCT3_MISC_MEM_FENCE_I, 0, 0),
         Ecall(())  => i_type(OPCODE_SYSTEM, 0, FUNCT3_SYSTEM_PRIV, 0, FUNCT12_SYSTEM_ECALL),
         Ebreak(()) => i_type(OPCODE_SYSTEM, 0, FUNCT3_SYSTEM_PRIV, 0, FUNCT12_SYSTEM_EBREAK),
     })
 }
 
-fn encode_rv64i(i: &RV64I) -> Result<u32, String> {
+/// Shift-immediate with a `shamt_bits`-wide shift amount under `funct7`.
+fn shift_imm(opcode: u32, funct3: u8, funct7: u8, i: &IType, shamt_bits: u32) -> Result<u32, String> {
+    let shamt = i.imm.low_u32();
+    validate_shamt(shamt, shamt_bits)?;
+    Ok(i_type(opcode, i.rd, funct3, i.rs1, ((funct7 as u32) << 5) | shamt))
+}
+
+fn encode_rv64i(i: &RV64I, xlen: Xlen) -> Result<u32, String> {
     use RV64I::*;
+    let shamt_bits = if xlen == Xlen::X128 { 7 } else { 6 };
     Ok(match i {
         // loads/stores
         Lwu(i) => i_type(OPCODE_LOAD, i.rd, FUNCT3_LOAD_LWU, i.rs1, i.imm.low_u32()),
@@ -194,50 +230,11 @@ fn encode_rv64i(i: &RV64I) -> Result<u32, String> {
         Srl(r) => r_type(OPCODE_OP, r.rd, FUNCT3_OP_SRL_SRA, r.rs1, r.rs2, 0),
         Sra(r) => r_type(OPCODE_OP, r.rd, FUNCT3_OP_SRL_SRA, r.rs1, r.rs2, FUNCT7_OP_SRA),
 
-        // I-type shifts (RV64I: 6-bit shamt), top 6 bits in funct7 except LSB holds shamt[5]
-        Slli(i)=> {
-            let shamt = i.imm.low_u32() & 0x3f;
-            validate_shamt(shamt, 6)?;
-            let imm_low5 = shamt & 0x1f;
-            let funct7 = (0u8 as u8) | ((shamt >> 5) as u8 & 0x1);
-            // Build instruction manually to control funct7 bit0 location
-            let mut ins = 0u32;
-            ins |= (funct7 as u32) << 25;
-            ins |= imm_low5 << 20;
-            ins |= (i.rs1 as u32) << 15;
-            ins |= (FUNCT3_OP_SLL as u32) << 12;
-            ins |= (i.rd as u32) << 7;
-            ins |= OPCODE_OP_IMM;
-            ins
-        }
-        Srli(i)=> {
-            let shamt = i.imm.low_u32() & 0x3f;
-            validate_shamt(shamt, 6)?;
-            let imm_low5 = shamt & 0x1f;
-            let funct7 = (FUNCT7_OP_SRL as u8) | ((shamt >> 5) as u8 & 0x1);
-            let mut ins = 0u32;
-            ins |= (funct7 as u32) << 25;
-            ins |= imm_low5 << 20;
-            ins |= (i.rs1 as u32) << 15;
-            ins |= (FUNCT3_OP_SRL_SRA as u32) << 12;
-            ins |= (i.rd as u32) << 7;
-            ins |= OPCODE_OP_IMM;
-            ins
-        }
-        Srai(i)=> {
-            let shamt = i.imm.low_u32() & 0x3f;
-            validate_shamt(shamt, 6)?;
-            let imm_low5 = shamt & 0x1f;
-            let funct7 = (FUNCT7_OP_SRA as u8) | ((shamt >> 5) as u8 & 0x1);
-            let mut ins = 0u32;
-            ins |= (funct7 as u32) << 25;
-            ins |= imm_low5 << 20;
-            ins |= (i.rs1 as u32) << 15;
-            ins |= (FUNCT3_OP_SRL_SRA as u32) << 12;
-            ins |= (i.rd as u32) << 7;
-            ins |= OPCODE_OP_IMM;
-            ins
-        }
+        // I-type shifts: 6-bit shamt on RV64, 7-bit on RV128, the bits above
+        // it in funct7
+        Slli(i) => shift_imm(OPCODE_OP_IMM, FUNCT3_OP_SLL, 0, i, shamt_bits)?,
+        Srli(i) => shift_imm(OPCODE_OP_IMM, FUNCT3_OP_SRL_SRA, FUNCT7_OP_SRL, i, shamt_bits)?,
+        Srai(i) => shift_imm(OPCODE_OP_IMM, FUNCT3_OP_SRL_SRA, FUNCT7_OP_SRA, i, shamt_bits)?,
 
         // OP-IMM-32 / OP-32 (W variants, 5-bit shamt)
         Addiw(i)=> i_type(OPCODE_OP_IMM32, i.rd, FUNCT3_OP_ADD_SUB, i.rs1, i.imm.low_u32()),
@@ -262,9 +259,199 @@ fn encode_rv64i(i: &RV64I) -> Result<u32, String> {
         Sllw(r) => r_type(OPCODE_OP_32, r.rd, FUNCT3_OP_SLL, r.rs1, r.rs2, 0),
         Srlw(r) => r_type(OPCODE_OP_32, r.rd, FUNCT3_OP_SRL_SRA, r.rs1, r.rs2, 0),
         Sraw(r) => r_type(OPCODE_OP_32, r.rd, FUNCT3_OP_SRL_SRA, r.rs1, r.rs2, FUNCT7_OP_SRA),
+
+        // RV64M (OP-32 with funct7 = MULDIV)
+        Mulw(r)  => r_type(OPCODE_OP_32, r.rd, FUNCT3_M_MUL,  r.rs1, r.rs2, FUNCT7_OP_MULDIV),
+        Divw(r)  => r_type(OPCODE_OP_32, r.rd, FUNCT3_M_DIV,  r.rs1, r.rs2, FUNCT7_OP_MULDIV),
+        Divuw(r) => r_type(OPCODE_OP_32, r.rd, FUNCT3_M_DIVU, r.rs1, r.rs2, FUNCT7_OP_MULDIV),
+        Remw(r)  => r_type(OPCODE_OP_32, r.rd, FUNCT3_M_REM,  r.rs1, r.rs2, FUNCT7_OP_MULDIV),
+        Remuw(r) => r_type(OPCODE_OP_32, r.rd, FUNCT3_M_REMU, r.rs1, r.rs2, FUNCT7_OP_MULDIV),
+    })
+}
+
+fn encode_rv128i(i: &RV128I) -> Result<u32, String> {
+    use RV128I::*;
+    Ok(match i {
+        Ldu(i) => i_type(OPCODE_LOAD, i.rd, FUNCT3_LOAD_LDU, i.rs1, i.imm.low_u32()),
+        Lq(i)  => i_type(OPCODE_MISC_MEM, i.rd, FUNCT3_MISC_MEM_LQ, i.rs1, i.imm.low_u32()),
+        Sq(s)  => s_type(OPCODE_STORE, FUNCT3_STORE_SQ, s.rs1, s.rs2, s.imm.low_u32()),
+
+        // OP-IMM-64 (6-bit shamt) / OP-64
+        Addid(i) => i_type(OPCODE_OP_IMM64, i.rd, FUNCT3_OP_ADD_SUB, i.rs1, i.imm.low_u32()),
+        Sllid(i) => shift_imm(OPCODE_OP_IMM64, FUNCT3_OP_SLL, 0, i, 6)?,
+        Srlid(i) => shift_imm(OPCODE_OP_IMM64, FUNCT3_OP_SRL_SRA, FUNCT7_OP_SRL, i, 6)?,
+        Sraid(i) => shift_imm(OPCODE_OP_IMM64, FUNCT3_OP_SRL_SRA, FUNCT7_OP_SRA, i, 6)?,
+
+        Addd(r) => r_type(OPCODE_OP_64, r.rd, FUNCT3_OP_ADD_SUB, r.rs1, r.rs2, FUNCT7_OP_ADD),
+        Subd(r) => r_type(OPCODE_OP_64, r.rd, FUNCT3_OP_ADD_SUB, r.rs1, r.rs2, FUNCT7_OP_SUB),
+        Slld(r) => r_type(OPCODE_OP_64, r.rd, FUNCT3_OP_SLL, r.rs1, r.rs2, 0),
+        Srld(r) => r_type(OPCODE_OP_64, r.rd, FUNCT3_OP_SRL_SRA, r.rs1, r.rs2, FUNCT7_OP_SRL),
+        Srad(r) => r_type(OPCODE_OP_64, r.rd, FUNCT3_OP_SRL_SRA, r.rs1, r.rs2, FUNCT7_OP_SRA),
+
+        // RV128M (OP-64 with funct7 = MULDIV)
+        Muld(r)  => r_type(OPCODE_OP_64, r.rd, FUNCT3_M_MUL,  r.rs1, r.rs2, FUNCT7_OP_MULDIV),
+        Divd(r)  => r_type(OPCODE_OP_64, r.rd, FUNCT3_M_DIV,  r.rs1, r.rs2, FUNCT7_OP_MULDIV),
+        Divud(r) => r_type(OPCODE_OP_64, r.rd, FUNCT3_M_DIVU, r.rs1, r.rs2, FUNCT7_OP_MULDIV),
+        Remd(r)  => r_type(OPCODE_OP_64, r.rd, FUNCT3_M_REM,  r.rs1, r.rs2, FUNCT7_OP_MULDIV),
+        Remud(r) => r_type(OPCODE_OP_64, r.rd, FUNCT3_M_REMU, r.rs1, r.rs2, FUNCT7_OP_MULDIV),
     })
 }
 
+/// OP-FP instruction with the format (S/D/H/Q) in the low bits of funct7.
+#[inline]
+fn fp_type(funct5: u8, fmt: FpFmt, rd: u8, funct3: u8, rs1: u8, rs2: u8) -> u32 {
+    r_type(OPCODE_FP, rd, funct3, rs1, rs2, (funct5 << 2) | fmt.bits())
+}
+
+fn encode_rvf(f: &RVF) -> Result<u32, String> {
+    use RVF::*;
+    Ok(match *f {
+        Load(fmt, i) => i_type(OPCODE_LOAD_FP, i.rd, fmt.width(), i.rs1, i.imm.low_u32()),
+        Store(fmt, s) => s_type(OPCODE_STORE_FP, fmt.width(), s.rs1, s.rs2, s.imm.low_u32()),
+
+        // R4-type fused multiply-add, funct3 holds the rounding mode
+        Fmadd(fmt, r)  => r4_type(OPCODE_FMADD,  r.rd, r.funct3, r.rs1, r.rs2, r.rs3, fmt.bits()),
+        Fmsub(fmt, r)  => r4_type(OPCODE_FMSUB,  r.rd, r.funct3, r.rs1, r.rs2, r.rs3, fmt.bits()),
+        Fnmsub(fmt, r) => r4_type(OPCODE_FNMSUB, r.rd, r.funct3, r.rs1, r.rs2, r.rs3, fmt.bits()),
+        Fnmadd(fmt, r) => r4_type(OPCODE_FNMADD, r.rd, r.funct3, r.rs1, r.rs2, r.rs3, fmt.bits()),
+
+        // arithmetic, funct3 holds the rounding mode
+        Fadd(fmt, r)  => fp_type(FUNCT_RS3_FP_ADD,  fmt, r.rd, r.funct3, r.rs1, r.rs2),
+        Fsub(fmt, r)  => fp_type(FUNCT_RS3_FP_SUB,  fmt, r.rd, r.funct3, r.rs1, r.rs2),
+        Fmul(fmt, r)  => fp_type(FUNCT_RS3_FP_MUL,  fmt, r.rd, r.funct3, r.rs1, r.rs2),
+        Fdiv(fmt, r)  => fp_type(FUNCT_RS3_FP_DIV,  fmt, r.rd, r.funct3, r.rs1, r.rs2),
+        Fsqrt(fmt, r) => fp_type(FUNCT_RS3_FP_SQRT, fmt, r.rd, r.funct3, r.rs1, 0),
+
+        Fsgnj(fmt, r)  => fp_type(FUNCT_RS3_FP_SGNJ,    fmt, r.rd, FUNCT3_FP_SGNJ,  r.rs1, r.rs2),
+        Fsgnjn(fmt, r) => fp_type(FUNCT_RS3_FP_SGNJ,    fmt, r.rd, FUNCT3_FP_SGNJN, r.rs1, r.rs2),
+        Fsgnjx(fmt, r) => fp_type(FUNCT_RS3_FP_SGNJ,    fmt, r.rd, FUNCT3_FP_SGNJX, r.rs1, r.rs2),
+        Fmin(fmt, r)   => fp_type(FUNCT_RS3_FP_MIN_MAX, fmt, r.rd, FUNCT3_FP_MIN,   r.rs1, r.rs2),
+        Fmax(fmt, r)   => fp_type(FUNCT_RS3_FP_MIN_MAX, fmt, r.rd, FUNCT3_FP_MAX,   r.rs1, r.rs2),
+
+        Feq(fmt, r) => fp_type(FUNCT_RS3_FP_CMP, fmt, r.rd, FUNCT3_FP_EQ, r.rs1, r.rs2),
+        Flt(fmt, r) => fp_type(FUNCT_RS3_FP_CMP, fmt, r.rd, FUNCT3_FP_LT, r.rs1, r.rs2),
+        Fle(fmt, r) => fp_type(FUNCT_RS3_FP_CMP, fmt, r.rd, FUNCT3_FP_LE, r.rs1, r.rs2),
+
+        // fcvt between formats: destination fmt in funct7, source fmt in rs2
+        Fcvt(to, from, r) => fp_type(FUNCT_RS3_FP_FCVT_FMT, to, r.rd, r.funct3, r.rs1, from.bits()),
+        // conversions with integers, the integer width goes in rs2
+        FcvtToInt(int, fmt, r)   => fp_type(FUNCT_RS3_FP_FCVTX, fmt, r.rd, r.funct3, r.rs1, int.rs2()),
+        FcvtFromInt(fmt, int, r) => fp_type(FUNCT_RS3_FP_XCVTF, fmt, r.rd, r.funct3, r.rs1, int.rs2()),
+
+        FmvToInt(fmt, r)   => fp_type(FUNCT_RS3_FP_FMVX_CLASS, fmt, r.rd, 0b000, r.rs1, 0),
+        Fclass(fmt, r)     => fp_type(FUNCT_RS3_FP_FMVX_CLASS, fmt, r.rd, 0b001, r.rs1, 0),
+        FmvFromInt(fmt, r) => fp_type(FUNCT_RS3_FP_XMVF,       fmt, r.rd, 0b000, r.rs1, 0),
+
+        // Zfa: mostly new rs2/funct3 values under the existing funct5 groups
+        Fli(fmt, r)      => fp_type(FUNCT_RS3_FP_XMVF,      fmt, r.rd, 0b000, r.rs1 & 0x1f, FUNCT_RS2_FLI),
+        Fminm(fmt, r)    => fp_type(FUNCT_RS3_FP_MIN_MAX,   fmt, r.rd, FUNCT3_FP_MINM, r.rs1, r.rs2),
+        Fmaxm(fmt, r)    => fp_type(FUNCT_RS3_FP_MIN_MAX,   fmt, r.rd, FUNCT3_FP_MAXM, r.rs1, r.rs2),
+        Fround(fmt, r)   => fp_type(FUNCT_RS3_FP_FCVT_FMT,  fmt, r.rd, r.funct3, r.rs1, FUNCT_RS2_FROUND),
+        Froundnx(fmt, r) => fp_type(FUNCT_RS3_FP_FCVT_FMT,  fmt, r.rd, r.funct3, r.rs1, FUNCT_RS2_FROUNDNX),
+        FcvtmodWD(r)     => fp_type(FUNCT_RS3_FP_FCVTX, FpFmt::D, r.rd, FRM_RTZ, r.rs1, FUNCT_RS2_FCVTMOD),
+        Fmvh(fmt, r)     => fp_type(FUNCT_RS3_FP_FMVX_CLASS, fmt, r.rd, 0b000, r.rs1, FUNCT_RS2_FMVH),
+        Fmvp(fmt, r)     => fp_type(FUNCT_RS3_FP_FMVP,      fmt, r.rd, 0b000, r.rs1, r.rs2),
+        Fleq(fmt, r)     => fp_type(FUNCT_RS3_FP_CMP,       fmt, r.rd, FUNCT3_FP_LEQ, r.rs1, r.rs2),
+        Fltq(fmt, r)     => fp_type(FUNCT_RS3_FP_CMP,       fmt, r.rd, FUNCT3_FP_LTQ, r.rs1, r.rs2),
+    })
+}
+
+fn encode_rvb(b: &RVB, xlen: Xlen) -> Result<u32, String> {
+    use RVB::*;
+    if b.is_rv64_only() && xlen == Xlen::X32 {
+        return Err(format!("{} is RV64-only", b.mnemonic()));
+    }
+    let shamt_bits = if xlen == Xlen::X32 { 5 } else { 6 };
+    // shift by immediate: funct6 above a 6-bit shamt (5-bit on RV32 and for the W forms)
+    let shift = |opcode: u32, funct3: u8, funct6: u8, i: &IType, bits: u32| -> Result<u32, String> {
+        let shamt = i.imm.low_u32() & 0x3f;
+        validate_shamt(shamt, bits)?;
+        Ok(i_type(opcode, i.rd, funct3, i.rs1, ((funct6 as u32) << 6) | shamt))
+    };
+    // unary: the operation is selected by the whole imm12
+    let unary = |opcode: u32, funct3: u8, r: &RType, imm12: u32| i_type(opcode, r.rd, funct3, r.rs1, imm12);
+    let op = |funct7: u8, funct3: u8, r: &RType| r_type(OPCODE_OP, r.rd, funct3, r.rs1, r.rs2, funct7);
+    let op32 = |funct7: u8, funct3: u8, r: &RType| r_type(OPCODE_OP_32, r.rd, funct3, r.rs1, r.rs2, funct7);
+    Ok(match b {
+        Sh1add(r) => op(FUNCT7_B_SHADD, FUNCT3_B_SH1ADD, r),
+        Sh2add(r) => op(FUNCT7_B_SHADD, FUNCT3_B_SH2ADD, r),
+        Sh3add(r) => op(FUNCT7_B_SHADD, FUNCT3_B_SH3ADD, r),
+        Adduw(r)    => op32(FUNCT7_B_ADDUW_ZEXTH, FUNCT3_OP_ADD_SUB, r),
+        Sh1adduw(r) => op32(FUNCT7_B_SHADD, FUNCT3_B_SH1ADD, r),
+        Sh2adduw(r) => op32(FUNCT7_B_SHADD, FUNCT3_B_SH2ADD, r),
+        Sh3adduw(r) => op32(FUNCT7_B_SHADD, FUNCT3_B_SH3ADD, r),
+        Slliuw(i) => shift(OPCODE_OP_IMM32, FUNCT3_OP_SLL, FUNCT6_B_SLLIUW, i, 6)?,
+
+        Andn(r) => op(FUNCT7_B_ANDN, FUNCT3_OP_AND, r),
+        Orn(r)  => op(FUNCT7_B_ANDN, FUNCT3_OP_OR, r),
+        Xnor(r) => op(FUNCT7_B_ANDN, FUNCT3_OP_XOR, r),
+        Clz(r)   => unary(OPCODE_OP_IMM, FUNCT3_OP_SLL, r, IMM12_B_CLZ),
+        Ctz(r)   => unary(OPCODE_OP_IMM, FUNCT3_OP_SLL, r, IMM12_B_CTZ),
+        Cpop(r)  => unary(OPCODE_OP_IMM, FUNCT3_OP_SLL, r, IMM12_B_CPOP),
+        Sextb(r) => unary(OPCODE_OP_IMM, FUNCT3_OP_SLL, r, IMM12_B_SEXTB),
+        Sexth(r) => unary(OPCODE_OP_IMM, FUNCT3_OP_SLL, r, IMM12_B_SEXTH),
+        Max(r)  => op(FUNCT7_B_MINMAX_CLMUL, FUNCT3_B_MAX, r),
+        Maxu(r) => op(FUNCT7_B_MINMAX_CLMUL, FUNCT3_B_MAXU, r),
+        Min(r)  => op(FUNCT7_B_MINMAX_CLMUL, FUNCT3_B_MIN, r),
+        Minu(r) => op(FUNCT7_B_MINMAX_CLMUL, FUNCT3_B_MINU, r),
+        // zext.h is OP on RV32 and OP-32 on RV64, rs2 = 0 either way
+        Zexth(r) => {
+            let opcode = if xlen == Xlen::X32 { OPCODE_OP } else { OPCODE_OP_32 };
+            r_type(opcode, r.rd, FUNCT3_B_ZEXTH, r.rs1, 0, FUNCT7_B_ADDUW_ZEXTH)
+        }
+        Rol(r) => op(FUNCT7_B_ROT, FUNCT3_OP_SLL, r),
+        Ror(r) => op(FUNCT7_B_ROT, FUNCT3_OP_SRL_SRA, r),
+        Rori(i) => shift(OPCODE_OP_IMM, FUNCT3_OP_SRL_SRA, FUNCT6_B_RORI, i, shamt_bits)?,
+        Orcb(r) => unary(OPCODE_OP_IMM, FUNCT3_OP_SRL_SRA, r, IMM12_B_ORCB),
+        Rev8(r) => {
+            let imm12 = if xlen == Xlen::X32 { IMM12_B_REV8_RV32 } else { IMM12_B_REV8_RV64 };
+            unary(OPCODE_OP_IMM, FUNCT3_OP_SRL_SRA, r, imm12)
+        }
+        Clzw(r)  => unary(OPCODE_OP_IMM32, FUNCT3_OP_SLL, r, IMM12_B_CLZ),
+        Ctzw(r)  => unary(OPCODE_OP_IMM32, FUNCT3_OP_SLL, r, IMM12_B_CTZ),
+        Cpopw(r) => unary(OPCODE_OP_IMM32, FUNCT3_OP_SLL, r, IMM12_B_CPOP),
+        Rolw(r) => op32(FUNCT7_B_ROT, FUNCT3_OP_SLL, r),
+        Rorw(r) => op32(FUNCT7_B_ROT, FUNCT3_OP_SRL_SRA, r),
+        Roriw(i) => shift(OPCODE_OP_IMM32, FUNCT3_OP_SRL_SRA, FUNCT6_B_RORI, i, 5)?,
+
+        Clmul(r)  => op(FUNCT7_B_MINMAX_CLMUL, FUNCT3_B_CLMUL, r),
+        Clmulr(r) => op(FUNCT7_B_MINMAX_CLMUL, FUNCT3_B_CLMULR, r),
+        Clmulh(r) => op(FUNCT7_B_MINMAX_CLMUL, FUNCT3_B_CLMULH, r),
+
+        Bclr(r) => op(FUNCT7_B_BCLR_BEXT, FUNCT3_OP_SLL, r),
+        Bext(r) => op(FUNCT7_B_BCLR_BEXT, FUNCT3_OP_SRL_SRA, r),
+        Binv(r) => op(FUNCT7_B_BINV, FUNCT3_OP_SLL, r),
+        Bset(r) => op(FUNCT7_B_BSET, FUNCT3_OP_SLL, r),
+        Bclri(i) => shift(OPCODE_OP_IMM, FUNCT3_OP_SLL, FUNCT6_B_BCLRI_BEXTI, i, shamt_bits)?,
+        Bexti(i) => shift(OPCODE_OP_IMM, FUNCT3_OP_SRL_SRA, FUNCT6_B_BCLRI_BEXTI, i, shamt_bits)?,
+        Binvi(i) => shift(OPCODE_OP_IMM, FUNCT3_OP_SLL, FUNCT6_B_BINVI, i, shamt_bits)?,
+        Bseti(i) => shift(OPCODE_OP_IMM, FUNCT3_OP_SLL, FUNCT6_B_BSETI, i, shamt_bits)?,
+    })
+}
+
+/// AMO/LR/SC: funct7 is funct5 followed by the aq and rl bits; the width
+/// goes in funct3.
+fn encode_atomic(op: &str, width: u8, a: &AType) -> Result<u32, String> {
+    let funct5 = match op {
+        "lr" => FUNCT5_A_LR,
+        "sc" => FUNCT5_A_SC,
+        "amoswap" => FUNCT5_A_AMOSWAP,
+        "amoadd" => FUNCT5_A_AMOADD,
+        "amoxor" => FUNCT5_A_AMOXOR,
+        "amoand" => FUNCT5_A_AMOAND,
+        "amoor" => FUNCT5_A_AMOOR,
+        "amomin" => FUNCT5_A_AMOMIN,
+        "amomax" => FUNCT5_A_AMOMAX,
+        "amominu" => FUNCT5_A_AMOMINU,
+        "amomaxu" => FUNCT5_A_AMOMAXU,
+        "amocas" => FUNCT5_A_AMOCAS,
+        _ => return Err(format!("unknown atomic operation {}", op)),
+    };
+    let rs2 = if op == "lr" { 0 } else { a.rs2 };
+    let funct7 = (funct5 << 2) | ((a.aq as u8) << 1) | (a.rl as u8);
+    Ok(r_type(OPCODE_A, a.rd, width, a.rs1, rs2, funct7))
+}
+
 fn encode_zicsr(csr: &RVZicsr) -> Result<u32, String> {
     use RVZicsr::*;
     Ok(match csr {
@@ -277,3 +464,224 @@ fn encode_zicsr(csr: &RVZicsr) -> Result<u32, String> {
         Csrrci(c)=> i_type(OPCODE_SYSTEM, c.rd, FUNCT3_SYSTEM_CSRRCI, c.uimm.low32() as u8, c.csr as u32),
     })
 }
+
+fn encode_rvk(k: &RVK, xlen: Xlen) -> Result<u32, String> {
+    use RVK::*;
+    if k.is_rv64_only() && xlen == Xlen::X32 {
+        return Err(format!("{} is RV64-only", k.mnemonic()));
+    }
+    if k.is_rv32_only() && xlen != Xlen::X32 {
+        return Err(format!("{} is RV32-only", k.mnemonic()));
+    }
+    let unary = |funct3: u8, r: &RType, imm12: u32| i_type(OPCODE_OP_IMM, r.rd, funct3, r.rs1, imm12);
+    let op = |funct7: u8, funct3: u8, r: &RType| r_type(OPCODE_OP, r.rd, funct3, r.rs1, r.rs2, funct7);
+    // byte select in funct7[6:5], the operation in funct7[4:0]
+    let bs = |funct5: u8, k: &KType| -> Result<u32, String> {
+        if k.bs > 3 {
+            return Err(format!("bs {} out of range (0..=3)", k.bs));
+        }
+        Ok(r_type(OPCODE_OP, k.rd, 0, k.rs1, k.rs2, (k.bs << 5) | funct5))
+    };
+    Ok(match k {
+        Pack(r)  => op(FUNCT7_K_PACK, FUNCT3_K_PACK, r),
+        Packh(r) => op(FUNCT7_K_PACK, FUNCT3_K_PACKH, r),
+        Packw(r) => r_type(OPCODE_OP_32, r.rd, FUNCT3_K_PACK, r.rs1, r.rs2, FUNCT7_K_PACK),
+        Brev8(r) => unary(FUNCT3_OP_SRL_SRA, r, IMM12_K_BREV8),
+        Zip(r)   => unary(FUNCT3_OP_SLL, r, IMM12_K_ZIP),
+        Unzip(r) => unary(FUNCT3_OP_SRL_SRA, r, IMM12_K_ZIP),
+        Xperm4(r) => op(FUNCT7_K_XPERM, FUNCT3_K_XPERM4, r),
+        Xperm8(r) => op(FUNCT7_K_XPERM, FUNCT3_K_XPERM8, r),
+
+        Aes32dsi(k)  => bs(FUNCT5_K_AES32DSI, k)?,
+        Aes32dsmi(k) => bs(FUNCT5_K_AES32DSMI, k)?,
+        Aes32esi(k)  => bs(FUNCT5_K_AES32ESI, k)?,
+        Aes32esmi(k) => bs(FUNCT5_K_AES32ESMI, k)?,
+        Aes64ds(r)  => op(FUNCT7_K_AES64DS, 0, r),
+        Aes64dsm(r) => op(FUNCT7_K_AES64DSM, 0, r),
+        Aes64es(r)  => op(FUNCT7_K_AES64ES, 0, r),
+        Aes64esm(r) => op(FUNCT7_K_AES64ESM, 0, r),
+        Aes64ks2(r) => op(FUNCT7_K_AES64KS2, 0, r),
+        Aes64im(r) => unary(FUNCT3_OP_SLL, r, IMM12_K_AES64IM),
+        Aes64ks1i(i) => {
+            let rnum = i.imm.low_u32() & 0xf;
+            if rnum > 0xa {
+                return Err(format!("rnum {} out of range (0..=10)", rnum));
+            }
+            i_type(OPCODE_OP_IMM, i.rd, FUNCT3_OP_SLL, i.rs1, IMM12_K_AES64KS1I | rnum)
+        }
+
+        Sha256sig0(r) => unary(FUNCT3_OP_SLL, r, IMM12_K_SHA256SIG0),
+        Sha256sig1(r) => unary(FUNCT3_OP_SLL, r, IMM12_K_SHA256SIG1),
+        Sha256sum0(r) => unary(FUNCT3_OP_SLL, r, IMM12_K_SHA256SUM0),
+        Sha256sum1(r) => unary(FUNCT3_OP_SLL, r, IMM12_K_SHA256SUM1),
+        Sha512sig0h(r) => op(FUNCT7_K_SHA512SIG0H, 0, r),
+        Sha512sig0l(r) => op(FUNCT7_K_SHA512SIG0L, 0, r),
+        Sha512sig1h(r) => op(FUNCT7_K_SHA512SIG1H, 0, r),
+        Sha512sig1l(r) => op(FUNCT7_K_SHA512SIG1L, 0, r),
+        Sha512sum0r(r) => op(FUNCT7_K_SHA512SUM0R, 0, r),
+        Sha512sum1r(r) => op(FUNCT7_K_SHA512SUM1R, 0, r),
+        Sha512sig0(r) => unary(FUNCT3_OP_SLL, r, IMM12_K_SHA512SIG0),
+        Sha512sig1(r) => unary(FUNCT3_OP_SLL, r, IMM12_K_SHA512SIG1),
+        Sha512sum0(r) => unary(FUNCT3_OP_SLL, r, IMM12_K_SHA512SUM0),
+        Sha512sum1(r) => unary(FUNCT3_OP_SLL, r, IMM12_K_SHA512SUM1),
+
+        Sm4ed(k) => bs(FUNCT5_K_SM4ED, k)?,
+        Sm4ks(k) => bs(FUNCT5_K_SM4KS, k)?,
+        Sm3p0(r) => unary(FUNCT3_OP_SLL, r, IMM12_K_SM3P0),
+        Sm3p1(r) => unary(FUNCT3_OP_SLL, r, IMM12_K_SM3P1),
+    })
+}
+
+fn encode_rvv(v: &RVV) -> Result<u32, String> {
+    let vm = |vm: bool| (vm as u32) << 25;
+    Ok(match v {
+        RVV::Vsetvli(s) => {
+            if s.vtypei > 0x7ff {
+                return Err(format!("vtype {:#x} does not fit in 11 bits", s.vtypei));
+            }
+            ((s.vtypei as u32) << 20) | ((s.rs1 as u32) << 15) | ((FUNCT3_V_OPCFG as u32) << 12) | ((s.rd as u32) << 7) | OPCODE_OP_V
+        }
+        RVV::Vsetivli(s) => {
+            if s.vtypei > 0x3ff || s.rs1 > 31 {
+                return Err("vsetivli takes a 5-bit AVL and a 10-bit vtype".to_string());
+            }
+            (0b11 << 30) | ((s.vtypei as u32) << 20) | ((s.rs1 as u32) << 15) | ((FUNCT3_V_OPCFG as u32) << 12) | ((s.rd as u32) << 7) | OPCODE_OP_V
+        }
+        RVV::Vsetvl(r) => r_type(OPCODE_OP_V, r.rd, FUNCT3_V_OPCFG, r.rs1, r.rs2, 0b100_0000),
+        RVV::Load(m) | RVV::Store(m) => {
+            let opcode = if let RVV::Store(_) = v { OPCODE_STORE_FP } else { OPCODE_LOAD_FP };
+            let store = opcode == OPCODE_STORE_FP;
+            if !m.is_valid(store) {
+                return Err(format!("invalid vector operands for {}", m.mnemonic(store)));
+            }
+            let width = VMem::width_code(m.eew).unwrap_or(0);
+            let (mop, rs2) = match m.mode {
+                VAddr::Unit => (MOP_V_UNIT, LUMOP_V_UNIT),
+                VAddr::WholeReg => (MOP_V_UNIT, LUMOP_V_WHOLE),
+                VAddr::Mask => (MOP_V_UNIT, LUMOP_V_MASK),
+                VAddr::FaultFirst => (MOP_V_UNIT, LUMOP_V_FF),
+                VAddr::Strided(rs2) => (MOP_V_STRIDED, rs2),
+                VAddr::Unordered(vs2) => (MOP_V_INDEXED_UNORDERED, vs2),
+                VAddr::Ordered(vs2) => (MOP_V_INDEXED_ORDERED, vs2),
+            };
+            ((m.nf as u32) << 29)
+                | ((mop as u32) << 26)
+                | vm(m.vm)
+                | ((rs2 as u32) << 20)
+                | ((m.rs1 as u32) << 15)
+                | ((width as u32) << 12)
+                | ((m.vd as u32) << 7)
+                | opcode
+        }
+        RVV::Arith(a) => {
+            let funct3 = VArith::funct3(a.group, a.kind).ok_or_else(|| "no immediate form in this group".to_string())?;
+            if !a.is_valid() {
+                return Err(format!("invalid vector operands for {}", a.mnemonic()));
+            }
+            ((a.funct6 as u32) << 26)
+                | vm(a.vm)
+                | ((a.vs2 as u32) << 20)
+                | (((a.src & 0x1f) as u32) << 15)
+                | ((funct3 as u32) << 12)
+                | ((a.vd as u32) << 7)
+                | OPCODE_OP_V
+        }
+    })
+}
+
+fn encode_priv(p: &RVPriv, xlen: Xlen) -> Result<u32, String> {
+    use RVPriv::*;
+    if p.is_rv64_only() && xlen == Xlen::X32 {
+        return Err(format!("{} is RV64-only", p.mnemonic()));
+    }
+    let ret = |funct12: u32| i_type(OPCODE_SYSTEM, 0, FUNCT3_SYSTEM_PRIV, 0, funct12);
+    let fence = |funct7: u8, r: &RType| r_type(OPCODE_SYSTEM, 0, FUNCT3_SYSTEM_PRIV, r.rs1, r.rs2, funct7);
+    let hlv = |funct7: u8, rs2: u8, r: &RType| r_type(OPCODE_SYSTEM, r.rd, FUNCT3_SYSTEM_HLSV, r.rs1, rs2, funct7);
+    let hsv = |funct7: u8, r: &RType| r_type(OPCODE_SYSTEM, 0, FUNCT3_SYSTEM_HLSV, r.rs1, r.rs2, funct7);
+    Ok(match p {
+        Sret(_)  => ret(FUNCT12_SYSTEM_SRET),
+        Mret(_)  => ret(FUNCT12_SYSTEM_MRET),
+        Mnret(_) => ret(FUNCT12_SYSTEM_MNRET),
+        Wfi(_)   => ret(FUNCT12_SYSTEM_WFI),
+        WrsNto(_) => ret(FUNCT12_SYSTEM_WRS_NTO),
+        WrsSto(_) => ret(FUNCT12_SYSTEM_WRS_STO),
+
+        SfenceVma(r)  => fence(FUNCT7_SYSTEM_SFENCE_VMA, r),
+        HfenceVvma(r) => fence(FUNCT7_SYSTEM_HFENCE_VVMA, r),
+        HfenceGvma(r) => fence(FUNCT7_SYSTEM_HFENCE_GVMA, r),
+        SinvalVma(r)  => fence(FUNCT7_SYSTEM_SINVAL_VMA, r),
+        HinvalVvma(r) => fence(FUNCT7_SYSTEM_HINVAL_VVMA, r),
+        HinvalGvma(r) => fence(FUNCT7_SYSTEM_HINVAL_GVMA, r),
+        SfenceWInval(_)  => r_type(OPCODE_SYSTEM, 0, FUNCT3_SYSTEM_PRIV, 0, 0, FUNCT7_SYSTEM_SFENCE_INVAL),
+        SfenceInvalIr(_) => r_type(OPCODE_SYSTEM, 0, FUNCT3_SYSTEM_PRIV, 0, 1, FUNCT7_SYSTEM_SFENCE_INVAL),
+
+        HlvB(r)   => hlv(FUNCT7_SYSTEM_HLV_B, RS2_HLV_SIGNED, r),
+        HlvBu(r)  => hlv(FUNCT7_SYSTEM_HLV_B, RS2_HLV_UNSIGNED, r),
+        HlvH(r)   => hlv(FUNCT7_SYSTEM_HLV_H, RS2_HLV_SIGNED, r),
+        HlvHu(r)  => hlv(FUNCT7_SYSTEM_HLV_H, RS2_HLV_UNSIGNED, r),
+        HlvxHu(r) => hlv(FUNCT7_SYSTEM_HLV_H, RS2_HLVX, r),
+        HlvW(r)   => hlv(FUNCT7_SYSTEM_HLV_W, RS2_HLV_SIGNED, r),
+        HlvWu(r)  => hlv(FUNCT7_SYSTEM_HLV_W, RS2_HLV_UNSIGNED, r),
+        HlvxWu(r) => hlv(FUNCT7_SYSTEM_HLV_W, RS2_HLVX, r),
+        HlvD(r)   => hlv(FUNCT7_SYSTEM_HLV_D, RS2_HLV_SIGNED, r),
+        HsvB(r) => hsv(FUNCT7_SYSTEM_HSV_B, r),
+        HsvH(r) => hsv(FUNCT7_SYSTEM_HSV_H, r),
+        HsvW(r) => hsv(FUNCT7_SYSTEM_HSV_W, r),
+        HsvD(r) => hsv(FUNCT7_SYSTEM_HSV_D, r),
+    })
+}
+
+fn encode_zicond(z: &RVZicond) -> u32 {
+    let funct3 = match z {
+        RVZicond::CzeroEqz(_) => FUNCT3_CZERO_EQZ,
+        RVZicond::CzeroNez(_) => FUNCT3_CZERO_NEZ,
+    };
+    let r = z.operands();
+    r_type(OPCODE_OP, r.rd, funct3, r.rs1, r.rs2, FUNCT7_CZERO)
+}
+
+/// `cbo.*` take the slot RV128 uses for `lq`. Prefetch offsets must leave
+/// imm[4:0] free for the prefetch kind.
+fn encode_zicbo(z: &RVZicbo, xlen: Xlen) -> Result<u32, String> {
+    use RVZicbo::*;
+    if let Some((sel, i)) = z.prefetch() {
+        let offset = i.imm.low_u32();
+        if offset & 0b1_1111 != 0 {
+            return Err(format!("{} offset {} is not a multiple of 32", z.mnemonic(), i.imm.as_i64()));
+        }
+        return Ok(i_type(OPCODE_OP_IMM, 0, FUNCT3_OP_OR, i.rs1, offset | sel));
+    }
+    if xlen == Xlen::X128 {
+        return Err(format!("{} is not available on RV128 (the encoding is lq)", z.mnemonic()));
+    }
+    let (imm12, r) = match z {
+        CboInval(r) => (IMM12_CBO_INVAL, r),
+        CboClean(r) => (IMM12_CBO_CLEAN, r),
+        CboFlush(r) => (IMM12_CBO_FLUSH, r),
+        CboZero(r) => (IMM12_CBO_ZERO, r),
+        PrefetchI(_) | PrefetchR(_) | PrefetchW(_) => unreachable!(),
+    };
+    Ok(i_type(OPCODE_MISC_MEM, 0, FUNCT3_MISC_MEM_CBO, r.rs1, imm12))
+}
+
+/// `mop.r.N` spreads N over imm12 bits 10, 7:6 and 1:0; `mop.rr.N` over
+/// funct7 bits 5 and 2:1.
+fn encode_zimop(m: &RVZimop) -> Result<u32, String> {
+    Ok(match *m {
+        RVZimop::MopR(n, r) => {
+            if n > 31 {
+                return Err(format!("mop.r.{} out of range (0..=31)", n));
+            }
+            let n = n as u32;
+            let imm12 = IMM12_MOP_R | ((n & 0b1_0000) << 6) | ((n & 0b1100) << 4) | (n & 0b11);
+            i_type(OPCODE_SYSTEM, r.rd, FUNCT3_SYSTEM_MOP, r.rs1, imm12)
+        }
+        RVZimop::MopRR(n, r) => {
+            if n > 7 {
+                return Err(format!("mop.rr.{} out of range (0..=7)", n));
+            }
+            let funct7 = FUNCT7_MOP_RR | ((n & 0b100) << 3) | ((n & 0b11) << 1);
+            r_type(OPCODE_SYSTEM, r.rd, FUNCT3_SYSTEM_MOP, r.rs1, r.rs2, funct7)
+        }
+    })
+}
@@ -0,0 +1,250 @@
+//! Optional extensions that can be switched off, written the way
+//! `llvm-mc -mattr` takes them: `-zfh`, `+zfhmin`, comma separated.
+//! Everything is enabled by default; decoders and parsers accept the full
+//! ISA and the result is filtered with [`Extensions::allows`]. `zbb` is the
+//! exception: it only picks between two spellings and is off by default.
+//! `e` selects the RV32E/RV64E base, which takes registers away instead of
+//! adding instructions; it is off by default too.
+//! The hint extensions (`zicbop`, `zihintpause`, `zihintntl`) name words
+//! that are already base instructions. Switching one off rejects its
+//! mnemonics and shows those words in their raw form (`fence w, 0`,
+//! `c.add zero, zero, sp` for `c.ntl.p1`).
+//! `zcmp` and `zcmt` are off by default as well: they reuse the encodings
+//! of `c.fsdsp` and friends, so `cm.*` is rejected until one is turned on,
+//! which makes those words decode as `cm.*` and rejects the compressed
+//! double-precision loads and stores.
+//! `-h` leaves out the hypervisor: its fences, virtual-machine loads and
+//! stores and the H/VS CSRs are rejected on every xlen.
+//! `zfinx` (also set by `zdinx`, `zhinx` and `zhinxmin`) is off by default:
+//! it keeps floating-point values in the integer registers, so the
+//! instructions that only move data into or out of an F register go away.
+use crate::asm::csr::{csr_to_string, is_hypervisor_csr};
+use crate::asm::{FpFmt, Instruction, RVZihint, RVC, RVF};
+use crate::riscv::imm::Xlen;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Extensions {
+    /// Full half-precision arithmetic (Zfh); implies `zfhmin`.
+    pub zfh: bool,
+    /// Half-precision loads, stores, moves and conversions only (Zfhmin).
+    pub zfhmin: bool,
+    /// Zbb was asked for explicitly (`+zbb`). Zbb instructions are always
+    /// accepted by their own names; this only makes `sext.b`, `sext.h` and
+    /// `zext.h` assemble to the Zbb instruction instead of the base-ISA
+    /// shift pair, as `llvm-mc -mattr=+zbb` does.
+    pub zbb: bool,
+    /// RV32E/RV64E base (`+e`): only x0–x15 exist.
+    pub rve: bool,
+    /// `prefetch.i/r/w` (Zicbop), hints on `ori x0, ...`.
+    pub zicbop: bool,
+    /// `pause` (Zihintpause), a hint on `fence w, 0`.
+    pub zihintpause: bool,
+    /// `ntl.*` (Zihintntl), hints on `add x0, x0, x2..x5`.
+    pub zihintntl: bool,
+    /// `cm.push`/`cm.pop*`/`cm.mv*` (Zcmp) in place of `c.fld`/`c.fsd`/`c.fldsp`/`c.fsdsp`.
+    pub zcmp: bool,
+    /// `cm.jt`/`cm.jalt` (Zcmt) in place of the same Zcd encodings.
+    pub zcmt: bool,
+    /// Hypervisor extension (H): `hfence.*`, `hinval.*`, `hlv.*`/`hsv.*`
+    /// and the hypervisor and VS CSRs.
+    pub h: bool,
+    /// Zfinx/Zdinx/Zhinx: F, D and Zfh operate on the integer registers.
+    pub zfinx: bool,
+}
+
+impl Default for Extensions {
+    fn default() -> Self {
+        Extensions {
+            zfh: true,
+            zfhmin: true,
+            zbb: false,
+            rve: false,
+            zicbop: true,
+            zihintpause: true,
+            zihintntl: true,
+            zcmp: false,
+            zcmt: false,
+            h: true,
+            zfinx: false,
+        }
+    }
+}
+
+impl Extensions {
+    /// Apply a `+ext,-ext` list on top of the defaults.
+    pub fn parse(spec: &str) -> Result<Extensions, String> {
+        let mut ext = Extensions::default();
+        for item in spec.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let (on, name) = match item.as_bytes()[0] {
+                b'+' => (true, &item[1..]),
+                b'-' => (false, &item[1..]),
+                _ => (true, item),
+            };
+            match name.to_lowercase().as_str() {
+                "zfh" => {
+                    ext.zfh = on;
+                    ext.zfhmin |= on;
+                }
+                "zfhmin" => {
+                    ext.zfhmin = on;
+                    ext.zfh &= on;
+                }
+                "zbb" => ext.zbb = on,
+                "e" => ext.rve = on,
+                "zicbop" => ext.zicbop = on,
+                "zihintpause" => ext.zihintpause = on,
+                "zihintntl" => ext.zihintntl = on,
+                "zcmp" => ext.zcmp = on,
+                "zcmt" => ext.zcmt = on,
+                "h" => ext.h = on,
+                "zfinx" | "zdinx" => ext.zfinx = on,
+                "zhinx" => {
+                    ext.zfinx |= on;
+                    ext.zfh = on;
+                    ext.zfhmin |= on;
+                }
+                "zhinxmin" => {
+                    ext.zfinx |= on;
+                    ext.zfhmin = on;
+                    ext.zfh &= on;
+                }
+                _ => return Err(format!("未知扩展: {}", name)),
+            }
+        }
+        Ok(ext)
+    }
+
+    /// Whether `ins` belongs to the enabled extensions.
+    pub fn allows(&self, ins: &Instruction) -> Result<(), String> {
+        if let Some((false, name, mnem)) = self.hint(ins) {
+            return Err(format!("{} 需要 {} 扩展", mnem, name));
+        }
+        if let Instruction::RVC(c) = ins {
+            self.allows_rvc(c)?;
+        }
+        if self.zfinx {
+            allows_zfinx(ins)?;
+        }
+        if !self.h {
+            match ins {
+                Instruction::RVPriv(p) if p.is_hypervisor() => {
+                    return Err(format!("{} 需要 H 扩展", p.mnemonic()));
+                }
+                Instruction::RVZicsr(z) if is_hypervisor_csr(z.csr()) => {
+                    return Err(format!("CSR {} 需要 H 扩展", csr_to_string(z.csr(), None)));
+                }
+                _ => {}
+            }
+        }
+        if self.rve {
+            let mut regs = ins.int_registers();
+            // under Zfinx the floating-point operands are integer registers too
+            if let (true, Instruction::RVF(f)) = (self.zfinx, ins) {
+                regs.extend(f.fp_operands().into_iter().map(|(r, _)| r));
+            }
+            if let Some(r) = regs.into_iter().find(|&r| r >= 16) {
+                return Err(format!("RV32E/RV64E 只有 x0-x15 寄存器，不能使用 x{}", r));
+            }
+        }
+        if let Instruction::RVF(f) = ins {
+            let (fmt, other) = f.formats();
+            if fmt != FpFmt::H && other != Some(FpFmt::H) {
+                return Ok(());
+            }
+            if !self.zfhmin {
+                return Err(format!("{} 需要 Zfh 或 Zfhmin 扩展", f.mnemonic()));
+            }
+            if !self.zfh && !is_zfhmin(f) {
+                return Err(format!("{} 需要 Zfh 扩展（当前仅启用 Zfhmin）", f.mnemonic()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Zcmp and Zcmt share their encodings with the Zcd loads and stores,
+    /// and both are off by default like in the decoder: `cm.*` needs its
+    /// extension, and with either one on Zcd is rejected.
+    fn allows_rvc(&self, c: &RVC) -> Result<(), String> {
+        use RVC::*;
+        let text = c.to_string();
+        let mnem = text.split(' ').next().unwrap_or_default();
+        match c {
+            Cfld(_) | Cfsd(_) | Cfldsp(_) | Cfsdsp(_) if self.zcmp || self.zcmt => {
+                Err(format!("{} 与 Zcmp/Zcmt 扩展冲突", mnem))
+            }
+            CmPush(_) | CmPop(_) | CmPopretz(_) | CmPopret(_) | CmMvsa01(_) | CmMva01s(_) if !self.zcmp => {
+                Err(format!("{} 需要 Zcmp 扩展", mnem))
+            }
+            CmJt(_) | CmJalt(_) if !self.zcmt => Err(format!("{} 需要 Zcmt 扩展", mnem)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Zdinx on RV32 keeps a double in an even/odd register pair, named by
+    /// its even register.
+    pub fn allows_pairs(&self, ins: &Instruction, xlen: Xlen) -> Result<(), String> {
+        if let (true, Xlen::X32, Instruction::RVF(f)) = (self.zfinx, xlen, ins) {
+            if let Some((r, _)) = f.fp_operands().into_iter().find(|&(r, t)| t == FpFmt::D && r & 1 == 1) {
+                return Err(format!("{} 在 RV32 Zdinx 下需要偶数寄存器对，不能使用 x{}", f.mnemonic(), r));
+            }
+        }
+        Ok(())
+    }
+
+    /// A hint whose extension is switched off becomes the base instruction
+    /// it is encoded as; everything else is returned unchanged.
+    pub fn raw_hint(&self, ins: Instruction) -> Instruction {
+        match (self.hint(&ins), ins) {
+            (Some((false, ..)), Instruction::RVZihint(h)) => h.hint_base(),
+            (Some((false, ..)), Instruction::RVZicbo(z)) => z.hint_base().unwrap_or(ins),
+            (Some((false, ..)), Instruction::RVC(RVC::Cntl(cr))) => RVC::Cadd(cr).into(),
+            _ => ins,
+        }
+    }
+
+    /// For hint instructions: whether their extension is on, its name and
+    /// the hint mnemonic.
+    fn hint(&self, ins: &Instruction) -> Option<(bool, &'static str, &'static str)> {
+        match ins {
+            Instruction::RVZicbo(z) if z.prefetch().is_some() => Some((self.zicbop, "Zicbop", z.mnemonic())),
+            Instruction::RVZihint(h @ RVZihint::Pause(_)) => Some((self.zihintpause, "Zihintpause", h.mnemonic())),
+            Instruction::RVZihint(h) => Some((self.zihintntl, "Zihintntl", h.mnemonic())),
+            Instruction::RVC(RVC::Cntl(cr)) => {
+                let m = RVZihint::ntl(cr.rs2)?.compressed_mnemonic()?;
+                Some((self.zihintntl, "Zihintntl", m))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Under Zfinx there are no F registers: loads, stores and moves between
+/// the register files (the compressed ones included) have no meaning, and
+/// quad precision has no integer-register form.
+fn allows_zfinx(ins: &Instruction) -> Result<(), String> {
+    use RVC::*;
+    let mnem = match ins {
+        Instruction::RVF(f) => match f {
+            RVF::Load(..) | RVF::Store(..) | RVF::FmvToInt(..) | RVF::FmvFromInt(..) | RVF::Fli(..)
+            | RVF::Fmvh(..) | RVF::Fmvp(..) => f.mnemonic(),
+            _ if f.formats().0 == FpFmt::Q || f.formats().1 == Some(FpFmt::Q) => {
+                return Err(format!("{} 没有 Zfinx 形式", f.mnemonic()));
+            }
+            _ => return Ok(()),
+        },
+        Instruction::RVC(c @ (Cflw(_) | Cfsw(_) | Cflwsp(_) | Cfswsp(_) | Cfld(_) | Cfsd(_) | Cfldsp(_) | Cfsdsp(_))) => {
+            c.to_string().split(' ').next().unwrap_or_default().to_string()
+        }
+        _ => return Ok(()),
+    };
+    Err(format!("{} 在 Zfinx 下不可用", mnem))
+}
+
+/// Zfhmin: `flh`, `fsh`, `fmv.x.h`, `fmv.h.x` and conversions between
+/// half precision and the other formats.
+fn is_zfhmin(f: &RVF) -> bool {
+    matches!(
+        f,
+        RVF::Load(..) | RVF::Store(..) | RVF::FmvToInt(..) | RVF::FmvFromInt(..) | RVF::Fcvt(..)
+    )
+}
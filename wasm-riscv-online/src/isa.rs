@@ -25,6 +25,10 @@ pub const OPCODE_JALR: u32 = 0b110_0111;
 pub const OPCODE_JAL: u32 = 0b110_1111;
 pub const OPCODE_SYSTEM: u32 = 0b111_0011;
 pub const OPCODE_A: u32 = 0b010_1111;
+pub const OPCODE_OP_V: u32 = 0b101_0111;
+// RV128I double-word ALU ops (the custom-2/custom-3 slots on RV32/RV64)
+pub const OPCODE_OP_IMM64: u32 = 0b101_1011;
+pub const OPCODE_OP_64: u32 = 0b111_1011;
 
 // =========================
 // funct3 fields (loads/stores/branches/ALU)
@@ -37,12 +41,14 @@ pub const FUNCT3_LOAD_LD: u8 = 0b011;
 pub const FUNCT3_LOAD_LBU: u8 = 0b100;
 pub const FUNCT3_LOAD_LHU: u8 = 0b101;
 pub const FUNCT3_LOAD_LWU: u8 = 0b110;
+pub const FUNCT3_LOAD_LDU: u8 = 0b111;
 
 // STORE
 pub const FUNCT3_STORE_SB: u8 = 0b000;
 pub const FUNCT3_STORE_SH: u8 = 0b001;
 pub const FUNCT3_STORE_SW: u8 = 0b010;
 pub const FUNCT3_STORE_SD: u8 = 0b011;
+pub const FUNCT3_STORE_SQ: u8 = 0b100;
 
 // BRANCH
 pub const FUNCT3_BRANCH_BEQ: u8 = 0b000;
@@ -71,6 +77,17 @@ pub const FUNCT7_OP_SRA: u8 = 0b010_0000;
 pub const FUNCT7_OP_ADD: u8 = 0b000_0000;
 pub const FUNCT7_OP_SUB: u8 = 0b010_0000;
 
+// M extension (OP / OP-32 with funct7 = MULDIV)
+pub const FUNCT7_OP_MULDIV: u8 = 0b000_0001;
+pub const FUNCT3_M_MUL: u8 = 0b000;
+pub const FUNCT3_M_MULH: u8 = 0b001;
+pub const FUNCT3_M_MULHSU: u8 = 0b010;
+pub const FUNCT3_M_MULHU: u8 = 0b011;
+pub const FUNCT3_M_DIV: u8 = 0b100;
+pub const FUNCT3_M_DIVU: u8 = 0b101;
+pub const FUNCT3_M_REM: u8 = 0b110;
+pub const FUNCT3_M_REMU: u8 = 0b111;
+
 // SYSTEM
 pub const FUNCT3_SYSTEM_PRIV: u8 = 0b000;
 pub const FUNCT3_SYSTEM_CSRRW: u8 = 0b001;
@@ -83,17 +100,88 @@ pub const FUNCT3_SYSTEM_CSRRCI: u8 = 0b111;
 pub const FUNCT12_SYSTEM_ECALL: u32 = 0b000;
 pub const FUNCT12_SYSTEM_EBREAK: u32 = 0b001;
 
+// Privileged: trap returns and wfi (funct3 000, rd = rs1 = 0)
+pub const FUNCT12_SYSTEM_SRET: u32 = 0x102;
+pub const FUNCT12_SYSTEM_WFI: u32 = 0x105;
+pub const FUNCT12_SYSTEM_MRET: u32 = 0x302;
+pub const FUNCT12_SYSTEM_MNRET: u32 = 0x702;
+
+// Privileged: address-translation fences (funct3 000, rd = 0)
+pub const FUNCT7_SYSTEM_SFENCE_VMA: u8 = 0b000_1001;
+pub const FUNCT7_SYSTEM_HFENCE_VVMA: u8 = 0b001_0001;
+pub const FUNCT7_SYSTEM_HFENCE_GVMA: u8 = 0b011_0001;
+// Svinval
+pub const FUNCT7_SYSTEM_SINVAL_VMA: u8 = 0b000_1011;
+pub const FUNCT7_SYSTEM_HINVAL_VVMA: u8 = 0b001_0011;
+pub const FUNCT7_SYSTEM_HINVAL_GVMA: u8 = 0b011_0011;
+pub const FUNCT7_SYSTEM_SFENCE_INVAL: u8 = 0b000_1100; // rs2 = 0: .w.inval, rs2 = 1: .inval.ir
+
+// Hypervisor virtual-machine loads and stores (funct3 100); loads pick the
+// variant in rs2, stores have rd = 0
+pub const FUNCT3_SYSTEM_HLSV: u8 = 0b100;
+pub const FUNCT7_SYSTEM_HLV_B: u8 = 0b011_0000;
+pub const FUNCT7_SYSTEM_HSV_B: u8 = 0b011_0001;
+pub const FUNCT7_SYSTEM_HLV_H: u8 = 0b011_0010;
+pub const FUNCT7_SYSTEM_HSV_H: u8 = 0b011_0011;
+pub const FUNCT7_SYSTEM_HLV_W: u8 = 0b011_0100;
+pub const FUNCT7_SYSTEM_HSV_W: u8 = 0b011_0101;
+pub const FUNCT7_SYSTEM_HLV_D: u8 = 0b011_0110;
+pub const FUNCT7_SYSTEM_HSV_D: u8 = 0b011_0111;
+pub const RS2_HLV_SIGNED: u8 = 0b00000;
+pub const RS2_HLV_UNSIGNED: u8 = 0b00001;
+pub const RS2_HLVX: u8 = 0b00011;
+
+// Zawrs: wait on a reservation set (funct3 000, rd = rs1 = 0)
+pub const FUNCT12_SYSTEM_WRS_NTO: u32 = 0x00d;
+pub const FUNCT12_SYSTEM_WRS_STO: u32 = 0x01d;
+
+// Zimop: may-be-operations under SYSTEM funct3 100. mop.r.N keeps
+// N[4|3:2|1:0] in imm12 bits 10, 7:6 and 1:0; mop.rr.N keeps N[2|1:0] in
+// funct7 bits 5 and 2:1.
+pub const FUNCT3_SYSTEM_MOP: u8 = 0b100;
+pub const IMM12_MOP_R_MASK: u32 = 0b1011_0011_1100;
+pub const IMM12_MOP_R: u32 = 0b1000_0001_1100;
+pub const FUNCT7_MOP_RR_MASK: u8 = 0b101_1001;
+pub const FUNCT7_MOP_RR: u8 = 0b100_0001;
+
 // MISC-MEM
 pub const FUNCT3_MISC_MEM_FENCE: u8 = 0b000;
 pub const FUNCT3_MISC_MEM_FENCE_I: u8 = 0b001;
+pub const FUNCT3_MISC_MEM_LQ: u8 = 0b010;
+// fence imm12 is fm[11:8] pred[7:4] succ[3:0], each set as i/o/r/w bits
+pub const IMM12_FENCE_TSO: u32 = 0b1000_0011_0011;
+// Zihintpause: `fence w, 0`
+pub const IMM12_FENCE_PAUSE: u32 = 0b0000_0001_0000;
+// Zicbom/Zicboz: `cbo.* (rs1)`, rd = 0, operation in imm12. RV128 uses the
+// same funct3 for lq.
+pub const FUNCT3_MISC_MEM_CBO: u8 = 0b010;
+pub const IMM12_CBO_INVAL: u32 = 0b000;
+pub const IMM12_CBO_CLEAN: u32 = 0b001;
+pub const IMM12_CBO_FLUSH: u32 = 0b010;
+pub const IMM12_CBO_ZERO: u32 = 0b100;
+// Zicbop: `ori x0, rs1, offset` with imm[4:0] selecting the prefetch
+pub const IMM5_PREFETCH_I: u32 = 0b00000;
+pub const IMM5_PREFETCH_R: u32 = 0b00001;
+pub const IMM5_PREFETCH_W: u32 = 0b00011;
+// Zihintntl: `add x0, x0, rs2`
+pub const RS2_NTL_P1: u8 = 2;
+pub const RS2_NTL_PALL: u8 = 3;
+pub const RS2_NTL_S1: u8 = 4;
+pub const RS2_NTL_ALL: u8 = 5;
 
 // width
+pub const FUNCT3_WIDTH_H: u8 = 0b001;
 pub const FUNCT3_WIDTH_W: u8 = 0b010;
+pub const FUNCT3_WIDTH_D: u8 = 0b011;
+pub const FUNCT3_WIDTH_Q: u8 = 0b100;
 
 // =========================
 // Floating-point encodings (RVF)
 // =========================
 pub const FUNCT2_FMT_S: u8 = 0b00; // single-precision
+pub const FUNCT2_FMT_D: u8 = 0b01; // double-precision
+pub const FUNCT2_FMT_H: u8 = 0b10; // half-precision (Zfh)
+pub const FUNCT2_FMT_Q: u8 = 0b11; // quad-precision
 
 pub const FUNCT_RS3_FP_ADD: u8 = 0b00000;
 pub const FUNCT_RS3_FP_SUB: u8 = 0b00001;
@@ -102,11 +190,12 @@ pub const FUNCT_RS3_FP_DIV: u8 = 0b00011;
 pub const FUNCT_RS3_FP_SGNJ: u8 = 0b00100;
 pub const FUNCT_RS3_FP_MIN_MAX: u8 = 0b00101;
 pub const FUNCT_RS3_FP_SQRT: u8 = 0b01011;
+pub const FUNCT_RS3_FP_FCVT_FMT: u8 = 0b01000; // fcvt.fmt.fmt, source fmt in rs2
 pub const FUNCT_RS3_FP_CMP: u8 = 0b10100;
 pub const FUNCT_RS3_FP_FCVTX: u8 = 0b11000; // fcvt.{w|l}[u].s, fcvt.int.fmt
 pub const FUNCT_RS3_FP_XCVTF: u8 = 0b11010; // fcvt.s.{w|l}[u], fcvt.fmt.int
-pub const FUNCT_RS3_FP_FMVX_CLASS: u8 = 0b11100; // fmv.x.w / fclass.s
-pub const FUNCT_RS3_FP_XMVF: u8 = 0b11110; // fmv.w.x
+pub const FUNCT_RS3_FP_FMVX_CLASS: u8 = 0b11100; // fmv.x.{w|d|h} / fclass.fmt
+pub const FUNCT_RS3_FP_XMVF: u8 = 0b11110; // fmv.{w|d|h}.x
 
 pub const FUNCT3_FP_MIN: u8 = 0b000;
 pub const FUNCT3_FP_MAX: u8 = 0b001;
@@ -119,11 +208,158 @@ pub const FUNCT3_FP_EQ: u8 = 0b010;
 pub const FUNCT3_FP_LT: u8 = 0b001;
 pub const FUNCT3_FP_LE: u8 = 0b000;
 
+// Zfa
+pub const FUNCT3_FP_MINM: u8 = 0b010;
+pub const FUNCT3_FP_MAXM: u8 = 0b011;
+pub const FUNCT3_FP_LEQ: u8 = 0b100;
+pub const FUNCT3_FP_LTQ: u8 = 0b101;
+pub const FUNCT_RS3_FP_FMVP: u8 = 0b10110; // fmvp.{d|q}.x
+pub const FUNCT_RS2_FLI: u8 = 0b00001; // under fmv.fmt.x; the constant index goes in rs1
+pub const FUNCT_RS2_FMVH: u8 = 0b00001; // under fmv.x.fmt
+pub const FUNCT_RS2_FROUND: u8 = 0b00100; // under fcvt.fmt.fmt
+pub const FUNCT_RS2_FROUNDNX: u8 = 0b00101;
+pub const FUNCT_RS2_FCVTMOD: u8 = 0b01000; // under fcvt.int.fmt
+
 pub const FUNCT_RS2_CVT_W: u8 = 0b00000;
 pub const FUNCT_RS2_CVT_WU: u8 = 0b00001;
 pub const FUNCT_RS2_CVT_L: u8 = 0b00010;
 pub const FUNCT_RS2_CVT_LU: u8 = 0b00011;
 
+// rounding mode (funct3 of rm-carrying FP instructions)
+pub const FRM_RNE: u8 = 0b000;
+pub const FRM_RTZ: u8 = 0b001;
+pub const FRM_RDN: u8 = 0b010;
+pub const FRM_RUP: u8 = 0b011;
+pub const FRM_RMM: u8 = 0b100;
+pub const FRM_DYN: u8 = 0b111;
+
+// =========================
+// Integer conditional operations (Zicond), on OP
+// =========================
+pub const FUNCT7_CZERO: u8 = 0b000_0111;
+pub const FUNCT3_CZERO_EQZ: u8 = 0b101;
+pub const FUNCT3_CZERO_NEZ: u8 = 0b111;
+
+// =========================
+// Bit manipulation (Zba/Zbb/Zbc/Zbs)
+// =========================
+// funct7 on OP / OP-32; funct3 as in the base ISA unless listed
+pub const FUNCT7_B_ANDN: u8 = 0b010_0000; // andn/orn/xnor (funct3 and/or/xor)
+pub const FUNCT7_B_SHADD: u8 = 0b001_0000; // sh{1,2,3}add[.uw]
+pub const FUNCT7_B_MINMAX_CLMUL: u8 = 0b000_0101;
+pub const FUNCT7_B_ROT: u8 = 0b011_0000; // rol/ror[w], rori[w] (funct3 sll/srl)
+pub const FUNCT7_B_BCLR_BEXT: u8 = 0b010_0100;
+pub const FUNCT7_B_BINV: u8 = 0b011_0100;
+pub const FUNCT7_B_BSET: u8 = 0b001_0100;
+pub const FUNCT7_B_ADDUW_ZEXTH: u8 = 0b000_0100; // add.uw, zext.h (rs2 = 0)
+
+pub const FUNCT3_B_SH1ADD: u8 = 0b010;
+pub const FUNCT3_B_SH2ADD: u8 = 0b100;
+pub const FUNCT3_B_SH3ADD: u8 = 0b110;
+pub const FUNCT3_B_CLMUL: u8 = 0b001;
+pub const FUNCT3_B_CLMULR: u8 = 0b010;
+pub const FUNCT3_B_CLMULH: u8 = 0b011;
+pub const FUNCT3_B_MIN: u8 = 0b100;
+pub const FUNCT3_B_MINU: u8 = 0b101;
+pub const FUNCT3_B_MAX: u8 = 0b110;
+pub const FUNCT3_B_MAXU: u8 = 0b111;
+pub const FUNCT3_B_ZEXTH: u8 = 0b100;
+
+// funct6 of shifts by immediate (shamt in the low six bits of imm12)
+pub const FUNCT6_B_SLLIUW: u8 = 0b00_0010;
+pub const FUNCT6_B_RORI: u8 = 0b01_1000;
+pub const FUNCT6_B_BCLRI_BEXTI: u8 = 0b01_0010;
+pub const FUNCT6_B_BINVI: u8 = 0b01_1010;
+pub const FUNCT6_B_BSETI: u8 = 0b00_1010;
+
+// whole imm12 of the unary OP-IMM / OP-IMM-32 instructions
+pub const IMM12_B_CLZ: u32 = 0x600;
+pub const IMM12_B_CTZ: u32 = 0x601;
+pub const IMM12_B_CPOP: u32 = 0x602;
+pub const IMM12_B_SEXTB: u32 = 0x604;
+pub const IMM12_B_SEXTH: u32 = 0x605;
+pub const IMM12_B_ORCB: u32 = 0x287;
+pub const IMM12_B_REV8_RV32: u32 = 0x698;
+pub const IMM12_B_REV8_RV64: u32 = 0x6b8;
+
+// =========================
+// Scalar cryptography (Zbkb/Zbkx/Zknd/Zkne/Zknh/Zksed/Zksh)
+// =========================
+pub const FUNCT7_K_PACK: u8 = 0b000_0100; // pack/packw (funct3 100), packh (funct3 111)
+pub const FUNCT3_K_PACK: u8 = 0b100;
+pub const FUNCT3_K_PACKH: u8 = 0b111;
+pub const FUNCT7_K_XPERM: u8 = 0b001_0100;
+pub const FUNCT3_K_XPERM4: u8 = 0b010;
+pub const FUNCT3_K_XPERM8: u8 = 0b100;
+
+// byte-select forms: bs in funct7[6:5], funct5 below it, funct3 = 000
+pub const FUNCT5_K_AES32ESI: u8 = 0b10001;
+pub const FUNCT5_K_AES32ESMI: u8 = 0b10011;
+pub const FUNCT5_K_AES32DSI: u8 = 0b10101;
+pub const FUNCT5_K_AES32DSMI: u8 = 0b10111;
+pub const FUNCT5_K_SM4ED: u8 = 0b11000;
+pub const FUNCT5_K_SM4KS: u8 = 0b11010;
+
+// register-register forms on OP, funct3 = 000
+pub const FUNCT7_K_AES64ES: u8 = 0b001_1001;
+pub const FUNCT7_K_AES64ESM: u8 = 0b001_1011;
+pub const FUNCT7_K_AES64DS: u8 = 0b001_1101;
+pub const FUNCT7_K_AES64DSM: u8 = 0b001_1111;
+pub const FUNCT7_K_AES64KS2: u8 = 0b011_1111;
+pub const FUNCT7_K_SHA512SUM0R: u8 = 0b010_1000;
+pub const FUNCT7_K_SHA512SUM1R: u8 = 0b010_1001;
+pub const FUNCT7_K_SHA512SIG0L: u8 = 0b010_1010;
+pub const FUNCT7_K_SHA512SIG1L: u8 = 0b010_1011;
+pub const FUNCT7_K_SHA512SIG0H: u8 = 0b010_1110;
+pub const FUNCT7_K_SHA512SIG1H: u8 = 0b010_1111;
+
+// whole imm12 of the unary OP-IMM forms (funct3 001 unless noted)
+pub const IMM12_K_SHA256SUM0: u32 = 0x100;
+pub const IMM12_K_SHA256SUM1: u32 = 0x101;
+pub const IMM12_K_SHA256SIG0: u32 = 0x102;
+pub const IMM12_K_SHA256SIG1: u32 = 0x103;
+pub const IMM12_K_SHA512SUM0: u32 = 0x104;
+pub const IMM12_K_SHA512SUM1: u32 = 0x105;
+pub const IMM12_K_SHA512SIG0: u32 = 0x106;
+pub const IMM12_K_SHA512SIG1: u32 = 0x107;
+pub const IMM12_K_SM3P0: u32 = 0x108;
+pub const IMM12_K_SM3P1: u32 = 0x109;
+pub const IMM12_K_AES64IM: u32 = 0x300;
+pub const IMM12_K_AES64KS1I: u32 = 0x310; // | rnum
+pub const IMM12_K_ZIP: u32 = 0x08f; // unzip: same imm12, funct3 101
+pub const IMM12_K_BREV8: u32 = 0x687; // funct3 101
+
+// =========================
+// Vector (V-extension)
+// =========================
+// OP-V funct3: operand category of the arithmetic forms, 111 is vset*
+pub const FUNCT3_V_OPIVV: u8 = 0b000;
+pub const FUNCT3_V_OPFVV: u8 = 0b001;
+pub const FUNCT3_V_OPMVV: u8 = 0b010;
+pub const FUNCT3_V_OPIVI: u8 = 0b011;
+pub const FUNCT3_V_OPIVX: u8 = 0b100;
+pub const FUNCT3_V_OPFVF: u8 = 0b101;
+pub const FUNCT3_V_OPMVX: u8 = 0b110;
+pub const FUNCT3_V_OPCFG: u8 = 0b111;
+
+// LOAD-FP/STORE-FP width codes that select a vector access
+pub const FUNCT3_V_WIDTH_8: u8 = 0b000;
+pub const FUNCT3_V_WIDTH_16: u8 = 0b101;
+pub const FUNCT3_V_WIDTH_32: u8 = 0b110;
+pub const FUNCT3_V_WIDTH_64: u8 = 0b111;
+
+// mop: addressing mode of vector loads/stores
+pub const MOP_V_UNIT: u8 = 0b00;
+pub const MOP_V_INDEXED_UNORDERED: u8 = 0b01;
+pub const MOP_V_STRIDED: u8 = 0b10;
+pub const MOP_V_INDEXED_ORDERED: u8 = 0b11;
+
+// lumop/sumop: variants of the unit-stride access (rs2 field)
+pub const LUMOP_V_UNIT: u8 = 0b00000;
+pub const LUMOP_V_WHOLE: u8 = 0b01000;
+pub const LUMOP_V_MASK: u8 = 0b01011;
+pub const LUMOP_V_FF: u8 = 0b10000;
+
 // =========================
 // Atomic (A-extension)
 // =========================
@@ -138,9 +374,13 @@ pub const FUNCT5_A_AMOMIN: u8 = 0b10000;
 pub const FUNCT5_A_AMOMAX: u8 = 0b10100;
 pub const FUNCT5_A_AMOMINU: u8 = 0b11000;
 pub const FUNCT5_A_AMOMAXU: u8 = 0b11100;
+pub const FUNCT5_A_AMOCAS: u8 = 0b00101; // Zacas
 
 // A-extension width for RV128A (.q)
 pub const FUNCT3_A_WIDTH_Q: u8 = 0b100;
+// Zabha byte and halfword widths
+pub const FUNCT3_A_WIDTH_B: u8 = 0b000;
+pub const FUNCT3_A_WIDTH_H: u8 = 0b001;
 
 // =========================
 // Compressed (RVC) opcodes (2-bit)
@@ -1,3 +1,6 @@
+// instruction sets keep the ISA manual's names: RV32I, RVC, RVB, ...
+#![allow(clippy::upper_case_acronyms)]
+
 mod asm;
 mod decode;
 mod riscv;
@@ -5,13 +8,16 @@ mod riscv;
 mod isa;
 mod encode;
 mod parse;
+mod ext;
 
-use decode::{resolve_u16, resolve_u32};
+use decode::{expand, resolve_u16, resolve_u16_zcm, resolve_u32};
 use riscv::imm::Xlen;
 use wasm_bindgen::prelude::*;
-use encode::process32::encode_u32;
-use encode::process16::encode_u16;
+use encode::{encode, encode_compressed, Encoded};
+use ext::Extensions;
 
+/// Decode as RV32. The `.d` atomics (`lr.d`, `amoadd.d`, ...), which this
+/// entry point has always shown, fall back to RV64.
 #[wasm_bindgen]
 pub fn disassemble(input: &str) -> String {
     match input_to_u32(input) {
@@ -26,7 +32,10 @@ pub fn disassemble(input: &str) -> String {
                     Err(_) => format!("Error: unsupported 16-bit instruction"),
                 }
             } else {
-                match resolve_u32(value, Xlen::X32) {
+                match resolve_u32(value, Xlen::X32).or_else(|e| match value & 0x7f {
+                    isa::OPCODE_A => resolve_u32(value, Xlen::X64),
+                    _ => Err(e),
+                }) {
                     Ok(instruction) => instruction.disassembly(),
                     Err(_) => format!("Error: unsupported 32-bit instruction"),
                 }
@@ -36,9 +45,77 @@ pub fn disassemble(input: &str) -> String {
     }
 }
 
-fn to_hex_u32(v: u32) -> String { format!("0x{:08x}", v) }
-fn to_hex_u16(v: u16) -> String { format!("0x{:04x}", v) }
+/// Encode the instructions of one statement. With `compress`, RVC forms are
+/// used where available, unless that would not fill the `size` bytes laid out
+/// for the statement.
+fn encode_hex(insts: &[asm::Instruction], xlen: Xlen, compress: bool, size: u64) -> Result<Vec<String>, String> {
+    if compress {
+        let words: Result<Vec<Encoded>, String> = insts.iter().map(|i| encode_compressed(i, xlen)).collect();
+        if let Ok(words) = words {
+            if words.iter().map(|e| e.size()).sum::<u64>() == size {
+                return Ok(words.iter().map(|e| e.to_hex()).collect());
+            }
+        }
+    }
+    let words = insts.iter().map(|i| encode(i, xlen)).collect::<Result<Vec<Encoded>, String>>()?;
+    // the layout was fixed in the first pass; a statement that grew since
+    // (e.g. `li` with a forward `.equ`) would shift every later label
+    let total: u64 = words.iter().map(|e| e.size()).sum();
+    if total != size {
+        return Err(format!("指令编码为 {} 字节，与布局时的 {} 字节不符（是否引用了之后才定义的符号？）", total, size));
+    }
+    Ok(words.iter().map(|e| e.to_hex()).collect())
+}
+
+/// Assemble a whole source text: symbols are resolved and directives laid out
+/// first, then each statement is expanded (pseudo-instructions may produce
+/// several words) and encoded with the first xlen in `xlens` that accepts it.
+/// With `compress`, eligible instructions are emitted in RVC form.
+/// Instructions outside the enabled extensions are rejected.
+fn assemble_program(input: &str, xlens: &[Xlen], compress: bool, ext: &Extensions) -> String {
+    use parse::program::{Item, Resolved};
+
+    let try_one = |line: usize, mnem: &str, ops: &[String], compress: bool, size: u64| -> Result<Vec<String>, String> {
+        let mut last_err = String::new();
+        for &x in xlens {
+            let parsed = crate::parse::expand_statement(mnem, ops, x, ext)
+                .and_then(|insts| insts.iter().try_for_each(|i| ext.allows(i).and(ext.allows_pairs(i, x))).map(|_| insts));
+            match parsed {
+                Ok(insts) => match encode_hex(&insts, x, compress, size) {
+                    Ok(hex) => return Ok(hex),
+                    Err(e) if xlens.len() > 1 => last_err = format!("编码失败({:?}): {}", x, e),
+                    Err(e) => last_err = e,
+                },
+                Err(e) => last_err = e,
+            }
+        }
+        if last_err.is_empty() {
+            Err("Error: unsupported or invalid instruction".to_string())
+        } else {
+            Err(format!("Error: 第 {} 行: {}", line, last_err))
+        }
+    };
+
+    let mut outputs: Vec<String> = Vec::new();
+    for stmt in parse::program::resolve_program(input, xlens, compress, ext) {
+        match stmt {
+            Ok(Resolved { line, item: Item::Inst { mnem, ops, compress, size } }) => {
+                match try_one(line, &mnem, &ops, compress, size) {
+                    Ok(hex) => outputs.extend(hex),
+                    Err(e) => outputs.push(e),
+                }
+            }
+            Ok(Resolved { item: Item::Data(lines), .. }) => outputs.extend(lines),
+            Err(e) => outputs.push(format!("Error: {}", e)),
+        }
+    }
+    outputs.join("\n")
+}
 
+/// Assemble with the default extensions. Zbb is not assumed, so `sext.b`,
+/// `sext.h` and `zext.h` become base-ISA shift pairs even though the
+/// disassembler prints the Zbb words under those names; feeding that text
+/// back in needs [`assemble_with_ext`] with `+zbb` to get the same words.
 #[wasm_bindgen]
 pub fn assemble_with_xlen(input: &str, xlen_bits: u32) -> String {
     let xlen = match xlen_bits {
@@ -47,65 +124,56 @@ pub fn assemble_with_xlen(input: &str, xlen_bits: u32) -> String {
         128 => Xlen::X128,
         _ => return format!("Error: invalid xlen {}, must be 32, 64, or 128", xlen_bits),
     };
+    assemble_program(input, &[xlen], false, &Extensions::default())
+}
 
-    let mut outputs: Vec<String> = Vec::new();
-    for line in input.lines() {
-        let trimmed = line.trim();
-        if trimmed.is_empty() { continue; }
-        match crate::parse::parse_line(trimmed, xlen) {
-            Ok(inst) => {
-                match inst {
-                    asm::Instruction::RVC(_) => match encode_u16(&inst, xlen) {
-                        Ok(bits16) => outputs.push(to_hex_u16(bits16)),
-                        Err(e) => outputs.push(format!("Error: {}", e)),
-                    },
-                    _ => match encode_u32(&inst, xlen) {
-                        Ok(bits) => outputs.push(to_hex_u32(bits)),
-                        Err(e) => outputs.push(format!("Error: {}", e)),
-                    }
-                }
-            },
-            Err(e) => outputs.push(format!("Error: {}", e)),
-        }
-    }
-    outputs.join("\n")
+/// Assemble with auto-compression: instructions that have an RVC equivalent
+/// (`addi sp, sp, -16`, `lw a0, 4(sp)`, ...) are emitted as 16-bit words.
+#[wasm_bindgen]
+pub fn assemble_compressed(input: &str, xlen_bits: u32) -> String {
+    let xlen = match xlen_bits {
+        32 => Xlen::X32,
+        64 => Xlen::X64,
+        128 => Xlen::X128,
+        _ => return format!("Error: invalid xlen {}, must be 32, 64, or 128", xlen_bits),
+    };
+    assemble_program(input, &[xlen], true, &Extensions::default())
 }
 
 #[wasm_bindgen]
 pub fn assemble_auto(input: &str) -> String {
-    fn try_one(line: &str) -> String {
-        let mut last_err = String::new();
-        for x in [Xlen::X32, Xlen::X64, Xlen::X128] {
-            match crate::parse::parse_line(line, x) {
-                Ok(inst) => {
-                    match inst {
-                        asm::Instruction::RVC(_) => match encode_u16(&inst, x) {
-                            Ok(bits16) => return to_hex_u16(bits16),
-                            Err(e) => last_err = format!("编码失败({:?}): {}", x, e),
-                        },
-                        _ => match encode_u32(&inst, x) {
-                            Ok(bits) => return to_hex_u32(bits),
-                            Err(e) => last_err = format!("编码失败({:?}): {}", x, e),
-                        }
-                    }
-                },
-                Err(e) => last_err = e,
-            }
-        }
-        if last_err.is_empty() {
-            "Error: unsupported or invalid instruction".to_string()
-        } else {
-            format!("Error: {}", last_err)
-        }
+    assemble_program(input, &[Xlen::X32, Xlen::X64, Xlen::X128], false, &Extensions::default())
+}
+
+/// Assemble with some extensions switched off. `ext` is an `-mattr` style
+/// list such as `-zfh` (leaving only Zfhmin); `+e` selects the RV32E/RV64E
+/// base, where x16–x31 do not exist. `-zicbop`, `-zihintpause` and
+/// `-zihintntl` reject the hint mnemonics. `cm.*` needs `+zcmp`/`+zcmt`,
+/// which reject the Zcd loads and stores they displace. `-h` rejects the
+/// hypervisor instructions and CSRs. `+zfinx` (`+zdinx`, `+zhinx`) takes
+/// integer registers in floating-point instructions and rejects the F
+/// loads, stores and moves. `+zbb` makes `sext.b`, `sext.h` and `zext.h`
+/// the single Zbb instructions.
+#[wasm_bindgen]
+pub fn assemble_with_ext(input: &str, xlen_bits: u32, ext: &str) -> String {
+    let xlen = match xlen_bits {
+        32 => Xlen::X32,
+        64 => Xlen::X64,
+        128 => Xlen::X128,
+        _ => return format!("Error: invalid xlen {}, must be 32, 64, or 128", xlen_bits),
+    };
+    match Extensions::parse(ext).and_then(|ext| check_base(ext, xlen)) {
+        Ok(ext) => assemble_program(input, &[xlen], false, &ext),
+        Err(e) => format!("Error: {}", e),
     }
+}
 
-    let mut outputs: Vec<String> = Vec::new();
-    for line in input.lines() {
-        let trimmed = line.trim();
-        if trimmed.is_empty() { continue; }
-        outputs.push(try_one(trimmed));
+/// The E base only exists for RV32 and RV64.
+fn check_base(ext: Extensions, xlen: Xlen) -> Result<Extensions, String> {
+    if ext.rve && xlen == Xlen::X128 {
+        return Err("RV128 没有 E 基础指令集".to_string());
     }
-    outputs.join("\n")
+    Ok(ext)
 }
 fn is_16_bit_instruction(value: u32) -> bool {
     // Example logic to determine if the instruction is 16-bit
@@ -139,12 +207,12 @@ pub fn disassemble_with_xlen(input: &str, xlen_bits: u32) -> String {
                     return format!("Error: invalid 16-bit instruction");
                 }
                 match resolve_u16((value & 0xFFFF) as u16, xlen) {
-                    Ok(instruction) => instruction.disassembly(),
+                    Ok(instruction) => instruction.disassembly_xlen(xlen),
                     Err(_) => format!("Error: unsupported 16-bit instruction"),
                 }
             } else {
                 match resolve_u32(value, xlen) {
-                    Ok(instruction) => instruction.disassembly(),
+                    Ok(instruction) => instruction.disassembly_xlen(xlen),
                     Err(_) => format!("Error: unsupported 32-bit instruction"),
                 }
             }
@@ -174,7 +242,7 @@ pub fn disassemble_auto(input: &str) -> String {
                 }
                 let try_decode = |xlen: Xlen| -> Result<String, ()> {
                     resolve_u16((value & 0xFFFF) as u16, xlen)
-                        .map(|ins| ins.disassembly())
+                        .map(|ins| ins.disassembly_xlen(xlen))
                 };
                 match try_all(try_decode) {
                     Some(s) => s,
@@ -182,7 +250,7 @@ pub fn disassemble_auto(input: &str) -> String {
                 }
             } else {
                 let try_decode = |xlen: Xlen| -> Result<String, ()> {
-                    resolve_u32(value, xlen).map(|ins| ins.disassembly())
+                    resolve_u32(value, xlen).map(|ins| ins.disassembly_xlen(xlen))
                 };
                 match try_all(try_decode) {
                     Some(s) => s,
@@ -194,6 +262,142 @@ pub fn disassemble_auto(input: &str) -> String {
     }
 }
 
+/// Decode with the first xlen in `xlens` that accepts the word and print it
+/// objdump-style.
+fn disassemble_objdump_with(input: &str, xlens: &[Xlen]) -> String {
+    let value = match input_to_u32(input) {
+        Ok(v) => v,
+        Err(e) => return format!("Error: invalid input: {}", e),
+    };
+    let half = is_16_bit_instruction(value);
+    if half && value > 0xFFFF {
+        return "Error: invalid 16-bit instruction".to_string();
+    }
+    for &xlen in xlens {
+        let decoded = if half { resolve_u16(value as u16, xlen) } else { resolve_u32(value, xlen) };
+        if let Ok(ins) = decoded {
+            return ins.disassembly_objdump(xlen);
+        }
+    }
+    if half {
+        "Error: unsupported 16-bit instruction".to_string()
+    } else {
+        "Error: unsupported 32-bit instruction".to_string()
+    }
+}
+
+/// Like `disassemble_with_xlen`, but prints pseudo-instruction aliases the
+/// way GNU objdump does (`ret`, `li a0,1`, `rdcycle a0`).
+#[wasm_bindgen]
+pub fn disassemble_objdump(input: &str, xlen_bits: u32) -> String {
+    let xlen = match xlen_bits {
+        32 => Xlen::X32,
+        64 => Xlen::X64,
+        128 => Xlen::X128,
+        _ => return format!("Error: invalid xlen {}, must be 32, 64, or 128", xlen_bits),
+    };
+    disassemble_objdump_with(input, &[xlen])
+}
+
+#[wasm_bindgen]
+pub fn disassemble_objdump_auto(input: &str) -> String {
+    disassemble_objdump_with(input, &[Xlen::X32, Xlen::X64, Xlen::X128])
+}
+
+/// Decode with the first xlen in `xlens` that accepts the word. Compressed
+/// instructions are followed by the base instruction they expand to and its
+/// 32-bit encoding: `c.addi4spn a0, sp, 16  # addi a0, sp, 16 (0x01010513)`.
+fn disassemble_expanded_with(input: &str, xlens: &[Xlen]) -> String {
+    let value = match input_to_u32(input) {
+        Ok(v) => v,
+        Err(e) => return format!("Error: invalid input: {}", e),
+    };
+    let half = is_16_bit_instruction(value);
+    if half && value > 0xFFFF {
+        return "Error: invalid 16-bit instruction".to_string();
+    }
+    for &xlen in xlens {
+        let decoded = if half { resolve_u16(value as u16, xlen) } else { resolve_u32(value, xlen) };
+        let ins = match decoded {
+            Ok(ins) => ins,
+            Err(_) => continue,
+        };
+        if let asm::Instruction::RVC(c) = ins {
+            if let Some(base) = expand(&c, xlen) {
+                if let Ok(word) = encode(&base, xlen) {
+                    return format!("{}  # {} ({})", ins.disassembly(), base.disassembly(), word.to_hex());
+                }
+            }
+        }
+        return ins.disassembly_xlen(xlen);
+    }
+    if half {
+        "Error: unsupported 16-bit instruction".to_string()
+    } else {
+        "Error: unsupported 32-bit instruction".to_string()
+    }
+}
+
+/// Like `disassemble_with_xlen`, but also shows what each compressed
+/// instruction expands to.
+#[wasm_bindgen]
+pub fn disassemble_expanded(input: &str, xlen_bits: u32) -> String {
+    let xlen = match xlen_bits {
+        32 => Xlen::X32,
+        64 => Xlen::X64,
+        128 => Xlen::X128,
+        _ => return format!("Error: invalid xlen {}, must be 32, 64, or 128", xlen_bits),
+    };
+    disassemble_expanded_with(input, &[xlen])
+}
+
+#[wasm_bindgen]
+pub fn disassemble_expanded_auto(input: &str) -> String {
+    disassemble_expanded_with(input, &[Xlen::X32, Xlen::X64, Xlen::X128])
+}
+
+/// Like `disassemble_with_xlen`, but words outside the extensions enabled by
+/// `ext` (see `assemble_with_ext`) are reported as unsupported. Hints whose
+/// extension is switched off (`-zihintpause`, `-zicbop`, `-zihintntl`) are
+/// shown as the base instruction they are encoded as. With `+zcmp` or
+/// `+zcmt` the Zcd encodings decode as `cm.*` instead; with `+zfinx`
+/// floating-point operands print as integer registers.
+#[wasm_bindgen]
+pub fn disassemble_with_ext(input: &str, xlen_bits: u32, ext: &str) -> String {
+    let xlen = match xlen_bits {
+        32 => Xlen::X32,
+        64 => Xlen::X64,
+        128 => Xlen::X128,
+        _ => return format!("Error: invalid xlen {}, must be 32, 64, or 128", xlen_bits),
+    };
+    let ext = match Extensions::parse(ext).and_then(|ext| check_base(ext, xlen)) {
+        Ok(ext) => ext,
+        Err(e) => return format!("Error: {}", e),
+    };
+    let value = match input_to_u32(input) {
+        Ok(v) => v,
+        Err(e) => return format!("Error: invalid input: {}", e),
+    };
+    let half = is_16_bit_instruction(value);
+    if half && value > 0xFFFF {
+        return "Error: invalid 16-bit instruction".to_string();
+    }
+    let decoded = match half {
+        true if ext.zcmp || ext.zcmt => resolve_u16_zcm(value as u16, xlen),
+        true => resolve_u16(value as u16, xlen),
+        false => resolve_u32(value, xlen),
+    };
+    match decoded.map(|ins| ext.raw_hint(ins)) {
+        Ok(ins) => match ext.allows(&ins).and(ext.allows_pairs(&ins, xlen)) {
+            Ok(()) if ext.zfinx => ins.disassembly_zfinx(),
+            Ok(()) => ins.disassembly_xlen(xlen),
+            Err(e) => format!("Error: {}", e),
+        },
+        Err(_) if half => "Error: unsupported 16-bit instruction".to_string(),
+        Err(_) => "Error: unsupported 32-bit instruction".to_string(),
+    }
+}
+
 fn input_to_u32(hex_str: &str) -> Result<u32, std::num::ParseIntError> {
     // 检查字符串是否以 "0x" 或 "0X" 开头，并将其剥离
     let trimmed_str = if hex_str.starts_with("0x") || hex_str.starts_with("0X") {
@@ -1,4 +1,5 @@
 use crate::asm;
+use crate::riscv::imm::Xlen;
 
 pub(crate) fn trim_comment(s: &str) -> &str {
     let s = s.split("//").next().unwrap_or(s);
@@ -14,6 +15,30 @@ pub(crate) fn parse_register(s: &str) -> Result<u8, String> {
     if let Some(r) = asm::from_register(s) { Ok(r) } else { Err(format!("未知寄存器: {}", s)) }
 }
 
+pub(crate) fn parse_fp_register(s: &str) -> Result<u8, String> {
+    if let Some(r) = asm::from_fp_register(s) { Ok(r) } else { Err(format!("未知浮点寄存器: {}", s)) }
+}
+
+pub(crate) fn parse_vector_register(s: &str) -> Result<u8, String> {
+    match s.trim().to_lowercase().strip_prefix('v').map(|n| n.parse::<u8>()) {
+        Some(Ok(n)) if n <= 31 => Ok(n),
+        _ => Err(format!("未知向量寄存器: {}", s)),
+    }
+}
+
+/// CSR operand: a number in 0..=0xfff or a CSR name such as `mstatus`.
+pub(crate) fn parse_csr(s: &str, xlen: Xlen) -> Result<u16, String> {
+    if let Some((num, rv32_only)) = asm::csr::csr_from_name(s) {
+        if rv32_only && xlen != Xlen::X32 { return Err(format!("CSR {} 仅在 RV32 可用", s.trim())); }
+        return Ok(num);
+    }
+    match parse_int(s) {
+        Ok(v) if (0..=0xFFF).contains(&v) => Ok(v as u16),
+        Ok(_) => Err("csr 编号应为 0..0xFFF".into()),
+        Err(_) => Err(format!("未知的 CSR: {}", s.trim())),
+    }
+}
+
 pub(crate) fn parse_int(s: &str) -> Result<i64, String> {
     let t = s.trim();
     if t.is_empty() { return Err("缺少立即数".into()); }
@@ -54,3 +79,120 @@ pub(crate) fn parse_mem_operand(s: &str) -> Result<(u32, u8), String> {
     let rs = parse_register(reg_str)?;
     Ok((imm_signed_bits(imm, 12)?, rs))
 }
+
+/// Address operand without an offset: `(rs1)`, also accepted as `0(rs1)`.
+pub(crate) fn parse_base_register(s: &str) -> Result<u8, String> {
+    let t = s.trim();
+    let inner = t
+        .strip_prefix('0')
+        .unwrap_or(t)
+        .strip_prefix('(')
+        .and_then(|r| r.strip_suffix(')'))
+        .ok_or_else(|| format!("地址操作数应为 (rs1): {}", s))?;
+    parse_register(inner)
+}
+
+/// Evaluate an integer expression made of numbers, symbols and the operators
+/// `+ - * / % << >> & | ^ ~` with parentheses. `lookup` resolves symbol names
+/// (labels, `.equ` constants, `1b`/`1f` local labels).
+pub(crate) fn eval_expr(s: &str, lookup: &dyn Fn(&str) -> Option<i64>) -> Result<i64, String> {
+    let tokens = tokenize_expr(s)?;
+    let mut p = ExprParser { tokens: &tokens, pos: 0, lookup };
+    let v = p.bitor()?;
+    if p.pos != tokens.len() { return Err(format!("表达式格式错误: {}", s)); }
+    Ok(v)
+}
+
+fn tokenize_expr(s: &str) -> Result<Vec<String>, String> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() { i += 1; continue; }
+        if c.is_ascii_alphanumeric() || c == '_' || c == '.' || c == '$' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || matches!(chars[i], '_' | '.' | '$')) { i += 1; }
+            out.push(chars[start..i].iter().collect());
+            continue;
+        }
+        if (c == '<' || c == '>') && chars.get(i + 1) == Some(&c) {
+            out.push(format!("{}{}", c, c));
+            i += 2;
+            continue;
+        }
+        if "+-*/%&|^~()".contains(c) { out.push(c.to_string()); i += 1; continue; }
+        return Err(format!("表达式中有非法字符: {}", c));
+    }
+    if out.is_empty() { return Err("缺少立即数".into()); }
+    Ok(out)
+}
+
+struct ExprParser<'a> {
+    tokens: &'a [String],
+    pos: usize,
+    lookup: &'a dyn Fn(&str) -> Option<i64>,
+}
+
+impl<'a> ExprParser<'a> {
+    fn peek(&self) -> Option<&str> { self.tokens.get(self.pos).map(|t| t.as_str()) }
+
+    fn binary(&mut self, ops: &[&str], next: fn(&mut Self) -> Result<i64, String>) -> Result<i64, String> {
+        let mut v = next(self)?;
+        while let Some(op) = self.peek().filter(|t| ops.contains(t)).map(|t| t.to_string()) {
+            self.pos += 1;
+            let rhs = next(self)?;
+            v = match op.as_str() {
+                "|" => v | rhs,
+                "^" => v ^ rhs,
+                "&" => v & rhs,
+                "<<" => v.wrapping_shl(rhs as u32),
+                ">>" => v.wrapping_shr(rhs as u32),
+                "+" => v.wrapping_add(rhs),
+                "-" => v.wrapping_sub(rhs),
+                "*" => v.wrapping_mul(rhs),
+                "/" | "%" if rhs == 0 => return Err("表达式除以 0".into()),
+                "/" => v.wrapping_div(rhs),
+                _ => v.wrapping_rem(rhs),
+            };
+        }
+        Ok(v)
+    }
+
+    fn bitor(&mut self) -> Result<i64, String> { self.binary(&["|"], Self::bitxor) }
+    fn bitxor(&mut self) -> Result<i64, String> { self.binary(&["^"], Self::bitand) }
+    fn bitand(&mut self) -> Result<i64, String> { self.binary(&["&"], Self::shift) }
+    fn shift(&mut self) -> Result<i64, String> { self.binary(&["<<", ">>"], Self::additive) }
+    fn additive(&mut self) -> Result<i64, String> { self.binary(&["+", "-"], Self::term) }
+    fn term(&mut self) -> Result<i64, String> { self.binary(&["*", "/", "%"], Self::unary) }
+
+    fn unary(&mut self) -> Result<i64, String> {
+        match self.peek() {
+            Some("-") => { self.pos += 1; Ok(self.unary()?.wrapping_neg()) }
+            Some("+") => { self.pos += 1; self.unary() }
+            Some("~") => { self.pos += 1; Ok(!self.unary()?) }
+            _ => self.primary(),
+        }
+    }
+
+    fn primary(&mut self) -> Result<i64, String> {
+        let tok = self.peek().ok_or_else(|| "表达式不完整".to_string())?.to_string();
+        self.pos += 1;
+        if tok == "(" {
+            let v = self.bitor()?;
+            if self.peek() != Some(")") { return Err("表达式缺少 )".into()); }
+            self.pos += 1;
+            return Ok(v);
+        }
+        let is_local_ref = tok.len() >= 2
+            && (tok.ends_with('b') || tok.ends_with('f'))
+            && tok[..tok.len() - 1].chars().all(|c| c.is_ascii_digit());
+        if tok.starts_with(|c: char| c.is_ascii_digit()) && !is_local_ref {
+            if let Some(bin) = tok.strip_prefix("0b").or_else(|| tok.strip_prefix("0B")) {
+                return i64::from_str_radix(bin, 2).map_err(|e| format!("立即数解析失败: {}", e));
+            }
+            return parse_int(&tok);
+        }
+        (self.lookup)(&tok).ok_or_else(|| format!("未定义的符号: {}", tok))
+    }
+}
@@ -0,0 +1,96 @@
+//! Assembler directives: data emission, alignment, origin and symbol
+//! definitions. Directive operands are kept as expressions here and
+//! evaluated by the program pass, which owns the symbol table.
+
+pub(crate) enum Directive {
+    /// `.byte/.half/.word/.dword` (and aliases); `width` in bytes
+    Data { width: u8, exprs: Vec<String> },
+    /// `.align/.p2align n` (2^n bytes) or `.balign n` (n bytes)
+    Align { pow2: bool, expr: String, fill: Option<String> },
+    /// `.org addr[, fill]`
+    Org { expr: String, fill: Option<String> },
+    /// `.equ/.set name, expr`
+    Set { name: String, expr: String },
+    /// Accepted for compatibility; no effect on the output
+    Ignored,
+}
+
+pub(crate) fn try_parse(mnem: &str, ops: &[String]) -> Option<Result<Directive, String>> {
+    if !mnem.starts_with('.') { return None; }
+    let res = match mnem {
+        ".byte" => data(mnem, 1, ops),
+        ".half" | ".short" | ".2byte" => data(mnem, 2, ops),
+        ".word" | ".long" | ".4byte" => data(mnem, 4, ops),
+        ".dword" | ".quad" | ".8byte" => data(mnem, 8, ops),
+        ".align" | ".p2align" | ".balign" => {
+            if ops.is_empty() || ops.len() > 2 { return Some(Err(format!("用法: {} n[, fill]", mnem))); }
+            Ok(Directive::Align { pow2: mnem != ".balign", expr: ops[0].clone(), fill: ops.get(1).cloned() })
+        }
+        ".org" => {
+            if ops.is_empty() || ops.len() > 2 { return Some(Err("用法: .org addr[, fill]".into())); }
+            Ok(Directive::Org { expr: ops[0].clone(), fill: ops.get(1).cloned() })
+        }
+        ".equ" | ".set" => {
+            if ops.len() != 2 { return Some(Err(format!("用法: {} name, expr", mnem))); }
+            Ok(Directive::Set { name: ops[0].clone(), expr: ops[1].clone() })
+        }
+        ".text" | ".globl" | ".global" | ".local" | ".type" | ".size" => Ok(Directive::Ignored),
+        _ => Err(format!("未支持的伪操作: {}", mnem)),
+    };
+    Some(res)
+}
+
+fn data(mnem: &str, width: u8, ops: &[String]) -> Result<Directive, String> {
+    if ops.is_empty() { return Err(format!("用法: {} expr[, expr...]", mnem)); }
+    Ok(Directive::Data { width, exprs: ops.to_vec() })
+}
+
+/// Format one data element as a hex line of its own width.
+pub(crate) fn data_hex(width: u8, value: i64) -> Result<String, String> {
+    let bits = width as u32 * 8;
+    if bits < 64 {
+        let min = -(1i64 << (bits - 1));
+        let max = (1i64 << bits) - 1;
+        if value < min || value > max {
+            return Err(format!("数据 {} 超出 {} 字节范围", value, width));
+        }
+    }
+    let v = if bits == 64 { value as u64 } else { (value as u64) & ((1u64 << bits) - 1) };
+    Ok(format!("0x{:0w$x}", v, w = width as usize * 2))
+}
+
+/// Padding lines for the gap `from..to`. With an explicit fill byte the gap is
+/// filled with that byte (grouped into words where aligned); without one the
+/// gap is treated as code and padded with `nop`/`c.nop` like GNU as does.
+pub(crate) fn padding(from: u64, to: u64, fill: Option<u8>) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut pc = from;
+    match fill {
+        Some(b) => {
+            while pc < to {
+                if pc & 3 == 0 && to - pc >= 4 {
+                    out.push(format!("0x{:08x}", u32::from_ne_bytes([b; 4])));
+                    pc += 4;
+                } else {
+                    out.push(format!("0x{:02x}", b));
+                    pc += 1;
+                }
+            }
+        }
+        None => {
+            while pc < to {
+                if pc & 1 != 0 || to - pc == 1 {
+                    out.push("0x00".to_string());
+                    pc += 1;
+                } else if pc & 3 != 0 || to - pc < 4 {
+                    out.push("0x0001".to_string()); // c.nop
+                    pc += 2;
+                } else {
+                    out.push("0x00000013".to_string()); // nop
+                    pc += 4;
+                }
+            }
+        }
+    }
+    out
+}
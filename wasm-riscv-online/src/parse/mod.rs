@@ -1,29 +1,54 @@
 mod common;
 mod rv_i;
+mod rv_m;
+mod rv_f;
+mod rv_a;
+mod rv_b;
+mod rv_k;
+mod rv_v;
 mod system;
 mod zicsr;
+mod zicond;
+mod zicbo;
+mod zihint;
 mod rvc;
+mod directive;
+mod pseudo;
+pub mod program;
 use crate::asm::*;
 use crate::riscv::imm::Xlen;
-use self::common::{
-    trim_comment,
-    split_operands,
-};
+use crate::ext::Extensions;
 
-pub fn parse_line(line: &str, xlen: Xlen) -> Result<Instruction, String> {
-    let raw = trim_comment(line).trim();
-    if raw.is_empty() { return Err("空行".into()); }
-    // split mnemonic and operands
-    let mut parts = raw.split_whitespace();
-    let mnem = parts.next().ok_or_else(|| "缺少助记符".to_string())?.to_lowercase();
-    let rest = parts.collect::<Vec<_>>().join(" ");
-    let ops = if rest.is_empty() { vec![] } else { split_operands(&rest) };
-
-    // New modular dispatch (RVC -> Zicsr -> System -> RV I)
-    if let Some(res) = rvc::try_parse(&mnem, &ops, xlen)   { return res; }
-    if let Some(res) = zicsr::try_parse(&mnem, &ops, xlen) { return res; }
-    if let Some(res) = system::try_parse(&mnem, &ops, xlen){ return res; }
-    if let Some(res) = rv_i::try_parse(&mnem, &ops, xlen)  { return res; }
+pub fn parse_statement(mnem: &str, ops: &[String], xlen: Xlen) -> Result<Instruction, String> {
+    // New modular dispatch (RVC -> Zicsr -> System -> RV I -> RV M -> Zicond -> Zicbo -> Zihint -> RV F -> RV A -> RV B -> RV K -> RV V)
+    if let Some(res) = rvc::try_parse(mnem, ops, xlen)   { return res; }
+    if let Some(res) = zicsr::try_parse(mnem, ops, xlen) { return res; }
+    if let Some(res) = system::try_parse(mnem, ops, xlen){ return res; }
+    if let Some(res) = rv_i::try_parse(mnem, ops, xlen)  { return res; }
+    if let Some(res) = rv_m::try_parse(mnem, ops, xlen)  { return res; }
+    if let Some(res) = zicond::try_parse(mnem, ops, xlen){ return res; }
+    if let Some(res) = zicbo::try_parse(mnem, ops, xlen) { return res; }
+    if let Some(res) = zihint::try_parse(mnem, ops, xlen){ return res; }
+    if let Some(res) = rv_f::try_parse(mnem, ops, xlen)  { return res; }
+    if let Some(res) = rv_a::try_parse(mnem, ops, xlen)  { return res; }
+    if let Some(res) = rv_b::try_parse(mnem, ops, xlen)  { return res; }
+    if let Some(res) = rv_k::try_parse(mnem, ops, xlen)  { return res; }
+    if let Some(res) = rv_v::try_parse(mnem, ops, xlen)  { return res; }
     // All known parsers failed; legacy fallback disabled. Return unsupported.
     return Err(format!("未支持的指令: {}", mnem));
 }
+
+/// Parse one source statement, expanding pseudo-instructions into the base
+/// instructions they stand for. With Zbb requested, `sext.b`, `sext.h` and
+/// `zext.h` are the Zbb instructions rather than shift pairs. With Zfinx,
+/// floating-point instructions take integer registers.
+pub fn expand_statement(mnem: &str, ops: &[String], xlen: Xlen, ext: &Extensions) -> Result<Vec<Instruction>, String> {
+    if ext.zfinx {
+        if let Some(res) = rv_f::try_parse_zfinx(mnem, ops, xlen) { return res.map(|inst| vec![inst]); }
+    }
+    let zbb_form = ext.zbb && matches!(mnem, "sext.b" | "sext.h" | "zext.h");
+    if !zbb_form {
+        if let Some(res) = pseudo::try_expand(mnem, ops, xlen) { return res; }
+    }
+    parse_statement(mnem, ops, xlen).map(|inst| vec![inst])
+}
@@ -0,0 +1,334 @@
+//! Multi-line assembly: collects label definitions in a first pass and
+//! resolves label operands into PC-relative immediates in a second pass.
+use std::collections::HashMap;
+use crate::asm::Instruction;
+use crate::riscv::imm::Xlen;
+use super::common::{trim_comment, split_operands, parse_int, eval_expr};
+use super::directive::{self, Directive};
+use super::expand_statement;
+use crate::ext::Extensions;
+
+/// What a resolved statement produces.
+pub enum Item {
+    /// An instruction, ready to be parsed for a given xlen. `compress` tells
+    /// whether it may be emitted in RVC form, `size` is the number of bytes
+    /// laid out for it.
+    Inst { mnem: String, ops: Vec<String>, compress: bool, size: u64 },
+    /// Raw data/padding, already formatted as hex lines.
+    Data(Vec<String>),
+}
+
+pub struct Resolved {
+    pub line: usize,
+    pub item: Item,
+}
+
+enum Kind {
+    Inst { mnem: String, ops: Vec<String> },
+    Dir(Directive),
+}
+
+struct Statement {
+    line: usize,
+    addr: u64,
+    size: u64,
+    kind: Kind,
+}
+
+#[derive(Default)]
+struct Symbols {
+    labels: HashMap<String, i64>,
+    // `.equ`/`.set` constants; may be redefined
+    consts: HashMap<String, i64>,
+    // numeric local labels (`1:` referenced as `1b` / `1f`), in definition order
+    locals: Vec<(u64, u64)>,
+}
+
+impl Symbols {
+    fn lookup(&self, name: &str, pc: u64) -> Option<i64> {
+        if let Some(v) = self.consts.get(name).or_else(|| self.labels.get(name)) { return Some(*v); }
+        let (num, dir) = name.split_at(name.len().checked_sub(1)?);
+        let num = num.parse::<u64>().ok()?;
+        let addr = match dir {
+            "b" => self.locals.iter().rev().find(|(n, a)| *n == num && *a <= pc),
+            "f" => self.locals.iter().find(|(n, a)| *n == num && *a > pc),
+            _ => None,
+        };
+        addr.map(|(_, a)| *a as i64)
+    }
+
+    fn eval(&self, expr: &str, pc: u64) -> Result<i64, String> {
+        eval_expr(expr, &|name| self.lookup(name, pc))
+    }
+
+    fn define_label(&mut self, name: &str, addr: u64) -> Result<(), String> {
+        if self.consts.contains_key(name) || self.labels.insert(name.to_string(), addr as i64).is_some() {
+            return Err(format!("标签重复定义: {}", name));
+        }
+        Ok(())
+    }
+
+    fn define_const(&mut self, name: &str, value: i64) -> Result<(), String> {
+        if !is_symbol_name(name) { return Err(format!("非法的符号名: {}", name)); }
+        if self.labels.contains_key(name) { return Err(format!("符号与标签重名: {}", name)); }
+        self.consts.insert(name.to_string(), value);
+        Ok(())
+    }
+}
+
+/// PC-relative target operand of a control-transfer instruction:
+/// (operand index, immediate width in bits).
+fn branch_target(mnem: &str, nops: usize) -> Option<(usize, u8)> {
+    match mnem {
+        "beq" | "bne" | "blt" | "bge" | "bltu" | "bgeu" if nops == 3 => Some((2, 13)),
+        "bgt" | "ble" | "bgtu" | "bleu" if nops == 3 => Some((2, 13)),
+        "beqz" | "bnez" | "blez" | "bgez" | "bltz" | "bgtz" if nops == 2 => Some((1, 13)),
+        "jal" if nops == 2 => Some((1, 21)),
+        "jal" | "j" if nops == 1 => Some((0, 21)),
+        // auipc-based sequences reach +-2GiB
+        "call" | "tail" if nops == 1 => Some((0, 32)),
+        "call" | "la" | "lla" if nops == 2 => Some((1, 32)),
+        "c.beqz" | "c.bnez" if nops == 2 => Some((1, 9)),
+        "c.j" | "c.jal" if nops == 1 => Some((0, 12)),
+        _ => None,
+    }
+}
+
+fn is_symbol_name(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' || c == '.' => {}
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.' || c == '$')
+}
+
+fn inst_size(inst: &Instruction, xlen: Xlen, compress: bool) -> u64 {
+    match inst {
+        Instruction::RVC(_) => 2,
+        _ if compress && crate::encode::compress::compress(inst, xlen).is_some() => 2,
+        _ => 4,
+    }
+}
+
+/// Branches and jumps to labels keep their full-size form under
+/// auto-compression, so that the layout does not depend on label distances.
+fn may_compress(mnem: &str, ops: &[String], compress: bool) -> bool {
+    compress && match branch_target(mnem, ops.len()) {
+        Some((t, _)) => parse_int(&ops[t]).is_ok(),
+        None => true,
+    }
+}
+
+/// Split `off(reg)` into (`off`, `(reg)`); other operands are returned whole.
+fn split_mem_operand(op: &str) -> (&str, &str) {
+    if let (Some(open), true) = (op.rfind('('), op.ends_with(')')) {
+        if crate::asm::from_register(&op[open + 1..op.len() - 1]).is_some() {
+            return op.split_at(open);
+        }
+    }
+    (op, "")
+}
+
+/// Replace symbol references in the operands of an instruction.
+/// When `strict` is false, undefined labels resolve to the statement itself
+/// (offset 0) so that the first pass can size the instruction.
+fn resolve(
+    mnem: &str,
+    ops: &[String],
+    pc: u64,
+    syms: &Symbols,
+    strict: bool,
+) -> Result<Vec<String>, String> {
+    let target = branch_target(mnem, ops.len());
+    let mut out = Vec::with_capacity(ops.len());
+    for (idx, op) in ops.iter().enumerate() {
+        match target {
+            Some((t, bits)) if t == idx && parse_int(op).is_err() => {
+                let addr = match syms.eval(op, pc) {
+                    Ok(a) => a,
+                    Err(e) if strict => return Err(e.replace("未定义的符号", "未定义的标签")),
+                    Err(_) => pc as i64,
+                };
+                let off = addr.wrapping_sub(pc as i64);
+                let min = -(1i64 << (bits - 1));
+                let max = (1i64 << (bits - 1)) - 2;
+                if strict && (off < min || off > max) {
+                    return Err(format!(
+                        "标签 {} 超出 {} 的跳转范围: 偏移 {} 不在 {}..={} 内",
+                        op, mnem, off, min, max
+                    ));
+                }
+                out.push(off.to_string());
+            }
+            _ => out.push(substitute_symbols(op, syms, pc)),
+        }
+    }
+    Ok(out)
+}
+
+/// Evaluate symbolic immediates such as `SIZE*4` or `buf(gp)`. Operands that
+/// do not evaluate are left to the instruction parsers (registers, CSR names,
+/// fence sets, ...).
+fn substitute_symbols(op: &str, syms: &Symbols, pc: u64) -> String {
+    let (head, tail) = split_mem_operand(op);
+    let expr = head.trim();
+    if expr.is_empty() || parse_int(expr).is_ok() || crate::asm::from_register(expr).is_some() {
+        return op.to_string();
+    }
+    match syms.eval(expr, pc) {
+        Ok(v) => format!("{}{}", v, tail),
+        Err(_) => op.to_string(),
+    }
+}
+
+fn size_of(mnem: &str, ops: &[String], pc: u64, syms: &Symbols, xlens: &[Xlen], compress: bool, ext: &Extensions) -> u64 {
+    let compress = may_compress(mnem, ops, compress);
+    if let Ok(ops) = resolve(mnem, ops, pc, syms, false) {
+        for &x in xlens {
+            if let Ok(insts) = expand_statement(mnem, &ops, x, ext) {
+                return insts.iter().map(|i| inst_size(i, x, compress)).sum();
+            }
+        }
+    }
+    if mnem.starts_with("c.") { 2 } else { 4 }
+}
+
+fn fill_byte(fill: &Option<String>, pc: u64, syms: &Symbols) -> Result<Option<u8>, String> {
+    match fill {
+        Some(f) => {
+            let v = syms.eval(f, pc)?;
+            if !(-128..=255).contains(&v) { return Err(format!("填充值 {} 超出 1 字节范围", v)); }
+            Ok(Some(v as u8))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Location counter after a layout directive at `pc`.
+fn layout_end(dir: &Directive, pc: u64, syms: &Symbols) -> Result<u64, String> {
+    match dir {
+        Directive::Data { width, exprs } => Ok(pc + *width as u64 * exprs.len() as u64),
+        Directive::Align { pow2, expr, .. } => {
+            let n = syms.eval(expr, pc)?;
+            let align = match (*pow2, n) {
+                (true, 0..=31) => 1u64 << n,
+                (false, n) if n > 0 && (n & (n - 1)) == 0 => n as u64,
+                _ => return Err(format!("非法的对齐值: {}", n)),
+            };
+            Ok((pc + align - 1) & !(align - 1))
+        }
+        Directive::Org { expr, .. } => {
+            let to = syms.eval(expr, pc)?;
+            if to < pc as i64 { return Err(format!(".org 不能回退位置计数器 ({:#x} < {:#x})", to, pc)); }
+            Ok(to as u64)
+        }
+        Directive::Set { .. } | Directive::Ignored => Ok(pc),
+    }
+}
+
+/// Hex lines emitted by a directive in the second pass.
+fn emit(dir: &Directive, pc: u64, syms: &mut Symbols) -> Result<Vec<String>, String> {
+    match dir {
+        Directive::Data { width, exprs } => exprs
+            .iter()
+            .map(|e| directive::data_hex(*width, syms.eval(e, pc)?))
+            .collect(),
+        Directive::Align { fill, .. } | Directive::Org { fill, .. } => {
+            let to = layout_end(dir, pc, syms)?;
+            let fill = match (dir, fill_byte(fill, pc, syms)?) {
+                (Directive::Org { .. }, None) => Some(0),
+                (_, f) => f,
+            };
+            Ok(directive::padding(pc, to, fill))
+        }
+        Directive::Set { name, expr } => {
+            let v = syms.eval(expr, pc)?;
+            syms.define_const(name, v)?;
+            Ok(vec![])
+        }
+        Directive::Ignored => Ok(vec![]),
+    }
+}
+
+/// Two-pass assembly front end over a whole source text. Returns one entry per
+/// statement that produces output; errors carry the 1-based source line number.
+/// With `compress`, instructions are laid out at their RVC size where they
+/// have a compressed equivalent.
+pub fn resolve_program(input: &str, xlens: &[Xlen], compress: bool, ext: &Extensions) -> Vec<Result<Resolved, String>> {
+    let mut syms = Symbols::default();
+    let mut stmts: Vec<Result<Statement, String>> = Vec::new();
+    let mut pc: u64 = 0;
+
+    // pass 1: record symbol values and statement sizes
+    for (idx, raw) in input.lines().enumerate() {
+        let line = idx + 1;
+        let mut rest = trim_comment(raw).trim();
+        while let Some(pos) = rest.find(':') {
+            let name = rest[..pos].trim();
+            if let Ok(num) = name.parse::<u64>() {
+                syms.locals.push((num, pc));
+            } else if is_symbol_name(name) {
+                if let Err(e) = syms.define_label(name, pc) {
+                    stmts.push(Err(format!("第 {} 行: {}", line, e)));
+                }
+            } else {
+                break;
+            }
+            rest = rest[pos + 1..].trim();
+        }
+        if rest.is_empty() { continue; }
+
+        let mut parts = rest.splitn(2, char::is_whitespace);
+        let mnem = parts.next().unwrap_or("").to_lowercase();
+        let ops = match parts.next() {
+            Some(r) if !r.trim().is_empty() => split_operands(r),
+            _ => vec![],
+        };
+        let kind = match directive::try_parse(&mnem, &ops) {
+            Some(Ok(dir)) => Kind::Dir(dir),
+            Some(Err(e)) => { stmts.push(Err(format!("第 {} 行: {}", line, e))); continue; }
+            None => Kind::Inst { mnem, ops },
+        };
+        let next = match &kind {
+            Kind::Inst { mnem, ops } => Ok(pc + size_of(mnem, ops, pc, &syms, xlens, compress, ext)),
+            Kind::Dir(Directive::Set { name, expr }) => {
+                // forward references are retried in the second pass
+                let v = syms.eval(expr, pc).unwrap_or(0);
+                syms.define_const(name, v).map(|_| pc)
+            }
+            Kind::Dir(dir) => layout_end(dir, pc, &syms),
+        };
+        match next {
+            Ok(next) => {
+                stmts.push(Ok(Statement { line, addr: pc, size: next - pc, kind }));
+                pc = next;
+            }
+            Err(e) => stmts.push(Err(format!("第 {} 行: {}", line, e))),
+        }
+    }
+
+    // pass 2: substitute final symbol values and emit data
+    let mut out = Vec::new();
+    for stmt in stmts {
+        let stmt = match stmt {
+            Ok(s) => s,
+            Err(e) => { out.push(Err(e)); continue; }
+        };
+        let size = stmt.size;
+        let item = match stmt.kind {
+            Kind::Inst { mnem, ops } => {
+                let compress = may_compress(&mnem, &ops, compress);
+                resolve(&mnem, &ops, stmt.addr, &syms, true)
+                    .map(|ops| Item::Inst { mnem, ops, compress, size })
+            }
+            Kind::Dir(dir) => emit(&dir, stmt.addr, &mut syms).map(Item::Data),
+        };
+        match item {
+            Ok(Item::Data(lines)) if lines.is_empty() => {}
+            Ok(item) => out.push(Ok(Resolved { line: stmt.line, item })),
+            Err(e) => out.push(Err(format!("第 {} 行: {}", stmt.line, e))),
+        }
+    }
+    out
+}
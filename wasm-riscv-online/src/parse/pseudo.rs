@@ -0,0 +1,194 @@
+//! Pseudo-instructions: one source statement expanding to one or more base
+//! instructions, following the GNU as expansions. Label operands of `la`,
+//! `call`, `tail`, `j`, `beqz`... arrive here already turned into PC-relative
+//! offsets by the program pass.
+use crate::asm::Instruction;
+use crate::riscv::imm::Xlen;
+use super::common::parse_int;
+use super::parse_statement;
+
+type Step = (&'static str, Vec<String>);
+
+fn step(mnem: &'static str, ops: &[&str]) -> Step {
+    (mnem, ops.iter().map(|s| s.to_string()).collect())
+}
+
+pub(crate) fn try_expand(mnem: &str, ops: &[String], xlen: Xlen) -> Option<Result<Vec<Instruction>, String>> {
+    let o: Vec<&str> = ops.iter().map(|s| s.as_str()).collect();
+    let steps: Vec<Step> = match (mnem, o.as_slice()) {
+        ("nop", []) => vec![step("addi", &["zero", "zero", "0"])],
+        ("li", [rd, imm]) => match li_value(imm, xlen) {
+            Ok(v) => {
+                let mut out = Vec::new();
+                load_const(rd, v, xlen, &mut out);
+                out
+            }
+            Err(e) => return Some(Err(e)),
+        },
+        ("la", [rd, off]) | ("lla", [rd, off]) => match pcrel(off) {
+            Ok((hi, lo)) => vec![step("auipc", &[rd, &hi]), step("addi", &[rd, rd, &lo])],
+            Err(e) => return Some(Err(e)),
+        },
+        ("call", [off]) => match pcrel(off) {
+            Ok((hi, lo)) => vec![step("auipc", &["ra", &hi]), step("jalr", &["ra", &format!("{}(ra)", lo)])],
+            Err(e) => return Some(Err(e)),
+        },
+        ("call", [rd, off]) => match pcrel(off) {
+            Ok((hi, lo)) => vec![step("auipc", &[rd, &hi]), step("jalr", &[rd, &format!("{}({})", lo, rd)])],
+            Err(e) => return Some(Err(e)),
+        },
+        ("tail", [off]) => match pcrel(off) {
+            Ok((hi, lo)) => vec![step("auipc", &["t1", &hi]), step("jalr", &["zero", &format!("{}(t1)", lo)])],
+            Err(e) => return Some(Err(e)),
+        },
+
+        ("mv", [rd, rs]) => vec![step("addi", &[rd, rs, "0"])],
+        ("not", [rd, rs]) => vec![step("xori", &[rd, rs, "-1"])],
+        ("neg", [rd, rs]) => vec![step("sub", &[rd, "zero", rs])],
+        ("negw", [rd, rs]) => vec![step("subw", &[rd, "zero", rs])],
+        ("sext.w", [rd, rs]) => vec![step("addiw", &[rd, rs, "0"])],
+        ("zext.b", [rd, rs]) => vec![step("andi", &[rd, rs, "255"])],
+        ("zext.h", [rd, rs]) => shift_pair("srli", rd, rs, xlen_bits(xlen) - 16),
+        ("sext.b", [rd, rs]) => shift_pair("srai", rd, rs, xlen_bits(xlen) - 8),
+        ("sext.h", [rd, rs]) => shift_pair("srai", rd, rs, xlen_bits(xlen) - 16),
+        ("zext.w", [rd, rs]) => {
+            if xlen == Xlen::X32 { return Some(Err("zext.w 仅在 RV64/128 可用".into())); }
+            shift_pair("srli", rd, rs, xlen_bits(xlen) - 32)
+        }
+        ("seqz", [rd, rs]) => vec![step("sltiu", &[rd, rs, "1"])],
+        ("snez", [rd, rs]) => vec![step("sltu", &[rd, "zero", rs])],
+        ("sltz", [rd, rs]) => vec![step("slt", &[rd, rs, "zero"])],
+        ("sgtz", [rd, rs]) => vec![step("slt", &[rd, "zero", rs])],
+        ("sgt", [rd, rs, rt]) => vec![step("slt", &[rd, rt, rs])],
+        ("sgtu", [rd, rs, rt]) => vec![step("sltu", &[rd, rt, rs])],
+
+        ("beqz", [rs, off]) => vec![step("beq", &[rs, "zero", off])],
+        ("bnez", [rs, off]) => vec![step("bne", &[rs, "zero", off])],
+        ("blez", [rs, off]) => vec![step("bge", &["zero", rs, off])],
+        ("bgez", [rs, off]) => vec![step("bge", &[rs, "zero", off])],
+        ("bltz", [rs, off]) => vec![step("blt", &[rs, "zero", off])],
+        ("bgtz", [rs, off]) => vec![step("blt", &["zero", rs, off])],
+        ("bgt", [rs, rt, off]) => vec![step("blt", &[rt, rs, off])],
+        ("ble", [rs, rt, off]) => vec![step("bge", &[rt, rs, off])],
+        ("bgtu", [rs, rt, off]) => vec![step("bltu", &[rt, rs, off])],
+        ("bleu", [rs, rt, off]) => vec![step("bgeu", &[rt, rs, off])],
+
+        ("j", [off]) => vec![step("jal", &["zero", off])],
+        ("jal", [off]) => vec![step("jal", &["ra", off])],
+        ("jr", [rs]) => vec![step("jalr", &["zero", &format!("0({})", rs)])],
+        ("jalr", [rs]) => vec![step("jalr", &["ra", &format!("0({})", rs)])],
+        ("ret", []) => vec![step("jalr", &["zero", "0(ra)"])],
+
+        ("fmv.s", [rd, rs]) => vec![step("fsgnj.s", &[rd, rs, rs])],
+        ("fmv.d", [rd, rs]) => vec![step("fsgnj.d", &[rd, rs, rs])],
+        ("fmv.h", [rd, rs]) => vec![step("fsgnj.h", &[rd, rs, rs])],
+        ("fmv.q", [rd, rs]) => vec![step("fsgnj.q", &[rd, rs, rs])],
+        ("fneg.s", [rd, rs]) => vec![step("fsgnjn.s", &[rd, rs, rs])],
+        ("fneg.d", [rd, rs]) => vec![step("fsgnjn.d", &[rd, rs, rs])],
+        ("fneg.h", [rd, rs]) => vec![step("fsgnjn.h", &[rd, rs, rs])],
+        ("fneg.q", [rd, rs]) => vec![step("fsgnjn.q", &[rd, rs, rs])],
+        ("fabs.s", [rd, rs]) => vec![step("fsgnjx.s", &[rd, rs, rs])],
+        ("fabs.d", [rd, rs]) => vec![step("fsgnjx.d", &[rd, rs, rs])],
+        ("fabs.h", [rd, rs]) => vec![step("fsgnjx.h", &[rd, rs, rs])],
+        ("fabs.q", [rd, rs]) => vec![step("fsgnjx.q", &[rd, rs, rs])],
+
+        ("nop", _) | ("ret", _) | ("li", _) | ("la", _) | ("lla", _) | ("call", _) | ("tail", _)
+        | ("mv", _) | ("not", _) | ("neg", _) | ("negw", _) | ("sext.w", _) | ("zext.b", _)
+        | ("zext.h", _) | ("sext.b", _) | ("sext.h", _) | ("zext.w", _) | ("seqz", _)
+        | ("snez", _) | ("sltz", _) | ("sgtz", _) | ("sgt", _) | ("sgtu", _) | ("beqz", _) | ("bnez", _) | ("blez", _)
+        | ("bgez", _) | ("bltz", _) | ("bgtz", _) | ("bgt", _) | ("ble", _) | ("bgtu", _)
+        | ("bleu", _) | ("j", _) | ("jr", _) | ("fmv.s", _) | ("fmv.d", _) | ("fmv.h", _)
+        | ("fmv.q", _) | ("fneg.s", _) | ("fneg.d", _) | ("fneg.h", _) | ("fneg.q", _)
+        | ("fabs.s", _) | ("fabs.d", _) | ("fabs.h", _) | ("fabs.q", _) => {
+            return Some(Err(format!("{} 的操作数个数不正确", mnem)));
+        }
+        _ => return None,
+    };
+    Some(steps.iter().map(|(m, ops)| parse_statement(m, ops, xlen)).collect())
+}
+
+fn xlen_bits(xlen: Xlen) -> u32 {
+    match xlen {
+        Xlen::X32 => 32,
+        Xlen::X64 => 64,
+        Xlen::X128 => 128,
+    }
+}
+
+fn shift_pair(right: &'static str, rd: &str, rs: &str, shamt: u32) -> Vec<Step> {
+    let sh = shamt.to_string();
+    vec![step("slli", &[rd, rs, &sh]), step(right, &[rd, rd, &sh])]
+}
+
+/// `li` immediate, normalised to the register width: on RV32 both signed and
+/// unsigned 32-bit spellings are accepted, on RV64 also unsigned 64-bit hex.
+/// RV128 keeps an unsigned 64-bit value zero-extended.
+fn li_value(s: &str, xlen: Xlen) -> Result<i128, String> {
+    let v = match parse_int(s) {
+        Ok(v) => v as i128,
+        Err(e) => {
+            let t = s.trim();
+            match t.strip_prefix("0x").or_else(|| t.strip_prefix("0X")) {
+                Some(hex) => u64::from_str_radix(hex, 16).map_err(|_| e)? as i128,
+                None => return Err(e),
+            }
+        }
+    };
+    match xlen {
+        Xlen::X32 => {
+            if v < i32::MIN as i128 || v > u32::MAX as i128 {
+                return Err(format!("立即数超出范围: {} (32 位)", v));
+            }
+            Ok(v as u32 as i32 as i128)
+        }
+        Xlen::X64 => Ok(v as u64 as i64 as i128),
+        Xlen::X128 => Ok(v),
+    }
+}
+
+/// Materialise `value` in `rd` with lui/addi(w)/slli, mirroring `load_const`
+/// in GNU as: values that are not sign-extended 32-bit are built from their
+/// upper bits, shifted into place and completed with a 12-bit addi. RV128
+/// splits in 128 bits; narrower registers wrap at 64 as GNU as does.
+fn load_const(rd: &str, value: i128, xlen: Xlen, out: &mut Vec<Step>) {
+    let lower = ((value & 0xfff) ^ 0x800) - 0x800;
+    let upper = match xlen {
+        Xlen::X128 => value - lower,
+        _ => (value as i64).wrapping_sub(lower as i64) as i128,
+    };
+    if xlen != Xlen::X32 && value != value as i32 as i128 {
+        let mut shift = 12;
+        while (upper >> shift) & 1 == 0 {
+            shift += 1;
+        }
+        load_const(rd, upper >> shift, xlen, out);
+        out.push(step("slli", &[rd, rd, &shift.to_string()]));
+        if lower != 0 {
+            out.push(step("addi", &[rd, rd, &lower.to_string()]));
+        }
+    } else {
+        let has_hi = upper != 0;
+        if has_hi {
+            out.push(step("lui", &[rd, &((upper as u32) >> 12).to_string()]));
+        }
+        if lower != 0 || !has_hi {
+            let (op, rs) = match (has_hi, xlen) {
+                (false, _) => ("addi", "zero"),
+                (true, Xlen::X32) => ("addi", rd),
+                (true, _) => ("addiw", rd),
+            };
+            out.push(step(op, &[rd, rs, &lower.to_string()]));
+        }
+    }
+}
+
+/// Split a PC-relative offset into the auipc upper immediate and the low
+/// 12 bits consumed by the following addi/jalr.
+fn pcrel(off: &str) -> Result<(String, String), String> {
+    let off = parse_int(off)?;
+    let hi = (off + 0x800) >> 12;
+    if !(-(1 << 19)..(1 << 19)).contains(&hi) {
+        return Err(format!("偏移 {} 超出 auipc 的寻址范围", off));
+    }
+    Ok((hi.to_string(), (off - (hi << 12)).to_string()))
+}
@@ -0,0 +1,124 @@
+use crate::asm::*;
+use crate::riscv::imm::Xlen;
+use super::common::{parse_int, parse_register};
+
+const OPS: [&str; 12] = [
+    "lr", "sc", "amoswap", "amoadd", "amoxor", "amoand", "amoor", "amomin", "amomax", "amominu", "amomaxu",
+    "amocas",
+];
+
+/// Address operand `(rs1)`; a zero offset (`0(rs1)`) is also accepted.
+fn parse_address(s: &str) -> Result<u8, String> {
+    let t = s.trim();
+    let open = t.find('(').ok_or_else(|| format!("原子指令的地址应写作 (rs1): {}", t))?;
+    if !t.ends_with(')') { return Err(format!("原子指令的地址应写作 (rs1): {}", t)); }
+    let off = t[..open].trim();
+    if !off.is_empty() && parse_int(off)? != 0 { return Err("原子指令不支持地址偏移".into()); }
+    parse_register(&t[open + 1..t.len() - 1])
+}
+
+fn parse(op: &str, width: &str, ordering: Option<&str>, ops: &[String], xlen: Xlen) -> Result<Instruction, String> {
+    let (aq, rl) = match ordering {
+        None => (false, false),
+        Some("aq") => (true, false),
+        Some("rl") => (false, true),
+        Some("aqrl") => (true, true),
+        Some(o) => return Err(format!("未知的内存序后缀: .{}", o)),
+    };
+    // amocas one step wider than XLEN works on an even/odd register pair
+    let pair = op == "amocas" && matches!((width, xlen), ("d", Xlen::X32) | ("q", Xlen::X64));
+    match (width, xlen) {
+        ("w", _) | ("d", Xlen::X64) | ("d", Xlen::X128) | ("q", Xlen::X128) => {}
+        _ if pair => {}
+        ("b", _) | ("h", _) if op == "lr" || op == "sc" => {
+            return Err(format!("{}.{} 不存在 (Zabha 没有 lr/sc)", op, width))
+        }
+        ("b", _) | ("h", _) => {}
+        ("d", _) => return Err(format!("{}.d 仅在 RV64/128 可用", op)),
+        ("q", _) => return Err(format!("{}.q 仅在 RV128 可用", op)),
+        _ => return Err(format!("未知的原子操作宽度: .{}", width)),
+    }
+    let a = if op == "lr" {
+        if ops.len() != 2 { return Err(format!("用法: lr.{} rd, (rs1)", width)); }
+        AType { rd: parse_register(&ops[0])?, rs1: parse_address(&ops[1])?, rs2: 0, aq, rl }
+    } else {
+        if ops.len() != 3 { return Err(format!("用法: {}.{} rd, rs2, (rs1)", op, width)); }
+        AType { rd: parse_register(&ops[0])?, rs2: parse_register(&ops[1])?, rs1: parse_address(&ops[2])?, aq, rl }
+    };
+    if pair && (a.rd % 2 != 0 || a.rs2 % 2 != 0) {
+        return Err(format!("{}.{} 的 rd 和 rs2 必须是偶数编号的寄存器（寄存器对）", op, width));
+    }
+    Ok(match width {
+        "b" | "h" => {
+            let w = if width == "b" { AmoWidth::B } else { AmoWidth::H };
+            match op {
+                "amoswap" => RVZabha::Amoswap(w, a),
+                "amoadd" => RVZabha::Amoadd(w, a),
+                "amoxor" => RVZabha::Amoxor(w, a),
+                "amoand" => RVZabha::Amoand(w, a),
+                "amoor" => RVZabha::Amoor(w, a),
+                "amomin" => RVZabha::Amomin(w, a),
+                "amomax" => RVZabha::Amomax(w, a),
+                "amominu" => RVZabha::Amominu(w, a),
+                "amomaxu" => RVZabha::Amomaxu(w, a),
+                _ => RVZabha::Amocas(w, a),
+            }.into()
+        }
+        "w" => match op {
+            "lr" => RV32A::Lrw(a),
+            "sc" => RV32A::Scw(a),
+            "amoswap" => RV32A::Amoswapw(a),
+            "amoadd" => RV32A::Amoaddw(a),
+            "amoxor" => RV32A::Amoxorw(a),
+            "amoand" => RV32A::Amoandw(a),
+            "amoor" => RV32A::Amoorw(a),
+            "amomin" => RV32A::Amominw(a),
+            "amomax" => RV32A::Amomaxw(a),
+            "amominu" => RV32A::Amominuw(a),
+            "amomaxu" => RV32A::Amomaxuw(a),
+            _ => RV32A::Amocasw(a),
+        }.into(),
+        "d" => match op {
+            "lr" => RV64A::Lrd(a),
+            "sc" => RV64A::Scd(a),
+            "amoswap" => RV64A::Amoswapd(a),
+            "amoadd" => RV64A::Amoaddd(a),
+            "amoxor" => RV64A::Amoxord(a),
+            "amoand" => RV64A::Amoandd(a),
+            "amoor" => RV64A::Amoord(a),
+            "amomin" => RV64A::Amomind(a),
+            "amomax" => RV64A::Amomaxd(a),
+            "amominu" => RV64A::Amominud(a),
+            "amomaxu" => RV64A::Amomaxud(a),
+            _ => RV64A::Amocasd(a),
+        }.into(),
+        _ => match op {
+            "lr" => RV128A::Lrq(a),
+            "sc" => RV128A::Scq(a),
+            "amoswap" => RV128A::Amoswapq(a),
+            "amoadd" => RV128A::Amoaddq(a),
+            "amoxor" => RV128A::Amoxorq(a),
+            "amoand" => RV128A::Amoandq(a),
+            "amoor" => RV128A::Amoorq(a),
+            "amomin" => RV128A::Amominq(a),
+            "amomax" => RV128A::Amomaxq(a),
+            "amominu" => RV128A::Amominuq(a),
+            "amomaxu" => RV128A::Amomaxuq(a),
+            _ => RV128A::Amocasq(a),
+        }.into(),
+    })
+}
+
+/// `lr/sc/amo*.{w,d,q}[.aq|.rl|.aqrl]`, and the Zabha `amo*.{b,h}`
+pub(crate) fn try_parse(mnem: &str, ops: &[String], xlen: Xlen) -> Option<Result<Instruction, String>> {
+    let mut parts = mnem.split('.');
+    let op = parts.next()?;
+    if !OPS.contains(&op) { return None; }
+    let width = match parts.next() {
+        Some(w) => w,
+        None => return Some(Err(format!("{} 缺少宽度后缀 (.b/.h/.w/.d/.q)", op))),
+    };
+    let ordering = parts.next();
+    if parts.next().is_some() { return Some(Err(format!("未支持的指令: {}", mnem))); }
+    Some(parse(op, width, ordering, ops, xlen))
+}
@@ -0,0 +1,111 @@
+use crate::asm::*;
+use crate::riscv::imm::{Imm, Xlen};
+use super::common::{parse_int, parse_register};
+
+fn r_type(rd: u8, rs1: u8, rs2: u8) -> RType {
+    RType { rd, rs1, rs2, funct3: 0, funct7: 0 }
+}
+
+/// Register-register forms: rd, rs1, rs2.
+fn binary(mnem: &str) -> Option<fn(RType) -> RVB> {
+    use RVB::*;
+    Some(match mnem {
+        "sh1add" => Sh1add,
+        "sh2add" => Sh2add,
+        "sh3add" => Sh3add,
+        "add.uw" => Adduw,
+        "sh1add.uw" => Sh1adduw,
+        "sh2add.uw" => Sh2adduw,
+        "sh3add.uw" => Sh3adduw,
+        "andn" => Andn,
+        "orn" => Orn,
+        "xnor" => Xnor,
+        "max" => Max,
+        "maxu" => Maxu,
+        "min" => Min,
+        "minu" => Minu,
+        "rol" => Rol,
+        "ror" => Ror,
+        "rolw" => Rolw,
+        "rorw" => Rorw,
+        "clmul" => Clmul,
+        "clmulr" => Clmulr,
+        "clmulh" => Clmulh,
+        "bclr" => Bclr,
+        "bext" => Bext,
+        "binv" => Binv,
+        "bset" => Bset,
+        _ => return None,
+    })
+}
+
+/// Unary forms: rd, rs1.
+fn unary(mnem: &str) -> Option<fn(RType) -> RVB> {
+    use RVB::*;
+    Some(match mnem {
+        "clz" => Clz,
+        "ctz" => Ctz,
+        "cpop" => Cpop,
+        "clzw" => Clzw,
+        "ctzw" => Ctzw,
+        "cpopw" => Cpopw,
+        "sext.b" => Sextb,
+        "sext.h" => Sexth,
+        "zext.h" => Zexth,
+        "orc.b" => Orcb,
+        "rev8" => Rev8,
+        _ => return None,
+    })
+}
+
+/// Shifts and single-bit operations by immediate: rd, rs1, shamt.
+fn shift(mnem: &str) -> Option<fn(IType) -> RVB> {
+    use RVB::*;
+    Some(match mnem {
+        "slli.uw" => Slliuw,
+        "rori" => Rori,
+        "roriw" => Roriw,
+        "bclri" => Bclri,
+        "bexti" => Bexti,
+        "binvi" => Binvi,
+        "bseti" => Bseti,
+        _ => return None,
+    })
+}
+
+fn parse(mnem: &str, ops: &[String], xlen: Xlen) -> Result<RVB, String> {
+    let reg = |s: &String| parse_register(s);
+    let inst = if let Some(make) = binary(mnem) {
+        if ops.len() != 3 { return Err(format!("用法: {} rd, rs1, rs2", mnem)); }
+        make(r_type(reg(&ops[0])?, reg(&ops[1])?, reg(&ops[2])?))
+    } else if let Some(make) = unary(mnem) {
+        if ops.len() != 2 { return Err(format!("用法: {} rd, rs1", mnem)); }
+        make(r_type(reg(&ops[0])?, reg(&ops[1])?, 0))
+    } else if let Some(make) = shift(mnem) {
+        if ops.len() != 3 { return Err(format!("用法: {} rd, rs1, shamt", mnem)); }
+        let bits = match mnem {
+            "slli.uw" => 6,
+            "roriw" => 5,
+            _ if xlen == Xlen::X32 => 5,
+            _ => 6,
+        };
+        let shamt = parse_int(&ops[2])?;
+        if !(0..(1i64 << bits)).contains(&shamt) {
+            return Err(format!("shamt {} 超出范围 ({} 位)", shamt, bits));
+        }
+        make(IType { rd: reg(&ops[0])?, rs1: reg(&ops[1])?, funct3: 0, imm: Imm::new(shamt as u32, 7) })
+    } else {
+        unreachable!()
+    };
+    if inst.is_rv64_only() && xlen == Xlen::X32 {
+        return Err(format!("{} 仅在 RV64/128 可用", mnem));
+    }
+    Ok(inst)
+}
+
+pub(crate) fn try_parse(mnem: &str, ops: &[String], xlen: Xlen) -> Option<Result<Instruction, String>> {
+    if binary(mnem).is_none() && unary(mnem).is_none() && shift(mnem).is_none() {
+        return None;
+    }
+    Some(parse(mnem, ops, xlen).map(Instruction::from))
+}
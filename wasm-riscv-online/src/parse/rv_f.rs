@@ -0,0 +1,261 @@
+use crate::asm::*;
+use crate::asm::rvf::{fli_constant, rounding_mode_from_name, FLI_CONSTANTS};
+use crate::isa::{FRM_DYN, FRM_RTZ};
+use crate::riscv::imm::{Imm, Xlen};
+use super::common::{parse_fp_register, parse_mem_operand, parse_register};
+
+/// Splits off the optional trailing rounding-mode operand of an instruction
+/// that otherwise takes `n` operands.
+fn split_rm<'a>(mnem: &str, ops: &'a [String], n: usize) -> Result<(&'a [String], Option<u8>), String> {
+    if ops.len() == n {
+        Ok((ops, None))
+    } else if ops.len() == n + 1 {
+        let rm = rounding_mode_from_name(&ops[n].to_lowercase())
+            .ok_or_else(|| format!("未知的舍入模式: {}（可选 rne/rtz/rdn/rup/rmm/dyn）", ops[n].trim()))?;
+        Ok((&ops[..n], Some(rm)))
+    } else {
+        Err(format!("{} 的操作数个数不正确", mnem))
+    }
+}
+
+/// Builds an rm-carrying instruction; without an explicit mode the
+/// instruction's default (`rne` for exact conversions, `dyn` otherwise) is used.
+fn with_rm(rm: Option<u8>, make: impl Fn(u8) -> RVF) -> Instruction {
+    let rm = rm.unwrap_or_else(|| make(FRM_DYN).default_rounding_mode());
+    make(rm).into()
+}
+
+fn expect(mnem: &str, ops: &[String], n: usize) -> Result<(), String> {
+    if ops.len() == n { Ok(()) } else { Err(format!("{} 的操作数个数不正确", mnem)) }
+}
+
+fn r_type(rd: u8, rs1: u8, rs2: u8, rm: u8) -> RType {
+    RType { rd, rs1, rs2, funct3: rm, funct7: 0 }
+}
+
+/// Format letter of loads, stores and `fmv`: `w` (or the older `s`) is single precision.
+fn mem_fmt(s: &str) -> Option<FpFmt> {
+    match s {
+        "w" | "s" => Some(FpFmt::S),
+        "d" | "h" | "q" => FpFmt::from_suffix(s),
+        _ => None,
+    }
+}
+
+/// Operand of `fli`: one of [`FLI_CONSTANTS`], by its name or by value;
+/// `min` is also accepted as the format's smallest normal number.
+fn parse_fli(s: &str, fmt: FpFmt) -> Result<u8, String> {
+    let s = s.trim().to_lowercase();
+    let index = FLI_CONSTANTS.iter().position(|&c| c == s).or_else(|| {
+        let v = s.parse::<f64>().ok()?;
+        // no constant is zero, and the Q minimum underflows an f64 to it
+        if v == 0.0 {
+            return (fli_constant(fmt, 1) == s).then_some(1);
+        }
+        (0..32).position(|i| fli_constant(fmt, i).parse::<f64>() == Ok(v))
+    });
+    index.map(|i| i as u8).ok_or_else(|| format!("fli 不能加载常量 {}", s))
+}
+
+/// The F/D/Q/Zfh/Zfa instruction a mnemonic names, before its operands are read.
+#[derive(Clone, Copy)]
+enum Form {
+    Load(FpFmt),
+    Store(FpFmt),
+    R4(fn(FpFmt, R4Type) -> RVF, FpFmt),
+    /// rd, rs1, rs2 with a rounding mode
+    Arith(fn(FpFmt, RType) -> RVF, FpFmt),
+    Sqrt(FpFmt),
+    /// rd, rs1, rs2 without a rounding mode
+    Sign(fn(FpFmt, RType) -> RVF, FpFmt),
+    Cmp(fn(FpFmt, RType) -> RVF, FpFmt),
+    Class(FpFmt),
+    Cvt(FpFmt, FpFmt),
+    ToInt(FpInt, FpFmt),
+    FromInt(FpFmt, FpInt),
+    MvToInt(FpFmt),
+    MvFromInt(FpFmt),
+    Fli(FpFmt),
+    /// rd, rs1 with a rounding mode
+    Round(fn(FpFmt, RType) -> RVF, FpFmt),
+    Cvtmod,
+    MvHigh(FpFmt),
+    MvPair(FpFmt),
+}
+
+fn classify(mnem: &str) -> Option<Form> {
+    let parts: Vec<&str> = mnem.split('.').collect();
+    Some(match parts.as_slice() {
+        [m] if m.len() == 3 && m.starts_with("fl") && !m.ends_with('s') => Form::Load(mem_fmt(&m[2..])?),
+        [m] if m.len() == 3 && m.starts_with("fs") && !m.ends_with('s') => Form::Store(mem_fmt(&m[2..])?),
+        ["fmv", "x", t] => Form::MvToInt(mem_fmt(t)?),
+        ["fmv", t, "x"] => Form::MvFromInt(mem_fmt(t)?),
+        ["fmvh", "x", t] => Form::MvHigh(FpFmt::from_suffix(t)?),
+        ["fmvp", t, "x"] => Form::MvPair(FpFmt::from_suffix(t)?),
+        ["fcvtmod", "w", "d"] => Form::Cvtmod,
+        ["fcvt", to, from] => match (FpFmt::from_suffix(to), FpFmt::from_suffix(from)) {
+            (Some(to), Some(from)) if to != from => Form::Cvt(to, from),
+            (Some(to), None) => Form::FromInt(to, FpInt::from_suffix(from)?),
+            (None, Some(from)) => Form::ToInt(FpInt::from_suffix(to)?, from),
+            _ => return None,
+        },
+        [op, t] => {
+            let fmt = FpFmt::from_suffix(t)?;
+            match *op {
+                "fmadd" => Form::R4(RVF::Fmadd, fmt),
+                "fmsub" => Form::R4(RVF::Fmsub, fmt),
+                "fnmsub" => Form::R4(RVF::Fnmsub, fmt),
+                "fnmadd" => Form::R4(RVF::Fnmadd, fmt),
+                "fadd" => Form::Arith(RVF::Fadd, fmt),
+                "fsub" => Form::Arith(RVF::Fsub, fmt),
+                "fmul" => Form::Arith(RVF::Fmul, fmt),
+                "fdiv" => Form::Arith(RVF::Fdiv, fmt),
+                "fsqrt" => Form::Sqrt(fmt),
+                "fsgnj" => Form::Sign(RVF::Fsgnj, fmt),
+                "fsgnjn" => Form::Sign(RVF::Fsgnjn, fmt),
+                "fsgnjx" => Form::Sign(RVF::Fsgnjx, fmt),
+                "fmin" => Form::Sign(RVF::Fmin, fmt),
+                "fmax" => Form::Sign(RVF::Fmax, fmt),
+                "feq" => Form::Cmp(RVF::Feq, fmt),
+                "flt" => Form::Cmp(RVF::Flt, fmt),
+                "fle" => Form::Cmp(RVF::Fle, fmt),
+                "fclass" => Form::Class(fmt),
+                "fli" => Form::Fli(fmt),
+                "fminm" => Form::Sign(RVF::Fminm, fmt),
+                "fmaxm" => Form::Sign(RVF::Fmaxm, fmt),
+                "fround" => Form::Round(RVF::Fround, fmt),
+                "froundnx" => Form::Round(RVF::Froundnx, fmt),
+                "fleq" => Form::Cmp(RVF::Fleq, fmt),
+                "fltq" => Form::Cmp(RVF::Fltq, fmt),
+                _ => return None,
+            }
+        }
+        _ => return None,
+    })
+}
+
+/// Under Zfinx (`zfinx`) the floating-point operands are integer registers.
+fn parse(form: Form, mnem: &str, ops: &[String], xlen: Xlen, zfinx: bool) -> Result<Instruction, String> {
+    let f: fn(&str) -> Result<u8, String> = if zfinx { parse_register } else { parse_fp_register };
+    let x = parse_register;
+    Ok(match form {
+        Form::Load(fmt) => {
+            expect(mnem, ops, 2)?;
+            let (imm_bits, rs1) = parse_mem_operand(&ops[1])?;
+            RVF::Load(fmt, IType { rd: f(&ops[0])?, rs1, funct3: 0, imm: Imm::new(imm_bits, 12) }).into()
+        }
+        Form::Store(fmt) => {
+            expect(mnem, ops, 2)?;
+            let (imm_bits, rs1) = parse_mem_operand(&ops[1])?;
+            RVF::Store(fmt, SType { rs1, rs2: f(&ops[0])?, funct3: 0, imm: Imm::new(imm_bits, 12) }).into()
+        }
+
+        // fused multiply-add: rd, rs1, rs2, rs3 [, rm]
+        Form::R4(make, fmt) => {
+            let (ops, rm) = split_rm(mnem, ops, 4)?;
+            let (rd, rs1, rs2, rs3) = (f(&ops[0])?, f(&ops[1])?, f(&ops[2])?, f(&ops[3])?);
+            with_rm(rm, |rm| make(fmt, R4Type { rd, rs1, rs2, rs3, funct3: rm, funct2: fmt.bits() }))
+        }
+        // arithmetic: rd, rs1, rs2 [, rm]
+        Form::Arith(make, fmt) => {
+            let (ops, rm) = split_rm(mnem, ops, 3)?;
+            let (rd, rs1, rs2) = (f(&ops[0])?, f(&ops[1])?, f(&ops[2])?);
+            with_rm(rm, |rm| make(fmt, r_type(rd, rs1, rs2, rm)))
+        }
+        Form::Sqrt(fmt) => {
+            let (ops, rm) = split_rm(mnem, ops, 2)?;
+            let (rd, rs1) = (f(&ops[0])?, f(&ops[1])?);
+            with_rm(rm, |rm| RVF::Fsqrt(fmt, r_type(rd, rs1, 0, rm)))
+        }
+
+        // sign injection and min/max: rd, rs1, rs2
+        Form::Sign(make, fmt) => {
+            expect(mnem, ops, 3)?;
+            make(fmt, r_type(f(&ops[0])?, f(&ops[1])?, f(&ops[2])?, 0)).into()
+        }
+        // comparisons and classify write an integer register
+        Form::Cmp(make, fmt) => {
+            expect(mnem, ops, 3)?;
+            make(fmt, r_type(x(&ops[0])?, f(&ops[1])?, f(&ops[2])?, 0)).into()
+        }
+        Form::Class(fmt) => {
+            expect(mnem, ops, 2)?;
+            RVF::Fclass(fmt, r_type(x(&ops[0])?, f(&ops[1])?, 0, 0)).into()
+        }
+
+        // conversions; exact ones (widening, or integers that fit) default to rne
+        Form::Cvt(to, from) => {
+            let (ops, rm) = split_rm(mnem, ops, 2)?;
+            let (rd, rs1) = (f(&ops[0])?, f(&ops[1])?);
+            with_rm(rm, |rm| RVF::Fcvt(to, from, r_type(rd, rs1, 0, rm)))
+        }
+        Form::ToInt(int, fmt) => {
+            if int.is_64() && xlen == Xlen::X32 { return Err(format!("{} 仅在 RV64/128 可用", mnem)); }
+            let (ops, rm) = split_rm(mnem, ops, 2)?;
+            let (rd, rs1) = (x(&ops[0])?, f(&ops[1])?);
+            with_rm(rm, |rm| RVF::FcvtToInt(int, fmt, r_type(rd, rs1, 0, rm)))
+        }
+        Form::FromInt(fmt, int) => {
+            if int.is_64() && xlen == Xlen::X32 { return Err(format!("{} 仅在 RV64/128 可用", mnem)); }
+            let (ops, rm) = split_rm(mnem, ops, 2)?;
+            let (rd, rs1) = (f(&ops[0])?, x(&ops[1])?);
+            with_rm(rm, |rm| RVF::FcvtFromInt(fmt, int, r_type(rd, rs1, 0, rm)))
+        }
+
+        // bit-pattern moves need an integer register as wide as the format
+        Form::MvToInt(fmt) | Form::MvFromInt(fmt) => {
+            match fmt {
+                FpFmt::D if xlen == Xlen::X32 => return Err(format!("{} 仅在 RV64/128 可用", mnem)),
+                FpFmt::Q if xlen != Xlen::X128 => return Err(format!("{} 仅在 RV128 可用", mnem)),
+                _ => {}
+            }
+            expect(mnem, ops, 2)?;
+            if let Form::MvToInt(_) = form {
+                RVF::FmvToInt(fmt, r_type(x(&ops[0])?, f(&ops[1])?, 0, 0)).into()
+            } else {
+                RVF::FmvFromInt(fmt, r_type(f(&ops[0])?, x(&ops[1])?, 0, 0)).into()
+            }
+        }
+
+        // Zfa
+        Form::Fli(fmt) => {
+            expect(mnem, ops, 2)?;
+            RVF::Fli(fmt, r_type(f(&ops[0])?, parse_fli(&ops[1], fmt)?, 0, 0)).into()
+        }
+        Form::Round(make, fmt) => {
+            let (ops, rm) = split_rm(mnem, ops, 2)?;
+            let (rd, rs1) = (f(&ops[0])?, f(&ops[1])?);
+            with_rm(rm, |rm| make(fmt, r_type(rd, rs1, 0, rm)))
+        }
+        Form::Cvtmod => {
+            let (ops, rm) = split_rm(mnem, ops, 2)?;
+            if rm != Some(FRM_RTZ) { return Err(format!("{} 的舍入模式必须写作 rtz", mnem)); }
+            RVF::FcvtmodWD(r_type(x(&ops[0])?, f(&ops[1])?, 0, FRM_RTZ)).into()
+        }
+        // the upper half / a register pair of a format twice as wide as XLEN
+        Form::MvHigh(fmt) | Form::MvPair(fmt) => {
+            match (fmt, xlen) {
+                (FpFmt::D, Xlen::X32) | (FpFmt::Q, Xlen::X64) => {}
+                (FpFmt::D, _) => return Err(format!("{} 仅在 RV32 可用", mnem)),
+                (FpFmt::Q, _) => return Err(format!("{} 仅在 RV64 可用", mnem)),
+                _ => return Err(format!("未支持的指令: {}", mnem)),
+            }
+            if let Form::MvHigh(_) = form {
+                expect(mnem, ops, 2)?;
+                RVF::Fmvh(fmt, r_type(x(&ops[0])?, f(&ops[1])?, 0, 0)).into()
+            } else {
+                expect(mnem, ops, 3)?;
+                RVF::Fmvp(fmt, r_type(f(&ops[0])?, x(&ops[1])?, x(&ops[2])?, 0)).into()
+            }
+        }
+    })
+}
+
+pub(crate) fn try_parse(mnem: &str, ops: &[String], xlen: Xlen) -> Option<Result<Instruction, String>> {
+    classify(mnem).map(|form| parse(form, mnem, ops, xlen, false))
+}
+
+/// F/D/Zfh with integer-register operands (Zfinx/Zdinx/Zhinx).
+pub(crate) fn try_parse_zfinx(mnem: &str, ops: &[String], xlen: Xlen) -> Option<Result<Instruction, String>> {
+    classify(mnem).map(|form| parse(form, mnem, ops, xlen, true))
+}
@@ -28,7 +28,7 @@ pub(crate) fn try_parse(mnem: &str, ops: &[String], xlen: Xlen) -> Option<Result
             let rd = match parse_register(&ops[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
             let imm = match parse_int(&ops[1]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
             let imm_bits = match imm_signed_bits(imm, 21) { Ok(v) => v, Err(e) => return Some(Err(e)) };
-            let j = JType { rd, imm: Imm::new(imm_bits, 12) }; // keep 12 valid bits consistent with decoder
+            let j = JType { rd, imm: Imm::new(imm_bits, 21) };
             Some(Ok(RV32I::Jal(j).into()))
         }
         // JALR
@@ -56,7 +56,7 @@ pub(crate) fn try_parse(mnem: &str, ops: &[String], xlen: Xlen) -> Option<Result
             let imm = match parse_int(&ops[2]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
             if (imm & 1) != 0 { return Some(Err("分支偏移必须是2字节对齐".into())); }
             let imm_bits = match imm_signed_bits(imm, 13) { Ok(v) => v, Err(e) => return Some(Err(e)) };
-            let b = BType { rs1, rs2, funct3: 0, imm: Imm::new(imm_bits, 12) };
+            let b = BType { rs1, rs2, funct3: 0, imm: Imm::new(imm_bits, 13) };
             let inst = match mnem {
                 "beq" => RV32I::Beq(b).into(),
                 "bne" => RV32I::Bne(b).into(),
@@ -70,7 +70,7 @@ pub(crate) fn try_parse(mnem: &str, ops: &[String], xlen: Xlen) -> Option<Result
         }
 
         // Loads
-        "lb" | "lh" | "lw" | "lbu" | "lhu" | "lwu" | "ld" => {
+        "lb" | "lh" | "lw" | "lbu" | "lhu" | "lwu" | "ld" | "ldu" | "lq" => {
             if ops.len() != 2 { return Some(Err("用法: lw rd, imm(rs1)".into())); }
             let rd = match parse_register(&ops[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
             let (imm_bits, rs1) = match parse_mem_operand(&ops[1]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
@@ -83,13 +83,15 @@ pub(crate) fn try_parse(mnem: &str, ops: &[String], xlen: Xlen) -> Option<Result
                 "lhu" => RV32I::Lhu(i).into(),
                 "lwu" => match xlen { Xlen::X64 | Xlen::X128 => RV64I::Lwu(i).into(), _ => return Some(Err("lwu 仅在 RV64/128 可用".into())) },
                 "ld"  => match xlen { Xlen::X64 | Xlen::X128 => RV64I::Ld(i).into(), _ => return Some(Err("ld 仅在 RV64/128 可用".into())) },
+                "ldu" => match xlen { Xlen::X128 => RV128I::Ldu(i).into(), _ => return Some(Err("ldu 仅在 RV128 可用".into())) },
+                "lq"  => match xlen { Xlen::X128 => RV128I::Lq(i).into(), _ => return Some(Err("lq 仅在 RV128 可用".into())) },
                 _ => unreachable!(),
             };
             Some(Ok(inst))
         }
 
         // Stores
-        "sb" | "sh" | "sw" | "sd" => {
+        "sb" | "sh" | "sw" | "sd" | "sq" => {
             if ops.len() != 2 { return Some(Err("用法: sw rs2, imm(rs1)".into())); }
             let rs2 = match parse_register(&ops[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
             let (imm_bits, rs1) = match parse_mem_operand(&ops[1]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
@@ -99,6 +101,7 @@ pub(crate) fn try_parse(mnem: &str, ops: &[String], xlen: Xlen) -> Option<Result
                 "sh" => RV32I::Sh(s).into(),
                 "sw" => RV32I::Sw(s).into(),
                 "sd" => match xlen { Xlen::X64 | Xlen::X128 => RV64I::Sd(s).into(), _ => return Some(Err("sd 仅在 RV64/128 可用".into())) },
+                "sq" => match xlen { Xlen::X128 => RV128I::Sq(s).into(), _ => return Some(Err("sq 仅在 RV128 可用".into())) },
                 _ => unreachable!(),
             };
             Some(Ok(inst))
@@ -129,7 +132,7 @@ pub(crate) fn try_parse(mnem: &str, ops: &[String], xlen: Xlen) -> Option<Result
             let rd = match parse_register(&ops[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
             let rs1 = match parse_register(&ops[1]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
             let shamt = match parse_int(&ops[2]) { Ok(v) => v as u32, Err(e) => return Some(Err(e)) };
-            let bits = match xlen { Xlen::X32 => 5, Xlen::X64 | Xlen::X128 => 6 };
+            let bits = match xlen { Xlen::X32 => 5, Xlen::X64 => 6, Xlen::X128 => 7 };
             // range check
             if shamt >= (1u32 << bits) { return Some(Err(format!("shamt {} 超出范围 ({} 位)", shamt, bits))); }
             let i = IType { rd, rs1, funct3: 0, imm: Imm::new(shamt, bits) };
@@ -206,6 +209,44 @@ pub(crate) fn try_parse(mnem: &str, ops: &[String], xlen: Xlen) -> Option<Result
                 }
             }
         }
+
+        // RV128I D variants
+        "addid" | "sllid" | "srlid" | "sraid" | "addd" | "subd" | "slld" | "srld" | "srad" => {
+            match xlen { Xlen::X128 => {}, _ => return Some(Err("该指令仅在 RV128 可用".into())) }
+            if ops.len() != 3 { return Some(Err(format!("用法: {} rd, rs1, {}", mnem, if mnem.ends_with("id") { "imm" } else { "rs2" }))); }
+            let rd = match parse_register(&ops[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
+            let rs1 = match parse_register(&ops[1]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
+            let inst = match mnem {
+                "addid" => {
+                    let imm = match parse_int(&ops[2]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
+                    let imm_bits = match imm_signed_bits(imm, 12) { Ok(v) => v, Err(e) => return Some(Err(e)) };
+                    RV128I::Addid(IType { rd, rs1, funct3: 0, imm: Imm::new(imm_bits, 12) }).into()
+                }
+                "sllid" | "srlid" | "sraid" => {
+                    let shamt = match parse_int(&ops[2]) { Ok(v) => v as u32, Err(e) => return Some(Err(e)) };
+                    if shamt >= 64 { return Some(Err(format!("shamt {} 超出范围 (6 位)", shamt))); }
+                    let i = IType { rd, rs1, funct3: 0, imm: Imm::new(shamt, 6) };
+                    match mnem {
+                        "sllid" => RV128I::Sllid(i).into(),
+                        "srlid" => RV128I::Srlid(i).into(),
+                        _ => RV128I::Sraid(i).into(),
+                    }
+                }
+                _ => {
+                    let rs2 = match parse_register(&ops[2]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
+                    let r = RType { rd, rs1, rs2, funct3: 0, funct7: 0 };
+                    match mnem {
+                        "addd" => RV128I::Addd(r).into(),
+                        "subd" => RV128I::Subd(r).into(),
+                        "slld" => RV128I::Slld(r).into(),
+                        "srld" => RV128I::Srld(r).into(),
+                        "srad" => RV128I::Srad(r).into(),
+                        _ => unreachable!(),
+                    }
+                }
+            };
+            Some(Ok(inst))
+        }
         _ => None,
     }
 }
@@ -0,0 +1,108 @@
+use crate::asm::*;
+use crate::riscv::imm::{Imm, Xlen};
+use super::common::{parse_int, parse_register};
+
+fn r_type(rd: u8, rs1: u8, rs2: u8) -> RType {
+    RType { rd, rs1, rs2, funct3: 0, funct7: 0 }
+}
+
+/// Register-register forms: rd, rs1, rs2.
+fn binary(mnem: &str) -> Option<fn(RType) -> RVK> {
+    use RVK::*;
+    Some(match mnem {
+        "pack" => Pack,
+        "packh" => Packh,
+        "packw" => Packw,
+        "xperm4" => Xperm4,
+        "xperm8" => Xperm8,
+        "aes64ds" => Aes64ds,
+        "aes64dsm" => Aes64dsm,
+        "aes64es" => Aes64es,
+        "aes64esm" => Aes64esm,
+        "aes64ks2" => Aes64ks2,
+        "sha512sig0h" => Sha512sig0h,
+        "sha512sig0l" => Sha512sig0l,
+        "sha512sig1h" => Sha512sig1h,
+        "sha512sig1l" => Sha512sig1l,
+        "sha512sum0r" => Sha512sum0r,
+        "sha512sum1r" => Sha512sum1r,
+        _ => return None,
+    })
+}
+
+/// Unary forms: rd, rs1.
+fn unary(mnem: &str) -> Option<fn(RType) -> RVK> {
+    use RVK::*;
+    Some(match mnem {
+        "brev8" => Brev8,
+        "zip" => Zip,
+        "unzip" => Unzip,
+        "aes64im" => Aes64im,
+        "sha256sig0" => Sha256sig0,
+        "sha256sig1" => Sha256sig1,
+        "sha256sum0" => Sha256sum0,
+        "sha256sum1" => Sha256sum1,
+        "sha512sig0" => Sha512sig0,
+        "sha512sig1" => Sha512sig1,
+        "sha512sum0" => Sha512sum0,
+        "sha512sum1" => Sha512sum1,
+        "sm3p0" => Sm3p0,
+        "sm3p1" => Sm3p1,
+        _ => return None,
+    })
+}
+
+/// Byte-select forms: rd, rs1, rs2, bs.
+fn byte_select(mnem: &str) -> Option<fn(KType) -> RVK> {
+    use RVK::*;
+    Some(match mnem {
+        "aes32dsi" => Aes32dsi,
+        "aes32dsmi" => Aes32dsmi,
+        "aes32esi" => Aes32esi,
+        "aes32esmi" => Aes32esmi,
+        "sm4ed" => Sm4ed,
+        "sm4ks" => Sm4ks,
+        _ => return None,
+    })
+}
+
+fn parse(mnem: &str, ops: &[String], xlen: Xlen) -> Result<RVK, String> {
+    let reg = |s: &String| parse_register(s);
+    let inst = if let Some(make) = binary(mnem) {
+        if ops.len() != 3 { return Err(format!("用法: {} rd, rs1, rs2", mnem)); }
+        make(r_type(reg(&ops[0])?, reg(&ops[1])?, reg(&ops[2])?))
+    } else if let Some(make) = unary(mnem) {
+        if ops.len() != 2 { return Err(format!("用法: {} rd, rs1", mnem)); }
+        make(r_type(reg(&ops[0])?, reg(&ops[1])?, 0))
+    } else if let Some(make) = byte_select(mnem) {
+        if ops.len() != 4 { return Err(format!("用法: {} rd, rs1, rs2, bs", mnem)); }
+        let bs = parse_int(&ops[3])?;
+        if !(0..=3).contains(&bs) {
+            return Err(format!("bs {} 超出范围 (0..=3)", bs));
+        }
+        make(KType { rd: reg(&ops[0])?, rs1: reg(&ops[1])?, rs2: reg(&ops[2])?, bs: bs as u8 })
+    } else if mnem == "aes64ks1i" {
+        if ops.len() != 3 { return Err(format!("用法: {} rd, rs1, rnum", mnem)); }
+        let rnum = parse_int(&ops[2])?;
+        if !(0..=10).contains(&rnum) {
+            return Err(format!("rnum {} 超出范围 (0..=10)", rnum));
+        }
+        RVK::Aes64ks1i(IType { rd: reg(&ops[0])?, rs1: reg(&ops[1])?, funct3: 0, imm: Imm::new(rnum as u32, 4) })
+    } else {
+        unreachable!()
+    };
+    if inst.is_rv64_only() && xlen == Xlen::X32 {
+        return Err(format!("{} 仅在 RV64/128 可用", mnem));
+    }
+    if inst.is_rv32_only() && xlen != Xlen::X32 {
+        return Err(format!("{} 仅在 RV32 可用", mnem));
+    }
+    Ok(inst)
+}
+
+pub(crate) fn try_parse(mnem: &str, ops: &[String], xlen: Xlen) -> Option<Result<Instruction, String>> {
+    if binary(mnem).is_none() && unary(mnem).is_none() && byte_select(mnem).is_none() && mnem != "aes64ks1i" {
+        return None;
+    }
+    Some(parse(mnem, ops, xlen).map(Instruction::from))
+}
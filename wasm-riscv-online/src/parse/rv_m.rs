@@ -0,0 +1,46 @@
+use crate::asm::*;
+use crate::riscv::imm::Xlen;
+use super::common::parse_register;
+
+pub(crate) fn try_parse(mnem: &str, ops: &[String], xlen: Xlen) -> Option<Result<Instruction, String>> {
+    match mnem {
+        "mul" | "mulh" | "mulhsu" | "mulhu" | "div" | "divu" | "rem" | "remu"
+        | "mulw" | "divw" | "divuw" | "remw" | "remuw"
+        | "muld" | "divd" | "divud" | "remd" | "remud" => {
+            if mnem.ends_with('w') {
+                match xlen { Xlen::X64 | Xlen::X128 => {}, _ => return Some(Err("该指令仅在 RV64/128 可用".into())) }
+            }
+            if mnem.ends_with('d') && xlen != Xlen::X128 {
+                return Some(Err("该指令仅在 RV128 可用".into()));
+            }
+            if ops.len() != 3 { return Some(Err(format!("用法: {} rd, rs1, rs2", mnem))); }
+            let rd = match parse_register(&ops[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
+            let rs1 = match parse_register(&ops[1]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
+            let rs2 = match parse_register(&ops[2]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
+            let r = RType { rd, rs1, rs2, funct3: 0, funct7: 0 };
+            let inst = match mnem {
+                "mul" => RV32I::Mul(r).into(),
+                "mulh" => RV32I::Mulh(r).into(),
+                "mulhsu" => RV32I::Mulhsu(r).into(),
+                "mulhu" => RV32I::Mulhu(r).into(),
+                "div" => RV32I::Div(r).into(),
+                "divu" => RV32I::Divu(r).into(),
+                "rem" => RV32I::Rem(r).into(),
+                "remu" => RV32I::Remu(r).into(),
+                "mulw" => RV64I::Mulw(r).into(),
+                "divw" => RV64I::Divw(r).into(),
+                "divuw" => RV64I::Divuw(r).into(),
+                "remw" => RV64I::Remw(r).into(),
+                "remuw" => RV64I::Remuw(r).into(),
+                "muld" => RV128I::Muld(r).into(),
+                "divd" => RV128I::Divd(r).into(),
+                "divud" => RV128I::Divud(r).into(),
+                "remd" => RV128I::Remd(r).into(),
+                "remud" => RV128I::Remud(r).into(),
+                _ => unreachable!(),
+            };
+            Some(Ok(inst))
+        }
+        _ => None,
+    }
+}
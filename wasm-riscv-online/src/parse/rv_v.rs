@@ -0,0 +1,322 @@
+use crate::asm::rvv::{src_bit, Shape, Sub, UOps, VGroup, VOp, VSrc, VUnary, BINARY, UNARY};
+use crate::asm::*;
+use crate::riscv::imm::Xlen;
+use super::common::{parse_base_register, parse_fp_register, parse_int, parse_register, parse_vector_register};
+
+/// Addressing of a load/store mnemonic, before the operands are known.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Addr {
+    Unit,
+    FaultFirst,
+    WholeReg,
+    Mask,
+    Strided,
+    Unordered,
+    Ordered,
+}
+
+struct MemForm {
+    store: bool,
+    addr: Addr,
+    eew: u16,
+    nf: u8,
+}
+
+/// `vle32.v`, `vlsseg2e16.v`, `vloxei8.v`, `vl2re32.v`, `vs4r.v`, `vlm.v`, ...
+fn mem_form(mnem: &str) -> Option<MemForm> {
+    let store = match mnem.get(..2)? {
+        "vl" => false,
+        "vs" => true,
+        _ => return None,
+    };
+    let body = mnem[2..].strip_suffix(".v")?;
+    let form = |addr, eew, nf| Some(MemForm { store, addr, eew, nf });
+    if body == "m" {
+        return form(Addr::Mask, 8, 0);
+    }
+    // whole registers: vl<n>re<eew>.v, vl<n>r.v (e8) and vs<n>r.v
+    let whole = match body.strip_suffix('r') {
+        Some(n) => Some((n, "8")),
+        None if !store => body.split_once("re"),
+        None => None,
+    };
+    if let Some((n, eew)) = whole {
+        if let (Ok(n @ (1 | 2 | 4 | 8)), Ok(eew)) = (n.parse::<u8>(), eew.parse::<u16>()) {
+            return form(Addr::WholeReg, eew, n - 1);
+        }
+    }
+    let (addr, rest) = if body.starts_with("seg") || body.starts_with('e') {
+        (Addr::Unit, body)
+    } else if let Some(rest) = body.strip_prefix("ux") {
+        (Addr::Unordered, rest)
+    } else if let Some(rest) = body.strip_prefix("ox") {
+        (Addr::Ordered, rest)
+    } else {
+        (Addr::Strided, body.strip_prefix('s')?)
+    };
+    let (nf, rest) = match rest.strip_prefix("seg") {
+        Some(r) => {
+            let digits = r.find(|c: char| !c.is_ascii_digit()).unwrap_or(r.len());
+            match r[..digits].parse::<u8>() {
+                Ok(n @ 2..=8) => (n - 1, &r[digits..]),
+                _ => return None,
+            }
+        }
+        None => (0, rest),
+    };
+    let indexed = matches!(addr, Addr::Unordered | Addr::Ordered);
+    let rest = rest.strip_prefix(if indexed { "ei" } else { "e" })?;
+    let (addr, rest) = match rest.strip_suffix("ff") {
+        Some(r) if addr == Addr::Unit && !store => (Addr::FaultFirst, r),
+        _ => (addr, rest),
+    };
+    match rest.parse::<u16>() {
+        Ok(eew @ (8 | 16 | 32 | 64)) => form(addr, eew, nf),
+        _ => None,
+    }
+}
+
+/// Strip a trailing `v0.t`; `true` in the result is the vm bit (unmasked).
+fn split_mask(ops: &[String]) -> (&[String], bool) {
+    match ops.split_last() {
+        Some((last, rest)) if last.trim().eq_ignore_ascii_case("v0.t") => (rest, false),
+        _ => (ops, true),
+    }
+}
+
+fn parse_mem(mnem: &str, f: &MemForm, ops: &[String]) -> Result<RVV, String> {
+    let (ops, vm) = split_mask(ops);
+    let extra = matches!(f.addr, Addr::Strided | Addr::Unordered | Addr::Ordered);
+    if ops.len() != 2 + extra as usize {
+        let usage = match f.addr {
+            Addr::Strided => "vd, (rs1), rs2",
+            Addr::Unordered | Addr::Ordered => "vd, (rs1), vs2",
+            _ => "vd, (rs1)",
+        };
+        return Err(format!("用法: {} {}[, v0.t]", mnem, usage));
+    }
+    let vd = parse_vector_register(&ops[0])?;
+    let rs1 = parse_base_register(&ops[1])?;
+    let mode = match f.addr {
+        Addr::Unit => VAddr::Unit,
+        Addr::FaultFirst => VAddr::FaultFirst,
+        Addr::WholeReg => VAddr::WholeReg,
+        Addr::Mask => VAddr::Mask,
+        Addr::Strided => VAddr::Strided(parse_register(&ops[2])?),
+        Addr::Unordered => VAddr::Unordered(parse_vector_register(&ops[2])?),
+        Addr::Ordered => VAddr::Ordered(parse_vector_register(&ops[2])?),
+    };
+    let m = VMem { vd, rs1, mode, eew: f.eew, nf: f.nf, vm };
+    if !m.is_valid(f.store) {
+        return Err(match f.addr {
+            Addr::WholeReg | Addr::Mask if !vm => format!("{} 不支持掩码", mnem),
+            Addr::WholeReg => format!("{} 的寄存器 v{} 未按 {} 对齐", mnem, vd, f.nf + 1),
+            _ => format!("无效的向量访存指令: {}", mnem),
+        });
+    }
+    Ok(if f.store { RVV::Store(m) } else { RVV::Load(m) })
+}
+
+/// `e32, m4, ta, ma`; LMUL and the policies may be left out (`m1, tu, mu`),
+/// a plain number is taken as the raw vtype.
+fn parse_vtype(ops: &[String]) -> Result<u16, String> {
+    if let [raw] = ops {
+        if let Ok(v) = parse_int(raw) {
+            if !(0..=0x7ff).contains(&v) {
+                return Err(format!("vtype 超出范围: {}", v));
+            }
+            return Ok(v as u16);
+        }
+    }
+    let mut it = ops.iter().map(|s| s.trim().to_lowercase()).peekable();
+    let sew = match it.next().as_deref() {
+        Some("e8") => 0,
+        Some("e16") => 1,
+        Some("e32") => 2,
+        Some("e64") => 3,
+        other => return Err(format!("未知的 SEW: {}", other.unwrap_or(""))),
+    };
+    let lmul = match it.peek().map(String::as_str) {
+        Some("m1") => Some(0b000),
+        Some("m2") => Some(0b001),
+        Some("m4") => Some(0b010),
+        Some("m8") => Some(0b011),
+        Some("mf8") => Some(0b101),
+        Some("mf4") => Some(0b110),
+        Some("mf2") => Some(0b111),
+        _ => None,
+    };
+    if lmul.is_some() {
+        it.next();
+    }
+    let lmul = lmul.unwrap_or(0b000);
+    let mut ta = false;
+    let mut ma = false;
+    if let Some(t @ ("ta" | "tu")) = it.peek().map(String::as_str) {
+        ta = t == "ta";
+        it.next();
+    }
+    if let Some(m @ ("ma" | "mu")) = it.peek().map(String::as_str) {
+        ma = m == "ma";
+        it.next();
+    }
+    if let Some(extra) = it.next() {
+        return Err(format!("无法识别的 vtype 字段: {}", extra));
+    }
+    Ok(((ma as u16) << 7) | ((ta as u16) << 6) | (sew << 3) | lmul)
+}
+
+fn parse_vset(mnem: &str, ops: &[String]) -> Result<RVV, String> {
+    match mnem {
+        "vsetvl" => {
+            if ops.len() != 3 { return Err("用法: vsetvl rd, rs1, rs2".into()); }
+            Ok(RVV::Vsetvl(RType {
+                rd: parse_register(&ops[0])?,
+                rs1: parse_register(&ops[1])?,
+                rs2: parse_register(&ops[2])?,
+                funct3: 0,
+                funct7: 0,
+            }))
+        }
+        _ => {
+            let imm = mnem == "vsetivli";
+            if ops.len() < 3 {
+                return Err(format!("用法: {} rd, {}, e<sew>[, m<lmul>][, ta|tu][, ma|mu]", mnem, if imm { "uimm" } else { "rs1" }));
+            }
+            let rd = parse_register(&ops[0])?;
+            let vtypei = parse_vtype(&ops[2..])?;
+            if imm {
+                let avl = parse_int(&ops[1])?;
+                if !(0..=31).contains(&avl) {
+                    return Err(format!("vsetivli 的 AVL 超出范围 (0..=31): {}", avl));
+                }
+                if vtypei > 0x3ff {
+                    return Err(format!("vsetivli 的 vtype 超出 10 位: {}", vtypei));
+                }
+                Ok(RVV::Vsetivli(VsetType { rd, rs1: avl as u8, vtypei }))
+            } else {
+                Ok(RVV::Vsetvli(VsetType { rd, rs1: parse_register(&ops[1])?, vtypei }))
+            }
+        }
+    }
+}
+
+/// The table entry and source kind a two-source mnemonic names.
+fn binary(mnem: &str) -> Option<(&'static VOp, VSrc, bool)> {
+    let (base, suffix) = mnem.split_at(mnem.find('.')?);
+    let suffix = &suffix[1..];
+    BINARY.iter().filter(|o| o.name == base).find_map(|o| {
+        [VSrc::V, VSrc::X, VSrc::I].iter().filter(|&&k| o.srcs & src_bit(k) != 0).find_map(|&k| {
+            // the suffix alone says whether v0 is a carry input
+            [true, false]
+                .iter()
+                .find(|&&vm| o.shape.suffix(o.group, k, vm) == suffix)
+                .map(|&vm| (o, k, vm))
+        })
+    })
+}
+
+fn parse_src(s: &str, group: VGroup, kind: VSrc, signed: bool) -> Result<u8, String> {
+    match kind {
+        VSrc::V => parse_vector_register(s),
+        VSrc::X if group == VGroup::F => parse_fp_register(s),
+        VSrc::X => parse_register(s),
+        VSrc::I => {
+            let v = parse_int(s)?;
+            let (lo, hi) = if signed { (-16, 15) } else { (0, 31) };
+            if !(lo..=hi).contains(&v) {
+                return Err(format!("立即数超出范围 ({}..={}): {}", lo, hi, v));
+            }
+            Ok((v & 0x1f) as u8)
+        }
+    }
+}
+
+fn parse_binary(mnem: &str, o: &VOp, kind: VSrc, plain_vm: bool, ops: &[String]) -> Result<VArith, String> {
+    let carry_in = o.shape == Shape::Carry || (o.shape == Shape::CarryOut && !plain_vm);
+    let (ops, vm) = if carry_in {
+        match ops.split_last() {
+            Some((v0, rest)) if v0.trim() == "v0" => (rest, false),
+            _ => return Err(format!("用法: {} vd, vs2, src, v0", mnem)),
+        }
+    } else {
+        split_mask(ops)
+    };
+    if ops.len() != 3 {
+        return Err(match o.shape {
+            Shape::MulAdd => format!("用法: {} vd, src, vs2[, v0.t]", mnem),
+            _ => format!("用法: {} vd, vs2, src[, v0.t]", mnem),
+        });
+    }
+    if !vm && !carry_in && matches!(o.shape, Shape::Mask | Shape::Compress) {
+        return Err(format!("{} 不支持掩码", mnem));
+    }
+    let signed = !matches!(o.shape, Shape::Uimm | Shape::Narrow);
+    let (src, vs2) = match o.shape {
+        Shape::MulAdd => (&ops[1], &ops[2]),
+        _ => (&ops[2], &ops[1]),
+    };
+    Ok(VArith {
+        funct6: o.funct6,
+        group: o.group,
+        kind,
+        vd: parse_vector_register(&ops[0])?,
+        vs2: parse_vector_register(vs2)?,
+        src: parse_src(src, o.group, kind, signed)?,
+        vm,
+    })
+}
+
+fn parse_unary(mnem: &str, u: &VUnary, ops: &[String]) -> Result<VArith, String> {
+    let (ops, vm) = split_mask(ops);
+    if !vm && !u.maskable {
+        return Err(format!("{} 不支持掩码", mnem));
+    }
+    let want = if u.ops == UOps::Vd { 1 } else { 2 };
+    if ops.len() != want {
+        return Err(format!("{} 需要 {} 个操作数", mnem, want));
+    }
+    let vd = match u.ops {
+        UOps::XdVs2 => parse_register(&ops[0])?,
+        UOps::FdVs2 => parse_fp_register(&ops[0])?,
+        _ => parse_vector_register(&ops[0])?,
+    };
+    let (vs2, src) = match (u.ops, u.sub) {
+        (UOps::Vd, Sub::Src(c)) => (0, c),
+        (UOps::VdSrc, Sub::Vs2(c)) => (c, parse_src(&ops[1], u.group, u.kind, true)?),
+        (_, Sub::Src(c)) => (parse_vector_register(&ops[1])?, c),
+        (_, Sub::Vs2(c)) => (c, parse_vector_register(&ops[1])?),
+    };
+    Ok(VArith { funct6: u.funct6, group: u.group, kind: u.kind, vd, vs2, src, vm })
+}
+
+pub(crate) fn try_parse(mnem: &str, ops: &[String], _xlen: Xlen) -> Option<Result<Instruction, String>> {
+    if !mnem.starts_with('v') {
+        return None;
+    }
+    let res = if matches!(mnem, "vsetvli" | "vsetivli" | "vsetvl") {
+        parse_vset(mnem, ops)
+    } else if let Some(f) = mem_form(mnem) {
+        parse_mem(mnem, &f, ops)
+    } else {
+        let arith = if let Some(u) = UNARY.iter().find(|u| u.name == mnem) {
+            parse_unary(mnem, u, ops)
+        } else if let Some((o, kind, vm)) = binary(mnem) {
+            parse_binary(mnem, o, kind, vm, ops)
+        } else {
+            return None;
+        };
+        // the decoder's tables have the final say (register-group alignment, ...)
+        arith.and_then(|a| {
+            if a.is_valid() && a.mnemonic() == mnem {
+                if a.masked_vd_overlaps_v0() {
+                    return Err(format!("{} 带掩码时 vd 不能是 v0", mnem));
+                }
+                Ok(RVV::Arith(a))
+            } else {
+                Err(format!("{} 的操作数无效", mnem))
+            }
+        })
+    };
+    Some(res.map(Instruction::from))
+}
@@ -1,4 +1,5 @@
 use crate::asm::*;
+use crate::isa::*;
 use crate::riscv::imm::{Imm, Uimm, Xlen};
 use super::common::{parse_int, parse_mem_operand, parse_register, imm_signed_bits};
 
@@ -246,6 +247,141 @@ pub(crate) fn try_parse(mnem: &str, ops: &[String], xlen: Xlen) -> Option<Result
             let css = CSSType { rs2, funct3: 0, imm: Imm::new(imm_bits, 9) };
             Some(Ok(RVC::Csdsp(css).into()))
         }
+        // Zcmop: c.mop.N, N odd in 1..=15
+        m if m.starts_with("c.mop.") => {
+            if !ops.is_empty() { return Some(Err(format!("{} 不带操作数", m))); }
+            match m["c.mop.".len()..].parse::<u8>() {
+                Ok(n) if n % 2 == 1 && n < 16 => Some(Ok(RVC::Cmop(CIType { rdrs1: n, funct3: 0, imm: Imm::new(0, 6) }).into())),
+                _ => Some(Err(format!("未支持的指令: {}（c.mop.N 的 N 为 1 到 15 的奇数）", m))),
+            }
+        }
+        // Zihintntl: c.ntl.p1 / c.ntl.pall / c.ntl.s1 / c.ntl.all
+        "c.ntl.p1" | "c.ntl.pall" | "c.ntl.s1" | "c.ntl.all" => {
+            if !ops.is_empty() { return Some(Err(format!("{} 不带操作数", mnem))); }
+            let rs2 = match mnem {
+                "c.ntl.p1" => RS2_NTL_P1,
+                "c.ntl.pall" => RS2_NTL_PALL,
+                "c.ntl.s1" => RS2_NTL_S1,
+                _ => RS2_NTL_ALL,
+            };
+            Some(Ok(RVC::Cntl(CRType { rdrs1: 0, rs2, funct4: 0b1001 }).into()))
+        }
+        // Zcb
+        "c.lbu" | "c.lhu" | "c.lh" | "c.sb" | "c.sh" => Some(parse_zcb_mem(mnem, ops).map(Instruction::from)),
+        "c.zext.b" | "c.sext.b" | "c.zext.h" | "c.sext.h" | "c.zext.w" | "c.not" => {
+            Some(parse_zcb_unary(mnem, ops, xlen).map(Instruction::from))
+        }
+        "c.mul" => {
+            if ops.len() != 2 { return Some(Err("用法: c.mul rd, rs2".into())); }
+            let rd = match parse_register(&ops[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
+            let rs2 = match parse_register(&ops[1]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
+            Some(Ok(RVC::Cmul(CAType { rdrs1: rd, rs2, funct2: 0, funct6: 0 }).into()))
+        }
+        // Zcmp / Zcmt
+        "cm.push" | "cm.pop" | "cm.popretz" | "cm.popret" => Some(parse_cm_pushpop(mnem, ops).map(Instruction::from)),
+        "cm.mvsa01" | "cm.mva01s" => {
+            if ops.len() != 2 { return Some(Err(format!("用法: {} r1s, r2s", mnem))); }
+            let r1s = match parse_register(&ops[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
+            let r2s = match parse_register(&ops[1]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
+            let ca = CAType { rdrs1: r1s, rs2: r2s, funct2: 0, funct6: 0 };
+            Some(Ok(if mnem == "cm.mvsa01" { RVC::CmMvsa01(ca) } else { RVC::CmMva01s(ca) }.into()))
+        }
+        "cm.jt" | "cm.jalt" => {
+            if ops.len() != 1 { return Some(Err(format!("用法: {} index", mnem))); }
+            let index = match parse_int(&ops[0]) {
+                Ok(v) if (0..=255).contains(&v) => v as u8,
+                Ok(_) => return Some(Err(format!("{} 的索引超出 0..=255", mnem))),
+                Err(e) => return Some(Err(e)),
+            };
+            Some(Ok(if mnem == "cm.jt" { RVC::CmJt(index) } else { RVC::CmJalt(index) }.into()))
+        }
         _ => None,
     }
 }
+
+/// Zcb `c.lbu`/`c.lhu`/`c.lh rd, uimm(rs1)` and `c.sb`/`c.sh rs2, uimm(rs1)`.
+fn parse_zcb_mem(mnem: &str, ops: &[String]) -> Result<RVC, String> {
+    let store = mnem == "c.sb" || mnem == "c.sh";
+    if ops.len() != 2 {
+        return Err(format!("用法: {} {}, uimm(rs1)", mnem, if store { "rs2" } else { "rd" }));
+    }
+    let reg = parse_register(&ops[0])?;
+    let (imm_bits, rs1) = parse_mem_operand(&ops[1])?;
+    let imm = Imm::new(imm_bits, 7);
+    Ok(match mnem {
+        "c.lbu" => RVC::Clbu(CLType { rd: reg, rs1, funct3: 0, imm }),
+        "c.lhu" => RVC::Clhu(CLType { rd: reg, rs1, funct3: 0, imm }),
+        "c.lh" => RVC::Clh(CLType { rd: reg, rs1, funct3: 0, imm }),
+        "c.sb" => RVC::Csb(CSType { rs1, rs2: reg, funct3: 0, imm }),
+        _ => RVC::Csh(CSType { rs1, rs2: reg, funct3: 0, imm }),
+    })
+}
+
+/// Zcb one-register forms: `c.zext.b rd` and friends.
+fn parse_zcb_unary(mnem: &str, ops: &[String], xlen: Xlen) -> Result<RVC, String> {
+    if ops.len() != 1 { return Err(format!("用法: {} rd", mnem)); }
+    let ci = CIType { rdrs1: parse_register(&ops[0])?, funct3: 0, imm: Imm::new(0, 6) };
+    Ok(match mnem {
+        "c.zext.b" => RVC::CzextB(ci),
+        "c.sext.b" => RVC::CsextB(ci),
+        "c.zext.h" => RVC::CzextH(ci),
+        "c.sext.h" => RVC::CsextH(ci),
+        "c.zext.w" if xlen == Xlen::X32 => return Err("c.zext.w 仅在 RV64/128 可用".into()),
+        "c.zext.w" => RVC::CzextW(ci),
+        _ => RVC::Cnot(ci),
+    })
+}
+
+/// Position of a saved register in the Zcmp list order s0, s1, s2, ..., s11.
+fn saved_ordinal(s: &str) -> Result<usize, String> {
+    match parse_register(s)? {
+        r @ (8 | 9) => Ok((r - 8) as usize),
+        r @ 18..=27 => Ok((r - 16) as usize),
+        _ => Err(format!("寄存器列表只能包含 ra 与 s0-s11: {}", s)),
+    }
+}
+
+/// A Zcmp register list: `{ra}`, `{ra, s0}`, `{ra, s0-sN}` or the same in
+/// x-names (`{x1, x8-x9, x18-xN}`). Returns the 4-bit `rlist` encoding.
+fn parse_rlist(s: &str) -> Result<u8, String> {
+    let inner = s.trim().strip_prefix('{').and_then(|t| t.strip_suffix('}'))
+        .ok_or_else(|| format!("寄存器列表应写在花括号中: {}", s))?;
+    let mut parts = inner.split(',').map(str::trim);
+    if parts.next().map(parse_register) != Some(Ok(1)) {
+        return Err(format!("寄存器列表必须以 ra 开头: {}", s));
+    }
+    let mut saved = Vec::new();
+    for part in parts {
+        let (lo, hi) = match part.split_once('-') {
+            Some((lo, hi)) => (saved_ordinal(lo)?, saved_ordinal(hi)?),
+            None => (saved_ordinal(part)?, saved_ordinal(part)?),
+        };
+        saved.extend(lo..=hi);
+    }
+    let want: Vec<u8> = saved.iter().map(|&i| [8, 9, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27][i]).collect();
+    (4..=15)
+        .find(|&rlist| CMPPType { rlist, stack_adj: 0 }.registers()[1..] == want[..])
+        .ok_or_else(|| format!("不支持的寄存器列表: {}", s))
+}
+
+/// `cm.push {reg_list}, -stack_adj` and `cm.pop*/cm.popret* {reg_list}, stack_adj`.
+fn parse_cm_pushpop(mnem: &str, ops: &[String]) -> Result<RVC, String> {
+    // The list itself contains commas, so everything before the last operand is the list.
+    let (adj, list) = match ops.split_last() {
+        Some((adj, list)) if !list.is_empty() => (adj, list.join(", ")),
+        _ => return Err(format!("用法: {} {{reg_list}}, stack_adj", mnem)),
+    };
+    let rlist = parse_rlist(&list)?;
+    let adj = parse_int(adj)?;
+    let push = mnem == "cm.push";
+    if (push && adj >= 0) || (!push && adj <= 0) {
+        return Err(format!("{} 的栈调整量应为{}数", mnem, if push { "负" } else { "正" }));
+    }
+    let pp = CMPPType { rlist, stack_adj: adj.unsigned_abs() as u32 };
+    Ok(match mnem {
+        "cm.push" => RVC::CmPush(pp),
+        "cm.pop" => RVC::CmPop(pp),
+        "cm.popretz" => RVC::CmPopretz(pp),
+        _ => RVC::CmPopret(pp),
+    })
+}
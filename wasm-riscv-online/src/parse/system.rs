@@ -1,12 +1,150 @@
 use crate::asm::*;
-use crate::riscv::imm::Xlen;
+use crate::riscv::imm::{Imm, Xlen};
+use super::common::{parse_base_register, parse_register};
 
-pub(crate) fn try_parse(mnem: &str, _ops: &[String], _xlen: Xlen) -> Option<Result<Instruction, String>> {
+type MakePriv = fn(RType) -> RVPriv;
+
+fn r_type(rd: u8, rs1: u8, rs2: u8) -> RType {
+    RType { rd, rs1, rs2, funct3: 0, funct7: 0 }
+}
+
+/// Translation fences: `rs1, rs2`, either of which may be left out (zero).
+fn fence(mnem: &str) -> Option<MakePriv> {
+    use RVPriv::*;
+    Some(match mnem {
+        "sfence.vma" => SfenceVma,
+        "hfence.vvma" => HfenceVvma,
+        "hfence.gvma" => HfenceGvma,
+        "sinval.vma" => SinvalVma,
+        "hinval.vvma" => HinvalVvma,
+        "hinval.gvma" => HinvalGvma,
+        _ => return None,
+    })
+}
+
+/// Hypervisor loads `rd, (rs1)` and stores `rs2, (rs1)`.
+fn hyper(mnem: &str) -> Option<(MakePriv, bool)> {
+    use RVPriv::*;
+    Some(match mnem {
+        "hlv.b" => (HlvB, false),
+        "hlv.bu" => (HlvBu, false),
+        "hlv.h" => (HlvH, false),
+        "hlv.hu" => (HlvHu, false),
+        "hlvx.hu" => (HlvxHu, false),
+        "hlv.w" => (HlvW, false),
+        "hlv.wu" => (HlvWu, false),
+        "hlvx.wu" => (HlvxWu, false),
+        "hlv.d" => (HlvD, false),
+        "hsv.b" => (HsvB, true),
+        "hsv.h" => (HsvH, true),
+        "hsv.w" => (HsvW, true),
+        "hsv.d" => (HsvD, true),
+        _ => return None,
+    })
+}
+
+/// Instructions without operands.
+fn bare(mnem: &str) -> Option<RVPriv> {
+    use RVPriv::*;
+    Some(match mnem {
+        "sret" => Sret(()),
+        "mret" => Mret(()),
+        "mnret" => Mnret(()),
+        "wfi" => Wfi(()),
+        "wrs.nto" => WrsNto(()),
+        "wrs.sto" => WrsSto(()),
+        "sfence.w.inval" => SfenceWInval(()),
+        "sfence.inval.ir" => SfenceInvalIr(()),
+        _ => return None,
+    })
+}
+
+/// A `fence` predecessor/successor set: `0` or letters out of `iorw`.
+fn fence_set(s: &str) -> Result<u32, String> {
+    let s = s.trim();
+    if s == "0" { return Ok(0); }
+    let mut bits = 0;
+    for c in s.chars() {
+        let bit = match c.to_ascii_lowercase() {
+            'i' => 0b1000,
+            'o' => 0b0100,
+            'r' => 0b0010,
+            'w' => 0b0001,
+            _ => return Err(format!("fence 操作数应为 iorw 的组合或 0: {}", s)),
+        };
+        if bits & bit != 0 { return Err(format!("fence 操作数重复: {}", s)); }
+        bits |= bit;
+    }
+    if bits == 0 { return Err("fence 缺少操作数".to_string()); }
+    Ok(bits)
+}
+
+/// `fence [pred, succ]` and `fence.tso`. A bare `fence` keeps the all-zero
+/// sets it has always assembled to.
+fn parse_fence(mnem: &str, ops: &[String]) -> Result<RV32I, String> {
+    let imm12 = match (mnem, ops.len()) {
+        ("fence", 0) => 0,
+        ("fence", 2) => (fence_set(&ops[0])? << 4) | fence_set(&ops[1])?,
+        ("fence", _) => return Err("用法: fence [pred, succ]".to_string()),
+        (_, 0) => crate::isa::IMM12_FENCE_TSO,
+        _ => return Err(format!("{} 不带操作数", mnem)),
+    };
+    Ok(RV32I::Fence(IType { rd: 0, rs1: 0, funct3: 0, imm: Imm::new(imm12, 12) }))
+}
+
+/// `mop.r.N rd, rs1` (N = 0..=31) and `mop.rr.N rd, rs1, rs2` (N = 0..=7).
+fn parse_mop(mnem: &str, ops: &[String]) -> Result<RVZimop, String> {
+    let (n, max, two) = match mnem.strip_prefix("mop.rr.") {
+        Some(n) => (n, 7, true),
+        None => (mnem.trim_start_matches("mop.r."), 31, false),
+    };
+    let n = match n.parse::<u8>() {
+        Ok(n) if n <= max => n,
+        _ => return Err(format!("未支持的指令: {}（N 取值 0..={}）", mnem, max)),
+    };
+    if ops.len() != if two { 3 } else { 2 } {
+        return Err(format!("用法: {} rd, rs1{}", mnem, if two { ", rs2" } else { "" }));
+    }
+    let rs2 = if two { parse_register(&ops[2])? } else { 0 };
+    let r = r_type(parse_register(&ops[0])?, parse_register(&ops[1])?, rs2);
+    Ok(if two { RVZimop::MopRR(n, r) } else { RVZimop::MopR(n, r) })
+}
+
+fn parse_priv(mnem: &str, ops: &[String], xlen: Xlen) -> Result<RVPriv, String> {
+    let inst = if let Some(p) = bare(mnem) {
+        if !ops.is_empty() { return Err(format!("{} 不带操作数", mnem)); }
+        p
+    } else if let Some(make) = fence(mnem) {
+        if ops.len() > 2 { return Err(format!("用法: {} [rs1[, rs2]]", mnem)); }
+        let reg = |i: usize| ops.get(i).map_or(Ok(0), |s| parse_register(s));
+        make(r_type(0, reg(0)?, reg(1)?))
+    } else if let Some((make, store)) = hyper(mnem) {
+        if ops.len() != 2 {
+            return Err(format!("用法: {} {}, (rs1)", mnem, if store { "rs2" } else { "rd" }));
+        }
+        let (reg, rs1) = (parse_register(&ops[0])?, parse_base_register(&ops[1])?);
+        make(if store { r_type(0, rs1, reg) } else { r_type(reg, rs1, 0) })
+    } else {
+        unreachable!()
+    };
+    if inst.is_rv64_only() && xlen == Xlen::X32 {
+        return Err(format!("{} 仅在 RV64/128 可用", mnem));
+    }
+    Ok(inst)
+}
+
+pub(crate) fn try_parse(mnem: &str, ops: &[String], xlen: Xlen) -> Option<Result<Instruction, String>> {
     match mnem {
         "ecall" => Some(Ok(RV32I::Ecall(()).into())),
         "ebreak" => Some(Ok(RV32I::Ebreak(()).into())),
-        "fence" => Some(Ok(RV32I::Fence(()).into())),
+        "fence" | "fence.tso" => Some(parse_fence(mnem, ops).map(Instruction::from)),
         "fence.i" | "fencei" => Some(Ok(RV32I::FenceI(()).into())),
+        _ if mnem.starts_with("mop.r.") || mnem.starts_with("mop.rr.") => {
+            Some(parse_mop(mnem, ops).map(Instruction::from))
+        }
+        _ if bare(mnem).is_some() || fence(mnem).is_some() || hyper(mnem).is_some() => {
+            Some(parse_priv(mnem, ops, xlen).map(Instruction::from))
+        }
         _ => None,
     }
 }
@@ -0,0 +1,52 @@
+use crate::asm::*;
+use crate::isa::FUNCT3_OP_OR;
+use crate::riscv::imm::{Imm, Xlen};
+use super::common::{parse_base_register, parse_mem_operand};
+
+enum Form {
+    /// `cbo.* (rs1)`
+    Cbo(fn(RType) -> RVZicbo),
+    /// `prefetch.* offset(rs1)`, offset a multiple of 32 and optional
+    Prefetch(fn(IType) -> RVZicbo),
+}
+
+fn form(mnem: &str) -> Option<Form> {
+    use RVZicbo::*;
+    Some(match mnem {
+        "cbo.inval" => Form::Cbo(CboInval),
+        "cbo.clean" => Form::Cbo(CboClean),
+        "cbo.flush" => Form::Cbo(CboFlush),
+        "cbo.zero" => Form::Cbo(CboZero),
+        "prefetch.i" => Form::Prefetch(PrefetchI),
+        "prefetch.r" => Form::Prefetch(PrefetchR),
+        "prefetch.w" => Form::Prefetch(PrefetchW),
+        _ => return None,
+    })
+}
+
+fn parse_zicbo(mnem: &str, form: Form, ops: &[String]) -> Result<RVZicbo, String> {
+    match form {
+        Form::Cbo(make) => {
+            if ops.len() != 1 { return Err(format!("用法: {} (rs1)", mnem)); }
+            let rs1 = parse_base_register(&ops[0])?;
+            Ok(make(RType { rd: 0, rs1, rs2: 0, funct3: 0, funct7: 0 }))
+        }
+        Form::Prefetch(make) => {
+            if ops.len() != 1 { return Err(format!("用法: {} offset(rs1)", mnem)); }
+            let (imm, rs1) = if ops[0].trim_start().starts_with('(') {
+                (0, parse_base_register(&ops[0])?)
+            } else {
+                parse_mem_operand(&ops[0])?
+            };
+            if imm & 0b1_1111 != 0 {
+                return Err(format!("{} 的偏移必须是 32 的倍数: {}", mnem, ops[0]));
+            }
+            Ok(make(IType { rd: 0, rs1, funct3: FUNCT3_OP_OR, imm: Imm::new(imm, 12) }))
+        }
+    }
+}
+
+pub(crate) fn try_parse(mnem: &str, ops: &[String], _xlen: Xlen) -> Option<Result<Instruction, String>> {
+    let form = form(mnem)?;
+    Some(parse_zicbo(mnem, form, ops).map(Instruction::from))
+}
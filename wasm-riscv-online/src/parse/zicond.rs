@@ -0,0 +1,18 @@
+use crate::asm::*;
+use crate::riscv::imm::Xlen;
+use super::common::parse_register;
+
+/// `czero.eqz` / `czero.nez rd, rs1, rs2`
+pub(crate) fn try_parse(mnem: &str, ops: &[String], _xlen: Xlen) -> Option<Result<Instruction, String>> {
+    let make: fn(RType) -> RVZicond = match mnem {
+        "czero.eqz" => RVZicond::CzeroEqz,
+        "czero.nez" => RVZicond::CzeroNez,
+        _ => return None,
+    };
+    if ops.len() != 3 { return Some(Err(format!("用法: {} rd, rs1, rs2", mnem))); }
+    let regs = (parse_register(&ops[0]), parse_register(&ops[1]), parse_register(&ops[2]));
+    Some(match regs {
+        (Ok(rd), Ok(rs1), Ok(rs2)) => Ok(make(RType { rd, rs1, rs2, funct3: 0, funct7: 0 }).into()),
+        (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => Err(e),
+    })
+}
@@ -1,40 +1,144 @@
 use crate::asm::*;
+use crate::asm::rvzicsr::{CSR_FCSR, CSR_FFLAGS, CSR_FRM};
 use crate::riscv::imm::{Uimm, Xlen};
-use super::common::{parse_int, parse_register};
+use super::common::{parse_csr, parse_int, parse_register};
 
-pub(crate) fn try_parse(mnem: &str, ops: &[String], _xlen: Xlen) -> Option<Result<Instruction, String>> {
+/// Source operand of a CSR instruction: a register, or a 5-bit immediate
+/// (which selects the `csrr?i` form, as GNU as does).
+enum Source {
+    Reg(u8),
+    Imm(Uimm),
+}
+
+fn parse_uimm5(s: &str) -> Result<Uimm, String> {
+    let v = parse_int(s)?;
+    if !(0..=31).contains(&v) { return Err("uimm 取值 0..31".into()); }
+    Ok(Uimm::new(v as u32, 5))
+}
+
+fn parse_source(s: &str) -> Result<Source, String> {
+    match parse_register(s) {
+        Ok(r) => Ok(Source::Reg(r)),
+        Err(_) => parse_uimm5(s).map(Source::Imm),
+    }
+}
+
+/// `op` is the access kind: 'w' (write), 's' (set) or 'c' (clear).
+fn build(op: char, rd: u8, csr: u16, src: Source) -> Instruction {
+    match src {
+        Source::Reg(rs1) => {
+            let c = CsrRType { rd, rs1, funct3: 0, csr };
+            match op {
+                'w' => RVZicsr::Csrrw(c).into(),
+                's' => RVZicsr::Csrrs(c).into(),
+                _ => RVZicsr::Csrrc(c).into(),
+            }
+        }
+        Source::Imm(uimm) => {
+            let c = CsrIType { rd, uimm, funct3: 0, csr };
+            match op {
+                'w' => RVZicsr::Csrrwi(c).into(),
+                's' => RVZicsr::Csrrsi(c).into(),
+                _ => RVZicsr::Csrrci(c).into(),
+            }
+        }
+    }
+}
+
+fn counter_csr(mnem: &str) -> Option<(u16, bool)> {
     match mnem {
+        "rdcycle" => Some((0xc00, false)),
+        "rdtime" => Some((0xc01, false)),
+        "rdinstret" => Some((0xc02, false)),
+        "rdcycleh" => Some((0xc80, true)),
+        "rdtimeh" => Some((0xc81, true)),
+        "rdinstreth" => Some((0xc82, true)),
+        _ => None,
+    }
+}
+
+fn float_csr(mnem: &str) -> Option<u16> {
+    match mnem {
+        "frcsr" | "fscsr" => Some(CSR_FCSR),
+        "frrm" | "fsrm" | "fsrmi" => Some(CSR_FRM),
+        "frflags" | "fsflags" | "fsflagsi" => Some(CSR_FFLAGS),
+        _ => None,
+    }
+}
+
+pub(crate) fn try_parse(mnem: &str, ops: &[String], xlen: Xlen) -> Option<Result<Instruction, String>> {
+    let res = match mnem {
         "csrrw" | "csrrs" | "csrrc" => {
             if ops.len() != 3 { return Some(Err("用法: csrr{w|s|c} rd, csr, rs1".into())); }
+            let op = mnem.chars().nth(4).unwrap();
             let rd = match parse_register(&ops[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
-            let csr_val = match parse_int(&ops[1]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
-            if csr_val < 0 || csr_val > 0xFFF { return Some(Err("csr 编号应为 0..0xFFF".into())); }
-            let rs1 = match parse_register(&ops[2]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
-            let c = CsrRType { rd, rs1, funct3: 0, csr: csr_val as u16 };
-            let inst = match mnem {
-                "csrrw" => RVZicsr::Csrrw(c).into(),
-                "csrrs" => RVZicsr::Csrrs(c).into(),
-                "csrrc" => RVZicsr::Csrrc(c).into(),
-                _ => unreachable!(),
-            };
-            Some(Ok(inst))
+            let csr = match parse_csr(&ops[1], xlen) { Ok(v) => v, Err(e) => return Some(Err(e)) };
+            let src = match parse_source(&ops[2]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
+            Ok(build(op, rd, csr, src))
         }
         "csrrwi" | "csrrsi" | "csrrci" => {
             if ops.len() != 3 { return Some(Err("用法: csrr{x}i rd, csr, uimm".into())); }
+            let op = mnem.chars().nth(4).unwrap();
             let rd = match parse_register(&ops[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
-            let csr_val = match parse_int(&ops[1]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
-            if csr_val < 0 || csr_val > 0xFFF { return Some(Err("csr 编号应为 0..0xFFF".into())); }
-            let uimm_val = match parse_int(&ops[2]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
-            if uimm_val < 0 || uimm_val > 31 { return Some(Err("uimm 取值 0..31".into())); }
-            let c = CsrIType { rd, uimm: Uimm::new(uimm_val as u32, 5), funct3: 0, csr: csr_val as u16 };
-            let inst = match mnem {
-                "csrrwi" => RVZicsr::Csrrwi(c).into(),
-                "csrrsi" => RVZicsr::Csrrsi(c).into(),
-                "csrrci" => RVZicsr::Csrrci(c).into(),
-                _ => unreachable!(),
-            };
-            Some(Ok(inst))
+            let csr = match parse_csr(&ops[1], xlen) { Ok(v) => v, Err(e) => return Some(Err(e)) };
+            let uimm = match parse_uimm5(&ops[2]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
+            Ok(build(op, rd, csr, Source::Imm(uimm)))
         }
-        _ => None,
-    }
+        "csrr" => {
+            if ops.len() != 2 { return Some(Err("用法: csrr rd, csr".into())); }
+            let rd = match parse_register(&ops[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
+            let csr = match parse_csr(&ops[1], xlen) { Ok(v) => v, Err(e) => return Some(Err(e)) };
+            Ok(build('s', rd, csr, Source::Reg(0)))
+        }
+        "csrw" | "csrs" | "csrc" => {
+            if ops.len() != 2 { return Some(Err(format!("用法: {} csr, rs1", mnem))); }
+            let op = mnem.chars().nth(3).unwrap();
+            let csr = match parse_csr(&ops[0], xlen) { Ok(v) => v, Err(e) => return Some(Err(e)) };
+            let src = match parse_source(&ops[1]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
+            Ok(build(op, 0, csr, src))
+        }
+        "csrwi" | "csrsi" | "csrci" => {
+            if ops.len() != 2 { return Some(Err(format!("用法: {} csr, uimm", mnem))); }
+            let op = mnem.chars().nth(3).unwrap();
+            let csr = match parse_csr(&ops[0], xlen) { Ok(v) => v, Err(e) => return Some(Err(e)) };
+            let uimm = match parse_uimm5(&ops[1]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
+            Ok(build(op, 0, csr, Source::Imm(uimm)))
+        }
+        "rdcycle" | "rdtime" | "rdinstret" | "rdcycleh" | "rdtimeh" | "rdinstreth" => {
+            let (csr, rv32_only) = counter_csr(mnem).unwrap();
+            if rv32_only && xlen != Xlen::X32 { return Some(Err(format!("{} 仅在 RV32 可用", mnem))); }
+            if ops.len() != 1 { return Some(Err(format!("用法: {} rd", mnem))); }
+            parse_register(&ops[0]).map(|rd| build('s', rd, csr, Source::Reg(0)))
+        }
+        "frcsr" | "frrm" | "frflags" => {
+            if ops.len() != 1 { return Some(Err(format!("用法: {} rd", mnem))); }
+            parse_register(&ops[0]).map(|rd| build('s', rd, float_csr(mnem).unwrap(), Source::Reg(0)))
+        }
+        "fscsr" | "fsrm" | "fsflags" => {
+            let csr = float_csr(mnem).unwrap();
+            match ops.len() {
+                1 => parse_register(&ops[0]).map(|rs1| build('w', 0, csr, Source::Reg(rs1))),
+                2 => {
+                    let rd = match parse_register(&ops[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
+                    let rs1 = match parse_register(&ops[1]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
+                    Ok(build('w', rd, csr, Source::Reg(rs1)))
+                }
+                _ => Err(format!("用法: {} [rd,] rs1", mnem)),
+            }
+        }
+        "fsrmi" | "fsflagsi" => {
+            let csr = float_csr(mnem).unwrap();
+            match ops.len() {
+                1 => parse_uimm5(&ops[0]).map(|imm| build('w', 0, csr, Source::Imm(imm))),
+                2 => {
+                    let rd = match parse_register(&ops[0]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
+                    let uimm = match parse_uimm5(&ops[1]) { Ok(v) => v, Err(e) => return Some(Err(e)) };
+                    Ok(build('w', rd, csr, Source::Imm(uimm)))
+                }
+                _ => Err(format!("用法: {} [rd,] uimm", mnem)),
+            }
+        }
+        _ => return None,
+    };
+    Some(res)
 }
@@ -0,0 +1,16 @@
+use crate::asm::*;
+use crate::riscv::imm::Xlen;
+
+/// `pause` and `ntl.p1` / `ntl.pall` / `ntl.s1` / `ntl.all`
+pub(crate) fn try_parse(mnem: &str, ops: &[String], _xlen: Xlen) -> Option<Result<Instruction, String>> {
+    let hint = match mnem {
+        "pause" => RVZihint::Pause(()),
+        "ntl.p1" => RVZihint::NtlP1(()),
+        "ntl.pall" => RVZihint::NtlPall(()),
+        "ntl.s1" => RVZihint::NtlS1(()),
+        "ntl.all" => RVZihint::NtlAll(()),
+        _ => return None,
+    };
+    if !ops.is_empty() { return Some(Err(format!("{} 不带操作数", mnem))); }
+    Some(Ok(hint.into()))
+}
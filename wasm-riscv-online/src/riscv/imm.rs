@@ -56,7 +56,7 @@ impl Imm {
                 }
                 Isize::I64(i64::from_ne_bytes(u64::to_ne_bytes(ans)))
             }
-            Xlen::X128 => panic!("Unsupported"),
+            Xlen::X128 => Isize::I128(self.as_i64() as i128),
         }
     }
 
@@ -67,6 +67,12 @@ impl Imm {
     pub fn low_i32(&self) -> i32 {
         i32::from_ne_bytes(u32::to_ne_bytes(self.low_u32()))
     }
+
+    /// Value sign-extended from `valid_bits`.
+    pub fn as_i64(&self) -> i64 {
+        let shift = 64 - self.valid_bits as u32;
+        ((self.low_u32() as u64) << shift) as i64 >> shift
+    }
 }
 
 impl Uimm {
@@ -74,18 +80,14 @@ impl Uimm {
         match xlen {
             Xlen::X32 => Usize::U32(self.data & MASK32[self.valid_bits as usize]),
             Xlen::X64 => Usize::U64((self.data & MASK32[self.valid_bits as usize]) as u64),
-            Xlen::X128 => panic!("Unsupported"),
+            Xlen::X128 => Usize::U128((self.data & MASK32[self.valid_bits as usize]) as u128),
         }
     }
 }
 
 impl core::fmt::Debug for Imm {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let num = self.data & MASK32[self.valid_bits as usize];
-        f.write_fmt(format_args!(
-            "{}",
-            i32::from_ne_bytes(u32::to_ne_bytes(num))
-        ))
+        f.write_fmt(format_args!("{}", self.as_i64()))
     }
 }
 
@@ -3,6 +3,7 @@
 pub enum Usize {
     U32(u32),
     U64(u64),
+    U128(u128),
 }
 
 impl Usize {
@@ -10,6 +11,7 @@ impl Usize {
         match self {
             Usize::U32(a) => a,
             Usize::U64(a) => (a & 0xFFFFFFFF) as u32,
+            Usize::U128(a) => (a & 0xFFFFFFFF) as u32,
         }
     }
 }
@@ -19,6 +21,7 @@ impl core::fmt::Debug for Usize {
         match self {
             Usize::U32(a) => f.write_fmt(format_args!("{}", a)),
             Usize::U64(a) => f.write_fmt(format_args!("{}", a)),
+            Usize::U128(a) => f.write_fmt(format_args!("{}", a)),
         }
     }
 }
@@ -28,6 +31,7 @@ impl core::fmt::UpperHex for Usize {
         match self {
             Usize::U32(a) => core::fmt::UpperHex::fmt(&a, f),
             Usize::U64(a) => core::fmt::UpperHex::fmt(&a, f),
+            Usize::U128(a) => core::fmt::UpperHex::fmt(&a, f),
         }
     }
 }
@@ -37,6 +41,7 @@ impl core::fmt::LowerHex for Usize {
         match self {
             Usize::U32(a) => core::fmt::LowerHex::fmt(&a, f),
             Usize::U64(a) => core::fmt::LowerHex::fmt(&a, f),
+            Usize::U128(a) => core::fmt::LowerHex::fmt(&a, f),
         }
     }
 }
@@ -47,6 +52,7 @@ impl core::cmp::PartialOrd for Usize {
         match (self, other) {
             (U32(a), U32(b)) => a.partial_cmp(b),
             (U64(a), U64(b)) => a.partial_cmp(b),
+            (U128(a), U128(b)) => a.partial_cmp(b),
             _ => None,
         }
     }
@@ -58,6 +64,7 @@ impl core::ops::Add<Usize> for Usize {
         match (self, rhs) {
             (Usize::U32(a), Usize::U32(b)) => Usize::U32(a.wrapping_add(b)),
             (Usize::U64(a), Usize::U64(b)) => Usize::U64(a.wrapping_add(b)),
+            (Usize::U128(a), Usize::U128(b)) => Usize::U128(a.wrapping_add(b)),
             _ => panic!("Not the same type"),
         }
     }
@@ -69,6 +76,7 @@ impl core::ops::Sub<Usize> for Usize {
         match (self, rhs) {
             (Usize::U32(a), Usize::U32(b)) => Usize::U32(a.wrapping_sub(b)),
             (Usize::U64(a), Usize::U64(b)) => Usize::U64(a.wrapping_sub(b)),
+            (Usize::U128(a), Usize::U128(b)) => Usize::U128(a.wrapping_sub(b)),
             _ => panic!("Not the same type"),
         }
     }
@@ -80,6 +88,7 @@ impl core::ops::Add<u32> for Usize {
         match self {
             Usize::U32(a) => Usize::U32(a.wrapping_add(rhs)),
             Usize::U64(a) => Usize::U64(a.wrapping_add(rhs as u64)),
+            Usize::U128(a) => Usize::U128(a.wrapping_add(rhs as u128)),
         }
     }
 }
@@ -88,16 +97,10 @@ impl core::ops::Add<Isize> for Usize {
     type Output = Usize;
     fn add(self, rhs: Isize) -> Self::Output {
         match (self, rhs) {
-            (Usize::U32(a), Isize::I32(b)) => Usize::U32(if b > 0 {
-                a.wrapping_add(b as u32)
-            } else {
-                a.wrapping_add(b.abs() as u32)
-            }),
-            (Usize::U64(a), Isize::I64(b)) => Usize::U64(if b > 0 {
-                a.wrapping_add(b as u64)
-            } else {
-                a.wrapping_add(b.abs() as u64)
-            }),
+            // two's complement: adding the wrapped bits subtracts for negative b
+            (Usize::U32(a), Isize::I32(b)) => Usize::U32(a.wrapping_add(b as u32)),
+            (Usize::U64(a), Isize::I64(b)) => Usize::U64(a.wrapping_add(b as u64)),
+            (Usize::U128(a), Isize::I128(b)) => Usize::U128(a.wrapping_add(b as u128)),
             _ => panic!("Not the same type"),
         }
     }
@@ -109,6 +112,7 @@ impl core::ops::BitAnd<Usize> for Usize {
         match (self, rhs) {
             (Usize::U32(a), Usize::U32(b)) => Usize::U32(a & b),
             (Usize::U64(a), Usize::U64(b)) => Usize::U64(a & b),
+            (Usize::U128(a), Usize::U128(b)) => Usize::U128(a & b),
             _ => panic!("Not the same type"),
         }
     }
@@ -120,6 +124,7 @@ impl core::ops::BitOr<Usize> for Usize {
         match (self, rhs) {
             (Usize::U32(a), Usize::U32(b)) => Usize::U32(a | b),
             (Usize::U64(a), Usize::U64(b)) => Usize::U64(a | b),
+            (Usize::U128(a), Usize::U128(b)) => Usize::U128(a | b),
             _ => panic!("Not the same type"),
         }
     }
@@ -131,6 +136,7 @@ impl core::ops::BitXor<Usize> for Usize {
         match (self, rhs) {
             (Usize::U32(a), Usize::U32(b)) => Usize::U32(a ^ b),
             (Usize::U64(a), Usize::U64(b)) => Usize::U64(a ^ b),
+            (Usize::U128(a), Usize::U128(b)) => Usize::U128(a ^ b),
             _ => panic!("Not the same type"),
         }
     }
@@ -146,6 +152,9 @@ impl core::ops::BitAnd<Isize> for Usize {
             (Usize::U64(a), Isize::I64(b)) => {
                 Usize::U64(a & u64::from_ne_bytes(i64::to_ne_bytes(b)))
             }
+            (Usize::U128(a), Isize::I128(b)) => {
+                Usize::U128(a & u128::from_ne_bytes(i128::to_ne_bytes(b)))
+            }
             _ => panic!("Not the same type"),
         }
     }
@@ -161,6 +170,9 @@ impl core::ops::BitOr<Isize> for Usize {
             (Usize::U64(a), Isize::I64(b)) => {
                 Usize::U64(a | u64::from_ne_bytes(i64::to_ne_bytes(b)))
             }
+            (Usize::U128(a), Isize::I128(b)) => {
+                Usize::U128(a | u128::from_ne_bytes(i128::to_ne_bytes(b)))
+            }
             _ => panic!("Not the same type"),
         }
     }
@@ -176,6 +188,9 @@ impl core::ops::BitXor<Isize> for Usize {
             (Usize::U64(a), Isize::I64(b)) => {
                 Usize::U64(a ^ u64::from_ne_bytes(i64::to_ne_bytes(b)))
             }
+            (Usize::U128(a), Isize::I128(b)) => {
+                Usize::U128(a ^ u128::from_ne_bytes(i128::to_ne_bytes(b)))
+            }
             _ => panic!("Not the same type"),
         }
     }
@@ -187,6 +202,7 @@ impl core::ops::Not for Usize {
         match self {
             Usize::U32(a) => Usize::U32(!a),
             Usize::U64(a) => Usize::U64(!a),
+            Usize::U128(a) => Usize::U128(!a),
         }
     }
 }
@@ -197,6 +213,7 @@ impl core::ops::Shl<u32> for Usize {
         match self {
             Usize::U32(a) => Usize::U32(a.checked_shl(rhs).unwrap_or(0)),
             Usize::U64(a) => Usize::U64(a.checked_shl(rhs).unwrap_or(0)),
+            Usize::U128(a) => Usize::U128(a.checked_shl(rhs).unwrap_or(0)),
         }
     }
 }
@@ -207,6 +224,7 @@ impl core::ops::Shr<u32> for Usize {
         match self {
             Usize::U32(a) => Usize::U32(a.checked_shr(rhs).unwrap_or(0)),
             Usize::U64(a) => Usize::U64(a.checked_shr(rhs).unwrap_or(0)),
+            Usize::U128(a) => Usize::U128(a.checked_shr(rhs).unwrap_or(0)),
         }
     }
 }
@@ -216,6 +234,7 @@ impl core::ops::AddAssign<Usize> for Usize {
         match (self, rhs) {
             (Usize::U32(a), Usize::U32(b)) => *a = a.wrapping_add(b),
             (Usize::U64(a), Usize::U64(b)) => *a = a.wrapping_add(b),
+            (Usize::U128(a), Usize::U128(b)) => *a = a.wrapping_add(b),
             _ => panic!("Not the same type"),
         }
     }
@@ -226,6 +245,7 @@ impl core::ops::AddAssign<u32> for Usize {
         match self {
             Usize::U32(a) => *a = a.wrapping_add(rhs),
             Usize::U64(a) => *a = a.wrapping_add(rhs as u64),
+            Usize::U128(a) => *a = a.wrapping_add(rhs as u128),
         }
     }
 }
@@ -234,6 +254,7 @@ impl core::ops::AddAssign<u32> for Usize {
 pub enum Isize {
     I32(i32),
     I64(i64),
+    I128(i128),
 }
 
 impl core::fmt::Debug for Isize {
@@ -241,6 +262,7 @@ impl core::fmt::Debug for Isize {
         match self {
             Isize::I32(a) => f.write_fmt(format_args!("{}", a)),
             Isize::I64(a) => f.write_fmt(format_args!("{}", a)),
+            Isize::I128(a) => f.write_fmt(format_args!("{}", a)),
         }
     }
 }
@@ -250,6 +272,7 @@ impl Isize {
         match self {
             Isize::I32(a) => Usize::U32(u32::from_ne_bytes(i32::to_ne_bytes(a))),
             Isize::I64(a) => Usize::U64(u64::from_ne_bytes(i64::to_ne_bytes(a))),
+            Isize::I128(a) => Usize::U128(u128::from_ne_bytes(i128::to_ne_bytes(a))),
         }
     }
 }
@@ -260,6 +283,7 @@ impl core::cmp::PartialOrd for Isize {
         match (self, other) {
             (I32(a), I32(b)) => a.partial_cmp(b),
             (I64(a), I64(b)) => a.partial_cmp(b),
+            (I128(a), I128(b)) => a.partial_cmp(b),
             _ => None,
         }
     }
@@ -271,6 +295,7 @@ impl core::ops::Shr<u32> for Isize {
         match self {
             Isize::I32(a) => Isize::I32(a.checked_shr(rhs).unwrap_or(-1)), // 0xFFFFFFFF
             Isize::I64(a) => Isize::I64(a.checked_shr(rhs).unwrap_or(-1)), // 0xFFFF....FFFF
+            Isize::I128(a) => Isize::I128(a.checked_shr(rhs).unwrap_or(-1)), // 0xFFFF....FFFF
         }
     }
 }
@@ -0,0 +1,80 @@
+//! Automatic RVC compression (`assemble_compressed`), checked against
+//! `llvm-mc -mattr=+c` output
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+use wasm_bindgen_test::*;
+use wasm_riscv_online::{assemble_compressed, assemble_with_xlen};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn rv32_instructions_compress_like_llvm_mc() {
+    let cases = [
+        ("addi sp, sp, -16", "0x1141"),
+        ("addi sp, sp, -64", "0x7139"),
+        ("addi a0, sp, 16", "0x0808"),
+        ("addi a0, zero, 5", "0x4515"),
+        ("addi a0, a1, 0", "0x852e"),
+        ("nop", "0x0001"),
+        ("lw a0, 4(sp)", "0x4512"),
+        ("lw a0, 4(a1)", "0x41c8"),
+        ("sw a5, 8(sp)", "0xc43e"),
+        ("sw a5, 124(a4)", "0xdf7c"),
+        ("lui a0, 1", "0x6505"),
+        ("lui a0, 0xfffff", "0x757d"),
+        ("add a0, a0, a1", "0x952e"),
+        ("add a0, zero, a1", "0x852e"),
+        ("sub a0, a0, a1", "0x8d0d"),
+        ("and a0, a1, a0", "0x8d6d"),
+        ("or a0, a0, a1", "0x8d4d"),
+        ("andi a0, a0, -3", "0x9975"),
+        ("slli a0, a0, 3", "0x050e"),
+        ("srli a5, a5, 3", "0x838d"),
+        ("srai a5, a5, 31", "0x87fd"),
+        ("jalr zero, 0(ra)", "0x8082"),
+        ("jalr ra, 0(a0)", "0x9502"),
+        ("jal zero, 16", "0xa801"),
+        ("jal ra, 16", "0x2801"),
+        ("beq a0, zero, 8", "0xc501"),
+        ("bne a0, zero, -8", "0xfd65"),
+        ("ebreak", "0x9002"),
+    ];
+    for (src, hex) in cases.iter() {
+        assert_eq!(assemble_compressed(src, 32), *hex, "input {}", src);
+    }
+}
+
+#[wasm_bindgen_test]
+fn ineligible_instructions_stay_32_bit() {
+    assert_eq!(assemble_compressed("lui sp, 1", 32), "0x00001137");
+    assert_eq!(assemble_compressed("addi a0, a1, 1", 32), "0x00158513");
+    assert_eq!(assemble_compressed("lw a0, 3(sp)", 32), "0x00312503");
+    // plain assembly never compresses
+    assert_eq!(assemble_with_xlen("addi sp, sp, -16", 32), "0xff010113");
+}
+
+#[wasm_bindgen_test]
+fn rv64_word_and_doubleword_forms() {
+    let cases = [
+        ("ld a0, 8(sp)", "0x6522"),
+        ("sd s0, 16(sp)", "0xe822"),
+        ("addiw a0, a0, 1", "0x2505"),
+        ("subw a0, a0, a1", "0x9d0d"),
+        ("addw a0, a1, a0", "0x9d2d"),
+        ("slli a0, a0, 40", "0x1522"),
+    ];
+    for (src, hex) in cases.iter() {
+        assert_eq!(assemble_compressed(src, 64), *hex, "input {}", src);
+    }
+    // c.jal is RV32-only
+    assert_eq!(assemble_compressed("jal ra, 16", 64), "0x010000ef");
+}
+
+#[wasm_bindgen_test]
+fn label_offsets_follow_compressed_sizes() {
+    // the addi shrinks to 2 bytes, so the branch back to `loop` is -2
+    let out = assemble_compressed("loop: addi a0, a0, -1\nbne a0, zero, loop", 32);
+    assert_eq!(out, "0x157d\n0xfe051fe3");
+}
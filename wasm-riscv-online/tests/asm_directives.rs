@@ -0,0 +1,65 @@
+//! Assembler directive tests (.word/.half/.byte/.dword/.align/.org/.equ)
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+use wasm_bindgen_test::*;
+use wasm_riscv_online::{assemble_auto, assemble_with_xlen};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn data_directives() {
+    let out = assemble_with_xlen(".word 0x12345678, -1\n.half 0x1234\n.byte 0x12, 255\n.dword 0x0123456789abcdef", 32);
+    assert_eq!(out, "0x12345678\n0xffffffff\n0x1234\n0x12\n0xff\n0x0123456789abcdef");
+
+    let out = assemble_with_xlen(".byte 256", 32);
+    assert!(out.starts_with("Error: 第 1 行"), "{}", out);
+}
+
+#[wasm_bindgen_test]
+fn equ_and_set_symbols_in_immediates() {
+    let src = ".equ SIZE, 16\n.set OFF, SIZE * 2\naddi sp, sp, -SIZE\nlw a0, OFF(sp)\n.word SIZE << 4";
+    let out = assemble_with_xlen(src, 32);
+    assert_eq!(out, "0xff010113\n0x02012503\n0x00000100");
+}
+
+#[wasm_bindgen_test]
+fn alignment_padding() {
+    // 1 data byte, then pad to 4: one zero byte and a c.nop
+    let out = assemble_with_xlen(".byte 1\n.align 2\naddi a0, a0, 1", 32);
+    assert_eq!(out, "0x01\n0x00\n0x0001\n0x00150513");
+
+    // explicit fill byte with .balign
+    let out = assemble_with_xlen("addi a0, a0, 1\n.balign 8, 0xff\n.word 7", 32);
+    assert_eq!(out, "0x00150513\n0xffffffff\n0x00000007");
+}
+
+#[wasm_bindgen_test]
+fn org_moves_labels() {
+    let src = "beq zero, zero, target\n.org 0x10\ntarget: addi a0, a0, 1\n.word target";
+    let out = assemble_auto(src);
+    assert_eq!(out, "0x00000863\n0x00000000\n0x00000000\n0x00000000\n0x00150513\n0x00000010");
+
+    let out = assemble_with_xlen("addi a0, a0, 1\naddi a0, a0, 1\n.org 4", 32);
+    assert!(out.lines().last().unwrap().starts_with("Error: 第 3 行"), "{}", out);
+}
+
+#[wasm_bindgen_test]
+fn unknown_directive_is_reported() {
+    let out = assemble_with_xlen(".frobnicate 1", 32);
+    assert!(out.starts_with("Error: 第 1 行"), "{}", out);
+}
+
+#[wasm_bindgen_test]
+fn forward_equ_that_changes_size_is_rejected() {
+    // defined first, `li` is laid out at its final 8 bytes
+    let out = assemble_with_xlen(".equ BIG, 0x12345678\nj end\nli a0, BIG\nend: nop", 32);
+    assert_eq!(out, "0x00c0006f\n0x12345537\n0x67850513\n0x00000013");
+    // defined after use, pass 1 sized it as one word; that layout is stale
+    let out = assemble_with_xlen("j end\nli a0, BIG\nend: nop\n.equ BIG, 0x12345678", 32);
+    assert!(out.contains("Error: 第 2 行"), "{}", out);
+    // a forward constant that does not change the size is fine
+    let out = assemble_with_xlen("li a0, SMALL\n.equ SMALL, 5", 32);
+    assert_eq!(out, "0x00500513");
+}
@@ -0,0 +1,56 @@
+//! Label resolution tests for the multi-line assembler
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+use wasm_bindgen_test::*;
+use wasm_riscv_online::{assemble_auto, assemble_with_xlen};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn backward_and_forward_branches() {
+    let src = "loop:\n    addi a0, a0, -1\n    bne a0, zero, loop\n    beq a0, a1, done\n    addi a1, a1, 1\ndone:\n    jal ra, loop";
+    let out = assemble_with_xlen(src, 32);
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines.len(), 5, "{}", out);
+    assert_eq!(lines[0], "0xfff50513"); // addi a0, a0, -1
+    assert_eq!(lines[1], "0xfe051ee3"); // bne a0, zero, -4
+    assert_eq!(lines[2], "0x00b50463"); // beq a0, a1, 8
+    assert_eq!(lines[3], "0x00158593"); // addi a1, a1, 1
+    assert_eq!(lines[4], "0xff1ff0ef"); // jal ra, -16
+}
+
+#[wasm_bindgen_test]
+fn label_on_same_line_and_local_labels() {
+    let out = assemble_with_xlen("1: addi a0, a0, -1\nbne a0, zero, 1b", 32);
+    assert_eq!(out, "0xfff50513\n0xfe051ee3");
+
+    let out = assemble_with_xlen("beq a0, zero, 1f\naddi a0, a0, 1\n1:", 32);
+    assert_eq!(out, "0x00050463\n0x00150513");
+}
+
+#[wasm_bindgen_test]
+fn compressed_targets_use_two_byte_sizes() {
+    // c.j skips a 2-byte c.nop and lands on the 4-byte addi
+    let out = assemble_with_xlen("c.j next\nc.nop\nnext: addi a0, a0, 1\nc.beqz a0, next", 32);
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines[0], "0xa011"); // c.j 4
+    assert_eq!(lines[1], "0x0001");
+    assert_eq!(lines[3], "0xdd75"); // c.beqz a0, -4
+}
+
+#[wasm_bindgen_test]
+fn out_of_range_and_undefined_labels_report_line() {
+    let src = format!("c.beqz a0, far\n{}far:", "addi a0, a0, 1\n".repeat(70));
+    let out = assemble_with_xlen(&src, 32);
+    let first = out.lines().next().unwrap();
+    assert!(first.starts_with("Error: 第 1 行"), "{}", first);
+    assert!(first.contains("far"), "{}", first);
+
+    let out = assemble_auto("addi a0, a0, 1\njal ra, missing");
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines[0], "0x00150513");
+    assert!(lines[1].starts_with("Error: 第 2 行"), "{}", lines[1]);
+    assert!(lines[1].contains("missing"), "{}", lines[1]);
+}
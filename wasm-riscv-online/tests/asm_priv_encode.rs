@@ -0,0 +1,99 @@
+//! Privileged instructions. Trap returns, `wfi` and `sfence.vma` are checked
+//! against `llvm-mc`; the hypervisor and Svinval forms follow the
+//! privileged spec encodings (rs2/rd fields as listed in the opcode map).
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+use wasm_bindgen_test::*;
+use wasm_riscv_online::{
+    assemble_with_ext, assemble_with_xlen, disassemble_objdump, disassemble_with_ext, disassemble_with_xlen,
+};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn rv32_privileged_round_trips() {
+    let cases = [
+        ("sret", "0x10200073"),
+        ("mret", "0x30200073"),
+        ("mnret", "0x70200073"),
+        ("wfi", "0x10500073"),
+        ("sfence.vma a0, a1", "0x12b50073"),
+        ("hfence.vvma a0, a1", "0x22b50073"),
+        ("hfence.gvma a0, a1", "0x62b50073"),
+        ("sinval.vma a0, a1", "0x16b50073"),
+        ("hinval.vvma a0, a1", "0x26b50073"),
+        ("hinval.gvma a0, a1", "0x66b50073"),
+        ("sfence.w.inval", "0x18000073"),
+        ("sfence.inval.ir", "0x18100073"),
+        ("hlv.b a0, (a1)", "0x6005c573"),
+        ("hlv.bu a0, (a1)", "0x6015c573"),
+        ("hlv.h a0, (a1)", "0x6405c573"),
+        ("hlv.hu a0, (a1)", "0x6415c573"),
+        ("hlvx.hu a0, (a1)", "0x6435c573"),
+        ("hlv.w a0, (a1)", "0x6805c573"),
+        ("hlvx.wu a0, (a1)", "0x6835c573"),
+        ("hsv.b a0, (a1)", "0x62a5c073"),
+        ("hsv.h a0, (a1)", "0x66a5c073"),
+        ("hsv.w a0, (a1)", "0x6aa5c073"),
+    ];
+    for (src, hex) in cases.iter() {
+        assert_eq!(assemble_with_xlen(src, 32), *hex, "input {}", src);
+        assert_eq!(disassemble_with_xlen(hex, 32), *src, "word {}", hex);
+    }
+}
+
+#[wasm_bindgen_test]
+fn rv64_hypervisor_doubleword_forms() {
+    let cases = [
+        ("hlv.wu a0, (a1)", "0x6815c573"),
+        ("hlv.d a0, (a1)", "0x6c05c573"),
+        ("hsv.d a0, (a1)", "0x6ea5c073"),
+    ];
+    for (src, hex) in cases.iter() {
+        assert_eq!(assemble_with_xlen(src, 64), *hex, "input {}", src);
+        assert_eq!(disassemble_with_xlen(hex, 64), *src, "word {}", hex);
+        assert!(assemble_with_xlen(src, 32).starts_with("Error:"), "input {}", src);
+        assert!(disassemble_with_xlen(hex, 32).starts_with("Error"), "word {}", hex);
+    }
+}
+
+#[wasm_bindgen_test]
+fn sfence_vma_operands_are_optional() {
+    assert_eq!(assemble_with_xlen("sfence.vma", 32), "0x12000073");
+    assert_eq!(assemble_with_xlen("sfence.vma a0", 32), "0x12050073");
+    assert_eq!(assemble_with_xlen("hlv.w a0, 0(a1)", 32), "0x6805c573");
+    assert_eq!(disassemble_objdump("0x12000073", 32), "sfence.vma");
+    assert_eq!(disassemble_objdump("0x12050073", 32), "sfence.vma a0");
+    assert!(assemble_with_xlen("mret a0", 32).starts_with("Error:"));
+    assert!(assemble_with_xlen("hlv.w a0, 4(a1)", 32).starts_with("Error:"));
+    // trap returns with non-zero rd/rs1 are reserved
+    assert!(disassemble_with_xlen("0x30200573", 32).starts_with("Error"));
+}
+
+#[wasm_bindgen_test]
+fn hypervisor_needs_h() {
+    let words = [
+        ("hlv.w a0, (a1)", "0x6805c573"),
+        ("hsv.w a0, (a1)", "0x6aa5c073"),
+        ("hfence.gvma a0, a1", "0x62b50073"),
+        ("hinval.vvma a0, a1", "0x26b50073"),
+        ("csrr a0, hgatp", "0x68002573"),
+        ("csrw vsatp, a0", "0x28051073"),
+        ("csrr a0, hvictl", "0x60902573"),
+        ("csrr a0, vstopi", "0xeb002573"),
+    ];
+    for xlen in [32, 64].iter() {
+        for (src, hex) in words.iter() {
+            assert_eq!(assemble_with_ext(src, *xlen, ""), *hex, "input {}", src);
+            assert_eq!(disassemble_with_ext(hex, *xlen, "+h"), *src, "word {}", hex);
+            assert!(assemble_with_ext(src, *xlen, "-h").starts_with("Error:"), "input {}", src);
+            assert!(disassemble_with_ext(hex, *xlen, "-h").starts_with("Error:"), "word {}", hex);
+        }
+    }
+    // the rest of the privileged set stays available
+    assert_eq!(assemble_with_ext("sfence.vma a0, a1", 32, "-h"), "0x12b50073");
+    assert_eq!(assemble_with_ext("csrr a0, satp", 32, "-h"), "0x18002573");
+    assert_eq!(disassemble_with_ext("0x34102573", 64, "-h"), "csrr a0, mepc");
+}
@@ -0,0 +1,83 @@
+//! Pseudo-instruction expansion tests for the assembler
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+use wasm_bindgen_test::*;
+use wasm_riscv_online::assemble_with_xlen;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn li_rv32() {
+    assert_eq!(assemble_with_xlen("li a0, 5", 32), "0x00500513");
+    assert_eq!(assemble_with_xlen("li a0, -2048", 32), "0x80000513");
+    // lui + addi
+    assert_eq!(assemble_with_xlen("li a0, 0x12345678", 32), "0x12345537\n0x67850513");
+    // unsigned spelling of -1
+    assert_eq!(assemble_with_xlen("li a0, 0xffffffff", 32), "0xfff00513");
+    assert!(assemble_with_xlen("li a0, 0x100000000", 32).starts_with("Error"));
+}
+
+#[wasm_bindgen_test]
+fn li_rv64_follows_gnu_sequences() {
+    // sign-extended 32-bit values use lui + addiw
+    assert_eq!(assemble_with_xlen("li a0, 0x12345678", 64), "0x12345537\n0x6785051b");
+    assert_eq!(assemble_with_xlen("li a0, 0x800", 64), "0x00001537\n0x8005051b");
+    // wider values are built from the upper bits and shifted into place
+    assert_eq!(assemble_with_xlen("li a0, 0x80000000", 64), "0x00100513\n0x01f51513");
+    assert_eq!(assemble_with_xlen("li a0, 0xffffffff", 64), "0x00100513\n0x02051513\n0xfff50513");
+    assert_eq!(
+        assemble_with_xlen("li a0, 0x123456789abcdef0", 64),
+        "0x00247537\n0x8ad5051b\n0x00e51513\n0xc4d50513\n0x00c51513\n0x5e750513\n0x00d51513\n0xef050513"
+    );
+}
+
+#[wasm_bindgen_test]
+fn li_rv128_near_i64_limits() {
+    // on RV64 the split wraps: -1 << 63, then - 1
+    assert_eq!(assemble_with_xlen("li a0, 0x7fffffffffffffff", 64), "0xfff00513\n0x03f51513\n0xfff50513");
+    // RV128 needs +1 << 63 for the same constant
+    assert_eq!(assemble_with_xlen("li a0, 0x7fffffffffffffff", 128), "0x00100513\n0x03f51513\n0xfff50513");
+    assert_eq!(assemble_with_xlen("li a0, -9223372036854775807", 128), "0xfff00513\n0x03f51513\n0x00150513");
+    // unsigned 64-bit hex is zero-extended to 128 bits
+    assert_eq!(assemble_with_xlen("li a0, 0xffffffffffffffff", 128), "0x00100513\n0x04051513\n0xfff50513");
+    assert_eq!(assemble_with_xlen("li a0, 0xffffffffffffffff", 64), "0xfff00513");
+}
+
+#[wasm_bindgen_test]
+fn register_pseudos() {
+    let src = "nop\nmv a1, a2\nnot a1, a2\nneg a1, a2\nseqz a0, a1\nsnez a0, a1\nble a0, a1, 8";
+    assert_eq!(
+        assemble_with_xlen(src, 32),
+        "0x00000013\n0x00060593\n0xfff64593\n0x40c005b3\n0x0015b513\n0x00b03533\n0x00a5d463"
+    );
+    assert_eq!(assemble_with_xlen("sext.w a0, a1", 64), "0x0005851b");
+    assert_eq!(assemble_with_xlen("sgt a0, a1, a2\nsgtu a0, a1, a2", 32), "0x00b62533\n0x00b63533");
+    assert!(assemble_with_xlen("sgt a0, a1", 32).starts_with("Error"));
+    assert_eq!(assemble_with_xlen("zext.h a0, a1", 64), "0x03059513\n0x03055513");
+}
+
+#[wasm_bindgen_test]
+fn float_sign_pseudos() {
+    let src = "fmv.s fa0, fa1\nfneg.d fa0, fa1\nfabs.h fa0, fa1";
+    assert_eq!(assemble_with_xlen(src, 64), "0x20b58553\n0x22b59553\n0x24b5a553");
+    assert!(assemble_with_xlen("fabs.d fa0", 64).starts_with("Error"));
+}
+
+#[wasm_bindgen_test]
+fn calls_jumps_and_addresses_use_labels() {
+    let src = "start: la a0, msg\ncall func\nj start\nfunc: beqz a0, start\nret\nmsg: .word 1";
+    assert_eq!(
+        assemble_with_xlen(src, 32),
+        "0x00000517\n0x01c50513\n0x00000097\n0x00c080e7\n0xff1ff06f\n0xfe0506e3\n0x00008067\n0x00000001"
+    );
+    // offsets whose low part is negative round the auipc immediate up
+    assert_eq!(assemble_with_xlen("tail 0x800", 32), "0x00001317\n0x80030067");
+}
+
+#[wasm_bindgen_test]
+fn pseudo_operand_errors_report_line() {
+    let out = assemble_with_xlen("nop\nmv a0", 32);
+    assert!(out.lines().nth(1).unwrap().starts_with("Error: 第 2 行"), "{}", out);
+}
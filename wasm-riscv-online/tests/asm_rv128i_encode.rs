@@ -0,0 +1,65 @@
+//! RV128I: quad-word loads and stores, the double-word ALU forms and 7-bit
+//! shift amounts. Neither binutils nor LLVM implements RV128, so the words
+//! follow the opcode map in the unprivileged spec.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+use wasm_bindgen_test::*;
+use wasm_riscv_online::{assemble_with_xlen, disassemble_expanded, disassemble_objdump, disassemble_with_xlen};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn rv128_round_trips() {
+    let cases = [
+        ("lq a0, 16(a1)", "0x0105a50f"),
+        ("sq a0, -16(a1)", "0xfea5c823"),
+        ("ldu a0, 8(a1)", "0x0085f503"),
+        ("addid a0, a1, -5", "0xffb5855b"),
+        ("sllid a0, a1, 63", "0x03f5955b"),
+        ("srlid a0, a1, 33", "0x0215d55b"),
+        ("sraid a0, a1, 1", "0x4015d55b"),
+        ("addd a0, a1, a2", "0x00c5857b"),
+        ("subd a0, a1, a2", "0x40c5857b"),
+        ("slld a0, a1, a2", "0x00c5957b"),
+        ("srld a0, a1, a2", "0x00c5d57b"),
+        ("srad a0, a1, a2", "0x40c5d57b"),
+        ("muld a0, a1, a2", "0x02c5857b"),
+        ("divd a0, a1, a2", "0x02c5c57b"),
+        ("divud a0, a1, a2", "0x02c5d57b"),
+        ("remd a0, a1, a2", "0x02c5e57b"),
+        ("remud a0, a1, a2", "0x02c5f57b"),
+        ("slli a0, a1, 127", "0x07f59513"),
+        ("srli a0, a1, 64", "0x0405d513"),
+        ("srai a0, a1, 100", "0x4645d513"),
+        ("addiw a0, a1, 1", "0x0015851b"),
+        ("addw a0, a1, a2", "0x00c5853b"),
+    ];
+    for (src, hex) in cases.iter() {
+        assert_eq!(assemble_with_xlen(src, 128), *hex, "input {}", src);
+        assert_eq!(disassemble_with_xlen(hex, 128), *src, "word {}", hex);
+    }
+}
+
+#[wasm_bindgen_test]
+fn rv128_forms_need_rv128() {
+    for src in ["lq a0, 0(a1)", "sq a0, 0(a1)", "ldu a0, 0(a1)", "addd a0, a1, a2", "sllid a0, a1, 1", "muld a0, a1, a2"].iter() {
+        assert!(assemble_with_xlen(src, 64).starts_with("Error:"), "input {}", src);
+    }
+    assert!(disassemble_with_xlen("0x00c5857b", 64).starts_with("Error"));
+    assert!(disassemble_with_xlen("0xfea5c823", 64).starts_with("Error"));
+    // shift amounts: 7 bits on RV128, 6 for the *d forms and on RV64
+    assert!(assemble_with_xlen("slli a0, a1, 128", 128).starts_with("Error:"));
+    assert!(assemble_with_xlen("sllid a0, a1, 64", 128).starts_with("Error:"));
+    assert!(assemble_with_xlen("slli a0, a1, 64", 64).starts_with("Error:"));
+    assert_eq!(disassemble_with_xlen("0x4235d513", 64), "srai a0, a1, 35");
+    assert_eq!(disassemble_objdump("0x4645d513", 128), "srai a0,a1,0x64");
+    assert_eq!(disassemble_objdump("0x0105a50f", 128), "lq a0,16(a1)");
+}
+
+#[wasm_bindgen_test]
+fn rv128_compressed_forms_expand() {
+    assert_eq!(disassemble_expanded("0x2988", 128), "c.lq a0, 16(a1)  # lq a0, 16(a1) (0x0105a50f)");
+    assert_eq!(disassemble_expanded("0x8101", 128), "c.srli64 a0, a0, 0  # srli a0, a0, 64 (0x04055513)");
+}
@@ -0,0 +1,108 @@
+//! Zicond, Zfa, Zacas, Zabha, Zawrs, Zimop and Zcmop; encodings follow the
+//! ratified specifications (llvm-mc 14 predates them).
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+use wasm_bindgen_test::*;
+use wasm_riscv_online::{assemble_with_xlen, disassemble_expanded, disassemble_with_xlen};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn round_trip(cases: &[(&str, &str)], xlen: u32) {
+    for (src, hex) in cases.iter() {
+        assert_eq!(assemble_with_xlen(src, xlen), *hex, "input {}", src);
+        assert_eq!(disassemble_with_xlen(hex, xlen), *src, "word {}", hex);
+    }
+}
+
+#[wasm_bindgen_test]
+fn zicond_zawrs_zimop_round_trips() {
+    let cases = [
+        ("czero.eqz a0, a1, a2", "0x0ec5d533"),
+        ("czero.nez a0, a1, a2", "0x0ec5f533"),
+        ("wrs.nto", "0x00d00073"),
+        ("wrs.sto", "0x01d00073"),
+        ("mop.r.0 a0, a1", "0x81c5c573"),
+        ("mop.r.31 a0, a1", "0xcdf5c573"),
+        // sspopchk ra and sspush ra from Zicfiss
+        ("mop.r.28 zero, ra", "0xcdc0c073"),
+        ("mop.rr.7 zero, zero, ra", "0xce104073"),
+        ("mop.rr.5 a0, a1, a2", "0xc6c5c573"),
+        ("c.mop.1", "0x6081"),
+        ("c.mop.15", "0x6781"),
+    ];
+    round_trip(&cases, 32);
+    round_trip(&cases, 64);
+    assert!(assemble_with_xlen("mop.r.32 a0, a1", 32).starts_with("Error:"));
+    assert!(assemble_with_xlen("mop.rr.8 a0, a1, a2", 32).starts_with("Error:"));
+    assert!(assemble_with_xlen("c.mop.2", 32).starts_with("Error:"));
+    // c.lui with a zero immediate and an even rd (x4) stays reserved
+    assert!(disassemble_with_xlen("0x6201", 32).starts_with("Error"));
+    // there is no 32-bit instruction behind c.mop.N
+    assert_eq!(disassemble_expanded("0x6281", 32), "c.mop.5");
+}
+
+#[wasm_bindgen_test]
+fn zfa_round_trips() {
+    let cases = [
+        ("fli.s f10, 0.5", "0xf0160553"),
+        ("fli.d f10, inf", "0xf21f0553"),
+        ("fli.h f1, 6.103515625e-05", "0xf41080d3"),
+        ("fminm.s f10, f11, f12", "0x28c5a553"),
+        ("fmaxm.d f10, f11, f12", "0x2ac5b553"),
+        ("fround.s f10, f11", "0x4045f553"),
+        ("froundnx.d f10, f11, rtz", "0x42559553"),
+        ("fcvtmod.w.d a0, f11, rtz", "0xc2859553"),
+        ("fleq.s a0, f11, f12", "0xa0c5c553"),
+        ("fltq.d a0, f11, f12", "0xa2c5d553"),
+    ];
+    round_trip(&cases, 64);
+    round_trip(&[("fmvh.x.d a0, f11", "0xe2158553"), ("fmvp.d.x f10, a1, a2", "0xb2c58553")], 32);
+    round_trip(&[("fmvh.x.q a0, f11", "0xe6158553")], 64);
+}
+
+#[wasm_bindgen_test]
+fn zfa_fli_constants() {
+    // written by value, in any spelling that parses to a table entry
+    assert_eq!(assemble_with_xlen("fli.s f10, 1.52587890625e-05", 32), "0xf0110553");
+    assert_eq!(assemble_with_xlen("fli.s f10, -1", 32), "0xf0100553");
+    assert!(assemble_with_xlen("fli.d f10, 0x1p-4", 32).starts_with("Error:"));
+    assert_eq!(assemble_with_xlen("fli.d f10, 65536", 32), "0xf21e8553");
+    assert_eq!(disassemble_with_xlen("0xf21e8553", 32), "fli.d f10, 65536.0");
+    assert_eq!(disassemble_with_xlen("0xf01f8553", 32), "fli.s f10, nan");
+    assert!(assemble_with_xlen("fli.s f10, 0.1", 32).starts_with("Error:"));
+    // index 1 is each format's smallest normal number, `min` to the parser
+    assert_eq!(assemble_with_xlen("fli.s f10, min", 32), "0xf0108553");
+    assert_eq!(disassemble_with_xlen("0xf0108553", 32), "fli.s f10, 1.17549435e-38");
+    assert_eq!(assemble_with_xlen("fli.d f10, 2.2250738585072014e-308", 32), "0xf2108553");
+    assert_eq!(disassemble_with_xlen("0xf2108553", 32), "fli.d f10, 2.2250738585072014e-308");
+    assert_eq!(disassemble_with_xlen("0xf6108553", 64), "fli.q f10, 3.362103143112093506262677817321753e-4932");
+    assert_eq!(assemble_with_xlen("fli.q f10, 3.362103143112093506262677817321753e-4932", 64), "0xf6108553");
+    assert!(assemble_with_xlen("fli.s f10, 2.2250738585072014e-308", 32).starts_with("Error:"));
+    assert!(assemble_with_xlen("fli.q f10, 0", 64).starts_with("Error:"));
+    // fcvtmod only rounds towards zero; fmvh/fmvp need a format twice XLEN
+    assert!(assemble_with_xlen("fcvtmod.w.d a0, f11", 32).starts_with("Error:"));
+    assert!(assemble_with_xlen("fmvh.x.d a0, f11", 64).starts_with("Error:"));
+    assert!(disassemble_with_xlen("0xe2158553", 64).starts_with("Error"));
+}
+
+#[wasm_bindgen_test]
+fn zacas_zabha_round_trips() {
+    round_trip(
+        &[
+            ("amocas.w a0, a1, (a2)", "0x28b6252f"),
+            ("amoadd.b a0, a1, (a2)", "0x00b6052f"),
+            ("amoswap.h.aq a0, a1, (a2)", "0x0cb6152f"),
+            ("amocas.b a0, a1, (a2)", "0x28b6052f"),
+            // rd and rs2 name even/odd register pairs here
+            ("amocas.d.aqrl a0, a2, (a1)", "0x2ec5b52f"),
+        ],
+        32,
+    );
+    round_trip(&[("amocas.q a0, a2, (a1)", "0x28c5c52f")], 64);
+    assert!(assemble_with_xlen("amocas.d a0, a1, (a2)", 32).starts_with("Error:"));
+    assert!(assemble_with_xlen("amocas.q a1, a2, (a3)", 64).starts_with("Error:"));
+    assert!(disassemble_with_xlen("0x28b5b52f", 32).starts_with("Error"));
+    assert!(assemble_with_xlen("lr.b a0, (a1)", 32).starts_with("Error:"));
+}
@@ -0,0 +1,51 @@
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+use wasm_bindgen_test::*;
+use wasm_riscv_online::{assemble_with_xlen, disassemble_objdump, disassemble_with_xlen};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn rva_round_trip_with_ordering_bits() {
+    let cases = [
+        ("lr.w a0, (a1)", "0x1005a52f"),
+        ("lr.w.aq t0, (sp)", "0x140122af"),
+        ("sc.w.rl a0, a2, (a1)", "0x1ac5a52f"),
+        ("amoswap.w.aqrl a0, a2, (a1)", "0x0ec5a52f"),
+        ("amoadd.w a0, a2, (a1)", "0x00c5a52f"),
+        ("amoxor.w.aq a0, a2, (a1)", "0x24c5a52f"),
+        ("amomaxu.w a0, a2, (a1)", "0xe0c5a52f"),
+        ("lr.d.aqrl a0, (a1)", "0x1605b52f"),
+        ("sc.d a0, a2, (a1)", "0x18c5b52f"),
+        ("amoand.d.rl a0, a2, (a1)", "0x62c5b52f"),
+        ("amominu.d a0, a2, (a1)", "0xc0c5b52f"),
+    ];
+    for (src, hex) in cases.iter() {
+        assert_eq!(assemble_with_xlen(src, 64), *hex, "input {}", src);
+        assert_eq!(disassemble_with_xlen(hex, 64), *src, "input {}", hex);
+    }
+    assert_eq!(disassemble_objdump("0x0ec5a52f", 32), "amoswap.w.aqrl a0,a2,(a1)");
+}
+
+#[wasm_bindgen_test]
+fn rva_width_and_syntax_checks() {
+    // a zero offset is accepted on the address operand
+    assert_eq!(assemble_with_xlen("amoadd.w a0, a2, 0(a1)", 32), "0x00c5a52f");
+    assert!(assemble_with_xlen("amoadd.d a0, a2, (a1)", 32).starts_with("Error:"));
+    assert!(assemble_with_xlen("amoadd.q a0, a2, (a1)", 64).starts_with("Error:"));
+    assert!(assemble_with_xlen("amoadd.w.acq a0, a2, (a1)", 32).starts_with("Error:"));
+    assert!(assemble_with_xlen("amoadd.w a0, a2, 4(a1)", 32).starts_with("Error:"));
+    assert!(assemble_with_xlen("lr.w a0, a2, (a1)", 32).starts_with("Error:"));
+}
+
+#[wasm_bindgen_test]
+fn rva_decode_checks() {
+    // lr.d, sc.d and amoand.d do not exist on RV32
+    for word in ["0x1605b52f", "0x18c5b52f", "0x62c5b52f"].iter() {
+        assert!(disassemble_with_xlen(word, 32).starts_with("Error"), "word {}", word);
+    }
+    // lr.w / lr.d with a nonzero rs2 field
+    assert!(disassemble_with_xlen("0x10c5a52f", 32).starts_with("Error"));
+    assert!(disassemble_with_xlen("0x10c5b52f", 64).starts_with("Error"));
+}
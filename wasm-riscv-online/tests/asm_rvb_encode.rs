@@ -0,0 +1,102 @@
+//! Zba/Zbb/Zbc/Zbs, checked against `llvm-mc -mattr=+zba,+zbb,+zbc,+zbs`
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+use wasm_bindgen_test::*;
+use wasm_riscv_online::{assemble_with_ext, assemble_with_xlen, disassemble_with_xlen};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn rv32_bitmanip_round_trips() {
+    let cases = [
+        ("sh1add a0, a1, a2", "0x20c5a533"),
+        ("sh2add a0, a1, a2", "0x20c5c533"),
+        ("sh3add a0, a1, a2", "0x20c5e533"),
+        ("andn a0, a1, a2", "0x40c5f533"),
+        ("orn a0, a1, a2", "0x40c5e533"),
+        ("xnor a0, a1, a2", "0x40c5c533"),
+        ("clz a0, a1", "0x60059513"),
+        ("ctz a0, a1", "0x60159513"),
+        ("cpop a0, a1", "0x60259513"),
+        ("max a0, a1, a2", "0x0ac5e533"),
+        ("maxu a0, a1, a2", "0x0ac5f533"),
+        ("min a0, a1, a2", "0x0ac5c533"),
+        ("minu a0, a1, a2", "0x0ac5d533"),
+        ("rol a0, a1, a2", "0x60c59533"),
+        ("ror a0, a1, a2", "0x60c5d533"),
+        ("rori a0, a1, 31", "0x61f5d513"),
+        ("orc.b a0, a1", "0x2875d513"),
+        ("rev8 a0, a1", "0x6985d513"),
+        ("clmul a0, a1, a2", "0x0ac59533"),
+        ("clmulr a0, a1, a2", "0x0ac5a533"),
+        ("clmulh a0, a1, a2", "0x0ac5b533"),
+        ("bclr a0, a1, a2", "0x48c59533"),
+        ("bclri a0, a1, 5", "0x48559513"),
+        ("bext a0, a1, a2", "0x48c5d533"),
+        ("bexti a0, a1, 5", "0x4855d513"),
+        ("binv a0, a1, a2", "0x68c59533"),
+        ("binvi a0, a1, 5", "0x68559513"),
+        ("bset a0, a1, a2", "0x28c59533"),
+        ("bseti a0, a1, 31", "0x29f59513"),
+    ];
+    for (src, hex) in cases.iter() {
+        assert_eq!(assemble_with_xlen(src, 32), *hex, "input {}", src);
+        assert_eq!(disassemble_with_xlen(hex, 32), *src, "word {}", hex);
+    }
+}
+
+#[wasm_bindgen_test]
+fn rv64_bitmanip_round_trips() {
+    let cases = [
+        ("add.uw a0, a1, a2", "0x08c5853b"),
+        ("sh1add.uw a0, a1, a2", "0x20c5a53b"),
+        ("sh2add.uw a0, a1, a2", "0x20c5c53b"),
+        ("sh3add.uw a0, a1, a2", "0x20c5e53b"),
+        ("slli.uw a0, a1, 40", "0x0a85951b"),
+        ("clzw a0, a1", "0x6005951b"),
+        ("ctzw a0, a1", "0x6015951b"),
+        ("cpopw a0, a1", "0x6025951b"),
+        ("rolw a0, a1, a2", "0x60c5953b"),
+        ("rorw a0, a1, a2", "0x60c5d53b"),
+        ("roriw a0, a1, 31", "0x61f5d51b"),
+        ("rori a0, a1, 63", "0x63f5d513"),
+        ("rev8 a0, a1", "0x6b85d513"),
+        ("bseti a0, a1, 63", "0x2bf59513"),
+        ("bexti a0, a1, 40", "0x4a85d513"),
+    ];
+    for (src, hex) in cases.iter() {
+        assert_eq!(assemble_with_xlen(src, 64), *hex, "input {}", src);
+        assert_eq!(disassemble_with_xlen(hex, 64), *src, "word {}", hex);
+    }
+    // the .uw and W forms do not exist on RV32, rev8 is re-encoded per XLEN
+    assert!(assemble_with_xlen("add.uw a0, a1, a2", 32).starts_with("Error:"));
+    assert!(assemble_with_xlen("rori a0, a1, 63", 32).starts_with("Error:"));
+    assert!(disassemble_with_xlen("0x08c5853b", 32).starts_with("Error"));
+    assert!(disassemble_with_xlen("0x6b85d513", 32).starts_with("Error"));
+    assert!(disassemble_with_xlen("0x6985d513", 64).starts_with("Error"));
+}
+
+#[wasm_bindgen_test]
+fn zbb_sign_and_zero_extension() {
+    // zext.h moves from OP to OP-32 on RV64
+    assert_eq!(disassemble_with_xlen("0x0805c533", 32), "zext.h a0, a1");
+    assert_eq!(disassemble_with_xlen("0x0805c53b", 64), "zext.h a0, a1");
+    assert_eq!(assemble_with_ext("zext.h a0, a1", 32, "+zbb"), "0x0805c533");
+    assert_eq!(assemble_with_ext("zext.h a0, a1", 64, "+zbb"), "0x0805c53b");
+    assert_eq!(assemble_with_ext("sext.b a0, a1\nsext.h a0, a1", 64, "+zbb"), "0x60459513\n0x60559513");
+    // without +zbb these stay the base-ISA shift pairs
+    assert_eq!(assemble_with_xlen("sext.b a0, a1", 64), "0x03859513\n0x43855513");
+}
+
+#[wasm_bindgen_test]
+fn zbb_text_round_trips_only_with_zbb() {
+    // the disassembler names the Zbb words; only +zbb turns the text back into them
+    let cases = [("0x60459513", "sext.b a0, a1"), ("0x60559513", "sext.h a0, a1"), ("0x0805c53b", "zext.h a0, a1")];
+    for (hex, text) in cases.iter() {
+        assert_eq!(disassemble_with_xlen(hex, 64), *text);
+        assert_eq!(assemble_with_ext(text, 64, "+zbb"), *hex);
+        assert!(assemble_with_xlen(text, 64).contains('\n'), "input {}", text);
+    }
+}
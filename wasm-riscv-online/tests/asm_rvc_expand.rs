@@ -0,0 +1,69 @@
+//! Expansion of compressed instructions (`disassemble_expanded`); the 32-bit
+//! words are checked against `llvm-mc` output for the base instruction
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+use wasm_bindgen_test::*;
+use wasm_riscv_online::{disassemble_expanded, disassemble_expanded_auto};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn check(hex: &str, xlen: u32, base: &str, word: &str) {
+    let out = disassemble_expanded(hex, xlen);
+    let (_, expansion) = out.split_once("  # ").unwrap_or_else(|| panic!("{}: {}", hex, out));
+    assert!(expansion.starts_with(base), "{}: {}", hex, out);
+    assert!(expansion.ends_with(&format!("({})", word)), "{}: {}", hex, out);
+}
+
+#[wasm_bindgen_test]
+fn rv32_expansions_match_llvm_mc() {
+    let cases = [
+        ("0x0808", "addi a0, sp, 16", "0x01010513"),
+        ("0x41c8", "lw a0, 4(a1)", "0x0045a503"),
+        ("0xdf7c", "sw a5, 124(a4)", "0x06f72e23"),
+        ("0x1575", "addi a0, a0, -3", "0xffd50513"),
+        ("0x55fd", "addi a1, zero, -1", "0xfff00593"),
+        ("0x7139", "addi sp, sp, -64", "0xfc010113"),
+        ("0x757d", "lui a0, ", "0xfffff537"),
+        ("0x87fd", "srai a5, a5, 31", "0x41f7d793"),
+        ("0x9975", "andi a0, a0, -3", "0xffd57513"),
+        ("0x8d0d", "sub a0, a0, a1", "0x40b50533"),
+        ("0xa801", "jal zero, ", "0x0100006f"),
+        ("0x3fc5", "jal ra, ", "0xff1ff0ef"),
+        ("0xc501", "beq a0, zero, ", "0x00050463"),
+        ("0xfd65", "bne a0, zero, ", "0xfe051ce3"),
+        ("0x050e", "slli a0, a0, 3", "0x00351513"),
+        ("0x50fe", "lw ra, 252(sp)", "0x0fc12083"),
+        ("0xdf86", "sw ra, 252(sp)", "0x0e112e23"),
+        ("0x8082", "jalr zero, ", "0x00008067"),
+        ("0x852e", "add a0, zero, a1", "0x00b00533"),
+        ("0x952e", "add a0, a0, a1", "0x00b50533"),
+        ("0x9002", "ebreak", "0x00100073"),
+        ("0x61c8", "flw f10, 4(a1)", "0x0045a507"),
+    ];
+    for (hex, base, word) in cases.iter() {
+        check(hex, 32, base, word);
+    }
+}
+
+#[wasm_bindgen_test]
+fn rv64_expansions_match_llvm_mc() {
+    let cases = [
+        ("0x6522", "ld a0, 8(sp)", "0x00813503"),
+        ("0x2505", "addiw a0, a0, 1", "0x0015051b"),
+        ("0x1522", "slli a0, a0, 40", "0x02851513"),
+        ("0xe822", "sd s0, 16(sp)", "0x00813823"),
+    ];
+    for (hex, base, word) in cases.iter() {
+        check(hex, 64, base, word);
+    }
+}
+
+#[wasm_bindgen_test]
+fn full_line_and_uncompressed_input() {
+    assert_eq!(disassemble_expanded("0x0808", 32), "c.addi4spn a0, sp, 16  # addi a0, sp, 16 (0x01010513)");
+    assert_eq!(disassemble_expanded_auto("0x0808"), "c.addi4spn a0, sp, 16  # addi a0, sp, 16 (0x01010513)");
+    // 32-bit words are printed as usual
+    assert_eq!(disassemble_expanded("0x01010513", 32), "addi a0, sp, 16");
+}
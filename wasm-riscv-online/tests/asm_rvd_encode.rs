@@ -0,0 +1,77 @@
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+use wasm_bindgen_test::*;
+use wasm_riscv_online::{assemble_with_xlen, disassemble_expanded, disassemble_with_xlen};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn rvd_encoding() {
+    let cases = [
+        ("fld fa0, 8(sp)", "0x00813507"),
+        ("fsd fs0, -16(a0)", "0xfe853827"),
+        ("fmadd.d fa0, fa1, fa2, fa3", "0x6ac5f543"),
+        ("fnmsub.d ft0, ft1, ft2, ft3, rtz", "0x1a20904b"),
+        ("fadd.d fa0, fa1, fa2", "0x02c5f553"),
+        ("fsub.d fa0, fa1, fa2, rne", "0x0ac58553"),
+        ("fmul.d fa0, fa1, fa2", "0x12c5f553"),
+        ("fdiv.d fa0, fa1, fa2", "0x1ac5f553"),
+        ("fsqrt.d fa0, fa1", "0x5a05f553"),
+        ("fsgnj.d fa0, fa1, fa2", "0x22c58553"),
+        ("fsgnjn.d fa0, fa1, fa2", "0x22c59553"),
+        ("fsgnjx.d fa0, fa1, fa2", "0x22c5a553"),
+        ("fmin.d fa0, fa1, fa2", "0x2ac58553"),
+        ("fmax.d fa0, fa1, fa2", "0x2ac59553"),
+        ("fcvt.s.d fa0, fa1", "0x4015f553"),
+        ("fcvt.d.s fa0, fa1", "0x42058553"),
+        ("feq.d a0, fa1, fa2", "0xa2c5a553"),
+        ("flt.d a0, fa1, fa2", "0xa2c59553"),
+        ("fle.d a0, fa1, fa2", "0xa2c58553"),
+        ("fclass.d a0, fa1", "0xe2059553"),
+        ("fcvt.w.d a0, fa1, rtz", "0xc2059553"),
+        ("fcvt.wu.d a0, fa1", "0xc215f553"),
+        ("fcvt.d.w fa0, a1", "0xd2058553"),
+        ("fcvt.d.wu fa0, a1", "0xd2158553"),
+    ];
+    for (src, hex) in cases.iter() {
+        assert_eq!(assemble_with_xlen(src, 32), *hex, "input {}", src);
+    }
+}
+
+#[wasm_bindgen_test]
+fn rv64d_encoding() {
+    let cases = [
+        ("fcvt.l.d a0, fa1", "0xc225f553"),
+        ("fcvt.lu.d a0, fa1, rdn", "0xc235a553"),
+        ("fcvt.d.l fa0, a1", "0xd225f553"),
+        ("fcvt.d.lu fa0, a1", "0xd235f553"),
+        ("fmv.x.d a0, fa1", "0xe2058553"),
+        ("fmv.d.x fa0, a1", "0xf2058553"),
+    ];
+    for (src, hex) in cases.iter() {
+        assert_eq!(assemble_with_xlen(src, 64), *hex, "input {}", src);
+        assert!(assemble_with_xlen(src, 32).starts_with("Error:"), "input {}", src);
+    }
+}
+
+#[wasm_bindgen_test]
+fn rvd_disassembly() {
+    assert_eq!(disassemble_with_xlen("0x00813507", 32), "fld f10, 8(sp)");
+    assert_eq!(disassemble_with_xlen("0xfe853827", 32), "fsd f8, -16(a0)");
+    assert_eq!(disassemble_with_xlen("0x1a20904b", 32), "fnmsub.d f0, f1, f2, f3, rtz");
+    assert_eq!(disassemble_with_xlen("0x4015f553", 32), "fcvt.s.d f10, f11");
+    // exact conversions default to rne, so only other modes are shown
+    assert_eq!(disassemble_with_xlen("0xd2058553", 32), "fcvt.d.w f10, a1");
+    assert_eq!(disassemble_with_xlen("0x42058553", 32), "fcvt.d.s f10, f11");
+    assert_eq!(disassemble_with_xlen("0xd205f553", 32), "fcvt.d.w f10, a1, dyn");
+    assert_eq!(disassemble_with_xlen("0xe2058553", 64), "fmv.x.d a0, f11");
+    assert!(disassemble_with_xlen("0xe2058553", 32).starts_with("Error"));
+}
+
+#[wasm_bindgen_test]
+fn compressed_double_loads_expand_to_fld() {
+    // c.fldsp fa0, 8(sp) / c.fsd fs0, 16(a0)
+    assert_eq!(disassemble_expanded("0x2522", 32), "c.fldsp f10, 8(sp)  # fld f10, 8(sp) (0x00813507)");
+    assert!(disassemble_expanded("0xa900", 64).ends_with("# fsd f8, 16(a0) (0x00853827)"));
+}
@@ -0,0 +1,39 @@
+//! RV32E/RV64E (`+e`): only x0–x15 exist, in every encoding that names an
+//! integer register.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+use wasm_bindgen_test::*;
+use wasm_riscv_online::{assemble_with_ext, disassemble_with_ext};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn rve_accepts_the_low_registers() {
+    assert_eq!(assemble_with_ext("add a0, a1, a5\nlw a0, 0(sp)", 32, "+e"), "0x00f58533\n0x00012503");
+    // rev8 and cpop keep a function code in the rs2 field, which is not a register
+    assert_eq!(assemble_with_ext("rev8 a0, a1\ncpop a0, a1", 64, "+e"), "0x6b85d513\n0x60259513");
+    assert_eq!(assemble_with_ext("vadd.vx v1, v2, a5", 32, "+e"), "0x0227c0d7");
+    assert_eq!(disassemble_with_ext("0x00f58533", 32, "+e"), "add a0, a1, a5");
+    assert_eq!(disassemble_with_ext("0x423027d7", 64, "+e"), "vmv.x.s a5, v3");
+}
+
+#[wasm_bindgen_test]
+fn rve_rejects_x16_to_x31() {
+    for src in ["add a0, a1, a6", "lw a0, 0(s2)", "csrr t6, mstatus", "vadd.vx v1, v2, s2", "fmv.x.w a7, fa0"].iter() {
+        let out = assemble_with_ext(src, 32, "+e");
+        assert!(out.starts_with("Error:") && out.contains("x0-x15"), "input {}: {}", src, out);
+    }
+    // add a0, a1, a6 and c.mv a0, a6
+    assert!(disassemble_with_ext("0x01058533", 32, "+e").starts_with("Error"));
+    assert!(disassemble_with_ext("0x8542", 32, "+e").starts_with("Error"));
+    // without +e the same words are fine
+    assert_eq!(disassemble_with_ext("0x01058533", 32, ""), "add a0, a1, a6");
+}
+
+#[wasm_bindgen_test]
+fn rve_has_no_rv128_form() {
+    assert!(assemble_with_ext("add a0, a1, a2", 128, "+e").starts_with("Error:"));
+    assert!(disassemble_with_ext("0x00c58533", 128, "+e").starts_with("Error"));
+}
@@ -0,0 +1,61 @@
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+use wasm_bindgen_test::*;
+use wasm_riscv_online::{assemble_with_xlen, disassemble_with_xlen};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn rvf_encoding() {
+    let cases = [
+        ("flw fa0, 8(sp)", "0x00812507"),
+        ("fsw ft1, -4(a0)", "0xfe152e27"),
+        ("fmadd.s fa0, fa1, fa2, fa3", "0x68c5f543"),
+        ("fnmsub.s f1, f2, f3, f4, rne", "0x203100cb"),
+        ("fadd.s fa0, fa1, fa2", "0x00c5f553"),
+        ("fsub.s fa0, fa1, fa2, rtz", "0x08c59553"),
+        ("fmul.s ft0, ft1, ft2, rdn", "0x1020a053"),
+        ("fdiv.s fs0, fs1, fs2, rup", "0x1924b453"),
+        ("fsqrt.s fa0, fa1, rmm", "0x5805c553"),
+        ("fsgnjn.s fa0, fa1, fa2", "0x20c59553"),
+        ("fmax.s fa0, fa1, fa2", "0x28c59553"),
+        ("flt.s a0, fa1, fa2", "0xa0c59553"),
+        ("fcvt.w.s a0, fa0, rtz", "0xc0051553"),
+        ("fcvt.s.wu fa0, a0", "0xd0157553"),
+        ("fcvt.l.s a0, fa0", "0xc0257553"),
+        ("fcvt.s.lu fa0, a0, rne", "0xd0350553"),
+        ("fmv.x.w a0, fa0", "0xe0050553"),
+        ("fmv.w.x fa0, a0", "0xf0050553"),
+        ("fclass.s a0, fa0", "0xe0051553"),
+    ];
+    for (src, hex) in cases.iter() {
+        assert_eq!(assemble_with_xlen(src, 64), *hex, "input {}", src);
+    }
+}
+
+#[wasm_bindgen_test]
+fn rvf_operands_and_rounding_modes() {
+    // numeric and ABI register names are interchangeable; dyn is the default
+    assert_eq!(assemble_with_xlen("fadd.s f10, f11, f12, dyn", 32), "0x00c5f553");
+    assert_eq!(assemble_with_xlen("fmv.s.x fa0, a0", 32), "0xf0050553");
+    assert!(assemble_with_xlen("fadd.s fa0, fa1, fa2, rxx", 32).starts_with("Error:"));
+    assert!(assemble_with_xlen("fadd.s fa0, fa1, a2", 32).starts_with("Error:"));
+    assert!(assemble_with_xlen("fcvt.l.s a0, fa0", 32).starts_with("Error:"));
+}
+
+#[wasm_bindgen_test]
+fn rvf_disassembly_shows_static_rounding_mode() {
+    assert_eq!(disassemble_with_xlen("0x00c5f553", 32), "fadd.s f10, f11, f12");
+    assert_eq!(disassemble_with_xlen("0xc0051553", 32), "fcvt.w.s a0, f10, rtz");
+    assert_eq!(disassemble_with_xlen("0x203100cb", 32), "fnmsub.s f1, f2, f3, f4, rne");
+}
+
+#[wasm_bindgen_test]
+fn rvf_reserved_rounding_modes_do_not_decode() {
+    // fadd.s, fnmsub.s, fcvt.w.s and fadd.d with rm 0b101 / 0b110
+    for word in ["0x00c5d553", "0x00c5e553", "0x203160cb", "0xc0055553", "0x02c5d553"].iter() {
+        assert!(disassemble_with_xlen(word, 32).starts_with("Error"), "word {}", word);
+        assert!(disassemble_with_xlen(word, 64).starts_with("Error"), "word {}", word);
+    }
+}
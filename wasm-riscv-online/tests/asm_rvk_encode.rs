@@ -0,0 +1,84 @@
+//! Scalar cryptography, checked against
+//! `llvm-mc -mattr=+zbkb,+zbkx,+zknd,+zkne,+zknh,+zksed,+zksh`
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+use wasm_bindgen_test::*;
+use wasm_riscv_online::{assemble_with_xlen, disassemble_with_xlen};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn rv32_crypto_round_trips() {
+    let cases = [
+        ("pack a0, a1, a2", "0x08c5c533"),
+        ("packh a0, a1, a2", "0x08c5f533"),
+        ("brev8 a0, a1", "0x6875d513"),
+        ("zip a0, a1", "0x08f59513"),
+        ("unzip a0, a1", "0x08f5d513"),
+        ("xperm4 a0, a1, a2", "0x28c5a533"),
+        ("xperm8 a0, a1, a2", "0x28c5c533"),
+        ("aes32dsi a0, a1, a2, 1", "0x6ac58533"),
+        ("aes32dsmi a0, a1, a2, 2", "0xaec58533"),
+        ("aes32esi a0, a1, a2, 3", "0xe2c58533"),
+        ("aes32esmi a0, a1, a2, 0", "0x26c58533"),
+        ("sha256sig0 a0, a1", "0x10259513"),
+        ("sha256sig1 a0, a1", "0x10359513"),
+        ("sha256sum0 a0, a1", "0x10059513"),
+        ("sha256sum1 a0, a1", "0x10159513"),
+        ("sha512sig0h a0, a1, a2", "0x5cc58533"),
+        ("sha512sig0l a0, a1, a2", "0x54c58533"),
+        ("sha512sig1h a0, a1, a2", "0x5ec58533"),
+        ("sha512sig1l a0, a1, a2", "0x56c58533"),
+        ("sha512sum0r a0, a1, a2", "0x50c58533"),
+        ("sha512sum1r a0, a1, a2", "0x52c58533"),
+        ("sm4ed a0, a1, a2, 1", "0x70c58533"),
+        ("sm4ks a0, a1, a2, 3", "0xf4c58533"),
+        ("sm3p0 a0, a1", "0x10859513"),
+        ("sm3p1 a0, a1", "0x10959513"),
+    ];
+    for (src, hex) in cases.iter() {
+        assert_eq!(assemble_with_xlen(src, 32), *hex, "input {}", src);
+        assert_eq!(disassemble_with_xlen(hex, 32), *src, "word {}", hex);
+    }
+}
+
+#[wasm_bindgen_test]
+fn rv64_crypto_round_trips() {
+    let cases = [
+        ("pack a0, a1, a2", "0x08c5c533"),
+        ("packw a0, a1, a2", "0x08c5c53b"),
+        ("aes64ds a0, a1, a2", "0x3ac58533"),
+        ("aes64dsm a0, a1, a2", "0x3ec58533"),
+        ("aes64es a0, a1, a2", "0x32c58533"),
+        ("aes64esm a0, a1, a2", "0x36c58533"),
+        ("aes64im a0, a1", "0x30059513"),
+        ("aes64ks1i a0, a1, 10", "0x31a59513"),
+        ("aes64ks2 a0, a1, a2", "0x7ec58533"),
+        ("sha512sig0 a0, a1", "0x10659513"),
+        ("sha512sig1 a0, a1", "0x10759513"),
+        ("sha512sum0 a0, a1", "0x10459513"),
+        ("sha512sum1 a0, a1", "0x10559513"),
+        ("sm4ed a0, a1, a2, 1", "0x70c58533"),
+    ];
+    for (src, hex) in cases.iter() {
+        assert_eq!(assemble_with_xlen(src, 64), *hex, "input {}", src);
+        assert_eq!(disassemble_with_xlen(hex, 64), *src, "word {}", hex);
+    }
+}
+
+#[wasm_bindgen_test]
+fn crypto_xlen_splits_and_immediates() {
+    // aes32*, sha512*[hl]/r, zip and unzip are RV32-only; aes64* and packw RV64-only
+    assert!(assemble_with_xlen("aes32esmi a0, a1, a2, 0", 64).starts_with("Error:"));
+    assert!(assemble_with_xlen("zip a0, a1", 64).starts_with("Error:"));
+    assert!(assemble_with_xlen("aes64ks1i a0, a1, 1", 32).starts_with("Error:"));
+    assert!(assemble_with_xlen("packw a0, a1, a2", 32).starts_with("Error:"));
+    assert!(disassemble_with_xlen("0x26c58533", 64).starts_with("Error"));
+    assert!(disassemble_with_xlen("0x31a59513", 32).starts_with("Error"));
+    // bs is two bits, rnum stops at 10
+    assert!(assemble_with_xlen("aes32esi a0, a1, a2, 4", 32).starts_with("Error:"));
+    assert!(assemble_with_xlen("aes64ks1i a0, a1, 11", 64).starts_with("Error:"));
+    assert!(disassemble_with_xlen("0x31b59513", 64).starts_with("Error"));
+}
@@ -0,0 +1,42 @@
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+use wasm_bindgen_test::*;
+use wasm_riscv_online::{assemble_auto, assemble_with_xlen, disassemble_with_xlen};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn rv32m_round_trip() {
+    let cases = [
+        ("mul a0, a1, a2", "0x02c58533"),
+        ("mulh a0, a1, a2", "0x02c59533"),
+        ("mulhsu a0, a1, a2", "0x02c5a533"),
+        ("mulhu a0, a1, a2", "0x02c5b533"),
+        ("div a0, a1, a2", "0x02c5c533"),
+        ("divu a0, a1, a2", "0x02c5d533"),
+        ("rem a0, a1, a2", "0x02c5e533"),
+        ("remu a0, a1, a2", "0x02c5f533"),
+    ];
+    for (src, hex) in cases.iter() {
+        assert_eq!(assemble_with_xlen(src, 32), *hex, "input {}", src);
+        assert_eq!(disassemble_with_xlen(hex, 32), *src, "input {}", hex);
+    }
+    assert_eq!(assemble_auto("mul a0, a1, a2"), "0x02c58533");
+}
+
+#[wasm_bindgen_test]
+fn rv64m_word_variants() {
+    let cases = [
+        ("mulw a0, a1, a2", "0x02c5853b"),
+        ("divw a0, a1, a2", "0x02c5c53b"),
+        ("divuw a0, a1, a2", "0x02c5d53b"),
+        ("remw a0, a1, a2", "0x02c5e53b"),
+        ("remuw a0, a1, a2", "0x02c5f53b"),
+    ];
+    for (src, hex) in cases.iter() {
+        assert_eq!(assemble_with_xlen(src, 64), *hex, "input {}", src);
+        assert_eq!(disassemble_with_xlen(hex, 64), *src, "input {}", hex);
+        assert!(assemble_with_xlen(src, 32).starts_with("Error:"));
+    }
+}
@@ -0,0 +1,79 @@
+//! RVV 1.0 decoding, checked against
+//! `llvm-mc --disassemble -mattr=+v -M no-aliases`
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+use wasm_bindgen_test::*;
+use wasm_riscv_online::disassemble_with_xlen;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn vector_config_and_memory() {
+    let cases = [
+        ("0x0d25f557", "vsetvli a0, a1, e32, m4, ta, ma"),
+        ("0xc072f557", "vsetivli a0, 5, e8, mf2, tu, mu"),
+        ("0x80c5f557", "vsetvl a0, a1, a2"),
+        ("0x1005f557", "vsetvli a0, a1, 256"),
+        ("0x02056407", "vle32.v v8, (a0)"),
+        ("0x00050407", "vle8.v v8, (a0), v0.t"),
+        ("0x0ab57407", "vlse64.v v8, (a0), a1"),
+        ("0x06456407", "vluxei32.v v8, (a0), v4"),
+        ("0x0c455407", "vloxei16.v v8, (a0), v4, v0.t"),
+        ("0x42055407", "vlseg3e16.v v8, (a0)"),
+        ("0x03056407", "vle32ff.v v8, (a0)"),
+        ("0x22856407", "vl2re32.v v8, (a0)"),
+        ("0x02b50407", "vlm.v v8, (a0)"),
+        ("0x02056427", "vse32.v v8, (a0)"),
+        ("0x08b55427", "vsse16.v v8, (a0), a1, v0.t"),
+        ("0x2e450427", "vsoxseg2ei8.v v8, (a0), v4"),
+        ("0x62850427", "vs4r.v v8, (a0)"),
+        ("0x02b50427", "vsm.v v8, (a0)"),
+    ];
+    for (hex, asm) in cases.iter() {
+        assert_eq!(disassemble_with_xlen(hex, 32), *asm, "word {}", hex);
+        assert_eq!(disassemble_with_xlen(hex, 64), *asm, "word {}", hex);
+    }
+    // scalar FP loads keep their width codes
+    assert_eq!(disassemble_with_xlen("0x0005a507", 32), "flw f10, 0(a1)");
+    // whole-register groups must be aligned
+    assert!(disassemble_with_xlen("0x22856487", 64).starts_with("Error"));
+}
+
+#[wasm_bindgen_test]
+fn vector_arithmetic() {
+    let cases = [
+        ("0x02460457", "vadd.vv v8, v4, v12"),
+        ("0x00454457", "vadd.vx v8, v4, a0, v0.t"),
+        ("0x024eb457", "vadd.vi v8, v4, -3"),
+        ("0x964fb457", "vsll.vi v8, v4, 31"),
+        ("0xb241b457", "vnsrl.wi v8, v4, 3"),
+        ("0xd2462457", "vwaddu.wv v8, v4, v12"),
+        ("0xb6456457", "vmacc.vx v8, a0, v4"),
+        ("0x02462457", "vredsum.vs v8, v4, v12"),
+        ("0x66462457", "vmand.mm v8, v4, v12"),
+        ("0x40460457", "vadc.vvm v8, v4, v12, v0"),
+        ("0x4642b457", "vmadc.vi v8, v4, 5"),
+        ("0x5c454457", "vmerge.vxm v8, v4, a0, v0"),
+        ("0x5e0fb457", "vmv.v.i v8, -1"),
+        ("0x42402557", "vmv.x.s a0, v4"),
+        ("0x40482557", "vcpop.m a0, v4, v0.t"),
+        ("0x4a432457", "vzext.vf2 v8, v4"),
+        ("0x5208a457", "vid.v v8"),
+        ("0x9e40b457", "vmv2r.v v8, v4"),
+        ("0x02455457", "vfadd.vf v8, v4, f10"),
+        ("0xb2461457", "vfmacc.vv v8, v12, v4"),
+        ("0x4a439457", "vfcvt.rtz.x.f.v v8, v4"),
+        ("0x4a4a1457", "vfncvt.f.f.w v8, v4"),
+        ("0x42401557", "vfmv.f.s f10, v4"),
+        ("0x5c455457", "vfmerge.vfm v8, v4, f10, v0"),
+        ("0x5e462457", "vcompress.vm v8, v4, v12"),
+    ];
+    for (hex, asm) in cases.iter() {
+        assert_eq!(disassemble_with_xlen(hex, 64), *asm, "word {}", hex);
+    }
+    // vadc needs v0 as the carry input, vcompress cannot be masked
+    assert!(disassemble_with_xlen("0x42460457", 64).starts_with("Error"));
+    assert!(disassemble_with_xlen("0x5c462457", 64).starts_with("Error"));
+}
@@ -0,0 +1,105 @@
+//! RVV 1.0 assembly, checked against `llvm-mc -mattr=+v,+d -show-encoding`
+//! and round-tripped through the decoder.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+use wasm_bindgen_test::*;
+use wasm_riscv_online::{assemble_with_xlen, disassemble_with_xlen};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const CASES: [(&str, &str); 42] = [
+    ("vsetvli a0, a1, e32, m4, ta, ma", "0x0d25f557"),
+    ("vsetivli a0, 5, e8, mf2, tu, mu", "0xc072f557"),
+    ("vsetvl a0, a1, a2", "0x80c5f557"),
+    ("vle32.v v8, (a0)", "0x02056407"),
+    ("vle8.v v8, (a0), v0.t", "0x00050407"),
+    ("vlse64.v v8, (a0), a1", "0x0ab57407"),
+    ("vluxei32.v v8, (a0), v4", "0x06456407"),
+    ("vloxei16.v v8, (a0), v4, v0.t", "0x0c455407"),
+    ("vlseg3e16.v v8, (a0)", "0x42055407"),
+    ("vle32ff.v v8, (a0)", "0x03056407"),
+    ("vl2re32.v v8, (a0)", "0x22856407"),
+    ("vlm.v v8, (a0)", "0x02b50407"),
+    ("vse32.v v8, (a0)", "0x02056427"),
+    ("vsse16.v v8, (a0), a1, v0.t", "0x08b55427"),
+    ("vsoxseg2ei8.v v8, (a0), v4", "0x2e450427"),
+    ("vs4r.v v8, (a0)", "0x62850427"),
+    ("vsm.v v8, (a0)", "0x02b50427"),
+    ("vadd.vv v8, v4, v12", "0x02460457"),
+    ("vadd.vx v8, v4, a0, v0.t", "0x00454457"),
+    ("vadd.vi v8, v4, -3", "0x024eb457"),
+    ("vsll.vi v8, v4, 31", "0x964fb457"),
+    ("vnsrl.wi v8, v4, 3", "0xb241b457"),
+    ("vwaddu.wv v8, v4, v12", "0xd2462457"),
+    ("vmacc.vx v8, a0, v4", "0xb6456457"),
+    ("vredsum.vs v8, v4, v12", "0x02462457"),
+    ("vmand.mm v8, v4, v12", "0x66462457"),
+    ("vadc.vvm v8, v4, v12, v0", "0x40460457"),
+    ("vmadc.vi v8, v4, 5", "0x4642b457"),
+    ("vmerge.vxm v8, v4, a0, v0", "0x5c454457"),
+    ("vmv.v.i v8, -1", "0x5e0fb457"),
+    ("vmv.x.s a0, v4", "0x42402557"),
+    ("vcpop.m a0, v4, v0.t", "0x40482557"),
+    ("vzext.vf2 v8, v4", "0x4a432457"),
+    ("vid.v v8", "0x5208a457"),
+    ("vmv2r.v v8, v4", "0x9e40b457"),
+    ("vfadd.vf v8, v4, fa0", "0x02455457"),
+    ("vfmacc.vv v8, v12, v4", "0xb2461457"),
+    ("vfcvt.rtz.x.f.v v8, v4", "0x4a439457"),
+    ("vfncvt.f.f.w v8, v4", "0x4a4a1457"),
+    ("vfmv.f.s fa0, v4", "0x42401557"),
+    ("vfmerge.vfm v8, v4, fa0, v0", "0x5c455457"),
+    ("vcompress.vm v8, v4, v12", "0x5e462457"),
+];
+
+#[wasm_bindgen_test]
+fn vector_encoding() {
+    for (src, hex) in CASES.iter() {
+        assert_eq!(assemble_with_xlen(src, 32), *hex, "input {}", src);
+        assert_eq!(assemble_with_xlen(src, 64), *hex, "input {}", src);
+    }
+}
+
+#[wasm_bindgen_test]
+fn vector_round_trip() {
+    // decoder output assembles back to the same word
+    for (_, hex) in CASES.iter() {
+        let text = disassemble_with_xlen(hex, 64);
+        assert_eq!(assemble_with_xlen(&text, 64), *hex, "text {}", text);
+    }
+}
+
+#[wasm_bindgen_test]
+fn vtype_and_operand_checks() {
+    // LMUL and the policies default to m1, tu, mu; a number is the raw vtype
+    assert_eq!(assemble_with_xlen("vsetvli a0, a1, e32", 64), "0x0105f557");
+    assert_eq!(assemble_with_xlen("vsetvli a0, a1, 0xd2", 64), "0x0d25f557");
+    assert!(assemble_with_xlen("vsetvli a0, a1, e128, m1", 64).starts_with("Error:"));
+    assert!(assemble_with_xlen("vsetivli a0, 32, e8, m1, ta, ma", 64).starts_with("Error:"));
+    // v0 is the carry input of vadc, never a mask for vmand
+    assert!(assemble_with_xlen("vadc.vvm v8, v4, v12", 64).starts_with("Error:"));
+    assert!(assemble_with_xlen("vmand.mm v8, v4, v12, v0.t", 64).starts_with("Error:"));
+    // a masked vector result may not overwrite the mask, a mask result may
+    assert!(assemble_with_xlen("vadd.vv v0, v2, v3, v0.t", 64).starts_with("Error:"));
+    assert!(assemble_with_xlen("vid.v v0, v0.t", 64).starts_with("Error:"));
+    assert!(assemble_with_xlen("vadc.vvm v0, v2, v3, v0", 64).starts_with("Error:"));
+    assert!(!assemble_with_xlen("vadd.vv v8, v2, v3, v0.t", 64).starts_with("Error:"));
+    assert!(!assemble_with_xlen("vmseq.vv v0, v2, v3, v0.t", 64).starts_with("Error:"));
+    assert!(!assemble_with_xlen("vmfeq.vf v0, v2, f1, v0.t", 64).starts_with("Error:"));
+    assert!(!assemble_with_xlen("vredsum.vs v0, v2, v3, v0.t", 64).starts_with("Error:"));
+    assert!(!assemble_with_xlen("vmadc.vvm v0, v2, v3, v0", 64).starts_with("Error:"));
+    // the decoder agrees
+    assert!(disassemble_with_xlen("0x00218057", 64).starts_with("Error"));
+    assert!(disassemble_with_xlen("0x40218057", 64).starts_with("Error"));
+    assert_eq!(disassemble_with_xlen("0x00218457", 64), "vadd.vv v8, v2, v3, v0.t");
+    assert_eq!(disassemble_with_xlen("0x60218057", 64), "vmseq.vv v0, v2, v3, v0.t");
+    // register groups and immediates
+    assert!(assemble_with_xlen("vmv2r.v v9, v4", 64).starts_with("Error:"));
+    assert!(assemble_with_xlen("vl2re32.v v9, (a0)", 64).starts_with("Error:"));
+    assert!(assemble_with_xlen("vadd.vi v8, v4, 16", 64).starts_with("Error:"));
+    assert!(assemble_with_xlen("vsll.vi v8, v4, -1", 64).starts_with("Error:"));
+    assert!(assemble_with_xlen("vle32.v v8, 4(a0)", 64).starts_with("Error:"));
+    assert!(assemble_with_xlen("vse32ff.v v8, (a0)", 64).starts_with("Error:"));
+}
@@ -0,0 +1,119 @@
+//! Additional compressed instructions: Zcb (byte/half-word memory ops and
+//! simple arithmetic), Zcmp (push/pop and s-register moves) and Zcmt
+//! (table jumps).
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+use wasm_bindgen_test::*;
+use wasm_riscv_online::{
+    assemble_with_ext, assemble_with_xlen, disassemble_expanded, disassemble_objdump, disassemble_with_ext,
+    disassemble_with_xlen,
+};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn zcb_round_trip() {
+    let cases = [
+        ("c.lbu a0, 1(a1)", "0x81c8"),
+        ("c.lhu a0, 2(a1)", "0x85a8"),
+        ("c.lh a0, 2(a1)", "0x85e8"),
+        ("c.sb a0, 3(a1)", "0x89e8"),
+        ("c.sh a0, 0(a1)", "0x8d88"),
+        ("c.zext.b a0", "0x9d61"),
+        ("c.sext.b a0", "0x9d65"),
+        ("c.zext.h a0", "0x9d69"),
+        ("c.sext.h a0", "0x9d6d"),
+        ("c.not a0", "0x9d75"),
+    ];
+    for xlen in [32, 64].iter() {
+        for (src, hex) in cases.iter() {
+            assert_eq!(assemble_with_xlen(src, *xlen), *hex, "input {}", src);
+            assert_eq!(disassemble_with_xlen(hex, *xlen), *src, "word {}", hex);
+        }
+    }
+    assert_eq!(assemble_with_xlen("c.zext.w a0", 64), "0x9d71");
+    assert_eq!(disassemble_with_xlen("0x9d71", 64), "c.zext.w a0");
+    assert_eq!(assemble_with_xlen("c.mul a0, a1", 32), "0x9d4d");
+    assert_eq!(disassemble_with_xlen("0x9d4d", 32), "c.mul a0, a0, a1");
+}
+
+#[wasm_bindgen_test]
+fn zcb_expansion() {
+    assert_eq!(disassemble_expanded("0x81c8", 32), "c.lbu a0, 1(a1)  # lbu a0, 1(a1) (0x0015c503)");
+    assert_eq!(disassemble_expanded("0x8d88", 32), "c.sh a0, 0(a1)  # sh a0, 0(a1) (0x00a59023)");
+    assert_eq!(disassemble_expanded("0x9d61", 32), "c.zext.b a0  # andi a0, a0, 255 (0x0ff57513)");
+    assert_eq!(disassemble_expanded("0x9d75", 32), "c.not a0  # xori a0, a0, -1 (0xfff54513)");
+    assert_eq!(disassemble_expanded("0x9d4d", 32), "c.mul a0, a0, a1  # mul a0, a0, a1 (0x02b50533)");
+    assert_eq!(disassemble_objdump("0x9d65", 32), "sext.b a0,a0");
+}
+
+#[wasm_bindgen_test]
+fn zcb_errors() {
+    assert!(assemble_with_xlen("c.lbu a0, 4(a1)", 32).starts_with("Error:"));
+    assert!(assemble_with_xlen("c.lhu a0, 1(a1)", 32).starts_with("Error:"));
+    assert!(assemble_with_xlen("c.sb t0, 0(a1)", 32).starts_with("Error:"));
+    assert!(assemble_with_xlen("c.zext.w a0", 32).starts_with("Error:"));
+    assert!(disassemble_with_xlen("0x9d71", 32).starts_with("Error:"));
+}
+
+#[wasm_bindgen_test]
+fn zcmp_zcmt_round_trip() {
+    let rv32 = [
+        ("cm.push {ra, s0-s2}, -32", "0xb876"),
+        ("cm.pop {ra}, 16", "0xba42"),
+        ("cm.popret {ra, s0-s11}, 112", "0xbefe"),
+        ("cm.mvsa01 s0, s1", "0xac26"),
+        ("cm.mva01s s2, s7", "0xad7e"),
+    ];
+    for (src, hex) in rv32.iter() {
+        assert_eq!(assemble_with_ext(src, 32, "+zcmp"), *hex, "input {}", src);
+        assert_eq!(disassemble_with_ext(hex, 32, "+zcmp"), *src, "word {}", hex);
+    }
+    assert_eq!(assemble_with_ext("cm.popretz {ra, s0}, 32", 64, "+zcmp"), "0xbc56");
+    assert_eq!(disassemble_with_ext("0xbc56", 64, "+zcmp"), "cm.popretz {ra, s0}, 32");
+    // x-names describe the same list
+    assert_eq!(assemble_with_ext("cm.push {x1, x8-x9, x18}, -32", 32, "+zcmp"), "0xb876");
+
+    assert_eq!(assemble_with_ext("cm.jt 5", 32, "+zcmt"), "0xa016");
+    assert_eq!(assemble_with_ext("cm.jalt 32", 32, "+zcmt"), "0xa082");
+    assert_eq!(disassemble_with_ext("0xa016", 32, "+zcmt"), "cm.jt 5");
+    assert_eq!(disassemble_with_ext("0xa082", 32, "+zcmt"), "cm.jalt 32");
+}
+
+#[wasm_bindgen_test]
+fn zcmp_replaces_zcd() {
+    // without Zcmp/Zcmt these words are c.fsdsp
+    assert!(disassemble_with_xlen("0xb876", 32).starts_with("c.fsdsp"));
+    assert!(disassemble_with_ext("0xb876", 32, "").starts_with("c.fsdsp"));
+    // by default the assembler agrees: cm.* needs its extension
+    assert!(assemble_with_xlen("cm.push {ra, s0-s2}, -32", 32).starts_with("Error:"));
+    assert!(assemble_with_xlen("cm.jt 5", 32).starts_with("Error:"));
+    // each of Zcmp and Zcmt only brings its own instructions
+    assert!(disassemble_with_ext("0xa016", 32, "+zcmp").starts_with("Error:"));
+    assert!(disassemble_with_ext("0xb876", 32, "+zcmt").starts_with("Error:"));
+    assert!(assemble_with_ext("c.fsdsp f0, 0(sp)", 32, "+zcmp").starts_with("Error:"));
+    assert!(assemble_with_ext("cm.jt 1", 32, "+zcmp").starts_with("Error:"));
+    assert_eq!(assemble_with_ext("cm.jt 1", 32, "+zcmt"), "0xa006");
+}
+
+#[wasm_bindgen_test]
+fn zcmp_errors() {
+    // stack adjustment outside base..=base+48 or not a multiple of 16
+    assert!(assemble_with_ext("cm.push {ra, s0-s5}, -16", 32, "+zcmp").starts_with("Error:"));
+    assert!(assemble_with_ext("cm.push {ra, s0-s2}, -80", 32, "+zcmp").starts_with("Error:"));
+    assert!(assemble_with_ext("cm.pop {ra}, 24", 32, "+zcmp").starts_with("Error:"));
+    // wrong sign
+    assert!(assemble_with_ext("cm.push {ra}, 16", 32, "+zcmp").starts_with("Error:"));
+    assert!(assemble_with_ext("cm.pop {ra}, -16", 32, "+zcmp").starts_with("Error:"));
+    // {ra, s0-s10} has no encoding; lists must start at ra and be contiguous
+    assert!(assemble_with_ext("cm.push {ra, s0-s10}, -64", 32, "+zcmp").starts_with("Error:"));
+    assert!(assemble_with_ext("cm.push {s0}, -16", 32, "+zcmp").starts_with("Error:"));
+    assert!(assemble_with_ext("cm.push {ra, s1}, -16", 32, "+zcmp").starts_with("Error:"));
+    assert!(assemble_with_ext("cm.push {ra}, -16", 128, "+zcmp").starts_with("Error:"));
+    assert!(assemble_with_ext("cm.mvsa01 s0, s0", 32, "+zcmp").starts_with("Error:"));
+    assert!(assemble_with_ext("cm.mvsa01 s0, s8", 32, "+zcmp").starts_with("Error:"));
+    assert!(assemble_with_ext("cm.jt 32", 32, "+zcmt").starts_with("Error:"));
+    assert!(assemble_with_ext("cm.jalt 31", 32, "+zcmt").starts_with("Error:"));
+}
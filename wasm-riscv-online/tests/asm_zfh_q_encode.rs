@@ -0,0 +1,87 @@
+//! Half (Zfh/Zfhmin) and quad (Q) precision. Zfh encodings come from
+//! `llvm-mc -mattr=+zfh,+d`; LLVM has no Q, so those follow the spec tables
+//! (fmt = 11, load/store width 100).
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+use wasm_bindgen_test::*;
+use wasm_riscv_online::{assemble_with_ext, assemble_with_xlen, disassemble_with_ext, disassemble_with_xlen};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn zfh_encoding() {
+    let cases = [
+        ("flh fa0, 2(a1)", "0x00259507"),
+        ("fsh fa0, 2(a1)", "0x00a59127"),
+        ("fmadd.h fa0, fa1, fa2, fa3", "0x6cc5f543"),
+        ("fadd.h fa0, fa1, fa2", "0x04c5f553"),
+        ("fsqrt.h fa0, fa1", "0x5c05f553"),
+        ("fmin.h fa0, fa1, fa2", "0x2cc58553"),
+        ("fsgnjx.h fa0, fa1, fa2", "0x24c5a553"),
+        ("feq.h a0, fa1, fa2", "0xa4c5a553"),
+        ("fclass.h a0, fa1", "0xe4059553"),
+        ("fcvt.s.h fa0, fa1", "0x40258553"),
+        ("fcvt.h.s fa0, fa1", "0x4405f553"),
+        ("fcvt.d.h fa0, fa1", "0x42258553"),
+        ("fcvt.h.d fa0, fa1", "0x4415f553"),
+        ("fcvt.w.h a0, fa1, rtz", "0xc4059553"),
+        ("fcvt.h.w fa0, a1", "0xd405f553"),
+        ("fcvt.h.wu fa0, a1", "0xd415f553"),
+        ("fmv.x.h a0, fa1", "0xe4058553"),
+        ("fmv.h.x fa0, a1", "0xf4058553"),
+    ];
+    for (src, hex) in cases.iter() {
+        assert_eq!(assemble_with_xlen(src, 32), *hex, "input {}", src);
+        assert_eq!(assemble_with_xlen(src, 64), *hex, "input {}", src);
+    }
+    assert_eq!(assemble_with_xlen("fcvt.l.h a0, fa1", 64), "0xc425f553");
+    assert_eq!(assemble_with_xlen("fcvt.h.lu fa0, a1", 64), "0xd435f553");
+    assert!(assemble_with_xlen("fcvt.l.h a0, fa1", 32).starts_with("Error:"));
+}
+
+#[wasm_bindgen_test]
+fn q_encoding() {
+    let cases = [
+        ("flq fa0, 16(a1)", "0x0105c507"),
+        ("fsq fa0, 16(a1)", "0x00a5c827"),
+        ("fadd.q fa0, fa1, fa2", "0x06c5f553"),
+        ("fcvt.q.d fa0, fa1", "0x46158553"),
+        ("fcvt.d.q fa0, fa1", "0x4235f553"),
+    ];
+    for (src, hex) in cases.iter() {
+        assert_eq!(assemble_with_xlen(src, 32), *hex, "input {}", src);
+    }
+    // every 64-bit integer fits in a quad, so the conversion defaults to rne
+    assert_eq!(assemble_with_xlen("fcvt.q.l fa0, a1", 64), "0xd6258553");
+    // moves between x and f registers need XLEN >= FLEN
+    assert!(assemble_with_xlen("fmv.x.q a0, fa1", 64).starts_with("Error:"));
+    assert!(assemble_with_xlen("fmv.x.q a0, fa1", 128).starts_with("0x"));
+}
+
+#[wasm_bindgen_test]
+fn zfh_and_q_disassembly() {
+    assert_eq!(disassemble_with_xlen("0x00259507", 32), "flh f10, 2(a1)");
+    assert_eq!(disassemble_with_xlen("0x04c5f553", 32), "fadd.h f10, f11, f12");
+    assert_eq!(disassemble_with_xlen("0x40258553", 32), "fcvt.s.h f10, f11");
+    assert_eq!(disassemble_with_xlen("0xe4058553", 32), "fmv.x.h a0, f11");
+    assert_eq!(disassemble_with_xlen("0x0105c507", 32), "flq f10, 16(a1)");
+    assert_eq!(disassemble_with_xlen("0x4235f553", 32), "fcvt.d.q f10, f11");
+    assert_eq!(disassemble_with_xlen("0xd6258553", 64), "fcvt.q.l f10, a1");
+}
+
+#[wasm_bindgen_test]
+fn zfhmin_is_a_subset() {
+    // -zfh leaves Zfhmin: loads, stores, moves and conversions only
+    assert_eq!(assemble_with_ext("flh fa0, 2(a1)\nfcvt.s.h fa0, fa1", 32, "-zfh"), "0x00259507\n0x40258553");
+    assert!(assemble_with_ext("fadd.h fa0, fa1, fa2", 32, "-zfh").starts_with("Error:"));
+    assert!(assemble_with_ext("fcvt.w.h a0, fa1", 32, "-zfh").starts_with("Error:"));
+    assert!(assemble_with_ext("flh fa0, 2(a1)", 32, "-zfhmin").starts_with("Error:"));
+    assert_eq!(assemble_with_ext("fadd.h fa0, fa1, fa2", 32, "-zfh,+zfh"), "0x04c5f553");
+    assert!(assemble_with_ext("fadd.s fa0, fa1, fa2", 32, "-zfhmin").starts_with("0x"));
+
+    assert_eq!(disassemble_with_ext("0xe4058553", 32, "-zfh"), "fmv.x.h a0, f11");
+    assert!(disassemble_with_ext("0x04c5f553", 32, "-zfh").starts_with("Error"));
+    assert!(disassemble_with_ext("0x04c5f553", 32, "+foo").starts_with("Error"));
+}
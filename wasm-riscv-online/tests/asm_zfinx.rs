@@ -0,0 +1,82 @@
+//! Zfinx/Zdinx/Zhinx: the F, D and Zfh instructions with their operands in
+//! the integer registers. The encodings are those of the F-register forms.
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+use wasm_bindgen_test::*;
+use wasm_riscv_online::{assemble_with_ext, assemble_with_xlen, disassemble_with_ext, disassemble_with_xlen};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn zfinx_round_trip() {
+    let cases = [
+        ("fadd.s a0, a1, a2", "fadd.s f10, f11, f12", "0x00c5f553"),
+        ("fmadd.s a0, a1, a2, a3", "fmadd.s f10, f11, f12, f13", "0x68c5f543"),
+        ("fsqrt.s t0, t1, rtz", "fsqrt.s f5, f6, rtz", "0x580312d3"),
+        ("feq.s a0, a1, a2", "feq.s a0, f11, f12", "0xa0c5a553"),
+        ("fcvt.w.s a0, a1", "fcvt.w.s a0, f11", "0xc005f553"),
+        ("fcvt.s.w a0, a1", "fcvt.s.w f10, a1", "0xd005f553"),
+        ("fclass.s a0, a1", "fclass.s a0, f11", "0xe0059553"),
+        ("fadd.h a0, a1, a2", "fadd.h f10, f11, f12", "0x04c5f553"),
+    ];
+    for xlen in [32, 64].iter() {
+        for (zfinx, f, hex) in cases.iter() {
+            assert_eq!(assemble_with_xlen(f, *xlen), *hex, "input {}", f);
+            assert_eq!(assemble_with_ext(zfinx, *xlen, "+zfinx"), *hex, "input {}", zfinx);
+            assert_eq!(disassemble_with_ext(hex, *xlen, "+zfinx"), *zfinx, "word {}", hex);
+            assert_eq!(disassemble_with_xlen(hex, *xlen), *f, "word {}", hex);
+        }
+    }
+    // F registers are not accepted in Zfinx mode
+    assert!(assemble_with_ext("fadd.s f10, f11, f12", 32, "+zfinx").starts_with("Error:"));
+}
+
+#[wasm_bindgen_test]
+fn zfinx_rejects_loads_stores_and_moves() {
+    let words = [
+        ("flw a0, 0(a1)", "0x0005a507"),
+        ("fsw a0, 0(a1)", "0x00a5a027"),
+        ("fmv.x.w a0, a1", "0xe0058553"),
+        ("fmv.w.x a0, a1", "0xf0058553"),
+    ];
+    for (src, hex) in words.iter() {
+        assert!(assemble_with_ext(src, 32, "+zfinx").starts_with("Error:"), "input {}", src);
+        assert!(disassemble_with_ext(hex, 32, "+zfinx").starts_with("Error:"), "word {}", hex);
+    }
+    // c.flw / c.flwsp
+    assert!(disassemble_with_ext("0x6188", 32, "+zfinx").starts_with("Error:"));
+    assert!(disassemble_with_ext("0x6502", 32, "+zfinx").starts_with("Error:"));
+    // quad precision has no integer-register form
+    assert!(assemble_with_ext("fadd.q a0, a2, a4", 64, "+zfinx").starts_with("Error:"));
+}
+
+#[wasm_bindgen_test]
+fn zdinx_register_pairs() {
+    // RV32 keeps a double in an even/odd pair
+    assert_eq!(assemble_with_ext("fadd.d a0, a2, a4", 32, "+zdinx"), "0x02e67553");
+    assert_eq!(disassemble_with_ext("0x02e67553", 32, "+zdinx"), "fadd.d a0, a2, a4");
+    assert!(assemble_with_ext("fadd.d a0, a1, a2", 32, "+zdinx").starts_with("Error:"));
+    assert!(disassemble_with_ext("0x02c5f553", 32, "+zdinx").starts_with("Error:"));
+    // a single-precision result may sit in any register
+    assert_eq!(assemble_with_ext("fcvt.s.d a1, a2", 32, "+zdinx"), "0x401675d3");
+    // RV64 has no pairs
+    assert_eq!(assemble_with_ext("fadd.d a0, a1, a2", 64, "+zdinx"), "0x02c5f553");
+    assert_eq!(disassemble_with_ext("0x02c5f553", 64, "+zdinx"), "fadd.d a0, a1, a2");
+}
+
+#[wasm_bindgen_test]
+fn zhinxmin() {
+    assert_eq!(assemble_with_ext("fcvt.s.h a0, a1", 32, "+zhinxmin"), "0x40258553");
+    assert!(assemble_with_ext("fadd.h a0, a1, a2", 32, "+zhinxmin,-zfh").starts_with("Error:"));
+}
+
+#[wasm_bindgen_test]
+fn zfinx_on_rve() {
+    assert_eq!(assemble_with_ext("fadd.s a0, a1, a2", 32, "+zfinx,+e"), "0x00c5f553");
+    assert!(assemble_with_ext("fadd.s a0, a1, s2", 32, "+zfinx,+e").starts_with("Error:"));
+    assert!(disassemble_with_ext("0x0125f553", 32, "+zfinx,+e").starts_with("Error:"));
+    // without Zfinx these are f registers, which E does not take away
+    assert_eq!(disassemble_with_ext("0x0125f553", 32, "+e"), "fadd.s f10, f11, f18");
+}
@@ -0,0 +1,111 @@
+//! Cache-block operations (Zicbom, Zicboz), prefetch hints (Zicbop), `pause`
+//! (Zihintpause) and the non-temporal locality hints (Zihintntl).
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+use wasm_bindgen_test::*;
+use wasm_riscv_online::{
+    assemble_with_ext, assemble_with_xlen, disassemble_expanded, disassemble_objdump, disassemble_with_ext,
+    disassemble_with_xlen,
+};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn cbo_prefetch_and_hints_round_trip() {
+    let cases = [
+        ("cbo.inval (a0)", "0x0005200f"),
+        ("cbo.clean (a0)", "0x0015200f"),
+        ("cbo.flush (a0)", "0x0025200f"),
+        ("cbo.zero (a0)", "0x0045200f"),
+        ("prefetch.i 0(a0)", "0x00056013"),
+        ("prefetch.r 32(a0)", "0x02156013"),
+        ("prefetch.w -32(sp)", "0xfe316013"),
+        ("pause", "0x0100000f"),
+        ("ntl.p1", "0x00200033"),
+        ("ntl.pall", "0x00300033"),
+        ("ntl.s1", "0x00400033"),
+        ("ntl.all", "0x00500033"),
+    ];
+    for xlen in [32, 64].iter() {
+        for (src, hex) in cases.iter() {
+            assert_eq!(assemble_with_xlen(src, *xlen), *hex, "input {}", src);
+            assert_eq!(disassemble_with_xlen(hex, *xlen), *src, "word {}", hex);
+        }
+    }
+    // the offset may be left out; cbo also takes 0(rs1)
+    assert_eq!(assemble_with_xlen("prefetch.r (a0)", 32), "0x00156013");
+    assert_eq!(assemble_with_xlen("cbo.zero 0(a0)", 32), "0x0045200f");
+    // other ori x0 / add x0 words stay what they were
+    assert_eq!(disassemble_with_xlen("0x00256013", 32), "ori zero, a0, 2");
+    assert_eq!(disassemble_with_xlen("0x00600033", 32), "add zero, zero, t1");
+}
+
+#[wasm_bindgen_test]
+fn compressed_ntl_hints() {
+    let cases = [
+        ("c.ntl.p1", "0x900a"),
+        ("c.ntl.pall", "0x900e"),
+        ("c.ntl.s1", "0x9012"),
+        ("c.ntl.all", "0x9016"),
+    ];
+    for xlen in [32, 64].iter() {
+        for (src, hex) in cases.iter() {
+            assert_eq!(assemble_with_xlen(src, *xlen), *hex, "input {}", src);
+            assert_eq!(disassemble_with_xlen(hex, *xlen), *src, "word {}", hex);
+        }
+    }
+    assert_eq!(disassemble_expanded("0x9016", 32), "c.ntl.all  # ntl.all (0x00500033)");
+    assert_eq!(disassemble_objdump("0x900a", 32), "ntl.p1");
+    assert!(assemble_with_xlen("c.ntl.p1 a0", 32).starts_with("Error:"));
+    // other c.add x0 words are not defined
+    assert!(disassemble_with_xlen("0x901a", 32).starts_with("Error:"));
+}
+
+#[wasm_bindgen_test]
+fn zicbo_errors() {
+    assert!(assemble_with_xlen("prefetch.r 16(a0)", 32).starts_with("Error:"));
+    assert!(assemble_with_xlen("cbo.clean a0", 32).starts_with("Error:"));
+    assert!(assemble_with_xlen("pause a0", 32).starts_with("Error:"));
+    // on RV128 the cbo encoding is lq
+    assert!(assemble_with_xlen("cbo.zero (a0)", 128).starts_with("Error:"));
+    assert_eq!(disassemble_with_xlen("0x0045200f", 128), "lq zero, 4(a0)");
+}
+
+#[wasm_bindgen_test]
+fn fence_operands() {
+    let cases = [
+        ("fence", "0x0000000f"),
+        ("fence rw, rw", "0x0330000f"),
+        ("fence iorw, iorw", "0x0ff0000f"),
+        ("fence r, 0", "0x0200000f"),
+        ("fence.tso", "0x8330000f"),
+    ];
+    for (src, hex) in cases.iter() {
+        assert_eq!(assemble_with_xlen(src, 32), *hex, "input {}", src);
+        assert_eq!(disassemble_with_xlen(hex, 32), *src, "word {}", hex);
+    }
+    assert_eq!(disassemble_objdump("0x0ff0000f", 32), "fence");
+    assert_eq!(disassemble_objdump("0x0330000f", 32), "fence rw,rw");
+    assert!(assemble_with_xlen("fence rx, w", 32).starts_with("Error:"));
+    assert!(assemble_with_xlen("fence rw", 32).starts_with("Error:"));
+}
+
+#[wasm_bindgen_test]
+fn raw_hint_form() {
+    assert_eq!(disassemble_with_ext("0x0100000f", 32, "-zihintpause"), "fence w, 0");
+    assert_eq!(disassemble_with_ext("0x02156013", 32, "-zicbop"), "ori zero, a0, 33");
+    assert_eq!(disassemble_with_ext("0x00200033", 32, "-zihintntl"), "add zero, zero, sp");
+    // only the extension that was switched off changes
+    assert_eq!(disassemble_with_ext("0x0100000f", 32, "-zihintntl"), "pause");
+    assert_eq!(disassemble_with_ext("0x0045200f", 32, "-zicbop"), "cbo.zero (a0)");
+
+    assert!(assemble_with_ext("pause", 32, "-zihintpause").starts_with("Error:"));
+    assert!(assemble_with_ext("prefetch.w 0(a0)", 32, "-zicbop").starts_with("Error:"));
+    assert!(assemble_with_ext("ntl.all", 32, "-zihintntl").starts_with("Error:"));
+    assert_eq!(disassemble_with_ext("0x900a", 32, "-zihintntl"), "c.add zero, zero, sp");
+    assert!(assemble_with_ext("c.ntl.all", 32, "-zihintntl").starts_with("Error:"));
+    assert_eq!(assemble_with_ext("c.add zero, sp", 32, "-zihintntl"), "0x900a");
+    assert_eq!(assemble_with_ext("fence w, 0", 32, "-zihintpause"), "0x0100000f");
+}
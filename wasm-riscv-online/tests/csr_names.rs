@@ -0,0 +1,40 @@
+//! Symbolic CSR names in the disassembler and assembler
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+use wasm_bindgen_test::*;
+use wasm_riscv_online::{assemble_with_xlen, disassemble_with_xlen};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn disassembly_prints_names() {
+    assert_eq!(disassemble_with_xlen("0x30059573", 32), "csrrw a0, mstatus, a1");
+    assert_eq!(disassemble_with_xlen("0xf1402573", 64), "csrr a0, mhartid");
+    assert_eq!(disassemble_with_xlen("0xb0302573", 32), "csrr a0, mhpmcounter3");
+    assert_eq!(disassemble_with_xlen("0xb9f02573", 32), "csrr a0, mhpmcounter31h");
+    assert_eq!(disassemble_with_xlen("0x3bf02573", 64), "csrr a0, pmpaddr15");
+    assert_eq!(disassemble_with_xlen("0x7b002573", 64), "csrr a0, dcsr");
+    assert_eq!(disassemble_with_xlen("0x6002d073", 64), "csrwi hstatus, 5");
+    // unknown numbers stay hex
+    assert_eq!(disassemble_with_xlen("0x7c059573", 32), "csrrw a0, 0x7c0, a1");
+}
+
+#[wasm_bindgen_test]
+fn assembler_accepts_names() {
+    assert_eq!(assemble_with_xlen("csrrw a0, mstatus, a1", 32), "0x30059573");
+    assert_eq!(assemble_with_xlen("csrrs a0, MHARTID, zero", 64), "0xf1402573");
+    assert_eq!(assemble_with_xlen("csrrs a0, mhpmcounter31, zero", 64), "0xb1f02573");
+    assert_eq!(assemble_with_xlen("csrrci zero, sstatus, 2", 64), "0x10017073");
+    // hex still works, and round-trips for unnamed CSRs
+    assert_eq!(assemble_with_xlen("csrrw a0, 0x7c0, a1", 32), "0x7c059573");
+}
+
+#[wasm_bindgen_test]
+fn assembler_rejects_bad_names() {
+    assert!(assemble_with_xlen("csrrs a0, cycleh, zero", 32).starts_with("0x"));
+    assert!(assemble_with_xlen("csrrs a0, cycleh, zero", 64).starts_with("Error"));
+    assert!(assemble_with_xlen("csrrs a0, mhpmcounter32, zero", 32).starts_with("Error"));
+    assert!(assemble_with_xlen("csrrs a0, nosuchcsr, zero", 32).contains("nosuchcsr"));
+}
@@ -0,0 +1,118 @@
+//! objdump-style (alias-aware) disassembly tests
+
+#![cfg(target_arch = "wasm32")]
+
+extern crate wasm_bindgen_test;
+use wasm_bindgen_test::*;
+use wasm_riscv_online::{disassemble_objdump, disassemble_objdump_auto};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn check(xlen: u32, cases: &[(&str, &str)]) {
+    for (hex, expected) in cases {
+        assert_eq!(disassemble_objdump(hex, xlen), *expected, "input {}", hex);
+    }
+}
+
+#[wasm_bindgen_test]
+fn base_integer_aliases() {
+    check(32, &[
+        ("0x00000013", "nop"),
+        ("0x00008067", "ret"),
+        ("0x00500513", "li a0,5"),
+        ("0x00058513", "mv a0,a1"),
+        ("0xfff5c513", "not a0,a1"),
+        ("0x40b00533", "neg a0,a1"),
+        ("0x0015b513", "seqz a0,a1"),
+        ("0x00b03533", "snez a0,a1"),
+        ("0x0005a533", "sltz a0,a1"),
+        ("0x00b02533", "sgtz a0,a1"),
+        ("0x0ff5f513", "zext.b a0,a1"),
+        ("0x00c58533", "add a0,a1,a2"),
+        ("0x12345537", "lui a0,0x12345"),
+        ("0x00359513", "slli a0,a1,0x3"),
+        ("0xffc12503", "lw a0,-4(sp)"),
+        ("0x00a12423", "sw a0,8(sp)"),
+    ]);
+}
+
+#[wasm_bindgen_test]
+fn branch_and_jump_aliases() {
+    check(32, &[
+        ("0x00050463", "beqz a0,8"),
+        ("0xfe051ee3", "bnez a0,fffffffc"),
+        ("0x00a05463", "blez a0,8"),
+        ("0x00a04463", "bgtz a0,8"),
+        ("0x00b50463", "beq a0,a1,8"),
+        ("0xff1ff06f", "j fffffff0"),
+        ("0x010000ef", "jal 10"),
+        ("0x00050067", "jr a0"),
+        ("0x000500e7", "jalr a0"),
+        ("0x000582e7", "jalr t0,a1"),
+        ("0x008580e7", "jalr 8(a1)"),
+    ]);
+    assert_eq!(disassemble_objdump("0xfe051ee3", 64), "bnez a0,fffffffffffffffc");
+}
+
+#[wasm_bindgen_test]
+fn csr_aliases() {
+    check(32, &[
+        ("0xc0002573", "rdcycle a0"),
+        ("0xc8002573", "rdcycleh a0"),
+        ("0xc0202573", "rdinstret a0"),
+        ("0x30002573", "csrr a0,mstatus"),
+        ("0x30051073", "csrw mstatus,a0"),
+        ("0x30052073", "csrs mstatus,a0"),
+        ("0x30053073", "csrc mstatus,a0"),
+        ("0x3002d073", "csrwi mstatus,5"),
+        ("0x30059573", "csrrw a0,mstatus,a1"),
+        ("0x7c002573", "csrr a0,0x7c0"),
+        ("0x00302573", "frcsr a0"),
+        ("0x00259073", "fsrm a1"),
+    ]);
+    // the high counter halves are RV32-only, so RV64 shows the number
+    assert_eq!(disassemble_objdump("0xc8002573", 64), "csrr a0,0xc80");
+}
+
+#[wasm_bindgen_test]
+fn compressed_use_base_mnemonics() {
+    check(32, &[
+        ("0x0001", "nop"),
+        ("0x8082", "ret"),
+        ("0x4515", "li a0,5"),
+        ("0x852e", "mv a0,a1"),
+        ("0x0808", "addi a0,sp,16"),
+        ("0x41a8", "lw a0,64(a1)"),
+        ("0x450e", "lw a0,192(sp)"),
+        ("0xdfaa", "sw a0,252(sp)"),
+        ("0x757d", "lui a0,0xfffff"),
+        ("0x713d", "addi sp,sp,-32"),
+        ("0x85fd", "srai a1,a1,0x1f"),
+        ("0x8d0d", "sub a0,a0,a1"),
+        ("0xc501", "beqz a0,8"),
+        ("0xfd75", "bnez a0,fffffffc"),
+        ("0x2ffd", "jal 7fe"),
+        ("0xb001", "j fffff800"),
+        ("0x6588", "flw fa0,8(a1)"),
+        ("0x9002", "ebreak"),
+    ]);
+    check(64, &[
+        ("0x2501", "sext.w a0,a0"),
+        ("0x3575", "addiw a0,a0,-3"),
+        ("0x757e", "ld a0,504(sp)"),
+        ("0x2588", "fld fa0,8(a1)"),
+        ("0x157e", "slli a0,a0,0x3f"),
+    ]);
+}
+
+#[wasm_bindgen_test]
+fn rv64_aliases_and_auto() {
+    check(64, &[
+        ("0x0005851b", "sext.w a0,a1"),
+        ("0x40b0053b", "negw a0,a1"),
+        ("0x02859513", "slli a0,a1,0x28"),
+        ("0x0035d51b", "srliw a0,a1,0x3"),
+    ]);
+    assert_eq!(disassemble_objdump_auto("0x00008067"), "ret");
+    assert!(disassemble_objdump_auto("0xffffffff").starts_with("Error"));
+}
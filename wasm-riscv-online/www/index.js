@@ -13,7 +13,11 @@ try {
     const copyButton = document.getElementById('copyButton');
     const input = document.getElementById('input');
     const xlenSelect = document.getElementById('xlenSelect');
+    const baseSelect = document.getElementById('baseSelect');
     const modeSelect = document.getElementById('modeSelect');
+    const syntaxSelect = document.getElementById('syntaxSelect');
+    const compressCheck = document.getElementById('compressCheck');
+    const extInput = document.getElementById('extInput');
     const inputDisplay = document.getElementById('inputDisplay');
     const outputDisplay = document.getElementById('outputDisplay');
     const inputStatus = document.getElementById('inputStatus');
@@ -117,23 +121,57 @@ try {
         return instructions;
     }
 
+    // E 基础指令集通过扩展列表中的 +e 传给 WASM（仅 RV32E/RV64E，auto 按 32 位处理）
+    function isBaseE() {
+        return baseSelect && baseSelect.value === 'e';
+    }
+
+    function extWithBase() {
+        const ext = extInput ? extInput.value.trim() : '';
+        return ext ? ext + ',+e' : '+e';
+    }
+
     // 根据 XLEN 模式调用合适的 WASM 导出函数（反汇编）
     function disassembleByMode(formattedHex) {
         const mode = xlenSelect ? xlenSelect.value : 'auto';
+        const objdump = syntaxSelect && syntaxSelect.value === 'objdump';
+        const expand = syntaxSelect && syntaxSelect.value === 'expand';
+        if (isBaseE()) {
+            return wasm.disassemble_with_ext(formattedHex, mode === 'auto' ? 32 : parseInt(mode, 10), extWithBase());
+        }
+        if (expand) {
+            return mode === 'auto'
+                ? wasm.disassemble_expanded_auto(formattedHex)
+                : wasm.disassemble_expanded(formattedHex, parseInt(mode, 10));
+        }
         if (mode === 'auto') {
-            return wasm.disassemble_auto(formattedHex);
+            return objdump ? wasm.disassemble_objdump_auto(formattedHex) : wasm.disassemble_auto(formattedHex);
         }
         const xlen = parseInt(mode, 10);
-        return wasm.disassemble_with_xlen(formattedHex, xlen);
+        const ext = extInput ? extInput.value.trim() : '';
+        if (ext && !objdump) {
+            return wasm.disassemble_with_ext(formattedHex, xlen, ext);
+        }
+        return objdump ? wasm.disassemble_objdump(formattedHex, xlen) : wasm.disassemble_with_xlen(formattedHex, xlen);
     }
 
     // 根据 XLEN 模式调用合适的 WASM 导出函数（汇编，整块文本）
     function assembleByModeWhole(text) {
         const mode = xlenSelect ? xlenSelect.value : 'auto';
+        if (isBaseE()) {
+            return wasm.assemble_with_ext(text, mode === 'auto' ? 32 : parseInt(mode, 10), extWithBase());
+        }
         if (mode === 'auto') {
             return wasm.assemble_auto(text);
         }
         const xlen = parseInt(mode, 10);
+        if (compressCheck && compressCheck.checked) {
+            return wasm.assemble_compressed(text, xlen);
+        }
+        const ext = extInput ? extInput.value.trim() : '';
+        if (ext) {
+            return wasm.assemble_with_ext(text, xlen, ext);
+        }
         return wasm.assemble_with_xlen(text, xlen);
     }
 